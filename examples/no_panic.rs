@@ -0,0 +1,39 @@
+//! Instantiates the hot paths of the encoder and the decoder, so that the `no-panic` feature
+//! proves at link time that they cannot panic.
+//!
+//! Build with `cargo build --profile no-panic --example no_panic --features no-panic`.
+
+use std::hint::black_box;
+
+use noalloc_slip_rs::slip::SlipDecoder;
+use noalloc_slip_rs::slip::SlipEncoder;
+use noalloc_slip_rs::stuffing::ByteStuffingCodec;
+use noalloc_slip_rs::stuffing::ByteStuffingDecoder;
+use noalloc_vec_rs::vec::Vec;
+
+fn main() {
+    let data = black_box([0xC0, 0x01, 0xDB, 0xDC, 0x02, 0xC0]);
+    let mut output = [0x00; 16];
+
+    let encoder = SlipEncoder::new();
+    let mut vec = Vec::<u8, 16>::from([0x01, 0xC0]);
+    black_box(encoder.encoded_len(&data));
+    black_box(encoder.encode(&mut vec)).ok();
+    black_box(encoder.encode_to_slice(&data, &mut output)).ok();
+
+    let mut decoder = SlipDecoder::<16>::new();
+    black_box(decoder.insert(data[0])).ok();
+    black_box(decoder.feed(&data));
+    let mut frame = data;
+    black_box(decoder.decode_in_place(&mut frame)).ok();
+
+    let codec = ByteStuffingCodec::SLIP;
+    let mut vec = Vec::<u8, 16>::from([0x01, 0xC0]);
+    black_box(codec.encoded_len(&data));
+    black_box(codec.encode(&mut vec)).ok();
+    black_box(codec.encode_to_slice(&data, &mut output)).ok();
+
+    let mut decoder = ByteStuffingDecoder::<16>::new(codec);
+    black_box(decoder.insert(data[0])).ok();
+    black_box(decoder.feed(&data));
+}
@@ -0,0 +1,29 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use noalloc_slip_rs::slip::END_CHAR;
+use noalloc_slip_rs::slip::FeedStatus;
+use noalloc_slip_rs::slip::SlipDecoder;
+
+fuzz_target!(|data: &[u8]| {
+    let mut decoder = SlipDecoder::<64>::new();
+
+    // Any byte stream must be consumed without panicking, whatever the frames it holds
+    let mut data = data;
+    while !data.is_empty() {
+        let result = decoder.feed(data);
+        assert!(result.consumed > 0);
+        assert!(decoder.get_buffer().len() <= 64);
+
+        if result.status != FeedStatus::Incomplete {
+            decoder.reset();
+        }
+        data = &data[result.consumed..];
+    }
+
+    // A reset decoder must decode the next frame, whatever came before
+    decoder.reset();
+    decoder.feed(&[END_CHAR, 0x01, END_CHAR]);
+
+    assert_eq!(decoder.take_frame().unwrap().as_slice(), [0x01]);
+});
@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use noalloc_slip_rs::fuzzing::SlipStream;
+use noalloc_slip_rs::slip::FeedStatus;
+use noalloc_slip_rs::slip::SlipVecDecoder;
+
+fuzz_target!(|stream: SlipStream| {
+    let mut decoder = SlipVecDecoder::new();
+    let mut frames = Vec::new();
+
+    // Whatever the noise and the chunk boundaries, the payloads must be decoded as they were sent
+    for mut chunk in stream.chunks() {
+        while !chunk.is_empty() {
+            let result = decoder.feed(chunk);
+            chunk = &chunk[result.consumed..];
+
+            if result.status == FeedStatus::Completed {
+                frames.push(decoder.get_buffer().to_vec());
+                decoder.reset();
+            } else {
+                assert_eq!(result.status, FeedStatus::Incomplete);
+            }
+        }
+    }
+
+    assert_eq!(frames, stream.payloads());
+});
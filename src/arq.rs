@@ -0,0 +1,258 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipArqError;
+use crate::wcet::WorkBound;
+
+/// Length of the header starting every frame of the reliability layer.
+///
+/// The first byte is the kind of the frame, `DATA_KIND` or `ACK_KIND`, and the second one is the
+/// sequence number of the payload carried or acknowledged.
+pub const ARQ_HEADER_LENGTH: usize = 2;
+
+/// Kind of a frame carrying a payload.
+pub const DATA_KIND: u8 = 0x00;
+
+/// Kind of a frame acknowledging a payload.
+pub const ACK_KIND: u8 = 0x01;
+
+/// The outcome of receiving a frame in an `ArqEndpoint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArqEvent<'a> {
+    /// The frame carries a new payload, which `ack` must be sent back for.
+    Data {
+        payload: &'a [u8],
+        ack: [u8; ARQ_HEADER_LENGTH],
+    },
+    /// The frame repeats the last payload received, whose acknowledgment was lost: `ack` must be
+    /// sent back again, but the payload must not be handled twice.
+    Duplicate { ack: [u8; ARQ_HEADER_LENGTH] },
+    /// The frame acknowledges the payload awaiting its acknowledgment.
+    Acknowledged,
+    /// The frame acknowledges a payload no longer awaiting its acknowledgment, and was ignored.
+    Stale,
+}
+
+/// One end of a stop-and-wait reliability layer over SLIP frames.
+///
+/// This struct numbers the payloads it sends and keeps a copy of the last one, of up to
+/// `MAX_LENGTH` bytes header included, until the other end acknowledges it. The caller encodes
+/// and sends the frames it returns, and feeds it every frame received, so that it can handle
+/// acknowledgments and tell repeated payloads apart. Time is given by the caller, as ticks of
+/// any monotonic clock: `poll` returns the frame to retransmit once `timeout` ticks have elapsed
+/// without acknowledgment, up to `max_retries` times.
+///
+/// Only one payload is in flight at a time, which suits command/response protocols. There is no
+/// handshake: both ends must start together, or the first payload after a restart may be taken
+/// for a repeat.
+pub struct ArqEndpoint<const MAX_LENGTH: usize> {
+    timeout: u64,
+    max_retries: u8,
+    // Frame awaiting its acknowledgment, header included
+    pending: Vec<u8, MAX_LENGTH>,
+    awaiting: bool,
+    sent_at: u64,
+    retries: u8,
+    // Sequence number of the next payload sent
+    next_sequence: u8,
+    // Sequence number of the last payload received, if any
+    last_received: Option<u8>,
+}
+
+impl<const MAX_LENGTH: usize> ArqEndpoint<MAX_LENGTH> {
+    /// Creates an endpoint retransmitting a payload every `timeout` ticks, up to `max_retries`
+    /// times.
+    #[must_use]
+    pub const fn new(timeout: u64, max_retries: u8) -> Self {
+        Self {
+            timeout,
+            max_retries,
+            pending: Vec::new(),
+            awaiting: false,
+            sent_at: 0,
+            retries: 0,
+            next_sequence: 0,
+            last_received: None,
+        }
+    }
+
+    /// Returns the frame carrying `payload`, to send at time `now`.
+    ///
+    /// Returns `Err(SlipArqError::Busy)` if the previous payload is still awaiting its
+    /// acknowledgment, or `Err(SlipArqError::PayloadTooLong)` if the frame does not fit in the
+    /// buffer.
+    pub fn send(&mut self, payload: &[u8], now: u64) -> Result<&[u8], SlipArqError> {
+        if self.awaiting {
+            return Err(SlipArqError::Busy);
+        }
+
+        self.pending.clear();
+        if self
+            .pending
+            .write_slice(0, &[DATA_KIND, self.next_sequence])
+            .is_err()
+            || self
+                .pending
+                .write_slice(ARQ_HEADER_LENGTH, payload)
+                .is_err()
+        {
+            self.pending.clear();
+
+            return Err(SlipArqError::PayloadTooLong);
+        }
+
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.awaiting = true;
+        self.sent_at = now;
+        self.retries = 0;
+
+        Ok(self.pending.as_slice())
+    }
+
+    /// Returns the worst-case work of `send`.
+    #[must_use]
+    pub const fn send_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Returns the frame to retransmit at time `now`, if the payload awaiting its acknowledgment
+    /// timed out.
+    ///
+    /// Returns `Err(SlipArqError::Unacknowledged)` once the payload timed out after its last
+    /// retransmission, and drops it, so that the next payload can be sent.
+    pub const fn poll(&mut self, now: u64) -> Result<Option<&[u8]>, SlipArqError> {
+        if !self.awaiting || now.wrapping_sub(self.sent_at) < self.timeout {
+            return Ok(None);
+        }
+
+        if self.retries >= self.max_retries {
+            self.awaiting = false;
+
+            return Err(SlipArqError::Unacknowledged);
+        }
+
+        self.retries += 1;
+        self.sent_at = now;
+
+        Ok(Some(self.pending.as_slice()))
+    }
+
+    /// Returns the worst-case work of `poll`.
+    #[must_use]
+    pub const fn poll_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Handles `frame`, as received from the other end.
+    ///
+    /// Returns `Err(SlipArqError::MalformedFrame)` if the frame is shorter than its header, or of
+    /// an unknown kind.
+    pub fn receive<'a>(&mut self, frame: &'a [u8]) -> Result<ArqEvent<'a>, SlipArqError> {
+        let &[kind, sequence, ref payload @ ..] = frame else {
+            return Err(SlipArqError::MalformedFrame);
+        };
+
+        match kind {
+            DATA_KIND => {
+                let ack = [ACK_KIND, sequence];
+                if self.last_received == Some(sequence) {
+                    return Ok(ArqEvent::Duplicate { ack });
+                }
+                self.last_received = Some(sequence);
+
+                Ok(ArqEvent::Data { payload, ack })
+            }
+            ACK_KIND => {
+                if !self.awaiting || self.pending.as_slice().get(1) != Some(&sequence) {
+                    return Ok(ArqEvent::Stale);
+                }
+                self.awaiting = false;
+
+                Ok(ArqEvent::Acknowledged)
+            }
+            _ => Err(SlipArqError::MalformedFrame),
+        }
+    }
+
+    /// Returns the worst-case work of `receive`.
+    #[must_use]
+    pub const fn receive_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Returns `true` if a payload is awaiting its acknowledgment.
+    #[must_use]
+    pub const fn is_awaiting_ack(&self) -> bool {
+        self.awaiting
+    }
+
+    /// Drops the payload awaiting its acknowledgment, if any, and forgets the last payload
+    /// received.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.awaiting = false;
+        self.last_received = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arq::ACK_KIND;
+    use crate::arq::ArqEndpoint;
+    use crate::arq::ArqEvent;
+    use crate::arq::DATA_KIND;
+    use crate::error::SlipArqError;
+
+    #[test]
+    fn test_arq() {
+        let mut sender = ArqEndpoint::<8>::new(10, 2);
+        let mut receiver = ArqEndpoint::<8>::new(10, 2);
+        let frame = [DATA_KIND, 0x00, 0x01, 0x02];
+
+        assert_eq!(sender.send(&[0x01, 0x02], 0), Ok(frame.as_slice()));
+        assert_eq!(sender.send(&[0x03], 5), Err(SlipArqError::Busy));
+
+        // The frame is lost, and retransmitted once the timeout elapses
+        assert_eq!(sender.poll(9), Ok(None));
+        assert_eq!(sender.poll(10), Ok(Some(frame.as_slice())));
+
+        let ack = [ACK_KIND, 0x00];
+        assert_eq!(
+            receiver.receive(&frame),
+            Ok(ArqEvent::Data {
+                payload: &[0x01, 0x02],
+                ack
+            })
+        );
+        assert_eq!(receiver.receive(&frame), Ok(ArqEvent::Duplicate { ack }));
+        assert_eq!(sender.receive(&ack), Ok(ArqEvent::Acknowledged));
+        assert_eq!(sender.receive(&ack), Ok(ArqEvent::Stale));
+        assert!(!sender.is_awaiting_ack());
+        assert_eq!(sender.poll(100), Ok(None));
+        assert_eq!(
+            sender.send(&[0x03], 100),
+            Ok([DATA_KIND, 0x01, 0x03].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_arq_unacknowledged() {
+        let mut sender = ArqEndpoint::<4>::new(10, 1);
+
+        assert_eq!(
+            sender.send(&[0x01, 0x02, 0x03], 0),
+            Err(SlipArqError::PayloadTooLong)
+        );
+        assert!(sender.send(&[0x01], 0).is_ok());
+        assert!(sender.poll(10).unwrap().is_some());
+        assert_eq!(sender.poll(20), Err(SlipArqError::Unacknowledged));
+        assert!(!sender.is_awaiting_ack());
+        assert_eq!(
+            sender.receive(&[0x02, 0x00]),
+            Err(SlipArqError::MalformedFrame)
+        );
+        assert_eq!(
+            sender.receive(&[ACK_KIND]),
+            Err(SlipArqError::MalformedFrame)
+        );
+    }
+}
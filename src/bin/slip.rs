@@ -0,0 +1,284 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::process::ExitCode;
+
+use noalloc_slip_rs::slip::EmptyFramePolicy;
+use noalloc_slip_rs::slip::FeedStatus;
+use noalloc_slip_rs::slip::SlipConfig;
+use noalloc_slip_rs::slip::SlipEncoder;
+use noalloc_slip_rs::slip::SlipVecDecoder;
+
+/// Help message printed on invalid arguments.
+const USAGE: &str = "\
+usage: slip <encode|decode> [--hex] [FILE]
+
+Encodes or decodes SLIP frames, reading FILE or the standard input and writing to the standard
+output.
+
+options:
+  --hex  read and write hexadecimal text instead of raw bytes: when encoding, every input line
+         is a payload; when decoding, every frame is printed on its own line";
+
+/// What the tool does with its input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Encode the input as frames.
+    Encode,
+    /// Decode the frames of the input.
+    Decode,
+}
+
+/// The command-line options of the tool.
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    mode: Mode,
+    hex: bool,
+    path: Option<String>,
+}
+
+/// Parses the command-line arguments, program name excluded.
+///
+/// Returns `None` if the arguments are invalid.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Option<Options> {
+    let mut args = args.into_iter();
+    let mode = match args.next()?.as_str() {
+        "encode" => Mode::Encode,
+        "decode" => Mode::Decode,
+        _ => return None,
+    };
+
+    let mut hex = false;
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            "--hex" => hex = true,
+            _ if arg.starts_with('-') || path.is_some() => return None,
+            _ => path = Some(arg),
+        }
+    }
+
+    Some(Options { mode, hex, path })
+}
+
+/// Parses hexadecimal text into bytes, ignoring whitespace.
+fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits = text
+        .chars()
+        .filter(|digit| !digit.is_whitespace())
+        .map(|digit| {
+            digit
+                .to_digit(16)
+                .and_then(|value| u8::try_from(value).ok())
+                .ok_or_else(|| format!("invalid hexadecimal digit: {digit:?}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if digits.len() % 2 != 0 {
+        return Err(String::from("odd number of hexadecimal digits"));
+    }
+
+    Ok(digits
+        .chunks_exact(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect())
+}
+
+/// Writes `bytes` as a line of space-separated hexadecimal bytes.
+fn write_hex(output: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    let line = bytes
+        .iter()
+        .map(|value| format!("{value:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    writeln!(output, "{line}")
+}
+
+/// Encodes `input` as frames into `output`.
+///
+/// In hexadecimal mode, every non-empty line of `input` is a payload. Otherwise, the whole of
+/// `input` is a single payload.
+fn encode(input: &[u8], hex: bool, output: &mut impl Write) -> Result<(), String> {
+    let encoder = SlipEncoder::default();
+
+    if !hex {
+        return output
+            .write_all(&encoder.encode_to_vec(input))
+            .map_err(|error| error.to_string());
+    }
+
+    let text = std::str::from_utf8(input).map_err(|error| error.to_string())?;
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        let payload = parse_hex(line)?;
+        write_hex(output, &encoder.encode_to_vec(&payload)).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Decodes the frames of `input` into `output`.
+///
+/// Frames that cannot be decoded, and a trailing unfinished frame, are reported on the standard
+/// error. Returns the number of frames dropped.
+fn decode(input: &[u8], hex: bool, output: &mut impl Write) -> Result<usize, String> {
+    let data = if hex {
+        let text = std::str::from_utf8(input).map_err(|error| error.to_string())?;
+        parse_hex(text)?
+    } else {
+        input.to_vec()
+    };
+
+    // Captures hold back-to-back terminators between frames, which are no frames of their own
+    let config = SlipConfig::new().with_empty_frames(EmptyFramePolicy::Skip);
+    let mut decoder = SlipVecDecoder::with_config(config);
+    let mut data = data.as_slice();
+    let mut dropped = 0;
+    loop {
+        let result = decoder.feed(data);
+        data = &data[result.consumed..];
+
+        match result.status {
+            FeedStatus::Completed => {
+                let frame = decoder.take_frame().unwrap_or_default();
+                if hex {
+                    write_hex(output, &frame)
+                } else {
+                    output.write_all(&frame)
+                }
+                .map_err(|error| error.to_string())?;
+            }
+            FeedStatus::Rejected(error) => {
+                eprintln!("slip: frame dropped: {error}");
+                decoder.reset();
+                dropped += 1;
+            }
+            FeedStatus::Incomplete => {
+                if !decoder.get_buffer().is_empty() {
+                    eprintln!("slip: unfinished frame dropped at the end of the input");
+                    dropped += 1;
+                }
+
+                return Ok(dropped);
+            }
+        }
+    }
+}
+
+/// Reads the whole input of the tool.
+fn read_input(path: Option<&str>) -> io::Result<Vec<u8>> {
+    if let Some(path) = path {
+        return fs::read(path);
+    }
+
+    let mut input = Vec::new();
+    io::stdin().read_to_end(&mut input)?;
+
+    Ok(input)
+}
+
+/// Runs the tool with `options`.
+///
+/// Returns `true` if every frame was handled.
+fn run(options: &Options) -> Result<bool, String> {
+    let input = read_input(options.path.as_deref()).map_err(|error| error.to_string())?;
+    let mut output = io::stdout().lock();
+
+    let handled = match options.mode {
+        Mode::Encode => encode(&input, options.hex, &mut output).map(|()| true)?,
+        Mode::Decode => decode(&input, options.hex, &mut output)? == 0,
+    };
+    output.flush().map_err(|error| error.to_string())?;
+
+    Ok(handled)
+}
+
+fn main() -> ExitCode {
+    let Some(options) = parse_args(env::args().skip(1)) else {
+        eprintln!("{USAGE}");
+
+        return ExitCode::from(2);
+    };
+
+    match run(&options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("slip: {error}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_slip_rs::slip::END_CHAR;
+    use noalloc_slip_rs::slip::ESC_CHAR;
+    use noalloc_slip_rs::slip::ESC_END_CHAR;
+
+    use crate::Mode;
+    use crate::Options;
+    use crate::decode;
+    use crate::encode;
+    use crate::parse_args;
+    use crate::parse_hex;
+
+    #[test]
+    fn test_parse_args() {
+        let args = ["decode", "--hex", "capture.txt"].map(String::from);
+
+        assert_eq!(
+            parse_args(args),
+            Some(Options {
+                mode: Mode::Decode,
+                hex: true,
+                path: Some(String::from("capture.txt")),
+            })
+        );
+        assert_eq!(parse_args(["send"].map(String::from)), None);
+        assert_eq!(parse_args(["encode", "--raw"].map(String::from)), None);
+        assert_eq!(parse_args(["encode", "a", "b"].map(String::from)), None);
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("C0 01\tdb\n"), Ok(vec![END_CHAR, 0x01, ESC_CHAR]));
+        assert!(parse_hex("C0 1").is_err());
+        assert!(parse_hex("G0").is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut output = Vec::new();
+        encode(&[0x01, END_CHAR], false, &mut output).unwrap();
+
+        assert_eq!(output, [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        let mut output = Vec::new();
+        encode(b"01 C0\n\n02\n", true, &mut output).unwrap();
+
+        assert_eq!(output, b"C0 01 DB DC C0\nC0 02 C0\n");
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut output = Vec::new();
+        let dropped = decode(
+            b"C0 01 DB DC C0 C0 DB 00 C0 C0 02 C0 C0 03",
+            true,
+            &mut output,
+        );
+
+        assert_eq!(dropped, Ok(2));
+        assert_eq!(output, b"01 C0\n02\n");
+
+        let mut output = Vec::new();
+        let dropped = decode(&[END_CHAR, 0x01, END_CHAR], false, &mut output);
+
+        assert_eq!(dropped, Ok(0));
+        assert_eq!(output, [0x01]);
+    }
+}
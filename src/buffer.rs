@@ -0,0 +1,456 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+
+/// The storage of the bytes decoded from a frame.
+///
+/// This trait is what a decoder needs from its buffer: appending decoded bytes, dropping them
+/// between frames, and exposing them once a frame is complete. Implementing it for another
+/// container lets `SlipBufferDecoder` store frames there without any change to the decoder.
+pub trait FrameBuffer {
+    /// Appends `value` to the buffer.
+    ///
+    /// Returns `Err(SlipDecodeError::BufferFull)` if the buffer cannot hold another byte.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError>;
+
+    /// Removes every byte from the buffer.
+    fn clear(&mut self);
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8];
+
+    /// Returns the maximum number of bytes the buffer can hold.
+    fn capacity(&self) -> usize;
+
+    /// Appends all of `values` to the buffer.
+    ///
+    /// Returns `Err(SlipDecodeError::BufferFull)`, leaving the buffer untouched, if the buffer
+    /// cannot hold them all. The default implementation pushes the bytes one at a time.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        if values.len() > self.capacity().saturating_sub(self.len()) {
+            return Err(SlipDecodeError::BufferFull);
+        }
+
+        for &value in values {
+            self.push(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends `value` to the buffer, which the caller has checked to have room for it.
+    ///
+    /// This is the fast path of `SlipBufferDecoder::insert_unchecked`. The default implementation
+    /// pushes the byte, dropping it if the buffer is full after all.
+    fn push_unchecked(&mut self, value: u8) {
+        let _ = self.push(value);
+    }
+
+    /// Returns the number of bytes appended since the buffer was last cleared.
+    ///
+    /// This is the length of `as_slice` for buffers storing every byte, which is what the default
+    /// implementation returns.
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if no byte was appended since the buffer was last cleared.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A frame buffer over a borrowed byte slice.
+///
+/// The capacity is the length of the slice, chosen at runtime, so that buffers can come from a
+/// pool without a const generic per size.
+pub struct SliceBuffer<'a> {
+    data: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceBuffer<'a> {
+    /// Creates an empty buffer storing its bytes in `data`.
+    #[must_use]
+    pub const fn new(data: &'a mut [u8]) -> Self {
+        Self { data, len: 0 }
+    }
+}
+
+/// Implementation of `FrameBuffer` for `SliceBuffer`.
+impl FrameBuffer for SliceBuffer<'_> {
+    /// Appends `value`, unless the slice is full.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        let slot = self
+            .data
+            .get_mut(self.len)
+            .ok_or(SlipDecodeError::BufferFull)?;
+        *slot = value;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Copies `values` at once, unless the slice cannot hold them all.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        let slots = self
+            .data
+            .get_mut(self.len..self.len + values.len())
+            .ok_or(SlipDecodeError::BufferFull)?;
+        slots.copy_from_slice(values);
+        self.len += values.len();
+
+        Ok(())
+    }
+
+    /// Removes every byte from the buffer.
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns the length of the borrowed slice.
+    fn capacity(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// A frame buffer counting bytes without storing them.
+///
+/// This backs `SlipValidator`, which checks the framing of a stream without the payloads taking
+/// any memory: `as_slice` is always empty, while `len` counts the bytes of the current frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CountingBuffer {
+    len: usize,
+    capacity: usize,
+}
+
+impl CountingBuffer {
+    /// Creates a buffer counting any number of bytes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_capacity(usize::MAX)
+    }
+
+    /// Creates a buffer counting up to `capacity` bytes, so that longer frames are reported as
+    /// they would be by a buffer of that size.
+    #[must_use]
+    pub const fn with_capacity(capacity: usize) -> Self {
+        Self { len: 0, capacity }
+    }
+}
+
+/// Default implementation for `CountingBuffer`.
+impl Default for CountingBuffer {
+    /// Creates a buffer counting any number of bytes.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of `FrameBuffer` for `CountingBuffer`.
+impl FrameBuffer for CountingBuffer {
+    /// Counts `value`, unless `capacity` bytes are already counted.
+    fn push(&mut self, _value: u8) -> Result<(), SlipDecodeError> {
+        if self.len == self.capacity {
+            return Err(SlipDecodeError::BufferFull);
+        }
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Counts `values` at once, unless `capacity` would be exceeded.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        if values.len() > self.capacity - self.len {
+            return Err(SlipDecodeError::BufferFull);
+        }
+        self.len += values.len();
+
+        Ok(())
+    }
+
+    /// Resets the count.
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Returns an empty slice, as no byte is stored.
+    fn as_slice(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Returns the maximum number of bytes counted in a frame.
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes counted.
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Implementation of `FrameBuffer` for the fixed-capacity `Vec`.
+impl<const MAX_LENGTH: usize> FrameBuffer for Vec<u8, MAX_LENGTH> {
+    /// Appends `value`, unless `MAX_LENGTH` bytes are already held.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        Self::push(self, value).map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Appends `value` without checking the capacity of the vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `MAX_LENGTH` bytes are already held.
+    fn push_unchecked(&mut self, value: u8) {
+        Self::push_unchecked(self, value);
+    }
+
+    /// Copies `values` at once, unless the vector cannot hold them all.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        if values.len() > MAX_LENGTH.saturating_sub(self.len()) {
+            return Err(SlipDecodeError::BufferFull);
+        }
+
+        // Pushing byte by byte keeps every write bounds-checked, unlike `write_slice`
+        for &value in values {
+            let _ = Self::push(self, value);
+        }
+
+        Ok(())
+    }
+
+    /// Removes every byte from the buffer.
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        Self::as_slice(self)
+    }
+
+    /// Returns `MAX_LENGTH`.
+    fn capacity(&self) -> usize {
+        MAX_LENGTH
+    }
+}
+
+/// Implementation of `FrameBuffer` for the growable `alloc::vec::Vec`.
+#[cfg(all(feature = "alloc", not(feature = "allocator-api")))]
+impl FrameBuffer for alloc::vec::Vec<u8> {
+    /// Appends `value`, growing the buffer as needed.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        Self::push(self, value);
+
+        Ok(())
+    }
+
+    /// Copies `values` at once, growing the buffer as needed.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        Self::extend_from_slice(self, values);
+
+        Ok(())
+    }
+
+    /// Removes every byte from the buffer, keeping its allocation.
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        Self::as_slice(self)
+    }
+
+    /// Returns `usize::MAX`, as the buffer is only bounded by the available memory.
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+}
+
+/// Implementation of `FrameBuffer` for the growable `alloc::vec::Vec`, over any allocator.
+#[cfg(feature = "allocator-api")]
+impl<A: core::alloc::Allocator> FrameBuffer for alloc::vec::Vec<u8, A> {
+    /// Appends `value`, growing the buffer as needed.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        Self::push(self, value);
+
+        Ok(())
+    }
+
+    /// Copies `values` at once, growing the buffer as needed.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        Self::extend_from_slice(self, values);
+
+        Ok(())
+    }
+
+    /// Removes every byte from the buffer, keeping its allocation.
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        Self::as_slice(self)
+    }
+
+    /// Returns `usize::MAX`, as the buffer is only bounded by the available memory.
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+}
+
+/// Implementation of `FrameBuffer` for an optional buffer.
+///
+/// A missing buffer holds nothing and has no room, e.g. while a pool has no free buffer to hand
+/// out: the bytes of a frame are then rejected as `SlipDecodeError::BufferFull`.
+impl<B: FrameBuffer> FrameBuffer for Option<B> {
+    /// Appends `value` to the buffer, if any.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.as_mut()
+            .map_or(Err(SlipDecodeError::BufferFull), |buffer| {
+                buffer.push(value)
+            })
+    }
+
+    /// Appends all of `values` to the buffer, if any.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        self.as_mut()
+            .map_or(Err(SlipDecodeError::BufferFull), |buffer| {
+                buffer.extend_from_slice(values)
+            })
+    }
+
+    /// Removes every byte from the buffer, if any.
+    fn clear(&mut self) {
+        if let Some(buffer) = self {
+            buffer.clear();
+        }
+    }
+
+    /// Returns the bytes held by the buffer, if any.
+    fn as_slice(&self) -> &[u8] {
+        self.as_ref().map_or(&[], FrameBuffer::as_slice)
+    }
+
+    /// Returns the capacity of the buffer, or zero if there is none.
+    fn capacity(&self) -> usize {
+        self.as_ref().map_or(0, FrameBuffer::capacity)
+    }
+
+    /// Returns the number of bytes appended to the buffer, if any.
+    fn len(&self) -> usize {
+        self.as_ref().map_or(0, FrameBuffer::len)
+    }
+}
+
+/// Implementation of `FrameBuffer` for `heapless::Vec`.
+#[cfg(feature = "heapless")]
+impl<const MAX_LENGTH: usize> FrameBuffer for heapless::Vec<u8, MAX_LENGTH> {
+    /// Appends `value`, unless `MAX_LENGTH` bytes are already held.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        Self::push(self, value).map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Removes every byte from the buffer.
+    fn clear(&mut self) {
+        Self::clear(self);
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        Self::as_slice(self)
+    }
+
+    /// Returns `MAX_LENGTH`.
+    fn capacity(&self) -> usize {
+        MAX_LENGTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer::CountingBuffer;
+    use crate::buffer::FrameBuffer;
+    use crate::buffer::SliceBuffer;
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipBufferDecoder;
+    use crate::slip::SlipConfig;
+
+    /// A buffer keeping the last byte of a frame only.
+    #[derive(Default)]
+    struct LastByte(Option<u8>);
+
+    impl FrameBuffer for LastByte {
+        fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+            self.0 = Some(value);
+
+            Ok(())
+        }
+
+        fn clear(&mut self) {
+            self.0 = None;
+        }
+
+        fn as_slice(&self) -> &[u8] {
+            self.0.as_slice()
+        }
+
+        fn capacity(&self) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn test_slice_buffer() {
+        let mut data = [0x00; 2];
+        let mut buffer = SliceBuffer::new(&mut data);
+
+        assert_eq!(buffer.capacity(), 2);
+
+        buffer.push(0x01).unwrap();
+        buffer.push(0x02).unwrap();
+
+        assert_eq!(buffer.push(0x03), Err(SlipDecodeError::BufferFull));
+        assert_eq!(buffer.as_slice(), [0x01, 0x02]);
+
+        buffer.clear();
+
+        assert!(buffer.as_slice().is_empty());
+    }
+
+    #[test]
+    fn test_counting_buffer() {
+        let mut buffer = CountingBuffer::with_capacity(2);
+
+        buffer.push(0x01).unwrap();
+        buffer.push(0x02).unwrap();
+
+        assert_eq!(buffer.push(0x03), Err(SlipDecodeError::BufferFull));
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.as_slice().is_empty());
+
+        buffer.clear();
+
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decoder_with_custom_buffer() {
+        let mut decoder = SlipBufferDecoder::with_buffer(LastByte::default(), SlipConfig::new());
+        let result = decoder.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.capacity(), 1);
+        assert_eq!(decoder.get_buffer(), [0x02]);
+    }
+}
@@ -0,0 +1,210 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::dispatch::FrameSink;
+use crate::queue::FrameProducer;
+
+/// A set of consumers, each handed every completed frame.
+///
+/// This struct is itself a `FrameSink`, so that a single `SlipDispatcher` tees the stream to up
+/// to `CONSUMERS` registered sinks, e.g. an IP stack, a logger and a diagnostics task, instead of
+/// one decoder per consumer. Sinks are handed the frame in the order they were registered, by
+/// reference: a sink keeping the frame for later copies it, as a `FrameSlot` or a
+/// `FrameProducer` does.
+pub struct FrameBus<'a, const CONSUMERS: usize> {
+    sinks: Vec<&'a mut dyn FrameSink, CONSUMERS>,
+}
+
+impl<'a, const CONSUMERS: usize> FrameBus<'a, CONSUMERS> {
+    /// Creates a bus without any consumer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { sinks: Vec::new() }
+    }
+
+    /// Registers `sink`, to be handed every frame from now on.
+    ///
+    /// Returns `Err(sink)` if `CONSUMERS` sinks are already registered.
+    pub fn register(&mut self, sink: &'a mut dyn FrameSink) -> Result<(), &'a mut dyn FrameSink> {
+        self.sinks.push(sink)
+    }
+
+    /// Returns the number of registered sinks.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if no sink is registered.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+}
+
+/// Default implementation for `FrameBus`.
+impl<const CONSUMERS: usize> Default for FrameBus<'_, CONSUMERS> {
+    /// Creates a bus without any consumer.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of `FrameSink` for `FrameBus`.
+impl<const CONSUMERS: usize> FrameSink for FrameBus<'_, CONSUMERS> {
+    /// Hands `frame` to every registered sink.
+    fn on_frame(&mut self, frame: &[u8]) {
+        for sink in self.sinks.as_mut_slice() {
+            sink.on_frame(frame);
+        }
+    }
+}
+
+/// A single-frame slot, holding a copy of a frame until its consumer takes it.
+///
+/// As a `FrameSink`, this struct copies the frame it is handed if it is empty, so that a consumer
+/// of a `FrameBus` gets its own copy, independent of the pace of the other consumers. Frames
+/// handed while the slot is full, or longer than `MAX_LENGTH`, are dropped and counted as
+/// missed.
+pub struct FrameSlot<const MAX_LENGTH: usize> {
+    frame: Vec<u8, MAX_LENGTH>,
+    full: bool,
+    missed: usize,
+}
+
+impl<const MAX_LENGTH: usize> FrameSlot<MAX_LENGTH> {
+    /// Creates an empty slot.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frame: Vec::new(),
+            full: false,
+            missed: 0,
+        }
+    }
+
+    /// Returns `true` if the slot holds a frame.
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Returns the number of frames dropped so far.
+    #[must_use]
+    pub const fn missed(&self) -> usize {
+        self.missed
+    }
+
+    /// Calls `f` with the frame held, then empties the slot.
+    ///
+    /// Returns the result of `f`, or `None` without calling it if the slot is empty.
+    pub fn take_with<R>(&mut self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        if !self.full {
+            return None;
+        }
+
+        self.full = false;
+
+        Some(f(self.frame.as_slice()))
+    }
+
+    /// Takes the frame held, emptying the slot.
+    pub fn take(&mut self) -> Option<Vec<u8, MAX_LENGTH>> {
+        if !self.full {
+            return None;
+        }
+
+        self.full = false;
+
+        Some(self.frame.clone())
+    }
+}
+
+/// Default implementation for `FrameSlot`.
+impl<const MAX_LENGTH: usize> Default for FrameSlot<MAX_LENGTH> {
+    /// Creates an empty slot.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of `FrameSink` for `FrameSlot`.
+impl<const MAX_LENGTH: usize> FrameSink for FrameSlot<MAX_LENGTH> {
+    /// Copies `frame` into the slot if it is empty, or counts it as missed.
+    fn on_frame(&mut self, frame: &[u8]) {
+        if self.full || frame.len() > MAX_LENGTH {
+            self.missed += 1;
+
+            return;
+        }
+
+        self.frame.clear();
+        // The frame fits, as its length was checked above
+        let _ = self.frame.write_slice(0, frame);
+        self.full = true;
+    }
+}
+
+/// Implementation of `FrameSink` for `FrameProducer`.
+impl<const SLOTS: usize, const MAX_LENGTH: usize> FrameSink
+    for FrameProducer<'_, SLOTS, MAX_LENGTH>
+{
+    /// Pushes a copy of `frame` into the queue, dropping it if the queue is full.
+    fn on_frame(&mut self, frame: &[u8]) {
+        let _ = self.push(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bus::FrameBus;
+    use crate::bus::FrameSlot;
+    use crate::dispatch::FrameSink;
+    use crate::dispatch::SlipDispatcher;
+    use crate::queue::FrameQueue;
+    use crate::slip::END_CHAR;
+
+    #[test]
+    fn test_bus_fans_out_frames() {
+        let mut queue = FrameQueue::<4, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut slot = FrameSlot::<4>::new();
+        let mut total = 0;
+        let mut count = |frame: &[u8]| total += frame.len();
+
+        let mut bus = FrameBus::<3>::new();
+        bus.register(&mut producer).ok().unwrap();
+        bus.register(&mut slot).ok().unwrap();
+        bus.register(&mut count).ok().unwrap();
+
+        let mut extra = |_: &[u8]| {};
+        assert!(bus.register(&mut extra).is_err());
+        assert_eq!(bus.len(), 3);
+
+        let mut dispatcher = SlipDispatcher::<_, 4>::new(bus);
+        let frames = dispatcher.feed(&[END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, 0x03, END_CHAR]);
+        assert_eq!(frames, 2);
+        drop(dispatcher);
+
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x01]);
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x02, 0x03]);
+        assert_eq!(slot.take().unwrap().as_slice(), [0x01]);
+        assert_eq!(slot.missed(), 1);
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_slot_holds_one_frame() {
+        let mut slot = FrameSlot::<2>::new();
+
+        assert_eq!(slot.take_with(<[u8]>::len), None);
+
+        slot.on_frame(&[0x01, 0x02, 0x03]);
+        slot.on_frame(&[0x04]);
+        slot.on_frame(&[0x05]);
+
+        assert!(slot.is_full());
+        assert_eq!(slot.missed(), 2);
+        assert_eq!(slot.take_with(|frame| frame == [0x04]), Some(true));
+        assert!(!slot.is_full());
+    }
+}
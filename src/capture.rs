@@ -0,0 +1,439 @@
+use std::io;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::time::Instant;
+use std::vec::Vec;
+
+use crate::buffer::FrameBuffer;
+use crate::error::SlipDecodeError;
+use crate::slip::FeedStatus;
+use crate::slip::SlipBufferDecoder;
+
+/// Bytes opening every capture file.
+pub const CAPTURE_MAGIC: [u8; 8] = *b"SLIPCAP1";
+
+/// Length of the header of a record: timestamp, direction and data length.
+const RECORD_HEADER_LENGTH: usize = 13;
+
+/// Direction of the bytes of a capture record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Bytes read from the transport.
+    Received,
+    /// Bytes written to the transport.
+    Sent,
+}
+
+/// A chunk of bytes passed through a transport, as read or written at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Microseconds elapsed since the start of the capture.
+    pub timestamp: u64,
+    /// Direction of the bytes.
+    pub direction: Direction,
+    /// Bytes of the chunk.
+    pub data: Vec<u8>,
+}
+
+/// A writer of capture files.
+///
+/// A capture file starts with `CAPTURE_MAGIC`, followed by one record per chunk: a little-endian
+/// `u64` timestamp, a direction byte, `0` for received bytes and `1` for sent bytes, a
+/// little-endian `u32` data length, then the data.
+pub struct CaptureWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Creates a writer over `inner`, writing the capture file header.
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(&CAPTURE_MAGIC)?;
+
+        Ok(Self { inner })
+    }
+
+    /// Writes a record of `data`, passed in `direction` at `timestamp`.
+    ///
+    /// Returns `ErrorKind::InvalidInput` if `data` is longer than `u32::MAX` bytes.
+    pub fn write_record(
+        &mut self,
+        timestamp: u64,
+        direction: Direction,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let length = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "capture record too long"))?;
+
+        let mut header = [0x00; RECORD_HEADER_LENGTH];
+        header[..8].copy_from_slice(&timestamp.to_le_bytes());
+        header[8] = match direction {
+            Direction::Received => 0,
+            Direction::Sent => 1,
+        };
+        header[9..].copy_from_slice(&length.to_le_bytes());
+
+        self.inner.write_all(&header)?;
+        self.inner.write_all(data)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Consumes the writer, returning the underlying writer.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A reader of capture files, iterating over their records.
+pub struct CaptureReader<R> {
+    inner: R,
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Creates a reader over `inner`, checking the capture file header.
+    ///
+    /// Returns `ErrorKind::InvalidData` if `inner` does not start with `CAPTURE_MAGIC`.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut magic = [0x00; CAPTURE_MAGIC.len()];
+        inner.read_exact(&mut magic)?;
+        if magic != CAPTURE_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a SLIP capture file",
+            ));
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Reads the next record, or `None` at the end of the file.
+    fn read_record(&mut self) -> io::Result<Option<CaptureRecord>> {
+        let mut header = [0x00; RECORD_HEADER_LENGTH];
+        let mut filled = 0;
+        while filled < header.len() {
+            match self.inner.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+                Ok(length) => filled += length,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut timestamp = [0x00; 8];
+        timestamp.copy_from_slice(&header[..8]);
+        let direction = match header[8] {
+            0 => Direction::Received,
+            1 => Direction::Sent,
+            _ => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "invalid capture record direction",
+                ));
+            }
+        };
+        let mut length = [0x00; 4];
+        length.copy_from_slice(&header[9..]);
+
+        let mut data = Vec::new();
+        let length = u64::from(u32::from_le_bytes(length));
+        if (&mut self.inner).take(length).read_to_end(&mut data)? as u64 != length {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        Ok(Some(CaptureRecord {
+            timestamp: u64::from_le_bytes(timestamp),
+            direction,
+            data,
+        }))
+    }
+
+    /// Consumes the reader, returning the underlying reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Implementation of `Iterator` for `CaptureReader`.
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = io::Result<CaptureRecord>;
+
+    /// Returns the next record, or `None` at the end of the file.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// A `std::io` transport recording the bytes passed through it to a capture file.
+///
+/// Every read and every write is recorded as a chunk, timestamped from the creation of the
+/// recorder. Wrapping the transport of a `SlipFrameReader`, `SlipFrameWriter` or
+/// `SlipTransport` captures the raw byte stream of the link, chunk boundaries included.
+pub struct Recorder<T, W> {
+    inner: T,
+    capture: CaptureWriter<W>,
+    start: Instant,
+}
+
+impl<T, W: Write> Recorder<T, W> {
+    /// Creates a recorder over `inner`, recording to `capture`.
+    #[must_use]
+    pub fn new(inner: T, capture: CaptureWriter<W>) -> Self {
+        Self {
+            inner,
+            capture,
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the microseconds elapsed since the creation of the recorder.
+    fn timestamp(&self) -> u64 {
+        u64::try_from(self.start.elapsed().as_micros()).unwrap_or(u64::MAX)
+    }
+
+    /// Consumes the recorder, returning the underlying transport and capture writer.
+    #[must_use]
+    pub fn into_parts(self) -> (T, CaptureWriter<W>) {
+        (self.inner, self.capture)
+    }
+}
+
+/// Implementation of `Read` for `Recorder`.
+impl<T: Read, W: Write> Read for Recorder<T, W> {
+    /// Reads bytes from the transport, recording them as received.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        if length > 0 {
+            let timestamp = self.timestamp();
+            self.capture
+                .write_record(timestamp, Direction::Received, &buf[..length])?;
+        }
+
+        Ok(length)
+    }
+}
+
+/// Implementation of `Write` for `Recorder`.
+impl<T: Write, W: Write> Write for Recorder<T, W> {
+    /// Writes bytes to the transport, recording the ones taken as sent.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let length = self.inner.write(buf)?;
+        if length > 0 {
+            let timestamp = self.timestamp();
+            self.capture
+                .write_record(timestamp, Direction::Sent, &buf[..length])?;
+        }
+
+        Ok(length)
+    }
+
+    /// Flushes both the transport and the capture file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        self.capture.flush()
+    }
+}
+
+/// Feeds the received chunks of `records` back through `decoder`, calling `on_frame` with every
+/// frame decoded, or decode error, along with the timestamp of the chunk completing it.
+///
+/// Chunks are fed in order with `feed_at`, at their timestamp, so that a replay gives the same
+/// frames and errors on every run, inter-byte timeouts included, once set in microseconds. Sent
+/// chunks are skipped. On a decode error, the frame is dropped and decoding goes on with the
+/// following bytes.
+pub fn replay<B: FrameBuffer>(
+    records: impl IntoIterator<Item = io::Result<CaptureRecord>>,
+    decoder: &mut SlipBufferDecoder<B>,
+    mut on_frame: impl FnMut(u64, Result<&[u8], SlipDecodeError>),
+) -> io::Result<()> {
+    for record in records {
+        let record = record?;
+        if record.direction != Direction::Received {
+            continue;
+        }
+
+        let mut data = record.data.as_slice();
+        while !data.is_empty() {
+            let result = decoder.feed_at(data, record.timestamp);
+            data = &data[result.consumed..];
+
+            match result.status {
+                FeedStatus::Completed => {
+                    if let Err(error) =
+                        decoder.take_frame_with(|frame| on_frame(record.timestamp, Ok(frame)))
+                    {
+                        on_frame(record.timestamp, Err(error));
+                    }
+                }
+                FeedStatus::Rejected(error) => {
+                    decoder.reset();
+                    on_frame(record.timestamp, Err(error));
+                }
+                FeedStatus::Incomplete => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+    use std::io::Read;
+    use std::io::Write;
+    use std::vec::Vec;
+
+    use crate::capture::CAPTURE_MAGIC;
+    use crate::capture::CaptureReader;
+    use crate::capture::CaptureWriter;
+    use crate::capture::Direction;
+    use crate::capture::Recorder;
+    use crate::capture::replay;
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::SlipDecoder;
+
+    struct MockSerial<'a> {
+        input: &'a [u8],
+        output: Vec<u8>,
+    }
+
+    impl Read for MockSerial<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            // Hands out at most 3 bytes at once, as a serial port
+            let length = buf.len().min(3);
+            self.input.read(&mut buf[..length])
+        }
+    }
+
+    impl Write for MockSerial<'_> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_record_transport() {
+        let serial = MockSerial {
+            input: &[END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR],
+            output: Vec::new(),
+        };
+        let mut recorder = Recorder::new(serial, CaptureWriter::new(Vec::new()).unwrap());
+
+        let mut buf = [0x00; 8];
+        while recorder.read(&mut buf).unwrap() > 0 {}
+        recorder.write_all(&[END_CHAR, 0x02, END_CHAR]).unwrap();
+
+        let (serial, capture) = recorder.into_parts();
+        let capture = capture.into_inner();
+        assert_eq!(serial.output, [END_CHAR, 0x02, END_CHAR]);
+        assert_eq!(capture[..8], CAPTURE_MAGIC);
+
+        let records = CaptureReader::new(capture.as_slice())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let chunks = records
+            .iter()
+            .map(|record| (record.direction, record.data.as_slice()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            chunks,
+            [
+                (Direction::Received, [END_CHAR, 0x01, ESC_CHAR].as_slice()),
+                (Direction::Received, [ESC_END_CHAR, END_CHAR].as_slice()),
+                (Direction::Sent, [END_CHAR, 0x02, END_CHAR].as_slice()),
+            ]
+        );
+        assert!(
+            records
+                .windows(2)
+                .all(|pair| pair[0].timestamp <= pair[1].timestamp)
+        );
+    }
+
+    #[test]
+    fn test_replay_capture() {
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        writer
+            .write_record(0, Direction::Received, &[END_CHAR, 0x01, ESC_CHAR])
+            .unwrap();
+        writer
+            .write_record(5, Direction::Sent, &[END_CHAR, 0x02, END_CHAR])
+            .unwrap();
+        writer
+            .write_record(10, Direction::Received, &[ESC_END_CHAR, END_CHAR, 0x03])
+            .unwrap();
+        writer
+            .write_record(100, Direction::Received, &[END_CHAR, 0x04, END_CHAR])
+            .unwrap();
+        writer
+            .write_record(110, Direction::Received, &[END_CHAR, ESC_CHAR, 0x05])
+            .unwrap();
+        let capture = writer.into_inner();
+
+        let mut slip_decoder = SlipDecoder::<4>::new();
+        slip_decoder.set_inter_byte_timeout(Some(50));
+
+        let mut frames = Vec::new();
+        replay(
+            CaptureReader::new(capture.as_slice()).unwrap(),
+            &mut slip_decoder,
+            |timestamp, frame| frames.push((timestamp, frame.map(<[u8]>::to_vec))),
+        )
+        .unwrap();
+
+        // The byte left half-received before the pause is dropped by the timeout
+        assert_eq!(
+            frames,
+            [
+                (10, Ok([0x01, END_CHAR].to_vec())),
+                (100, Ok([0x04].to_vec())),
+                (110, Err(SlipDecodeError::InvalidEscape(0x05))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_capture() {
+        assert_eq!(
+            CaptureReader::new([0x00; 8].as_slice())
+                .err()
+                .map(|error| error.kind()),
+            Some(ErrorKind::InvalidData)
+        );
+
+        let mut capture = CAPTURE_MAGIC.to_vec();
+        capture.extend_from_slice(&[0x00; 10]);
+        let records = CaptureReader::new(capture.as_slice())
+            .unwrap()
+            .collect::<Vec<_>>();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].as_ref().err().map(std::io::Error::kind),
+            Some(ErrorKind::UnexpectedEof)
+        );
+
+        assert!(
+            CaptureReader::new(CAPTURE_MAGIC.as_slice())
+                .unwrap()
+                .next()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::error::SlipIoError;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+
+/// Maximum packet size of a full-speed USB bulk endpoint, read from the port at once.
+const PACKET_LENGTH: usize = 64;
+
+/// A SLIP link over a poll-driven serial port, such as a USB CDC-ACM port.
+///
+/// The transfers of the class are chunked and poll-driven: after each poll of the USB device,
+/// the port hands out the bytes of the packets received so far, and takes as many bytes as its
+/// endpoint buffer has room for. This struct keeps the bytes read past the end of a frame for the
+/// next one, and the encoded frames the port did not take yet, of up to `TX_LENGTH` bytes, so
+/// that frames can be read and written whatever the packet boundaries. Received frames are at
+/// most `MAX_LENGTH` bytes long.
+///
+/// The port is not owned, as the USB device also needs it for its polls: it is reached through
+/// the closures given to `receive` and `transmit`, which return `Ok(0)` when no byte can be
+/// transferred until the next poll. This crate does not depend on any USB stack, so there is no
+/// adapter for `usbd_serial::SerialPort` yet: its closures are written by the application, mapping
+/// `UsbError::WouldBlock` to `Ok(0)`.
+pub struct CdcAcmLink<const MAX_LENGTH: usize, const TX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    encoder: SlipEncoder,
+    rx: [u8; PACKET_LENGTH],
+    rx_start: usize,
+    rx_end: usize,
+    tx: [u8; TX_LENGTH],
+    tx_start: usize,
+    tx_end: usize,
+}
+
+impl<const MAX_LENGTH: usize, const TX_LENGTH: usize> CdcAcmLink<MAX_LENGTH, TX_LENGTH> {
+    /// Creates a link using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a link using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            encoder: SlipEncoder::with_config(config),
+            rx: [0; PACKET_LENGTH],
+            rx_start: 0,
+            rx_end: 0,
+            tx: [0; TX_LENGTH],
+            tx_start: 0,
+            tx_end: 0,
+        }
+    }
+
+    /// Reads the bytes received by the port with `read`, and copies the next frame into `buf`.
+    ///
+    /// Returns the length of the frame, or `None` once `read` has no more bytes to hand out
+    /// before a frame is complete. The bytes of a partial frame stay pending, to be completed by
+    /// the next call. On a decode error, the frame is dropped and the next call starts over with
+    /// the following bytes.
+    pub fn receive<E>(
+        &mut self,
+        mut read: impl FnMut(&mut [u8]) -> Result<usize, E>,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, SlipIoError<E>> {
+        loop {
+            let result = self.decoder.feed(&self.rx[self.rx_start..self.rx_end]);
+            self.rx_start += result.consumed;
+
+            match result.status {
+                FeedStatus::Completed => {
+                    return match self.decoder.read_frame_into(buf) {
+                        Ok(len) => Ok(Some(len)),
+                        Err(SlipDecodeError::OutputTooSmall(len)) => {
+                            Err(SlipIoError::BufferTooSmall(len))
+                        }
+                        Err(error) => Err(SlipIoError::Decode(error)),
+                    };
+                }
+                FeedStatus::Rejected(error) => {
+                    self.decoder.reset();
+
+                    return Err(SlipIoError::Decode(error));
+                }
+                FeedStatus::Incomplete => {}
+            }
+
+            let length = read(&mut self.rx).map_err(SlipIoError::Io)?;
+            if length == 0 {
+                return Ok(None);
+            }
+
+            self.rx_start = 0;
+            self.rx_end = length.min(PACKET_LENGTH);
+        }
+    }
+
+    /// Encodes `payload` as a frame, queued behind the frames not transmitted yet.
+    ///
+    /// Returns `Err(SlipEncodeError::InsufficientCapacity)` if the frame does not fit in the room
+    /// left in the transmit buffer, with the room available, in which case nothing is queued:
+    /// `transmit` frees up room as the port takes the queued bytes.
+    pub fn queue_frame(&mut self, payload: &[u8]) -> Result<(), SlipEncodeError> {
+        self.tx.copy_within(self.tx_start..self.tx_end, 0);
+        self.tx_end -= self.tx_start;
+        self.tx_start = 0;
+
+        let length = self
+            .encoder
+            .encode_to_slice(payload, &mut self.tx[self.tx_end..])?;
+        self.tx_end += length;
+
+        Ok(())
+    }
+
+    /// Writes the queued bytes to the port with `write`, until it takes no more.
+    ///
+    /// Returns `true` once every queued frame has been taken by the port. Otherwise, the rest is
+    /// written by the next call, e.g. after the next poll of the USB device.
+    pub fn transmit<E>(
+        &mut self,
+        mut write: impl FnMut(&[u8]) -> Result<usize, E>,
+    ) -> Result<bool, E> {
+        while self.tx_start < self.tx_end {
+            let length = write(&self.tx[self.tx_start..self.tx_end])?;
+            if length == 0 {
+                return Ok(false);
+            }
+
+            self.tx_start = (self.tx_start + length).min(self.tx_end);
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the number of encoded bytes waiting to be taken by the port.
+    #[must_use]
+    pub const fn pending_len(&self) -> usize {
+        self.tx_end - self.tx_start
+    }
+
+    /// Drops the frame being received and the frames not transmitted yet.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.rx_start = 0;
+        self.rx_end = 0;
+        self.tx_start = 0;
+        self.tx_end = 0;
+    }
+}
+
+/// Default implementation for `CdcAcmLink`.
+impl<const MAX_LENGTH: usize, const TX_LENGTH: usize> Default
+    for CdcAcmLink<MAX_LENGTH, TX_LENGTH>
+{
+    /// Creates a link using the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::cdc::CdcAcmLink;
+    use crate::error::SlipEncodeError;
+    use crate::error::SlipIoError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    /// Returns a closure handing out `packets` one at a time, as a CDC-ACM port between polls.
+    fn packets<'a>(packets: &'a [&'a [u8]]) -> impl FnMut(&mut [u8]) -> Result<usize, ()> + 'a {
+        let mut packets = packets.iter();
+
+        move |buf| {
+            let Some(packet) = packets.next() else {
+                return Ok(0);
+            };
+            buf[..packet.len()].copy_from_slice(packet);
+
+            Ok(packet.len())
+        }
+    }
+
+    #[test]
+    fn test_receive_across_packets() {
+        let mut link = CdcAcmLink::<4, 16>::new();
+        let mut read = packets(&[
+            &[END_CHAR, 0x01, ESC_CHAR],
+            &[ESC_END_CHAR, END_CHAR, END_CHAR, 0x02],
+            &[END_CHAR],
+        ]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(link.receive(&mut read, &mut buf), Ok(Some(2)));
+        assert_eq!(buf[..2], [0x01, END_CHAR]);
+        assert_eq!(link.receive(&mut read, &mut buf), Ok(Some(1)));
+        assert_eq!(buf[..1], [0x02]);
+        assert_eq!(link.receive(&mut read, &mut buf), Ok(None));
+
+        let mut read = packets(&[&[END_CHAR, 0x03, 0x04, END_CHAR]]);
+        let mut buf = [0x00; 1];
+
+        assert_eq!(
+            link.receive(&mut read, &mut buf),
+            Err(SlipIoError::BufferTooSmall(2))
+        );
+    }
+
+    #[test]
+    fn test_transmit_in_chunks() {
+        let mut link = CdcAcmLink::<4, 8>::new();
+        let mut written = [0x00; 8];
+        let mut len = 0;
+
+        link.queue_frame(&[0x01, END_CHAR]).unwrap();
+
+        assert_eq!(
+            link.queue_frame(&[0x02, 0x03]),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 4,
+                available: 3
+            })
+        );
+
+        // The port takes 3 bytes until the next poll
+        let room = Cell::new(3);
+        let mut write = |data: &[u8]| -> Result<usize, ()> {
+            let length = data.len().min(room.get());
+            written[len..len + length].copy_from_slice(&data[..length]);
+            len += length;
+            room.set(room.get() - length);
+
+            Ok(length)
+        };
+
+        assert_eq!(link.transmit(&mut write), Ok(false));
+        assert_eq!(link.pending_len(), 2);
+
+        link.queue_frame(&[0x02]).unwrap();
+        room.set(8);
+
+        assert_eq!(link.transmit(&mut write), Ok(true));
+        assert_eq!(
+            written,
+            [
+                END_CHAR,
+                0x01,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                END_CHAR,
+                END_CHAR,
+                0x02,
+                END_CHAR
+            ]
+        );
+    }
+}
@@ -0,0 +1,705 @@
+use core::marker::PhantomData;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::buffer::FrameBuffer;
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::ESC_CHAR;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipBufferDecoder;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+use crate::stuffing::ByteStuffingCodec;
+use crate::wcet::WorkBound;
+
+/// Largest digest length supported by `ChecksumValidator`.
+pub const MAX_DIGEST_LENGTH: usize = 8;
+
+/// A checksum protecting the payload of a frame.
+///
+/// The checksum is updated with the payload bytes, in order, then finalized into a digest of
+/// `LENGTH` bytes which is appended to the payload before the closing terminator.
+pub trait FrameChecksum: Default {
+    /// The digest appended to the payload.
+    type Digest: AsRef<[u8]>;
+
+    /// Length of the digest, in bytes.
+    const LENGTH: usize;
+
+    /// Updates the checksum with `bytes`.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the digest of the bytes the checksum has been updated with.
+    fn finalize(self) -> Self::Digest;
+}
+
+/// A SLIP encoder appending a checksum to every frame.
+///
+/// The digest of the checksum `C` over the payload is appended to it, so that it sits right before
+/// the closing terminator.
+pub struct ChecksumEncoder<C> {
+    config: SlipConfig,
+    checksum: PhantomData<C>,
+}
+
+impl<C: FrameChecksum> ChecksumEncoder<C> {
+    /// Creates an encoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            config,
+            checksum: PhantomData,
+        }
+    }
+
+    /// Appends the digest of the payload held by `vec`, then encodes it in place as a SLIP frame.
+    ///
+    /// Returns `Err(SlipEncodeError::BufferTooSmall)` or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the vector is too small to hold the frame,
+    /// in which case it is left untouched.
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+
+        let mut checksum = C::default();
+        checksum.update(vec.as_slice());
+        for &value in checksum.finalize().as_ref() {
+            if vec.push(value).is_err() {
+                vec.truncate(payload_len);
+
+                return Err(SlipEncodeError::BufferTooSmall);
+            }
+        }
+
+        SlipEncoder::with_config(self.config)
+            .encode(vec)
+            .inspect_err(|_| {
+                vec.truncate(payload_len);
+            })
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to compute the checksum, then the encoding itself
+        WorkBound::linear(MAX_LENGTH).then(SlipEncoder::encode_work_bound::<MAX_LENGTH>())
+    }
+
+    /// Encodes `input` as a SLIP frame into `output`, followed by its checksum.
+    ///
+    /// Returns the number of bytes written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` is too small to hold the frame,
+    /// with the length of the frame. Nothing is written to `output` on error.
+    pub fn encode_to_slice(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, SlipEncodeError> {
+        let codec = self.config.codec();
+
+        let mut checksum = C::default();
+        checksum.update(input);
+        let digest = checksum.finalize();
+
+        let required = digest
+            .as_ref()
+            .iter()
+            .map(|&value| 1 + usize::from(codec.substitute(value).is_some()))
+            .fold(codec.encoded_len(input), usize::saturating_add);
+        if required > output.len() {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required,
+                available: output.len(),
+            });
+        }
+
+        let mut writer = SliceWriter {
+            output,
+            index: 0,
+            codec,
+        };
+
+        if self.config.leading_end() {
+            writer.write(codec.delimiter())?;
+        }
+
+        for &value in input.iter().chain(digest.as_ref()) {
+            writer.write_escaped(value)?;
+        }
+
+        writer.write(codec.delimiter())?;
+
+        Ok(writer.index)
+    }
+
+    /// Returns the worst-case work of `encode_to_slice` for an input of `input_len` bytes.
+    #[must_use]
+    pub const fn encode_to_slice_work_bound(input_len: usize) -> WorkBound {
+        // One pass to compute the checksum, one pass to compute the encoded length, one pass to
+        // write the frame
+        WorkBound::linear(input_len)
+            .then(WorkBound::linear(input_len))
+            .then(WorkBound::linear(input_len.saturating_add(C::LENGTH)))
+    }
+}
+
+/// Default implementation for `ChecksumEncoder`.
+impl<C: FrameChecksum> Default for ChecksumEncoder<C> {
+    /// Creates an encoder using the default SLIP configuration.
+    fn default() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+}
+
+/// Implementation of `Clone` for `ChecksumEncoder`.
+impl<C> Clone for ChecksumEncoder<C> {
+    /// Returns a copy of the encoder.
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Implementation of `Copy` for `ChecksumEncoder`.
+impl<C> Copy for ChecksumEncoder<C> {}
+
+/// A cursor writing a SLIP frame into a slice.
+struct SliceWriter<'a> {
+    output: &'a mut [u8],
+    index: usize,
+    codec: ByteStuffingCodec,
+}
+
+impl SliceWriter<'_> {
+    /// Writes `value` as is.
+    fn write(&mut self, value: u8) -> Result<(), SlipEncodeError> {
+        let slot = self
+            .output
+            .get_mut(self.index)
+            .ok_or(SlipEncodeError::BufferTooSmall)?;
+        *slot = value;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    /// Writes `value`, escaping it if needed.
+    fn write_escaped(&mut self, value: u8) -> Result<(), SlipEncodeError> {
+        if let Some(substitute) = self.codec.substitute(value) {
+            self.write(ESC_CHAR)?;
+            self.write(substitute)
+        } else {
+            self.write(value)
+        }
+    }
+}
+
+/// A frame buffer running the checksum of a frame as its bytes are unescaped, holding back from
+/// it only the bytes which may turn out to be its digest.
+struct ChecksumBuffer<C, const MAX_LENGTH: usize> {
+    bytes: Vec<u8, MAX_LENGTH>,
+    checksum: C,
+}
+
+impl<C: FrameChecksum, const MAX_LENGTH: usize> ChecksumBuffer<C, MAX_LENGTH> {
+    /// Creates the buffer of an empty frame.
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            checksum: C::default(),
+        }
+    }
+
+    /// Updates the checksum with the bytes pushed out of the digest window since the buffer held
+    /// `previous_len` bytes.
+    fn update(&mut self, previous_len: usize) {
+        let start = previous_len.saturating_sub(C::LENGTH);
+        let end = self.bytes.len().saturating_sub(C::LENGTH);
+
+        if let Some(bytes) = self.bytes.get(start..end) {
+            self.checksum.update(bytes);
+        }
+    }
+}
+
+/// Implementation of `FrameBuffer` for `ChecksumBuffer`.
+impl<C: FrameChecksum, const MAX_LENGTH: usize> FrameBuffer for ChecksumBuffer<C, MAX_LENGTH> {
+    /// Appends `value`, updating the checksum with the byte it pushes out of the digest window.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        let previous_len = self.bytes.len();
+        FrameBuffer::push(&mut self.bytes, value)?;
+        self.update(previous_len);
+
+        Ok(())
+    }
+
+    /// Copies `values` at once, updating the checksum with the bytes they push out of the digest
+    /// window.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        let previous_len = self.bytes.len();
+        FrameBuffer::extend_from_slice(&mut self.bytes, values)?;
+        self.update(previous_len);
+
+        Ok(())
+    }
+
+    /// Drops the frame, restarting the checksum.
+    fn clear(&mut self) {
+        self.bytes.clear();
+        self.checksum = C::default();
+    }
+
+    /// Returns the decoded bytes, the digest included.
+    fn as_slice(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Returns `MAX_LENGTH`.
+    fn capacity(&self) -> usize {
+        MAX_LENGTH
+    }
+}
+
+/// A SLIP decoder verifying and stripping the checksum of every frame.
+///
+/// `MAX_LENGTH` bounds the decoded frame, the trailing digest included. The checksum `C` is
+/// updated as bytes are unescaped, holding back the last `C::LENGTH` bytes which may turn out to
+/// be the digest, so that completing a frame does not need a second pass over it. A completed
+/// frame whose digest does not match is dropped and reported as
+/// `SlipDecodeError::ChecksumMismatch`.
+pub struct ChecksumDecoder<C, const MAX_LENGTH: usize> {
+    decoder: SlipBufferDecoder<ChecksumBuffer<C, MAX_LENGTH>>,
+}
+
+impl<C: FrameChecksum, const MAX_LENGTH: usize> ChecksumDecoder<C, MAX_LENGTH> {
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipBufferDecoder::with_buffer(ChecksumBuffer::new(), config),
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected, the frame is too long
+    /// to be buffered, or the frame it completes fails its checksum.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+
+        if self.decoder.is_buffer_completed() {
+            self.verify()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        // Completing a frame compares its digest
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound().then(WorkBound::linear(C::LENGTH))
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, as for
+    /// `SlipDecoder::feed`. A completed frame failing its checksum is rejected.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+
+        match result.status {
+            FeedStatus::Completed => match self.verify() {
+                Ok(()) => result,
+                Err(error) => FeedResult {
+                    consumed: result.consumed,
+                    status: FeedStatus::Rejected(error),
+                },
+            },
+            _ => result,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len).then(WorkBound::linear(C::LENGTH))
+    }
+
+    /// Checks the digest of the completed frame, dropping the frame if it does not match.
+    fn verify(&mut self) -> Result<(), SlipDecodeError> {
+        let buffer = self.decoder.buffer_mut();
+        let checksum = core::mem::take(&mut buffer.checksum);
+        let digest = buffer
+            .as_slice()
+            .get(buffer.bytes.len().wrapping_sub(C::LENGTH)..);
+
+        if digest != Some(checksum.finalize().as_ref()) {
+            self.reset();
+
+            return Err(SlipDecodeError::ChecksumMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Resets the decoder for the next frame.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns `true` if a complete frame, with a valid checksum, has been decoded.
+    #[must_use]
+    pub fn is_buffer_completed(&self) -> bool {
+        self.decoder.is_buffer_completed()
+    }
+
+    /// Returns the decoded payload, without its digest.
+    ///
+    /// The payload is only meaningful once the frame is complete.
+    #[must_use]
+    pub fn get_buffer(&self) -> &[u8] {
+        let frame = self.decoder.get_buffer();
+
+        &frame[..frame.len().saturating_sub(C::LENGTH)]
+    }
+}
+
+/// Default implementation for `ChecksumDecoder`.
+impl<C: FrameChecksum, const MAX_LENGTH: usize> Default for ChecksumDecoder<C, MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    fn default() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+}
+
+/// A frame buffer running the checksum of a frame, holding back only the bytes which may turn out
+/// to be its digest.
+struct DigestWindow<C> {
+    checksum: C,
+    tail: [u8; MAX_DIGEST_LENGTH],
+    held: usize,
+    len: usize,
+}
+
+impl<C: FrameChecksum> DigestWindow<C> {
+    /// Creates the window of an empty frame.
+    fn new() -> Self {
+        Self {
+            checksum: C::default(),
+            tail: [0; MAX_DIGEST_LENGTH],
+            held: 0,
+            len: 0,
+        }
+    }
+}
+
+/// Implementation of `FrameBuffer` for `DigestWindow`.
+impl<C: FrameChecksum> FrameBuffer for DigestWindow<C> {
+    /// Holds back `value`, updating the checksum with the byte it pushes out of the window.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        if self.held < C::LENGTH {
+            self.tail[self.held] = value;
+            self.held += 1;
+        } else if C::LENGTH == 0 {
+            self.checksum.update(&[value]);
+        } else {
+            self.checksum.update(&self.tail[..1]);
+            self.tail[..C::LENGTH].rotate_left(1);
+            self.tail[C::LENGTH - 1] = value;
+        }
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Drops the frame, restarting the checksum.
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns the bytes held back, which are the digest once the frame is complete.
+    fn as_slice(&self) -> &[u8] {
+        &self.tail[..self.held]
+    }
+
+    /// Returns `usize::MAX`, as frames of any length can be checked.
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns the number of decoded bytes of the frame, the digest included.
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// A SLIP decoder verifying the checksum of every frame without storing its payload.
+///
+/// This is the validating counterpart of `ChecksumDecoder`: escape sequences and checksums are
+/// verified as bytes arrive, and only the last `C::LENGTH` bytes of a frame are held, so that
+/// frames of any length are checked in constant memory. The digest of `C` must not be longer
+/// than `MAX_DIGEST_LENGTH`.
+pub struct ChecksumValidator<C> {
+    decoder: SlipBufferDecoder<DigestWindow<C>>,
+}
+
+impl<C: FrameChecksum> ChecksumValidator<C> {
+    /// Creates a validator using the framing options of `config`.
+    ///
+    /// The integrity check is turned off, as there is no stored payload to guard.
+    #[must_use]
+    pub fn with_config(config: SlipConfig) -> Self {
+        const {
+            assert!(
+                C::LENGTH <= MAX_DIGEST_LENGTH,
+                "the digest is longer than MAX_DIGEST_LENGTH"
+            );
+        }
+
+        Self {
+            decoder: SlipBufferDecoder::with_buffer(
+                DigestWindow::new(),
+                config.with_integrity_check(false),
+            ),
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected or the frame it
+    /// completes fails its checksum.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+
+        if self.decoder.is_buffer_completed() {
+            self.verify()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        // Every byte shifts the window, and completing a frame compares its digest
+        WorkBound::linear(C::LENGTH).then(WorkBound::linear(C::LENGTH))
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, as for
+    /// `SlipDecoder::feed`. A completed frame failing its checksum is rejected.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+
+        match result.status {
+            FeedStatus::Completed => match self.verify() {
+                Ok(()) => result,
+                Err(error) => FeedResult {
+                    consumed: result.consumed,
+                    status: FeedStatus::Rejected(error),
+                },
+            },
+            _ => result,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len.saturating_mul(C::LENGTH)).then(WorkBound::linear(C::LENGTH))
+    }
+
+    /// Checks the digest of the completed frame, dropping the frame if it does not match.
+    fn verify(&mut self) -> Result<(), SlipDecodeError> {
+        let window = self.decoder.buffer_mut();
+        let checksum = core::mem::take(&mut window.checksum);
+
+        if window.held < C::LENGTH || checksum.finalize().as_ref() != window.as_slice() {
+            self.reset();
+
+            return Err(SlipDecodeError::ChecksumMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Resets the validator for the next frame.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns `true` if a complete frame, with a valid checksum, has been checked.
+    #[must_use]
+    pub fn is_buffer_completed(&self) -> bool {
+        self.decoder.is_buffer_completed()
+    }
+
+    /// Returns the number of payload bytes of the completed frame, without its digest.
+    ///
+    /// The length is only meaningful once the frame is complete.
+    #[must_use]
+    pub fn payload_len(&self) -> usize {
+        self.decoder.frame_len().saturating_sub(C::LENGTH)
+    }
+}
+
+/// Default implementation for `ChecksumValidator`.
+impl<C: FrameChecksum> Default for ChecksumValidator<C> {
+    /// Creates a validator using the default SLIP configuration.
+    fn default() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checksum::ChecksumDecoder;
+    use crate::checksum::ChecksumEncoder;
+    use crate::checksum::ChecksumValidator;
+    use crate::checksum::FrameChecksum;
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::FeedStatus;
+
+    /// A checksum XOR-ing the payload bytes together.
+    #[derive(Default)]
+    struct Xor(u8);
+
+    impl FrameChecksum for Xor {
+        type Digest = [u8; 1];
+
+        const LENGTH: usize = 1;
+
+        fn update(&mut self, bytes: &[u8]) {
+            for value in bytes {
+                self.0 ^= value;
+            }
+        }
+
+        fn finalize(self) -> Self::Digest {
+            [self.0]
+        }
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        let mut output = [0x00; 8];
+        let length = ChecksumEncoder::<Xor>::default()
+            .encode_to_slice(&[0x01, 0x02, END_CHAR ^ 0x03], &mut output)
+            .unwrap();
+
+        assert_eq!(
+            output[..length],
+            [
+                END_CHAR,
+                0x01,
+                0x02,
+                END_CHAR ^ 0x03,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                END_CHAR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_to_slice_too_small() {
+        let mut output = [0x00; 6];
+
+        assert_eq!(
+            ChecksumEncoder::<Xor>::default()
+                .encode_to_slice(&[0x01, 0x02, END_CHAR ^ 0x03], &mut output),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 7,
+                available: 6
+            })
+        );
+        assert_eq!(output, [0x00; 6]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = [
+            END_CHAR, 0x01, 0x02, 0x03, END_CHAR, END_CHAR, 0x01, 0x02, END_CHAR,
+        ];
+        let mut decoder = ChecksumDecoder::<Xor, 8>::default();
+
+        let result = decoder.feed(&data);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x01, 0x02]);
+
+        decoder.reset();
+
+        assert_eq!(
+            decoder.feed(&data[result.consumed..]).status,
+            FeedStatus::Rejected(SlipDecodeError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn test_decode_byte_by_byte() {
+        let mut output = [0x00; 16];
+        let length = ChecksumEncoder::<Xor>::default()
+            .encode_to_slice(&[0x01, ESC_CHAR, 0x03, 0x04], &mut output)
+            .unwrap();
+
+        let mut decoder = ChecksumDecoder::<Xor, 8>::default();
+        for &value in &output[..length] {
+            decoder.insert(value).unwrap();
+        }
+
+        assert!(decoder.is_buffer_completed());
+        assert_eq!(decoder.get_buffer(), [0x01, ESC_CHAR, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn test_decode_in_chunks() {
+        let mut output = [0x00; 16];
+        let length = ChecksumEncoder::<Xor>::default()
+            .encode_to_slice(&[0x01, 0x02, ESC_CHAR, 0x04, 0x05], &mut output)
+            .unwrap();
+
+        let mut decoder = ChecksumDecoder::<Xor, 8>::default();
+        for chunk in output[..length].chunks(3) {
+            decoder.feed(chunk);
+        }
+
+        assert!(decoder.is_buffer_completed());
+        assert_eq!(decoder.get_buffer(), [0x01, 0x02, ESC_CHAR, 0x04, 0x05]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut output = [0x00; 16];
+        let length = ChecksumEncoder::<Xor>::default()
+            .encode_to_slice(&[0x01, ESC_CHAR, 0x03, 0x04], &mut output)
+            .unwrap();
+
+        let mut validator = ChecksumValidator::<Xor>::default();
+        for &value in &output[..length] {
+            validator.insert(value).unwrap();
+        }
+
+        assert!(validator.is_buffer_completed());
+        assert_eq!(validator.payload_len(), 4);
+
+        validator.reset();
+        output[1] ^= 0x10;
+
+        assert_eq!(
+            validator.feed(&output[..length]).status,
+            FeedStatus::Rejected(SlipDecodeError::ChecksumMismatch)
+        );
+        assert!(!validator.is_buffer_completed());
+    }
+}
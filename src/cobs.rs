@@ -0,0 +1,517 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::codec::FrameCodec;
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::wcet::WorkBound;
+
+/// Marks the end of a COBS frame.
+pub const COBS_DELIMITER: u8 = 0x00;
+
+/// Largest code byte, introducing a block of 254 non-zero bytes without a trailing zero.
+const MAX_CODE: u8 = 0xFF;
+
+/// A COBS encoder.
+///
+/// Consistent Overhead Byte Stuffing removes every zero byte from the payload, at the cost of
+/// one byte per 254 bytes, so that a zero byte can terminate frames. This struct mirrors the
+/// interface of `SlipEncoder`, so that switching framing is a type change.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CobsEncoder;
+
+impl CobsEncoder {
+    /// Creates a COBS encoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Returns the number of bytes `payload` occupies once encoded, delimiter included.
+    #[must_use]
+    pub fn encoded_len(&self, payload: &[u8]) -> usize {
+        let mut code = 1;
+        let mut full_blocks = 0;
+
+        for &value in payload {
+            if value == COBS_DELIMITER {
+                code = 1;
+            } else {
+                code += 1;
+
+                if code == MAX_CODE {
+                    full_blocks += 1;
+                    code = 1;
+                }
+            }
+        }
+
+        payload.len() + full_blocks + 2
+    }
+
+    /// Encodes `vec` in place as a COBS frame.
+    ///
+    /// The payload is first moved to the end of the frame, then encoded front-to-back in a
+    /// single pass.
+    ///
+    /// Returns `Ok(())` on success, or `Err(SlipEncodeError::InsufficientCapacity)` if `vec`
+    /// lacks capacity for the framing overhead, with the capacity the frame requires. `vec` is
+    /// left untouched on error.
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+        let frame_len = self.encoded_len(vec);
+        let error = SlipEncodeError::InsufficientCapacity {
+            required: frame_len,
+            available: MAX_LENGTH,
+        };
+        if frame_len > MAX_LENGTH {
+            return Err(error);
+        }
+
+        // Grow the vector to the frame length
+        while vec.len() < frame_len {
+            vec.push(0).map_err(|_| error)?;
+        }
+
+        let payload_start = frame_len - 1 - payload_len;
+        let buffer = vec.as_mut_slice();
+        buffer.copy_within(..payload_len, payload_start);
+        Self::encode_tail(buffer, payload_start);
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to compute the encoded length, one to grow the vector, one to move the
+        // payload, one to encode it
+        WorkBound::linear(MAX_LENGTH)
+            .then(WorkBound::linear(MAX_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Encodes `input` as a COBS frame into `output`.
+    ///
+    /// Returns the number of bytes written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` is too small to hold the frame,
+    /// with the length of the frame. Nothing is written to `output` on error.
+    pub fn encode_to_slice(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, SlipEncodeError> {
+        let frame_len = self.encoded_len(input);
+        let available = output.len();
+        let Some(frame) = output.get_mut(..frame_len) else {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available,
+            });
+        };
+
+        let payload_start = frame_len - 1 - input.len();
+        frame[payload_start..frame_len - 1].copy_from_slice(input);
+        Self::encode_tail(frame, payload_start);
+
+        Ok(frame_len)
+    }
+
+    /// Returns the worst-case work of `encode_to_slice` for an input of `input_len` bytes.
+    #[must_use]
+    pub const fn encode_to_slice_work_bound(input_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one to copy the input, one to encode it
+        WorkBound::linear(input_len)
+            .then(WorkBound::linear(input_len))
+            .then(WorkBound::linear(
+                input_len.saturating_add(input_len / 254 + 2),
+            ))
+    }
+
+    /// Encodes the payload held by `frame` from `payload_start` up to the delimiter slot.
+    ///
+    /// The write index never overtakes the read index, as it only gains on it by the overhead
+    /// that remains to be written.
+    fn encode_tail(frame: &mut [u8], payload_start: usize) {
+        let delimiter_index = frame.len() - 1;
+        let mut code_index = 0;
+        let mut write_index = 1;
+        let mut code = 1;
+
+        let mut read_index = payload_start;
+        while read_index < delimiter_index {
+            let value = frame[read_index];
+            read_index += 1;
+
+            if value == COBS_DELIMITER {
+                frame[code_index] = code;
+                code_index = write_index;
+                write_index += 1;
+                code = 1;
+            } else {
+                frame[write_index] = value;
+                write_index += 1;
+                code += 1;
+
+                if code == MAX_CODE {
+                    frame[code_index] = code;
+                    code_index = write_index;
+                    write_index += 1;
+                    code = 1;
+                }
+            }
+        }
+
+        frame[code_index] = code;
+        frame[delimiter_index] = COBS_DELIMITER;
+    }
+}
+
+/// The state of the COBS decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CobsDecoderState {
+    /// Waiting for a code byte.
+    Code,
+    /// Accumulating the given number of remaining bytes of a block.
+    Block(u8),
+    /// Received the delimiter; frame is complete.
+    End,
+}
+
+/// A COBS decoder.
+///
+/// This struct mirrors the interface of `SlipDecoder`, so that switching framing is a type
+/// change. Delimiters outside of a frame are skipped.
+pub struct CobsDecoder<const MAX_LENGTH: usize> {
+    state: CobsDecoderState,
+    buffer: Vec<u8, MAX_LENGTH>,
+    started: bool,
+    pending_zero: bool,
+}
+
+impl<const MAX_LENGTH: usize> CobsDecoder<MAX_LENGTH> {
+    /// Creates a COBS decoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: CobsDecoderState::Code,
+            buffer: Vec::new(),
+            started: false,
+            pending_zero: false,
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, `Err(SlipDecodeError::MalformedFrame)` if a block is cut
+    /// short by a delimiter, or `Err(SlipDecodeError::BufferFull)` if the buffer is full.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        match self.state {
+            CobsDecoderState::Code => {
+                if value == COBS_DELIMITER {
+                    if self.started {
+                        self.state = CobsDecoderState::End;
+                    }
+
+                    return Ok(());
+                }
+
+                if self.pending_zero {
+                    self.push(COBS_DELIMITER)?;
+                }
+                self.started = true;
+                self.start_block(value);
+
+                Ok(())
+            }
+            CobsDecoderState::Block(remaining) => {
+                if value == COBS_DELIMITER {
+                    return Err(SlipDecodeError::MalformedFrame);
+                }
+
+                self.push(value)?;
+                if remaining == 1 {
+                    self.state = CobsDecoderState::Code;
+                } else {
+                    self.state = CobsDecoderState::Block(remaining - 1);
+                }
+
+                Ok(())
+            }
+            CobsDecoderState::End => Err(SlipDecodeError::FrameCompleted),
+        }
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Starts the block introduced by the code byte `code`.
+    const fn start_block(&mut self, code: u8) {
+        self.pending_zero = code != MAX_CODE;
+        self.state = if code == 1 {
+            CobsDecoderState::Code
+        } else {
+            CobsDecoderState::Block(code - 1)
+        };
+    }
+
+    /// Appends `value` to the buffer.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.buffer
+            .push(value)
+            .map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, so that the caller can
+    /// continue from the leftover bytes once the frame has been handled and the decoder reset.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        for (index, &value) in data.iter().enumerate() {
+            if self.is_buffer_completed() {
+                return FeedResult {
+                    consumed: index,
+                    status: FeedStatus::Completed,
+                };
+            }
+
+            if let Err(error) = self.insert(value) {
+                return FeedResult {
+                    consumed: index + 1,
+                    status: FeedStatus::Rejected(error),
+                };
+            }
+        }
+
+        let status = if self.is_buffer_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult {
+            consumed: data.len(),
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Feeds the bytes of `first` then `second` into the decoder state machine.
+    ///
+    /// This is meant for the two regions of a wrapped-around ring buffer, as for
+    /// `SlipDecoder::feed_split`. Bytes are consumed up to the end of a frame or to a rejected
+    /// byte; `consumed` counts across both slices.
+    pub fn feed_split(&mut self, first: &[u8], second: &[u8]) -> FeedResult {
+        let result = self.feed(first);
+        if result.status != FeedStatus::Incomplete {
+            return result;
+        }
+
+        let result = self.feed(second);
+
+        FeedResult {
+            consumed: first.len() + result.consumed,
+            status: result.status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed_split` for `data_len` bytes across both slices.
+    #[must_use]
+    pub const fn feed_split_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Resets the decoder to its initial state.
+    pub fn reset(&mut self) {
+        self.state = CobsDecoderState::Code;
+        self.buffer.clear();
+        self.started = false;
+        self.pending_zero = false;
+    }
+
+    /// Returns `true` if the decoder has received a complete COBS frame.
+    #[must_use]
+    pub fn is_buffer_completed(&self) -> bool {
+        self.state == CobsDecoderState::End
+    }
+
+    /// Returns a slice of the decoded bytes accumulated so far.
+    #[must_use]
+    pub const fn get_buffer(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+}
+
+/// Default implementation for `CobsDecoder`.
+impl<const MAX_LENGTH: usize> Default for CobsDecoder<MAX_LENGTH> {
+    /// Creates a COBS decoder.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A bidirectional COBS codec.
+///
+/// This struct pairs a `CobsEncoder` with a `CobsDecoder` of frame payloads of up to
+/// `MAX_LENGTH` bytes, to serve as a `FrameCodec`.
+#[derive(Default)]
+pub struct CobsCodec<const MAX_LENGTH: usize> {
+    decoder: CobsDecoder<MAX_LENGTH>,
+}
+
+impl<const MAX_LENGTH: usize> CobsCodec<MAX_LENGTH> {
+    /// Creates a COBS codec.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decoder: CobsDecoder::new(),
+        }
+    }
+}
+
+/// Implementation of `FrameCodec` for `CobsCodec`.
+impl<const MAX_LENGTH: usize> FrameCodec for CobsCodec<MAX_LENGTH> {
+    /// Encodes `payload` as a COBS frame into `out`.
+    fn encode(&self, payload: &[u8], out: &mut [u8]) -> Result<usize, SlipEncodeError> {
+        CobsEncoder::new().encode_to_slice(payload, out)
+    }
+
+    /// Feeds the bytes of `data` into the COBS decoder.
+    fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+        if matches!(result.status, FeedStatus::Rejected(_)) {
+            self.decoder.reset();
+        }
+
+        result
+    }
+
+    /// Calls `f` with the completed COBS frame, then drops it.
+    fn take_frame_with(&mut self, f: &mut dyn FnMut(&[u8])) -> Result<(), SlipDecodeError> {
+        if !self.decoder.is_buffer_completed() {
+            return Err(SlipDecodeError::FrameIncomplete);
+        }
+
+        f(self.decoder.get_buffer());
+        self.decoder.reset();
+
+        Ok(())
+    }
+
+    /// Drops the COBS frame being decoded, if any.
+    fn reset(&mut self) {
+        self.decoder.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::cobs::CobsDecoder;
+    use crate::cobs::CobsEncoder;
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_encode() {
+        let mut vec = Vec::<u8, 8>::from([0x11, 0x00, 0x00, 0x22]);
+        CobsEncoder::new().encode(&mut vec).unwrap();
+
+        assert_eq!(vec.as_slice(), [0x02, 0x11, 0x01, 0x02, 0x22, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_empty() {
+        let mut vec = Vec::<u8, 2>::new();
+        CobsEncoder::new().encode(&mut vec).unwrap();
+
+        assert_eq!(vec.as_slice(), [0x01, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_long_block() {
+        let payload = [0x01; 300];
+        let mut output = [0x00; 304];
+        let length = CobsEncoder::new()
+            .encode_to_slice(&payload, &mut output)
+            .unwrap();
+
+        assert_eq!(length, 303);
+        assert_eq!(output[0], 0xFF);
+        assert_eq!(output[255], 47);
+        assert_eq!(output[302], 0x00);
+
+        let mut decoder = CobsDecoder::<300>::new();
+        let result = decoder.feed(&output[..length]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), payload);
+    }
+
+    #[test]
+    fn test_encode_with_not_enough_space() {
+        let mut vec = Vec::<u8, 4>::from([0x11, 0x22, 0x33]);
+
+        assert_eq!(
+            CobsEncoder::new().encode(&mut vec),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 5,
+                available: 4
+            })
+        );
+        assert_eq!(vec.as_slice(), [0x11, 0x22, 0x33]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = [0x00, 0x02, 0x11, 0x01, 0x02, 0x22, 0x00, 0x01, 0x00];
+        let mut decoder = CobsDecoder::<8>::new();
+
+        let result = decoder.feed(&data);
+
+        assert_eq!(result.consumed, 7);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x11, 0x00, 0x00, 0x22]);
+
+        decoder.reset();
+
+        assert_eq!(decoder.feed(&data[7..]).status, FeedStatus::Completed);
+        assert!(decoder.get_buffer().is_empty());
+    }
+
+    #[test]
+    fn test_decode_split() {
+        let mut decoder = CobsDecoder::<8>::new();
+
+        let result = decoder.feed_split(&[0x02, 0x11], &[0x02, 0x22, 0x00, 0x01]);
+
+        assert_eq!(result.consumed, 5);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x11, 0x00, 0x22]);
+    }
+
+    #[test]
+    fn test_decode_truncated_block() {
+        let mut decoder = CobsDecoder::<8>::new();
+
+        assert_eq!(
+            decoder.feed(&[0x03, 0x11, 0x00]).status,
+            FeedStatus::Rejected(SlipDecodeError::MalformedFrame)
+        );
+    }
+}
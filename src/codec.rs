@@ -0,0 +1,479 @@
+#[cfg(feature = "tokio")]
+use tokio_util::bytes::BufMut;
+#[cfg(feature = "tokio")]
+use tokio_util::bytes::BytesMut;
+#[cfg(feature = "tokio")]
+use tokio_util::codec::Decoder;
+#[cfg(feature = "tokio")]
+use tokio_util::codec::Encoder;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+#[cfg(feature = "tokio")]
+use crate::error::SlipIoError;
+use crate::observer::SlipObserver;
+use crate::observer::observe_feed;
+#[cfg(feature = "tokio")]
+use crate::slip::EscapedSegments;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+#[cfg(feature = "tokio")]
+use crate::slip::FrameResync;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+use crate::slip::SlipFrames;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A framing codec selected at runtime.
+///
+/// This trait is object-safe, so that a gateway can pick the framing of each port at runtime
+/// through a `&mut dyn FrameCodec`, rather than monomorphizing its code for every combination
+/// of ports and framings. It is implemented by `SlipCodec` and `CobsCodec`.
+pub trait FrameCodec {
+    /// Encodes `payload` as a frame into `out`.
+    ///
+    /// Returns the number of bytes written to `out`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `out` is too small to hold the frame.
+    fn encode(&self, payload: &[u8], out: &mut [u8]) -> Result<usize, SlipEncodeError>;
+
+    /// Feeds the bytes of `data` into the decoder.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte. A completed frame is
+    /// held until taken with `take_frame_with`, while a rejected frame is dropped, so that
+    /// decoding resumes with the following bytes.
+    fn feed(&mut self, data: &[u8]) -> FeedResult;
+
+    /// Calls `f` with the completed frame, then drops it to decode the next frame.
+    ///
+    /// Returns `Err(SlipDecodeError::FrameIncomplete)` without calling `f` if no frame is complete
+    /// yet, or the error that made the frame be dropped instead of handed over.
+    fn take_frame_with(&mut self, f: &mut dyn FnMut(&[u8])) -> Result<(), SlipDecodeError>;
+
+    /// Drops the frame being decoded, if any.
+    fn reset(&mut self);
+}
+
+/// A bidirectional SLIP codec, for both ends of a link sharing a single configuration.
+///
+/// This struct encodes outgoing payloads into frames, and decodes incoming bytes into frame
+/// payloads of up to `MAX_LENGTH` bytes. A frame that cannot be decoded is dropped and reported
+/// as an error, and decoding resumes with the following bytes. With the `tokio` feature, it is
+/// also a codec for `tokio_util::codec::Framed` and friends, which drops such a frame without
+/// any error, as an error would end the stream.
+///
+/// The frames encoded, decoded and dropped, and the bytes discarded between frames, are reported
+/// to the `SlipObserver` `O`, which observes nothing by default.
+#[derive(Default)]
+pub struct SlipCodec<const MAX_LENGTH: usize, O = ()> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    config: SlipConfig,
+    observer: O,
+    #[cfg(feature = "tokio")]
+    resync: FrameResync,
+}
+
+impl<const MAX_LENGTH: usize> SlipCodec<MAX_LENGTH> {
+    /// Creates a codec, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a codec, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self::with_observer(config, ())
+    }
+}
+
+impl<const MAX_LENGTH: usize, O: SlipObserver> SlipCodec<MAX_LENGTH, O> {
+    /// Creates a codec reporting to `observer`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_observer(config: SlipConfig, observer: O) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            config,
+            observer,
+            #[cfg(feature = "tokio")]
+            resync: FrameResync::with_config(config),
+        }
+    }
+
+    /// Encodes `payload` as a SLIP frame into `out`.
+    ///
+    /// Returns the number of bytes written to `out`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `out` is too small to hold the frame, with
+    /// the length of the frame. Nothing is written to `out` on error.
+    pub fn encode(&self, payload: &[u8], out: &mut [u8]) -> Result<usize, SlipEncodeError> {
+        let length = SlipEncoder::with_config(self.config).encode_to_slice(payload, out)?;
+        self.observer.frame_encoded(payload.len());
+
+        Ok(length)
+    }
+
+    /// Returns the worst-case work of `encode` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn encode_work_bound(payload_len: usize) -> WorkBound {
+        SlipEncoder::encode_to_slice_work_bound(payload_len)
+    }
+
+    /// Returns an iterator over the frames completed by the bytes of `data`, starting with a frame
+    /// left pending by a previous call.
+    ///
+    /// The bytes of a trailing unfinished frame stay in the codec, to be completed by the next
+    /// chunk.
+    pub const fn feed<'a>(&'a mut self, data: &'a [u8]) -> SlipFrames<'a, MAX_LENGTH, &'a O> {
+        SlipFrames::with_observer(&mut self.decoder, data, &self.observer)
+    }
+
+    /// Feeds the bytes of `data` into the decoder, reporting the outcome to the observer.
+    fn feed_observed(&mut self, data: &[u8]) -> FeedResult {
+        let discarded = self.decoder.stats().bytes_discarded;
+        let result = self.decoder.feed(data);
+        observe_feed(
+            &self.observer,
+            result,
+            self.decoder.stats().bytes_discarded.wrapping_sub(discarded),
+            self.decoder.frame_len(),
+        );
+
+        result
+    }
+
+    /// Drops the frame being decoded, if any.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns the configuration shared by both directions.
+    #[must_use]
+    pub const fn config(&self) -> SlipConfig {
+        self.config
+    }
+
+    /// Returns the statistics of the decoding direction, such as the number of frames dropped.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+
+    /// Returns a reference to the observer.
+    #[must_use]
+    pub const fn observer(&self) -> &O {
+        &self.observer
+    }
+}
+
+/// Implementation of `FrameCodec` for `SlipCodec`.
+impl<const MAX_LENGTH: usize, O: SlipObserver> FrameCodec for SlipCodec<MAX_LENGTH, O> {
+    /// Encodes `payload` as a SLIP frame into `out`.
+    fn encode(&self, payload: &[u8], out: &mut [u8]) -> Result<usize, SlipEncodeError> {
+        Self::encode(self, payload, out)
+    }
+
+    /// Feeds the bytes of `data` into the SLIP decoder.
+    fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.feed_observed(data);
+        if matches!(result.status, FeedStatus::Rejected(_)) {
+            self.decoder.reset();
+        }
+
+        result
+    }
+
+    /// Calls `f` with the completed SLIP frame, then drops it.
+    fn take_frame_with(&mut self, f: &mut dyn FnMut(&[u8])) -> Result<(), SlipDecodeError> {
+        self.decoder.take_frame_with(f)
+    }
+
+    /// Drops the SLIP frame being decoded, if any.
+    fn reset(&mut self) {
+        Self::reset(self);
+    }
+}
+
+/// Implementation of `Decoder` for `SlipCodec`.
+#[cfg(feature = "tokio")]
+impl<const MAX_LENGTH: usize, O: SlipObserver> Decoder for SlipCodec<MAX_LENGTH, O> {
+    type Item = BytesMut;
+    type Error = SlipIoError<std::io::Error>;
+
+    /// Decodes the bytes of `src` until a frame is complete.
+    ///
+    /// A frame that cannot be decoded is dropped, as `Framed` ends the stream on any error, and
+    /// only reported to the observer and counted in `stats`. Decoding then goes on with the next
+    /// frame, so that `Ok(None)` is only returned once more bytes are needed.
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            let skipped = self.resync.skip(&mut self.decoder, src);
+            let _ = src.split_to(skipped);
+            if self.resync.is_pending() {
+                return Ok(None);
+            }
+
+            let result = self.feed_observed(src);
+            let _ = src.split_to(result.consumed);
+
+            match result.status {
+                FeedStatus::Incomplete => return Ok(None),
+                FeedStatus::Completed => {
+                    match self.decoder.take_frame_with(|frame| BytesMut::from(frame)) {
+                        Ok(frame) => return Ok(Some(frame)),
+                        Err(error) => self.observer.frame_dropped(error),
+                    }
+                }
+                FeedStatus::Rejected(error) => self.resync.rejected(&mut self.decoder, error),
+            }
+        }
+    }
+}
+
+/// Implementation of `Encoder` for `SlipCodec`.
+#[cfg(feature = "tokio")]
+impl<T: AsRef<[u8]>, const MAX_LENGTH: usize, O: SlipObserver> Encoder<T>
+    for SlipCodec<MAX_LENGTH, O>
+{
+    type Error = SlipIoError<std::io::Error>;
+
+    /// Encodes `item` as a complete frame at the end of `dst`.
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let payload = item.as_ref();
+        dst.reserve(payload.len() + 2);
+
+        if self.config.leading_end() {
+            dst.put_u8(self.config.end_char());
+        }
+        for segment in EscapedSegments::new(payload, self.config) {
+            dst.put_slice(segment);
+        }
+        dst.put_u8(self.config.end_char());
+        self.observer.frame_encoded(payload.len());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use core::pin::Pin;
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use core::task::Context;
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use core::task::Poll;
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use core::task::Waker;
+
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use futures_core::Stream;
+    use noalloc_vec_rs::vec::Vec;
+    #[cfg(feature = "tokio")]
+    use tokio_util::bytes::BytesMut;
+    #[cfg(feature = "tokio")]
+    use tokio_util::codec::Decoder;
+    #[cfg(feature = "tokio")]
+    use tokio_util::codec::Encoder;
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    use tokio_util::codec::FramedRead;
+
+    use crate::cobs::COBS_DELIMITER;
+    use crate::cobs::CobsCodec;
+    use crate::codec::FrameCodec;
+    use crate::codec::SlipCodec;
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::observer::SlipObserver;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipConfig;
+
+    #[test]
+    fn test_encode_and_feed() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let mut codec = SlipCodec::<4>::with_config(config);
+        let mut out = [0x00; 8];
+
+        let length = codec.encode(&[0x00, END_CHAR], &mut out).unwrap();
+
+        assert_eq!(out[..length], [0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+        assert_eq!(
+            codec.encode(&[0x00; 8], &mut out),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 9,
+                available: 8
+            })
+        );
+
+        let mut frames = codec.feed(&out[..length - 1]);
+
+        assert!(frames.next().is_none());
+
+        let mut frames = codec.feed(&[END_CHAR, ESC_CHAR, 0x01, 0x02]);
+
+        assert_eq!(frames.next().unwrap().unwrap().as_slice(), [0x00, END_CHAR]);
+        assert_eq!(
+            frames.next().unwrap(),
+            Err(SlipDecodeError::InvalidEscape(0x01))
+        );
+        assert!(frames.next().is_none());
+    }
+
+    #[test]
+    fn test_frame_codec_selected_at_runtime() {
+        let mut slip = SlipCodec::<4>::new();
+        let mut cobs = CobsCodec::<4>::new();
+        let mut ports: [&mut dyn FrameCodec; 2] = [&mut slip, &mut cobs];
+
+        for port in &mut ports {
+            let mut out = [0x00; 8];
+            let length = port.encode(&[0x00, END_CHAR], &mut out).unwrap();
+            let mut frame = Vec::<u8, 4>::new();
+
+            assert_eq!(port.feed(&out[..length]).status, FeedStatus::Completed);
+            port.take_frame_with(&mut |payload| frame.extend(payload.iter().copied()))
+                .unwrap();
+            assert_eq!(frame.as_slice(), [0x00, END_CHAR]);
+            assert_eq!(
+                port.take_frame_with(&mut |_| {}),
+                Err(SlipDecodeError::FrameIncomplete)
+            );
+        }
+
+        let result = ports[1].feed(&[0x03, 0x01, COBS_DELIMITER, 0x02, 0x01, COBS_DELIMITER]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(
+            ports[1].feed(&[0x02, 0x01, COBS_DELIMITER]).status,
+            FeedStatus::Completed
+        );
+    }
+
+    /// An observer counting the events it is notified of.
+    #[derive(Default)]
+    struct Counters {
+        encoded: Cell<usize>,
+        decoded: Cell<usize>,
+        dropped: Cell<Option<SlipDecodeError>>,
+        discarded: Cell<usize>,
+    }
+
+    impl SlipObserver for Counters {
+        fn frame_encoded(&self, len: usize) {
+            self.encoded.set(self.encoded.get() + len);
+        }
+
+        fn frame_decoded(&self, len: usize) {
+            self.decoded.set(self.decoded.get() + len);
+        }
+
+        fn frame_dropped(&self, reason: SlipDecodeError) {
+            self.dropped.set(Some(reason));
+        }
+
+        fn bytes_discarded(&self, count: usize) {
+            self.discarded.set(self.discarded.get() + count);
+        }
+    }
+
+    #[test]
+    fn test_observer() {
+        let mut codec = SlipCodec::<4, _>::with_observer(SlipConfig::new(), Counters::default());
+        let mut out = [0x00; 8];
+
+        let length = codec.encode(&[0x00, END_CHAR], &mut out).unwrap();
+
+        assert_eq!(codec.observer().encoded.get(), 2);
+
+        let frames = codec.feed(&out[..length]).count();
+
+        assert_eq!(frames, 1);
+        assert_eq!(codec.observer().decoded.get(), 2);
+
+        let frames = codec.feed(&[0x01, 0x02, END_CHAR, ESC_CHAR, 0x01]).count();
+
+        assert_eq!(frames, 1);
+        assert_eq!(codec.observer().discarded.get(), 2);
+        assert_eq!(
+            codec.observer().dropped.get(),
+            Some(SlipDecodeError::InvalidEscape(0x01))
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_encode() {
+        let mut codec = SlipCodec::<4>::new();
+        let mut dst = BytesMut::new();
+
+        Encoder::encode(&mut codec, [0x00, END_CHAR, 0x01], &mut dst).unwrap();
+
+        assert_eq!(
+            dst[..],
+            [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, 0x01, END_CHAR]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_decode() {
+        let mut codec = SlipCodec::<4>::new();
+        let mut src = BytesMut::from(&[END_CHAR, 0x00, ESC_CHAR][..]);
+
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        assert!(src.is_empty());
+
+        src.extend_from_slice(&[ESC_END_CHAR, END_CHAR, END_CHAR, 0x01, END_CHAR]);
+
+        assert_eq!(
+            codec.decode(&mut src).unwrap().unwrap()[..],
+            [0x00, END_CHAR]
+        );
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap()[..], [0x01]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_decode_error() {
+        let mut codec = SlipCodec::<4>::new();
+        let mut src = BytesMut::from(&[END_CHAR, ESC_CHAR, 0x01, END_CHAR, 0x02, END_CHAR][..]);
+
+        assert_eq!(codec.decode(&mut src).unwrap().unwrap()[..], [0x02]);
+        assert_eq!(codec.stats().frames_dropped, 1);
+    }
+
+    /// Polls `f` until it is ready, assuming it never has to wait.
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    fn block_on<T>(mut f: impl FnMut(&mut Context<'_>) -> Poll<T>) -> T {
+        let mut context = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = f(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[cfg(all(feature = "tokio", feature = "futures"))]
+    #[test]
+    fn test_framed_survives_corrupt_frame() {
+        let data = [
+            END_CHAR, ESC_CHAR, 0x01, 0x02, END_CHAR, END_CHAR, 0x03, END_CHAR, END_CHAR, 0x04,
+            END_CHAR,
+        ];
+        let mut framed = FramedRead::new(&data[..], SlipCodec::<4>::new());
+        let mut next = || block_on(|cx| Pin::new(&mut framed).poll_next(cx));
+
+        assert_eq!(next().unwrap().unwrap()[..], [0x03]);
+        assert_eq!(next().unwrap().unwrap()[..], [0x04]);
+        assert!(next().is_none());
+    }
+}
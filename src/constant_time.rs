@@ -0,0 +1,162 @@
+use crate::error::SlipDecodeError;
+use crate::slip::ESC_CHAR;
+use crate::slip::ESC_END_CHAR;
+use crate::slip::ESC_ESC_CHAR;
+use crate::slip::SlipConfig;
+use crate::wcet::WorkBound;
+
+/// Returns `0xFF` if `a == b`, or `0x00` otherwise, without branching.
+const fn mask_eq(a: u8, b: u8) -> u8 {
+    let difference = (a ^ b) as u16;
+
+    (difference.wrapping_sub(1) >> 8) as u8
+}
+
+/// Returns `0xFF` if `a >= b`, or `0x00` otherwise, without branching.
+const fn mask_ge(a: usize, b: usize) -> u8 {
+    let (_, borrow) = a.overflowing_sub(b);
+
+    (borrow as u8).wrapping_sub(1)
+}
+
+/// A SLIP decoder without data-dependent branches.
+///
+/// This struct decodes frames carrying sensitive payloads (e.g. key material) with a fixed amount
+/// of work per input byte: byte classification uses arithmetic masks rather than `match` arms or
+/// lookup tables indexed by payload values, and errors are only reported once the whole frame
+/// has been processed.
+///
+/// This is best-effort side-channel hardening: the length of the decoded payload, which depends
+/// on the number of escape sequences, is observable, and the compiler gives no formal guarantee
+/// that the generated code stays branch-free.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConstantTimeDecoder {
+    config: SlipConfig,
+}
+
+impl ConstantTimeDecoder {
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self { config }
+    }
+
+    /// Decodes the escaped body of a frame, without its delimiters, into `output`.
+    ///
+    /// Returns the length of the decoded payload, `Err(SlipDecodeError::BufferFull)` if `output`
+    /// is too small, or `Err(SlipDecodeError::MalformedFrame)` if the body contains an invalid
+    /// escape sequence or an unescaped terminator. The content of `output` is unspecified on
+    /// error.
+    pub fn decode(&self, body: &[u8], output: &mut [u8]) -> Result<usize, SlipDecodeError> {
+        if output.is_empty() {
+            return if body.is_empty() {
+                Ok(0)
+            } else {
+                Err(SlipDecodeError::BufferFull)
+            };
+        }
+
+        let end = self.config.end_char();
+        let last = output.len() - 1;
+
+        let mut length = 0;
+        let mut escaping = 0x00_u8;
+        let mut malformed = 0x00_u8;
+        let mut overflow = 0x00_u8;
+
+        for &value in body {
+            let is_esc = mask_eq(value, ESC_CHAR);
+            let is_esc_end = mask_eq(value, ESC_END_CHAR);
+            let is_esc_esc = mask_eq(value, ESC_ESC_CHAR);
+            let is_end = mask_eq(value, end);
+
+            let unescaped = (is_esc_end & end) | (is_esc_esc & ESC_CHAR);
+            let decoded = (escaping & unescaped) | (!escaping & value);
+            let emit = escaping | !is_esc;
+
+            malformed |= escaping & !(is_esc_end | is_esc_esc);
+            malformed |= !escaping & is_end;
+            overflow |= emit & mask_ge(length, output.len());
+
+            // Always write, keeping the current byte when nothing is emitted
+            let index = length.min(last);
+            output[index] = (emit & decoded) | (!emit & output[index]);
+            length += usize::from(emit & 1);
+
+            escaping = !escaping & is_esc;
+        }
+
+        malformed |= escaping;
+
+        if malformed != 0 {
+            Err(SlipDecodeError::MalformedFrame)
+        } else if overflow != 0 {
+            Err(SlipDecodeError::BufferFull)
+        } else {
+            Ok(length)
+        }
+    }
+
+    /// Returns the worst-case work of `decode` for a body of `body_len` bytes.
+    #[must_use]
+    pub const fn decode_work_bound(body_len: usize) -> WorkBound {
+        WorkBound::linear(body_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant_time::ConstantTimeDecoder;
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::ESC_ESC_CHAR;
+
+    #[test]
+    fn test_decode() {
+        let mut output = [0x00; 4];
+
+        let result = ConstantTimeDecoder::default().decode(
+            &[0x01, ESC_CHAR, ESC_END_CHAR, ESC_CHAR, ESC_ESC_CHAR],
+            &mut output,
+        );
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(output[..3], [0x01, END_CHAR, ESC_CHAR]);
+    }
+
+    #[test]
+    fn test_decode_with_bad_escape_character() {
+        let mut output = [0x00; 4];
+
+        let result = ConstantTimeDecoder::default().decode(&[ESC_CHAR, 0x01], &mut output);
+        assert_eq!(result, Err(SlipDecodeError::MalformedFrame));
+
+        let result = ConstantTimeDecoder::default().decode(&[0x01, ESC_CHAR], &mut output);
+        assert_eq!(result, Err(SlipDecodeError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_decode_with_terminator() {
+        let mut output = [0x00; 4];
+
+        let result = ConstantTimeDecoder::default().decode(&[0x01, END_CHAR], &mut output);
+
+        assert_eq!(result, Err(SlipDecodeError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_decode_with_not_enough_space() {
+        let mut output = [0x00; 2];
+
+        let result = ConstantTimeDecoder::default().decode(&[0x01, 0x02], &mut output);
+        assert_eq!(result, Ok(2));
+
+        let result = ConstantTimeDecoder::default().decode(&[0x01, 0x02, 0x03], &mut output);
+        assert_eq!(result, Err(SlipDecodeError::BufferFull));
+
+        let result = ConstantTimeDecoder::default().decode(&[0x01], &mut []);
+        assert_eq!(result, Err(SlipDecodeError::BufferFull));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::checksum::ChecksumDecoder;
+use crate::checksum::ChecksumEncoder;
+use crate::checksum::ChecksumValidator;
+use crate::checksum::FrameChecksum;
+
+/// Length of the CRC appended to every frame.
+pub const CRC_LENGTH: usize = 2;
+
+/// Computes the CRC-16/CCITT-FALSE of `bytes`.
+///
+/// This is the CRC-16 with polynomial `0x1021` and initial value `0xFFFF`, without reflection nor
+/// final XOR.
+#[must_use]
+pub const fn crc16(bytes: &[u8]) -> u16 {
+    crc16_update(0xFFFF, bytes)
+}
+
+/// Updates the CRC-16/CCITT-FALSE `crc` with `bytes`.
+const fn crc16_update(mut crc: u16, bytes: &[u8]) -> u16 {
+    let mut index = 0;
+    while index < bytes.len() {
+        crc ^= (bytes[index] as u16) << 8;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x1021
+            };
+            bit += 1;
+        }
+        index += 1;
+    }
+
+    crc
+}
+
+/// The CRC-16/CCITT-FALSE, as a frame checksum.
+///
+/// The CRC is appended to the payload most significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc16(u16);
+
+/// Default implementation for `Crc16`.
+impl Default for Crc16 {
+    /// Creates the CRC of an empty payload.
+    fn default() -> Self {
+        Self(0xFFFF)
+    }
+}
+
+/// Implementation of `FrameChecksum` for `Crc16`.
+impl FrameChecksum for Crc16 {
+    type Digest = [u8; CRC_LENGTH];
+
+    const LENGTH: usize = CRC_LENGTH;
+
+    /// Updates the CRC with `bytes`.
+    fn update(&mut self, bytes: &[u8]) {
+        self.0 = crc16_update(self.0, bytes);
+    }
+
+    /// Returns the CRC, most significant byte first.
+    fn finalize(self) -> Self::Digest {
+        self.0.to_be_bytes()
+    }
+}
+
+/// A SLIP encoder protecting every frame with a CRC-16.
+///
+/// The CRC-16/CCITT-FALSE of the payload sits right before the closing terminator.
+pub type SlipCrc16Encoder = ChecksumEncoder<Crc16>;
+
+/// A SLIP decoder verifying and stripping the CRC-16 of every frame.
+///
+/// `MAX_LENGTH` bounds the decoded frame, the trailing CRC included.
+pub type SlipCrc16Decoder<const MAX_LENGTH: usize> = ChecksumDecoder<Crc16, MAX_LENGTH>;
+
+/// A SLIP validator verifying the CRC-16 of every frame, without storing the payload.
+pub type SlipCrc16Validator = ChecksumValidator<Crc16>;
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::crc::SlipCrc16Decoder;
+    use crate::crc::SlipCrc16Encoder;
+    use crate::crc::SlipCrc16Validator;
+    use crate::crc::crc16;
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_crc16() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+        assert_eq!(crc16(&[]), 0xFFFF);
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let mut vec = Vec::<u8, 16>::from([0x01, END_CHAR, 0x03]);
+        SlipCrc16Encoder::default().encode(&mut vec).unwrap();
+
+        let mut decoder = SlipCrc16Decoder::<8>::default();
+        let result = decoder.feed(vec.as_slice());
+
+        assert_eq!(result.consumed, vec.len());
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x01, END_CHAR, 0x03]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut vec = Vec::<u8, 16>::from([0x01, END_CHAR, 0x03]);
+        SlipCrc16Encoder::default().encode(&mut vec).unwrap();
+
+        let mut validator = SlipCrc16Validator::default();
+        let result = validator.feed(vec.as_slice());
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(validator.payload_len(), 3);
+    }
+
+    #[test]
+    fn test_encode_with_not_enough_space() {
+        let mut vec = Vec::<u8, 4>::from([0x01, 0x02, 0x03]);
+
+        assert_eq!(
+            SlipCrc16Encoder::default().encode(&mut vec),
+            Err(SlipEncodeError::BufferTooSmall)
+        );
+        assert_eq!(vec.as_slice(), [0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_decode_with_corrupted_frame() {
+        let mut vec = Vec::<u8, 16>::from([0x01, 0x02, 0x03]);
+        SlipCrc16Encoder::default().encode(&mut vec).unwrap();
+        vec.as_mut_slice()[2] ^= 0x10;
+
+        let mut decoder = SlipCrc16Decoder::<8>::default();
+
+        assert_eq!(
+            decoder.feed(vec.as_slice()).status,
+            FeedStatus::Rejected(SlipDecodeError::ChecksumMismatch)
+        );
+        assert!(!decoder.is_buffer_completed());
+    }
+
+    #[test]
+    fn test_decode_insert_with_short_frame() {
+        let mut decoder = SlipCrc16Decoder::<8>::default();
+
+        assert_eq!(decoder.insert(END_CHAR), Ok(()));
+        assert_eq!(decoder.insert(0x01), Ok(()));
+        assert_eq!(
+            decoder.insert(END_CHAR),
+            Err(SlipDecodeError::ChecksumMismatch)
+        );
+    }
+}
@@ -0,0 +1,798 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::wcet::WorkBound;
+
+/// Maximum length of the TCP/IP header of a compressible packet.
+pub const MAX_HEADER_LENGTH: usize = 128;
+
+/// Maximum length of a compressed header.
+const MAX_COMPRESSED_HEADER_LENGTH: usize = 19;
+
+/// First byte marker of an uncompressed TCP packet.
+const TYPE_UNCOMPRESSED_TCP: u8 = 0x70;
+/// First byte marker of a compressed TCP packet.
+const TYPE_COMPRESSED_TCP: u8 = 0x80;
+
+/// Change mask bit: the connection number is present.
+const NEW_C: u8 = 0x40;
+/// Change mask bit: the IP identifier delta is present.
+const NEW_I: u8 = 0x20;
+/// Change mask bit: the TCP push flag is set.
+const TCP_PUSH_BIT: u8 = 0x10;
+/// Change mask bit: the sequence number delta is present.
+const NEW_S: u8 = 0x08;
+/// Change mask bit: the acknowledgment number delta is present.
+const NEW_A: u8 = 0x04;
+/// Change mask bit: the window delta is present.
+const NEW_W: u8 = 0x02;
+/// Change mask bit: the urgent pointer is present.
+const NEW_U: u8 = 0x01;
+/// Change mask bits holding the special cases.
+const SPECIALS_MASK: u8 = NEW_S | NEW_A | NEW_W | NEW_U;
+/// Special case of echoed interactive traffic: sequence and acknowledgment move by the last
+/// payload length.
+const SPECIAL_I: u8 = NEW_S | NEW_W | NEW_U;
+/// Special case of unidirectional data: the sequence moves by the last payload length.
+const SPECIAL_D: u8 = SPECIALS_MASK;
+
+/// IP protocol number of TCP.
+const IPPROTO_TCP: u8 = 6;
+
+/// TCP flag closing the connection.
+const TH_FIN: u8 = 0x01;
+/// TCP flag opening the connection.
+const TH_SYN: u8 = 0x02;
+/// TCP flag resetting the connection.
+const TH_RST: u8 = 0x04;
+/// TCP flag pushing the data to the application.
+const TH_PUSH: u8 = 0x08;
+/// TCP flag marking the acknowledgment number valid.
+const TH_ACK: u8 = 0x10;
+/// TCP flag marking the urgent pointer valid.
+const TH_URG: u8 = 0x20;
+
+/// The type of a CSLIP packet, carried by the high bits of its first byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CslipPacketType {
+    /// A packet sent as is.
+    Ip,
+    /// A TCP packet sent with its full header, which refreshes the state of its connection.
+    UncompressedTcp,
+    /// A TCP packet whose header is replaced by its differences from the previous one.
+    CompressedTcp,
+}
+
+/// The header of a TCP/IP packet, located in the packet bytes.
+#[derive(Clone, Copy)]
+struct TcpIpHeader {
+    /// Length of the IP header.
+    ip_len: usize,
+    /// Length of the IP and TCP headers.
+    len: usize,
+}
+
+impl TcpIpHeader {
+    /// Locates the header of `packet`, if it is an IPv4 TCP packet with a consistent header.
+    fn parse(packet: &[u8]) -> Option<Self> {
+        let &first = packet.first()?;
+        if first >> 4 != 4 || packet.len() < 20 || packet[9] != IPPROTO_TCP {
+            return None;
+        }
+
+        let ip_len = usize::from(first & 0x0F) * 4;
+        let tcp_offset = *packet.get(ip_len + 12)?;
+        let len = ip_len + usize::from(tcp_offset >> 4) * 4;
+
+        (ip_len >= 20 && len >= ip_len + 20 && len <= packet.len().min(MAX_HEADER_LENGTH))
+            .then_some(Self { ip_len, len })
+    }
+}
+
+/// Reads the big-endian `u16` at `index` of `bytes`.
+fn read_u16(bytes: &[u8], index: usize) -> u16 {
+    u16::from_be_bytes([bytes[index], bytes[index + 1]])
+}
+
+/// Reads the big-endian `u32` at `index` of `bytes`.
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    u32::from_be_bytes([
+        bytes[index],
+        bytes[index + 1],
+        bytes[index + 2],
+        bytes[index + 3],
+    ])
+}
+
+/// Writes `value` as a big-endian `u16` at `index` of `bytes`.
+fn write_u16(bytes: &mut [u8], index: usize, value: u16) {
+    bytes[index..index + 2].copy_from_slice(&value.to_be_bytes());
+}
+
+/// Writes `value` as a big-endian `u32` at `index` of `bytes`.
+fn write_u32(bytes: &mut [u8], index: usize, value: u32) {
+    bytes[index..index + 4].copy_from_slice(&value.to_be_bytes());
+}
+
+/// Computes the Internet checksum of the IP header `header`, its checksum field included.
+fn ip_checksum(header: &[u8]) -> u16 {
+    let mut sum = header
+        .chunks(2)
+        .map(|word| u32::from(word[0]) << 8 | u32::from(word.get(1).copied().unwrap_or(0)))
+        .fold(0u32, u32::wrapping_add);
+
+    while sum > 0xFFFF {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+
+    // The loop above folds the sum into 16 bits
+    #[allow(clippy::cast_possible_truncation)]
+    let sum = sum as u16;
+
+    !sum
+}
+
+/// The header of the last packet of a connection.
+#[derive(Clone, Copy)]
+struct ConnectionState {
+    header: [u8; MAX_HEADER_LENGTH],
+    len: usize,
+    ip_len: usize,
+    last_used: u32,
+}
+
+impl ConnectionState {
+    /// State of a connection slot not used yet.
+    const EMPTY: Self = Self {
+        header: [0; MAX_HEADER_LENGTH],
+        len: 0,
+        ip_len: 0,
+        last_used: 0,
+    };
+
+    /// Returns the saved header.
+    fn header(&self) -> &[u8] {
+        &self.header[..self.len]
+    }
+
+    /// Saves the header of `packet`.
+    fn save(&mut self, packet: &[u8], header: TcpIpHeader) {
+        self.header[..header.len].copy_from_slice(&packet[..header.len]);
+        self.len = header.len;
+        self.ip_len = header.ip_len;
+    }
+
+    /// Returns `true` if `packet` belongs to the connection of the saved header.
+    fn matches(&self, packet: &[u8], header: TcpIpHeader) -> bool {
+        self.len != 0
+            && self.header[12..20] == packet[12..20]
+            && self.header[self.ip_len..self.ip_len + 4] == packet[header.ip_len..header.ip_len + 4]
+    }
+}
+
+/// The compressed header being built.
+struct CompressedHeader {
+    bytes: [u8; MAX_COMPRESSED_HEADER_LENGTH],
+    len: usize,
+}
+
+impl CompressedHeader {
+    /// Appends `value` to the header.
+    const fn push(&mut self, value: u8) {
+        self.bytes[self.len] = value;
+        self.len += 1;
+    }
+
+    /// Appends `delta`, on one byte if it fits in `1..=255`, on three bytes otherwise.
+    fn push_delta(&mut self, delta: u16) {
+        match u8::try_from(delta) {
+            Ok(value) if value != 0 => self.push(value),
+            _ => {
+                let [high, low] = delta.to_be_bytes();
+                self.push(0);
+                self.push(high);
+                self.push(low);
+            }
+        }
+    }
+}
+
+/// A cursor reading the compressed header of a packet.
+struct CompressedReader<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl CompressedReader<'_> {
+    /// Reads a byte.
+    fn byte(&mut self) -> Result<u8, SlipDecodeError> {
+        let value = *self
+            .bytes
+            .get(self.index)
+            .ok_or(SlipDecodeError::MalformedFrame)?;
+        self.index += 1;
+
+        Ok(value)
+    }
+
+    /// Reads a delta, encoded on one byte if it fits in `1..=255`, on three bytes otherwise.
+    fn delta(&mut self) -> Result<u16, SlipDecodeError> {
+        match self.byte()? {
+            0 => Ok(u16::from_be_bytes([self.byte()?, self.byte()?])),
+            value => Ok(u16::from(value)),
+        }
+    }
+}
+
+/// A Van Jacobson TCP/IP header compressor, as described by RFC 1144.
+///
+/// The compressor keeps the header of the last packet of up to `SLOTS` TCP connections, and
+/// replaces the header of the next packet of a connection by its differences from the previous
+/// one, typically shrinking 40 bytes of header to 3 to 5 bytes. The packet type is carried by its
+/// first byte, so that a compressed packet is sent as a single SLIP frame.
+pub struct CslipCompressor<const SLOTS: usize> {
+    slots: [ConnectionState; SLOTS],
+    last_sent: Option<u8>,
+    clock: u32,
+}
+
+impl<const SLOTS: usize> CslipCompressor<SLOTS> {
+    /// Creates a compressor tracking up to `SLOTS` connections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `SLOTS` is not within `1..=256`.
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(
+            SLOTS > 0 && SLOTS <= 256,
+            "CSLIP supports 1 to 256 connections"
+        );
+
+        Self {
+            slots: [ConnectionState::EMPTY; SLOTS],
+            last_sent: None,
+            clock: 0,
+        }
+    }
+
+    /// Compresses the TCP/IP header of the packet held by `vec` in place.
+    ///
+    /// Packets which are not compressible, such as non-TCP packets, fragments or connection
+    /// setup and teardown segments, are left untouched.
+    ///
+    /// Returns the type of the packet.
+    pub fn compress<const MAX_LENGTH: usize>(
+        &mut self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> CslipPacketType {
+        let packet = vec.as_slice();
+        let Some(header) = TcpIpHeader::parse(packet) else {
+            return CslipPacketType::Ip;
+        };
+
+        let tcp = header.ip_len;
+        if read_u16(packet, 6) & 0x3FFF != 0
+            || packet[tcp + 13] & (TH_SYN | TH_FIN | TH_RST | TH_ACK) != TH_ACK
+        {
+            return CslipPacketType::Ip;
+        }
+
+        self.clock = self.clock.wrapping_add(1);
+        let (slot, found) = self.find_slot(packet, header);
+        self.slots[slot].last_used = self.clock;
+        // Slots are indexed by a `u8`, as `SLOTS` is at most 256
+        #[allow(clippy::cast_possible_truncation)]
+        let connection = slot as u8;
+
+        let compressed = if found {
+            Self::compress_header(&self.slots[slot], packet, header)
+        } else {
+            None
+        };
+        self.slots[slot].save(packet, header);
+
+        let Some(compressed) = compressed else {
+            self.last_sent = Some(connection);
+
+            let buffer = vec.as_mut_slice();
+            buffer[0] |= TYPE_UNCOMPRESSED_TCP;
+            buffer[9] = connection;
+
+            return CslipPacketType::UncompressedTcp;
+        };
+
+        let mut output = CompressedHeader {
+            bytes: [0; MAX_COMPRESSED_HEADER_LENGTH],
+            len: 0,
+        };
+        if self.last_sent == Some(connection) {
+            output.push(compressed.bytes[0]);
+        } else {
+            output.push(compressed.bytes[0] | NEW_C);
+            output.push(connection);
+            self.last_sent = Some(connection);
+        }
+        output.push(packet[tcp + 16]);
+        output.push(packet[tcp + 17]);
+        for &value in &compressed.bytes[1..compressed.len] {
+            output.push(value);
+        }
+
+        let data_len = packet.len() - header.len;
+        let buffer = vec.as_mut_slice();
+        buffer.copy_within(header.len.., output.len);
+        buffer[..output.len].copy_from_slice(&output.bytes[..output.len]);
+        vec.truncate(output.len + data_len);
+
+        CslipPacketType::CompressedTcp
+    }
+
+    /// Returns the worst-case work of `compress` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn compress_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to find the connection, one to compare and save its header, one to shift the
+        // payload
+        WorkBound::linear(SLOTS)
+            .then(WorkBound::linear(2 * MAX_HEADER_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Returns the slot of the connection of `packet`, and whether it was already tracked.
+    ///
+    /// An untracked connection takes over the least recently used slot.
+    fn find_slot(&self, packet: &[u8], header: TcpIpHeader) -> (usize, bool) {
+        if let Some(slot) = self
+            .slots
+            .iter()
+            .position(|state| state.matches(packet, header))
+        {
+            return (slot, true);
+        }
+
+        let oldest = self
+            .slots
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, state)| (state.len != 0, self.clock.wrapping_sub(state.last_used)))
+            .map_or(0, |(slot, _)| slot);
+
+        (oldest, false)
+    }
+
+    /// Builds the change mask and deltas of `packet` from the saved header of its connection.
+    ///
+    /// The first byte of the returned header holds the change mask, and the checksum is left
+    /// out. Returns `None` if the packet must be sent uncompressed.
+    fn compress_header(
+        state: &ConnectionState,
+        packet: &[u8],
+        header: TcpIpHeader,
+    ) -> Option<CompressedHeader> {
+        let saved = state.header();
+        let tcp = header.ip_len;
+
+        // Fields which never change within a connection, options included
+        if packet[0..2] != saved[0..2]
+            || packet[6..10] != saved[6..10]
+            || packet[tcp + 12] >> 4 != saved[tcp + 12] >> 4
+            || packet[20..tcp] != saved[20..tcp]
+            || packet[tcp + 20..header.len] != saved[tcp + 20..header.len]
+        {
+            return None;
+        }
+
+        let mut output = CompressedHeader {
+            bytes: [0; MAX_COMPRESSED_HEADER_LENGTH],
+            len: 1,
+        };
+        let mut changes = 0;
+
+        if packet[tcp + 13] & TH_URG != 0 {
+            output.push_delta(read_u16(packet, tcp + 18));
+            changes |= NEW_U;
+        } else if read_u16(packet, tcp + 18) != read_u16(saved, tcp + 18) {
+            return None;
+        }
+
+        let window = read_u16(packet, tcp + 14).wrapping_sub(read_u16(saved, tcp + 14));
+        if window != 0 {
+            output.push_delta(window);
+            changes |= NEW_W;
+        }
+
+        let ack = read_u32(packet, tcp + 8).wrapping_sub(read_u32(saved, tcp + 8));
+        if ack != 0 {
+            output.push_delta(u16::try_from(ack).ok()?);
+            changes |= NEW_A;
+        }
+
+        let seq = read_u32(packet, tcp + 4).wrapping_sub(read_u32(saved, tcp + 4));
+        if seq != 0 {
+            output.push_delta(u16::try_from(seq).ok()?);
+            changes |= NEW_S;
+        }
+
+        let saved_data_len = usize::from(read_u16(saved, 2)).wrapping_sub(header.len);
+        let is_saved_data_len = |delta: u32| usize::try_from(delta) == Ok(saved_data_len);
+
+        match changes {
+            // A retransmission is sent uncompressed in case the peer lost its state, while a
+            // packet with data following a bare acknowledgment is sent compressed
+            0 if read_u16(packet, 2) == read_u16(saved, 2) || saved_data_len != 0 => return None,
+            SPECIAL_I | SPECIAL_D => return None,
+            _ if changes == NEW_S | NEW_A && seq == ack && is_saved_data_len(seq) => {
+                output.len = 1;
+                changes = SPECIAL_I;
+            }
+            NEW_S if is_saved_data_len(seq) => {
+                output.len = 1;
+                changes = SPECIAL_D;
+            }
+            _ => {}
+        }
+
+        let id = read_u16(packet, 4).wrapping_sub(read_u16(saved, 4));
+        if id != 1 {
+            output.push_delta(id);
+            changes |= NEW_I;
+        }
+
+        if packet[tcp + 13] & TH_PUSH != 0 {
+            changes |= TCP_PUSH_BIT;
+        }
+
+        output.bytes[0] = TYPE_COMPRESSED_TCP | changes;
+
+        Some(output)
+    }
+}
+
+/// Default implementation for `CslipCompressor`.
+impl<const SLOTS: usize> Default for CslipCompressor<SLOTS> {
+    /// Creates a compressor tracking up to `SLOTS` connections.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A Van Jacobson TCP/IP header decompressor, as described by RFC 1144.
+///
+/// The decompressor mirrors the state of the compressor on the other end of the link, and must
+/// use the same number of `SLOTS`. After a corrupted frame, the state of the compressor may be
+/// ahead: compressed packets are then rejected until a packet refreshes the state of their
+/// connection.
+pub struct CslipDecompressor<const SLOTS: usize> {
+    slots: [ConnectionState; SLOTS],
+    last_received: usize,
+    toss: bool,
+}
+
+impl<const SLOTS: usize> CslipDecompressor<SLOTS> {
+    /// Creates a decompressor tracking up to `SLOTS` connections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `SLOTS` is not within `1..=256`.
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(
+            SLOTS > 0 && SLOTS <= 256,
+            "CSLIP supports 1 to 256 connections"
+        );
+
+        Self {
+            slots: [ConnectionState::EMPTY; SLOTS],
+            last_received: 0,
+            toss: false,
+        }
+    }
+
+    /// Records that a frame has been lost, so that the following compressed packets are rejected
+    /// until the state of their connection is refreshed.
+    ///
+    /// This must be called whenever the SLIP decoder rejects a frame.
+    pub const fn mark_error(&mut self) {
+        self.toss = true;
+    }
+
+    /// Restores the TCP/IP header of the packet held by `vec` in place.
+    ///
+    /// Returns the type of the packet, `Err(SlipDecodeError::MalformedFrame)` if the packet is
+    /// invalid or cannot be restored, or `Err(SlipDecodeError::BufferFull)` if the vector is too
+    /// small to hold the restored packet. The vector is left untouched on error.
+    pub fn decompress<const MAX_LENGTH: usize>(
+        &mut self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<CslipPacketType, SlipDecodeError> {
+        let result = match vec.as_slice().first() {
+            Some(&first) if first & TYPE_COMPRESSED_TCP != 0 => self.restore(vec),
+            Some(&first) if first >= TYPE_UNCOMPRESSED_TCP => self.refresh(vec),
+            Some(&first) if first >= 0x40 => Ok(CslipPacketType::Ip),
+            _ => Err(SlipDecodeError::MalformedFrame),
+        };
+
+        if result.is_err() {
+            self.mark_error();
+        }
+
+        result
+    }
+
+    /// Returns the worst-case work of `decompress` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn decompress_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to build and save the header, one to checksum it, one to shift the payload
+        WorkBound::linear(2 * MAX_HEADER_LENGTH)
+            .then(WorkBound::linear(MAX_HEADER_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Saves the header of an uncompressed TCP packet, restoring its protocol field.
+    fn refresh<const MAX_LENGTH: usize>(
+        &mut self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<CslipPacketType, SlipDecodeError> {
+        let mut header = [0; MAX_HEADER_LENGTH];
+        let len = vec.len().min(MAX_HEADER_LENGTH);
+        header[..len].copy_from_slice(&vec.as_slice()[..len]);
+
+        let slot = usize::from(header[9]);
+        header[0] &= !(TYPE_UNCOMPRESSED_TCP & !0x40);
+        header[9] = IPPROTO_TCP;
+
+        let parsed = TcpIpHeader::parse(&header[..len]).ok_or(SlipDecodeError::MalformedFrame)?;
+        let state = self
+            .slots
+            .get_mut(slot)
+            .ok_or(SlipDecodeError::MalformedFrame)?;
+        state.save(&header, parsed);
+        self.last_received = slot;
+        self.toss = false;
+
+        let buffer = vec.as_mut_slice();
+        buffer[0] = header[0];
+        buffer[9] = header[9];
+
+        Ok(CslipPacketType::UncompressedTcp)
+    }
+
+    /// Rebuilds the header of a compressed TCP packet from the saved header of its connection.
+    fn restore<const MAX_LENGTH: usize>(
+        &mut self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<CslipPacketType, SlipDecodeError> {
+        let packet = vec.as_slice();
+        let changes = packet[0];
+        let mut reader = CompressedReader {
+            bytes: packet,
+            index: 1,
+        };
+
+        let mut slot = self.last_received;
+        if changes & NEW_C != 0 {
+            slot = usize::from(reader.byte()?);
+        } else if self.toss {
+            return Err(SlipDecodeError::MalformedFrame);
+        }
+
+        let state = self
+            .slots
+            .get(slot)
+            .ok_or(SlipDecodeError::MalformedFrame)?;
+        if state.len == 0 {
+            return Err(SlipDecodeError::MalformedFrame);
+        }
+
+        let mut header = state.header;
+        let (len, tcp) = (state.len, state.ip_len);
+        let checksum = [reader.byte()?, reader.byte()?];
+
+        if changes & TCP_PUSH_BIT == 0 {
+            header[tcp + 13] &= !TH_PUSH;
+        } else {
+            header[tcp + 13] |= TH_PUSH;
+        }
+
+        let header_len = u16::try_from(len).map_err(|_| SlipDecodeError::MalformedFrame)?;
+        let saved_data_len = u32::from(read_u16(&header, 2).wrapping_sub(header_len));
+        match changes & SPECIALS_MASK {
+            SPECIAL_I => {
+                let ack = read_u32(&header, tcp + 8).wrapping_add(saved_data_len);
+                let seq = read_u32(&header, tcp + 4).wrapping_add(saved_data_len);
+                write_u32(&mut header, tcp + 8, ack);
+                write_u32(&mut header, tcp + 4, seq);
+            }
+            SPECIAL_D => {
+                let seq = read_u32(&header, tcp + 4).wrapping_add(saved_data_len);
+                write_u32(&mut header, tcp + 4, seq);
+            }
+            _ => {
+                if changes & NEW_U == 0 {
+                    header[tcp + 13] &= !TH_URG;
+                } else {
+                    header[tcp + 13] |= TH_URG;
+                    let urgent = reader.delta()?;
+                    write_u16(&mut header, tcp + 18, urgent);
+                }
+                if changes & NEW_W != 0 {
+                    let window = read_u16(&header, tcp + 14).wrapping_add(reader.delta()?);
+                    write_u16(&mut header, tcp + 14, window);
+                }
+                if changes & NEW_A != 0 {
+                    let ack = read_u32(&header, tcp + 8).wrapping_add(reader.delta()?.into());
+                    write_u32(&mut header, tcp + 8, ack);
+                }
+                if changes & NEW_S != 0 {
+                    let seq = read_u32(&header, tcp + 4).wrapping_add(reader.delta()?.into());
+                    write_u32(&mut header, tcp + 4, seq);
+                }
+            }
+        }
+
+        let id_delta = if changes & NEW_I == 0 {
+            1
+        } else {
+            reader.delta()?
+        };
+        let id = read_u16(&header, 4).wrapping_add(id_delta);
+        write_u16(&mut header, 4, id);
+
+        let consumed = reader.index;
+        let data_len = packet.len() - consumed;
+        let total_len =
+            u16::try_from(len + data_len).map_err(|_| SlipDecodeError::MalformedFrame)?;
+        if len + data_len > MAX_LENGTH {
+            return Err(SlipDecodeError::BufferFull);
+        }
+
+        write_u16(&mut header, 2, total_len);
+        header[tcp + 16..tcp + 18].copy_from_slice(&checksum);
+        write_u16(&mut header, 10, 0);
+        let ip_checksum = ip_checksum(&header[..tcp]);
+        write_u16(&mut header, 10, ip_checksum);
+
+        while vec.len() < len + data_len {
+            vec.push(0).map_err(|_| SlipDecodeError::BufferFull)?;
+        }
+        let buffer = vec.as_mut_slice();
+        buffer.copy_within(consumed..consumed + data_len, len);
+        buffer[..len].copy_from_slice(&header[..len]);
+        vec.truncate(len + data_len);
+
+        let state = &mut self.slots[slot];
+        state.header = header;
+        self.last_received = slot;
+
+        Ok(CslipPacketType::CompressedTcp)
+    }
+}
+
+/// Default implementation for `CslipDecompressor`.
+impl<const SLOTS: usize> Default for CslipDecompressor<SLOTS> {
+    /// Creates a decompressor tracking up to `SLOTS` connections.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::cslip::CslipCompressor;
+    use crate::cslip::CslipDecompressor;
+    use crate::cslip::CslipPacketType;
+    use crate::cslip::ip_checksum;
+    use crate::error::SlipDecodeError;
+
+    /// Builds a TCP/IP packet with a valid IP checksum.
+    fn packet(id: u16, seq: u32, ack: u32, flags: u8, data: &[u8]) -> Vec<u8, 128> {
+        let mut packet = Vec::<u8, 128>::new();
+        let total_len = u16::try_from(40 + data.len()).unwrap();
+
+        let mut header = [0x00; 40];
+        header[0] = 0x45;
+        header[2..4].copy_from_slice(&total_len.to_be_bytes());
+        header[4..6].copy_from_slice(&id.to_be_bytes());
+        header[8] = 64;
+        header[9] = 6;
+        header[12..16].copy_from_slice(&[10, 0, 0, 1]);
+        header[16..20].copy_from_slice(&[10, 0, 0, 2]);
+        header[20..22].copy_from_slice(&1234u16.to_be_bytes());
+        header[22..24].copy_from_slice(&80u16.to_be_bytes());
+        header[24..28].copy_from_slice(&seq.to_be_bytes());
+        header[28..32].copy_from_slice(&ack.to_be_bytes());
+        header[32] = 0x50;
+        header[33] = flags;
+        header[34..36].copy_from_slice(&4096u16.to_be_bytes());
+        header[36..38].copy_from_slice(&[0xAB, 0xCD]);
+        let checksum = ip_checksum(&header[..20]);
+        header[10..12].copy_from_slice(&checksum.to_be_bytes());
+
+        packet.extend(header);
+        packet.extend(data.iter().copied());
+
+        packet
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let packets = [
+            packet(1, 1000, 500, 0x10, b"hello"),
+            packet(2, 1005, 500, 0x18, b"world"),
+            packet(3, 1010, 510, 0x10, b"!"),
+            packet(7, 1011, 510, 0x10, &[]),
+            packet(8, 1011, 510, 0x10, b"data"),
+        ];
+        let types = [
+            CslipPacketType::UncompressedTcp,
+            CslipPacketType::CompressedTcp,
+            CslipPacketType::CompressedTcp,
+            CslipPacketType::CompressedTcp,
+            CslipPacketType::CompressedTcp,
+        ];
+
+        let mut compressor = CslipCompressor::<4>::new();
+        let mut decompressor = CslipDecompressor::<4>::new();
+
+        for (packet, packet_type) in packets.iter().zip(types) {
+            let mut frame = packet.clone();
+
+            assert_eq!(compressor.compress(&mut frame), packet_type);
+            if packet_type == CslipPacketType::CompressedTcp {
+                assert!(frame.len() < packet.len());
+            }
+
+            assert_eq!(decompressor.decompress(&mut frame), Ok(packet_type));
+            assert_eq!(frame.as_slice(), packet.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_compress_unidirectional_data() {
+        let mut compressor = CslipCompressor::<4>::new();
+
+        let mut frame = packet(1, 1000, 500, 0x10, b"hello");
+        compressor.compress(&mut frame);
+        let mut frame = packet(2, 1005, 500, 0x10, b"world");
+
+        assert_eq!(
+            compressor.compress(&mut frame),
+            CslipPacketType::CompressedTcp
+        );
+        // Change mask, checksum, then the payload
+        assert_eq!(frame.as_slice(), b"\x8F\xAB\xCDworld");
+    }
+
+    #[test]
+    fn test_compress_not_tcp() {
+        let mut frame = packet(1, 1000, 500, 0x02, b"syn");
+        let original = frame.clone();
+
+        assert_eq!(
+            CslipCompressor::<4>::new().compress(&mut frame),
+            CslipPacketType::Ip
+        );
+        assert_eq!(frame.as_slice(), original.as_slice());
+    }
+
+    #[test]
+    fn test_decompress_after_error() {
+        let mut compressor = CslipCompressor::<4>::new();
+        let mut decompressor = CslipDecompressor::<4>::new();
+
+        let mut frame = packet(1, 1000, 500, 0x10, b"hello");
+        compressor.compress(&mut frame);
+        decompressor.decompress(&mut frame).unwrap();
+
+        let mut frame = packet(2, 1005, 500, 0x10, b"world");
+        compressor.compress(&mut frame);
+        decompressor.mark_error();
+
+        assert_eq!(
+            decompressor.decompress(&mut frame),
+            Err(SlipDecodeError::MalformedFrame)
+        );
+    }
+}
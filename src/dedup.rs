@@ -0,0 +1,102 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::wcet::WorkBound;
+
+/// The outcome of pushing a frame into a `FrameDeduplicator`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DedupEvent {
+    /// The frame differs from the previous one and should be reported.
+    ///
+    /// `suppressed` is the number of repeats of the previous frame that were collapsed.
+    New { suppressed: usize },
+    /// The frame is identical to the previous one and was collapsed.
+    Repeat,
+}
+
+/// A de-duplication stage for monitoring decoded frames.
+///
+/// This struct collapses runs of identical consecutive frames into the first occurrence plus a
+/// repeat count. Frames longer than `MAX_LENGTH` are never collapsed.
+#[derive(Default)]
+pub struct FrameDeduplicator<const MAX_LENGTH: usize> {
+    last: Option<Vec<u8, MAX_LENGTH>>,
+    repeats: usize,
+}
+
+impl<const MAX_LENGTH: usize> FrameDeduplicator<MAX_LENGTH> {
+    /// Creates an empty deduplicator.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            last: None,
+            repeats: 0,
+        }
+    }
+
+    /// Records `frame` and reports whether it repeats the previous one.
+    pub fn push(&mut self, frame: &[u8]) -> DedupEvent {
+        if self.last.as_deref() == Some(frame) {
+            self.repeats += 1;
+
+            return DedupEvent::Repeat;
+        }
+
+        let suppressed = self.flush();
+        self.last = Vec::try_from(frame).ok();
+
+        DedupEvent::New { suppressed }
+    }
+
+    /// Returns the worst-case work of `push`.
+    #[must_use]
+    pub const fn push_work_bound() -> WorkBound {
+        // Comparison with the previous frame, then copy of the new one
+        WorkBound::linear(MAX_LENGTH).then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Returns the number of repeats collapsed since the last reported frame.
+    #[must_use]
+    pub const fn repeats(&self) -> usize {
+        self.repeats
+    }
+
+    /// Forgets the previous frame and returns the number of its collapsed repeats.
+    ///
+    /// Call this before shutting down a monitor so that a pending run is not lost.
+    pub fn flush(&mut self) -> usize {
+        self.last = None;
+
+        core::mem::take(&mut self.repeats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dedup::DedupEvent;
+    use crate::dedup::FrameDeduplicator;
+
+    #[test]
+    fn test_dedup() {
+        let mut dedup = FrameDeduplicator::<4>::new();
+
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::New { suppressed: 0 });
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::Repeat);
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::Repeat);
+        assert_eq!(dedup.repeats(), 2);
+
+        assert_eq!(dedup.push(&[0x02]), DedupEvent::New { suppressed: 2 });
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::New { suppressed: 0 });
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::Repeat);
+
+        assert_eq!(dedup.flush(), 1);
+        assert_eq!(dedup.push(&[0x01]), DedupEvent::New { suppressed: 0 });
+    }
+
+    #[test]
+    fn test_dedup_with_oversized_frame() {
+        let mut dedup = FrameDeduplicator::<1>::new();
+
+        assert_eq!(dedup.push(&[0x01, 0x02]), DedupEvent::New { suppressed: 0 });
+        assert_eq!(dedup.push(&[0x01, 0x02]), DedupEvent::New { suppressed: 0 });
+    }
+}
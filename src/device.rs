@@ -0,0 +1,298 @@
+use smoltcp::phy::Device;
+use smoltcp::phy::DeviceCapabilities;
+use smoltcp::phy::Medium;
+use smoltcp::phy::RxToken;
+use smoltcp::phy::TxToken;
+use smoltcp::time::Instant;
+
+use crate::slip::EscapedSegments;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+
+/// A serial line carrying SLIP frames.
+///
+/// This trait is the minimal interface `SlipDevice` needs from the underlying transport. Errors
+/// are left to the implementation: smoltcp has no way to report them, and drops packets anyway
+/// when a link misbehaves.
+pub trait SerialPort {
+    /// Returns the next received byte, or `None` if no byte is available yet.
+    ///
+    /// This must not block, as it is called while polling the network stack.
+    fn read_byte(&mut self) -> Option<u8>;
+
+    /// Writes all of `data` to the line.
+    fn write(&mut self, data: &[u8]);
+}
+
+/// A smoltcp network device over a SLIP link.
+///
+/// This struct frames outgoing IP packets and unframes incoming ones, so that a serial line can
+/// be handed to a smoltcp interface using `Medium::Ip`. Packets are at most `MTU` bytes long;
+/// incoming frames that are malformed or too long are dropped.
+pub struct SlipDevice<S, const MTU: usize> {
+    serial: S,
+    config: SlipConfig,
+    decoder: SlipDecoder<MTU>,
+    tx_buffer: [u8; MTU],
+}
+
+impl<S: SerialPort, const MTU: usize> SlipDevice<S, MTU> {
+    /// Creates a device over `serial`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(serial: S) -> Self {
+        Self::with_config(serial, SlipConfig::new())
+    }
+
+    /// Creates a device over `serial`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(serial: S, config: SlipConfig) -> Self {
+        Self {
+            serial,
+            config,
+            decoder: SlipDecoder::with_config(config),
+            tx_buffer: [0; MTU],
+        }
+    }
+
+    /// Decodes the available bytes until a frame is complete.
+    ///
+    /// Returns `true` if a frame is ready to be consumed.
+    fn poll_frame(&mut self) -> bool {
+        while !self.decoder.is_buffer_completed() {
+            let Some(value) = self.serial.read_byte() else {
+                return false;
+            };
+
+            if self.decoder.insert(value).is_err() {
+                self.decoder.reset();
+            }
+        }
+
+        true
+    }
+
+    /// Returns a reference to the underlying serial line.
+    #[must_use]
+    pub const fn get_ref(&self) -> &S {
+        &self.serial
+    }
+
+    /// Returns a mutable reference to the underlying serial line.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    /// Consumes the device, returning the underlying serial line.
+    ///
+    /// Bytes of a partially received frame are lost.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.serial
+    }
+}
+
+/// Implementation of `Device` for `SlipDevice`.
+impl<S: SerialPort, const MTU: usize> Device for SlipDevice<S, MTU> {
+    type RxToken<'a>
+        = SlipRxToken<'a, MTU>
+    where
+        Self: 'a;
+    type TxToken<'a>
+        = SlipTxToken<'a, S, MTU>
+    where
+        Self: 'a;
+
+    /// Returns the next received packet, along with a token to reply to it.
+    fn receive(&mut self, _timestamp: Instant) -> Option<(Self::RxToken<'_>, Self::TxToken<'_>)> {
+        if !self.poll_frame() {
+            return None;
+        }
+
+        Some((
+            SlipRxToken {
+                decoder: &mut self.decoder,
+            },
+            SlipTxToken {
+                serial: &mut self.serial,
+                config: self.config,
+                buffer: &mut self.tx_buffer,
+            },
+        ))
+    }
+
+    /// Returns a token to send a packet.
+    ///
+    /// The serial line is always ready to send, as writes block until the frame is out.
+    fn transmit(&mut self, _timestamp: Instant) -> Option<Self::TxToken<'_>> {
+        Some(SlipTxToken {
+            serial: &mut self.serial,
+            config: self.config,
+            buffer: &mut self.tx_buffer,
+        })
+    }
+
+    /// Returns the capabilities of a SLIP link: raw IP packets of at most `MTU` bytes.
+    fn capabilities(&self) -> DeviceCapabilities {
+        let mut capabilities = DeviceCapabilities::default();
+        capabilities.medium = Medium::Ip;
+        capabilities.max_transmission_unit = MTU;
+
+        capabilities
+    }
+}
+
+/// A token to receive a packet from a `SlipDevice`.
+///
+/// The frame is released once the token is consumed or dropped.
+pub struct SlipRxToken<'a, const MTU: usize> {
+    decoder: &'a mut SlipDecoder<MTU>,
+}
+
+/// Implementation of `RxToken` for `SlipRxToken`.
+impl<const MTU: usize> RxToken for SlipRxToken<'_, MTU> {
+    /// Calls `f` with the received packet.
+    fn consume<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        f(self.decoder.get_buffer())
+    }
+}
+
+/// Drop implementation for `SlipRxToken`.
+impl<const MTU: usize> Drop for SlipRxToken<'_, MTU> {
+    /// Resets the decoder, so that the next frame can be received.
+    fn drop(&mut self) {
+        self.decoder.reset();
+    }
+}
+
+/// A token to send a packet through a `SlipDevice`.
+pub struct SlipTxToken<'a, S, const MTU: usize> {
+    serial: &'a mut S,
+    config: SlipConfig,
+    buffer: &'a mut [u8; MTU],
+}
+
+/// Implementation of `TxToken` for `SlipTxToken`.
+impl<S: SerialPort, const MTU: usize> TxToken for SlipTxToken<'_, S, MTU> {
+    /// Lets `f` build a packet of `len` bytes, then writes it to the serial line as a frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds `MTU`. smoltcp never asks for more than the advertised maximum
+    /// transmission unit.
+    fn consume<R, F>(self, len: usize, f: F) -> R
+    where
+        F: FnOnce(&mut [u8]) -> R,
+    {
+        let packet = &mut self.buffer[..len];
+        let result = f(packet);
+
+        if self.config.leading_end() {
+            self.serial.write(&[self.config.end_char()]);
+        }
+        for segment in EscapedSegments::new(packet, self.config) {
+            self.serial.write(segment);
+        }
+        self.serial.write(&[self.config.end_char()]);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+    use smoltcp::phy::Device;
+    use smoltcp::phy::Medium;
+    use smoltcp::phy::RxToken;
+    use smoltcp::phy::TxToken;
+    use smoltcp::time::Instant;
+
+    use crate::device::SerialPort;
+    use crate::device::SlipDevice;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    struct MockSerial<'a> {
+        input: &'a [u8],
+        output: Vec<u8, 32>,
+    }
+
+    impl SerialPort for MockSerial<'_> {
+        fn read_byte(&mut self) -> Option<u8> {
+            let (&value, rest) = self.input.split_first()?;
+            self.input = rest;
+
+            Some(value)
+        }
+
+        fn write(&mut self, data: &[u8]) {
+            self.output.extend(data.iter().copied());
+        }
+    }
+
+    #[test]
+    fn test_receive() {
+        let serial = MockSerial {
+            input: &[
+                END_CHAR, 0x45, ESC_CHAR, END_CHAR, END_CHAR, 0x45, 0x01, END_CHAR,
+            ],
+            output: Vec::new(),
+        };
+        let mut device = SlipDevice::<_, 8>::new(serial);
+
+        let (rx, _) = device.receive(Instant::ZERO).unwrap();
+        rx.consume(|packet| assert_eq!(packet, [0x45, 0x01]));
+
+        assert!(device.receive(Instant::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_receive_partial_frame() {
+        let serial = MockSerial {
+            input: &[END_CHAR, 0x45],
+            output: Vec::new(),
+        };
+        let mut device = SlipDevice::<_, 8>::new(serial);
+
+        assert!(device.receive(Instant::ZERO).is_none());
+
+        device.get_mut().input = &[0x01, END_CHAR];
+        let (rx, _) = device.receive(Instant::ZERO).unwrap();
+        rx.consume(|packet| assert_eq!(packet, [0x45, 0x01]));
+    }
+
+    #[test]
+    fn test_transmit() {
+        let serial = MockSerial {
+            input: &[],
+            output: Vec::new(),
+        };
+        let mut device = SlipDevice::<_, 8>::new(serial);
+
+        let tx = device.transmit(Instant::ZERO).unwrap();
+        tx.consume(2, |packet| packet.copy_from_slice(&[0x45, END_CHAR]));
+
+        assert_eq!(
+            device.into_inner().output.as_slice(),
+            [END_CHAR, 0x45, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let serial = MockSerial {
+            input: &[],
+            output: Vec::new(),
+        };
+        let device = SlipDevice::<_, 296>::new(serial);
+        let capabilities = device.capabilities();
+
+        assert_eq!(capabilities.medium, Medium::Ip);
+        assert_eq!(capabilities.max_transmission_unit, 296);
+    }
+}
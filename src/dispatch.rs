@@ -0,0 +1,176 @@
+use crate::error::SlipDecodeError;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A receiver of decoded frames.
+///
+/// This trait is implemented by any `FnMut(&[u8])` closure, so that a dispatcher can hand
+/// frames to a closure as well as to a dedicated type.
+pub trait FrameSink {
+    /// Handles the payload of a completed frame.
+    ///
+    /// `frame` is only valid for the duration of the call: the decoder buffer is reused for the
+    /// next frame right after.
+    fn on_frame(&mut self, frame: &[u8]);
+}
+
+/// Implementation of `FrameSink` for closures.
+impl<F: FnMut(&[u8])> FrameSink for F {
+    /// Calls the closure with `frame`.
+    fn on_frame(&mut self, frame: &[u8]) {
+        self(frame);
+    }
+}
+
+/// A SLIP decoder handing each completed frame to a sink.
+///
+/// This struct decodes bytes as they come, calls its sink with every completed frame, and
+/// resets itself for the next one. It suits interrupt handlers, which can feed it bytes and
+/// dispatch frames without any other code touching the decoder. A frame is borrowed by the sink
+/// straight from the decoder buffer, without being copied.
+pub struct SlipDispatcher<S, const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    sink: S,
+}
+
+impl<S: FrameSink, const MAX_LENGTH: usize> SlipDispatcher<S, MAX_LENGTH> {
+    /// Creates a dispatcher handing frames to `sink`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(sink: S) -> Self {
+        Self::with_config(sink, SlipConfig::new())
+    }
+
+    /// Creates a dispatcher handing frames to `sink`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(sink: S, config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            sink,
+        }
+    }
+
+    /// Feeds `value` into the decoder, handing the frame it completes to the sink.
+    ///
+    /// Returns `Ok(())` on success, or the error that interrupted the frame. The frame is then
+    /// dropped and the decoder reset, so that the next frame is decoded without intervention.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.dispatch(value).map(|_| ())
+    }
+
+    /// Returns the worst-case work of `insert`, excluding the time spent in the sink.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound()
+            .then(SlipDecoder::<MAX_LENGTH>::reset_work_bound())
+    }
+
+    /// Feeds the bytes of `data` into the decoder, handing every frame they complete to the sink.
+    ///
+    /// Frames interrupted by an error are dropped, and only show in the statistics of the link.
+    /// Returns the number of frames handed to the sink.
+    pub fn feed(&mut self, data: &[u8]) -> usize {
+        data.iter()
+            .filter(|&&value| self.dispatch(value) == Ok(true))
+            .count()
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes, excluding the time spent in
+    /// the sink.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Feeds `value` into the decoder, handing the frame it completes to the sink.
+    ///
+    /// Returns `Ok(true)` if a frame was handed to the sink.
+    fn dispatch(&mut self, value: u8) -> Result<bool, SlipDecodeError> {
+        if let Err(error) = self.decoder.insert(value) {
+            self.decoder.reset();
+
+            return Err(error);
+        }
+
+        if !self.decoder.is_buffer_completed() {
+            return Ok(false);
+        }
+
+        self.sink.on_frame(self.decoder.get_buffer());
+        self.decoder.reset();
+
+        Ok(true)
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+
+    /// Returns a reference to the sink.
+    #[must_use]
+    pub const fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns a mutable reference to the sink.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consumes the dispatcher, returning the sink.
+    ///
+    /// The bytes of a partially decoded frame are lost.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::dispatch::SlipDispatcher;
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+
+    #[test]
+    fn test_dispatch() {
+        let mut frames = Vec::<Vec<u8, 4>, 4>::new();
+        let mut dispatcher = SlipDispatcher::<_, 4>::new(|frame: &[u8]| {
+            let _ = frames.push(Vec::try_from(frame).unwrap());
+        });
+
+        let count = dispatcher.feed(&[END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, 0x03, END_CHAR]);
+
+        assert_eq!(count, 2);
+
+        drop(dispatcher);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].as_slice(), [0x01]);
+        assert_eq!(frames[1].as_slice(), [0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_dispatch_after_error() {
+        let mut count = 0;
+        let mut dispatcher = SlipDispatcher::<_, 4>::new(|_: &[u8]| count += 1);
+
+        assert_eq!(dispatcher.insert(END_CHAR), Ok(()));
+        assert_eq!(dispatcher.insert(ESC_CHAR), Ok(()));
+        assert_eq!(
+            dispatcher.insert(0x01),
+            Err(SlipDecodeError::InvalidEscape(0x01))
+        );
+        assert_eq!(dispatcher.feed(&[END_CHAR, 0x01, END_CHAR]), 1);
+        assert_eq!(dispatcher.stats().frames_dropped, 1);
+
+        drop(dispatcher);
+        assert_eq!(count, 1);
+    }
+}
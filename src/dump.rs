@@ -0,0 +1,189 @@
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Write;
+
+use crate::slip::SlipConfig;
+use crate::slip::SlipEncodeIter;
+
+/// Number of bytes rendered on each line of a dump.
+const LINE_LENGTH: usize = 16;
+
+/// What a `FrameDump` renders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DumpView {
+    /// The bytes as they are.
+    Raw,
+    /// The frame encoding the bytes, using the given configuration.
+    Escaped(SlipConfig),
+}
+
+/// A hexadecimal dump of a frame, for logging.
+///
+/// This struct renders bytes the way `hexdump -C` does, 16 per line, with their offset and their
+/// ASCII representation, through `Display` and `Debug` alike, so that it can be logged with any
+/// formatting macro without a buffer. The bytes are either rendered as they are, e.g. a received
+/// frame, or as the frame encoding them, to see the escape sequences of a payload on the wire.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FrameDump<'a> {
+    bytes: &'a [u8],
+    view: DumpView,
+}
+
+impl<'a> FrameDump<'a> {
+    /// Creates a dump of `bytes` as they are.
+    #[must_use]
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            view: DumpView::Raw,
+        }
+    }
+
+    /// Creates a dump of the frame encoding `payload`, using the framing options of `config`.
+    #[must_use]
+    pub const fn escaped(payload: &'a [u8], config: SlipConfig) -> Self {
+        Self {
+            bytes: payload,
+            view: DumpView::Escaped(config),
+        }
+    }
+
+    /// Renders the dump, one line per `LINE_LENGTH` bytes.
+    fn render(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let bytes = self.bytes.iter().copied();
+        match self.view {
+            DumpView::Raw => render_lines(f, bytes),
+            DumpView::Escaped(config) => {
+                render_lines(f, SlipEncodeIter::with_config(bytes, config))
+            }
+        }
+    }
+}
+
+/// Renders `bytes` as lines of `LINE_LENGTH` bytes.
+fn render_lines(f: &mut Formatter<'_>, bytes: impl Iterator<Item = u8>) -> fmt::Result {
+    let mut line = [0; LINE_LENGTH];
+    let mut length = 0;
+    let mut offset = 0;
+
+    for value in bytes {
+        line[length] = value;
+        length += 1;
+
+        if length == LINE_LENGTH {
+            render_line(f, offset, &line)?;
+            offset += LINE_LENGTH;
+            length = 0;
+        }
+    }
+
+    if length > 0 {
+        render_line(f, offset, &line[..length])?;
+    }
+
+    Ok(())
+}
+
+/// Renders the bytes of `line`, found at `offset`.
+fn render_line(f: &mut Formatter<'_>, offset: usize, line: &[u8]) -> fmt::Result {
+    if offset > 0 {
+        f.write_char('\n')?;
+    }
+    write!(f, "{offset:08X} ")?;
+
+    for index in 0..LINE_LENGTH {
+        if index == LINE_LENGTH / 2 {
+            f.write_char(' ')?;
+        }
+
+        match line.get(index) {
+            Some(value) => write!(f, " {value:02X}")?,
+            None => f.write_str("   ")?,
+        }
+    }
+
+    f.write_str("  |")?;
+    for &value in line {
+        let printable = value.is_ascii_graphic() || value == b' ';
+        f.write_char(if printable { char::from(value) } else { '.' })?;
+    }
+
+    f.write_char('|')
+}
+
+/// Implementation of `Display` for `FrameDump`.
+impl Display for FrameDump<'_> {
+    /// Formats the bytes as a hexadecimal dump.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+/// Implementation of `Debug` for `FrameDump`.
+impl Debug for FrameDump<'_> {
+    /// Formats the bytes as a hexadecimal dump, as `Display` does.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt;
+    use core::fmt::Write;
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::dump::FrameDump;
+    use crate::slip::END_CHAR;
+    use crate::slip::SlipConfig;
+
+    /// Text formatted into a fixed-capacity buffer.
+    #[derive(Default)]
+    struct Text(Vec<u8, 256>);
+
+    impl Write for Text {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for &value in s.as_bytes() {
+                self.0.push(value).map_err(|_| fmt::Error)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut bytes = [0x00; 18];
+        bytes[..5].copy_from_slice(b"SLIP ");
+        bytes[17] = END_CHAR;
+
+        let mut text = Text::default();
+        write!(text, "{}", FrameDump::new(&bytes)).unwrap();
+
+        assert_eq!(
+            core::str::from_utf8(text.0.as_slice()),
+            Ok(concat!(
+                "00000000  53 4C 49 50 20 00 00 00  00 00 00 00 00 00 00 00  |SLIP ...........|\n",
+                "00000010  00 C0                                             |..|",
+            ))
+        );
+    }
+
+    #[test]
+    fn test_dump_escaped() {
+        let mut text = Text::default();
+        write!(
+            text,
+            "{:?}",
+            FrameDump::escaped(&[b'A', END_CHAR], SlipConfig::new())
+        )
+        .unwrap();
+
+        assert_eq!(
+            core::str::from_utf8(text.0.as_slice()),
+            Ok("00000000  C0 41 DB DC C0                                    |.A...|")
+        );
+    }
+}
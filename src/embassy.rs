@@ -0,0 +1,127 @@
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Receiver;
+use embassy_sync::channel::Sender;
+use embedded_io_async::Read;
+use embedded_io_async::Write;
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipIoError;
+use crate::io_async::SlipFrameReader;
+use crate::io_async::SlipFrameWriter;
+
+/// Reads frames from `reader` and sends their payloads to a channel, through `sender`.
+///
+/// This is the receive pump of an embassy task: it runs until the transport fails or ends,
+/// waiting on the channel whenever it is full. Frames interrupted by a decode error are dropped,
+/// and only show in the statistics of the link. Returns the error that stopped the pump.
+#[allow(clippy::future_not_send)]
+pub async fn receive_frames<R: Read, M: RawMutex, const MAX_LENGTH: usize, const N: usize>(
+    reader: &mut SlipFrameReader<R, MAX_LENGTH>,
+    sender: Sender<'_, M, Vec<u8, MAX_LENGTH>, N>,
+) -> SlipIoError<R::Error> {
+    let mut buf = [0; MAX_LENGTH];
+
+    loop {
+        match reader.read_frame(&mut buf).await {
+            Ok(length) => {
+                // A payload never exceeds the capacity of the decoder
+                if let Ok(frame) = Vec::try_from(&buf[..length]) {
+                    sender.send(frame).await;
+                }
+            }
+            Err(SlipIoError::Decode(_)) => {}
+            Err(error) => return error,
+        }
+    }
+}
+
+/// Receives payloads from a channel, through `receiver`, and writes each of them to `writer` as
+/// a frame.
+///
+/// This is the send pump of an embassy task: it runs until the transport fails, waiting on the
+/// channel whenever it is empty. The transport is flushed after every frame. Returns the error
+/// that stopped the pump.
+#[allow(clippy::future_not_send)]
+pub async fn send_frames<W: Write, M: RawMutex, T: AsRef<[u8]>, const N: usize>(
+    writer: &mut SlipFrameWriter<W>,
+    receiver: Receiver<'_, M, T, N>,
+) -> W::Error {
+    loop {
+        let payload = receiver.receive().await;
+
+        let result = match writer.write_frame(payload.as_ref()).await {
+            Ok(()) => writer.flush().await,
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            return error;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::pin::pin;
+    use core::task::Context;
+    use core::task::Poll;
+    use core::task::Waker;
+    use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+    use embassy_sync::channel::Channel;
+    use embedded_io_async::Error;
+    use embedded_io_async::ErrorKind;
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::embassy::receive_frames;
+    use crate::embassy::send_frames;
+    use crate::error::SlipIoError;
+    use crate::io_async::SlipFrameReader;
+    use crate::io_async::SlipFrameWriter;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    /// Polls `future` to completion, assuming it never has to wait.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_receive_frames() {
+        let data = [
+            END_CHAR, 0x01, END_CHAR, END_CHAR, ESC_CHAR, 0x00, END_CHAR, 0x02, END_CHAR,
+        ];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+        let channel = Channel::<NoopRawMutex, Vec<u8, 4>, 2>::new();
+
+        let error = block_on(receive_frames(&mut reader, channel.sender()));
+
+        assert_eq!(error, SlipIoError::UnexpectedEof);
+        assert_eq!(channel.try_receive().unwrap().as_slice(), [0x01]);
+        assert_eq!(channel.try_receive().unwrap().as_slice(), [0x02]);
+        assert!(channel.try_receive().is_err());
+    }
+
+    #[test]
+    fn test_send_frames() {
+        let mut output = [0x00; 6];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+        let channel = Channel::<NoopRawMutex, [u8; 2], 2>::new();
+        channel.try_send([0x01, END_CHAR]).unwrap();
+        channel.try_send([0x02, 0x03]).unwrap();
+
+        let error = block_on(send_frames(&mut writer, channel.receiver()));
+
+        assert_eq!(error.kind(), ErrorKind::WriteZero);
+        assert_eq!(
+            output,
+            [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR, END_CHAR]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+/// A sink writing SLIP frames, such as the frame writers over a transport.
+///
+/// A frame is written with one call to `start_frame`, any number of calls to `write`, each
+/// escaping its bytes, and one call to `end_frame`. Implementing this trait lets `SlipEncode`
+/// types be framed directly into the sink.
+pub trait FrameWrite {
+    /// The error returned by the sink.
+    type Error;
+
+    /// Begins a new frame.
+    fn start_frame(&mut self) -> Result<(), Self::Error>;
+
+    /// Escapes `data` and writes it as part of the current frame.
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Ends the current frame.
+    fn end_frame(&mut self) -> Result<(), Self::Error>;
+
+    /// Writes `message` as a complete frame.
+    fn send<T: SlipEncode + ?Sized>(&mut self, message: &T) -> Result<(), Self::Error> {
+        self.start_frame()?;
+        message.encode_slip(self)?;
+        self.end_frame()
+    }
+}
+
+/// A type with a wire representation, which can be sent as the payload of a SLIP frame.
+///
+/// Implementations write their representation piece by piece, so that messages are framed
+/// without being serialized into a buffer first.
+pub trait SlipEncode {
+    /// Writes the wire representation of the value into the current frame of `writer`.
+    fn encode_slip<W: FrameWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error>;
+}
+
+/// Implementation of `SlipEncode` for byte slices.
+impl SlipEncode for [u8] {
+    /// Writes the bytes as is.
+    fn encode_slip<W: FrameWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write(self)
+    }
+}
+
+/// Implementation of `SlipEncode` for byte arrays.
+impl<const LENGTH: usize> SlipEncode for [u8; LENGTH] {
+    /// Writes the bytes as is.
+    fn encode_slip<W: FrameWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error> {
+        writer.write(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::encode::FrameWrite;
+    use crate::encode::SlipEncode;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::SlipEncoder;
+
+    /// A sink recording the payload of the current frame, then encoding it as a whole.
+    #[derive(Default)]
+    struct MockWriter {
+        payload: Vec<u8, 8>,
+        frame: Vec<u8, 16>,
+    }
+
+    impl FrameWrite for MockWriter {
+        type Error = Infallible;
+
+        fn start_frame(&mut self) -> Result<(), Self::Error> {
+            self.payload.clear();
+
+            Ok(())
+        }
+
+        fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+            for &value in data {
+                let _ = self.payload.push(value);
+            }
+
+            Ok(())
+        }
+
+        fn end_frame(&mut self) -> Result<(), Self::Error> {
+            self.frame = Vec::from(&self.payload);
+            let _ = SlipEncoder::default().encode(&mut self.frame);
+
+            Ok(())
+        }
+    }
+
+    /// A reading of a sensor, sent as its identifier then its big-endian value.
+    struct Reading {
+        sensor: u8,
+        value: i16,
+    }
+
+    impl SlipEncode for Reading {
+        fn encode_slip<W: FrameWrite + ?Sized>(&self, writer: &mut W) -> Result<(), W::Error> {
+            writer.write(&[self.sensor])?;
+            writer.write(&self.value.to_be_bytes())
+        }
+    }
+
+    #[test]
+    fn test_send() {
+        let mut writer = MockWriter::default();
+        let reading = Reading {
+            sensor: END_CHAR,
+            value: 0x0102,
+        };
+
+        writer.send(&reading).unwrap();
+
+        assert_eq!(
+            writer.frame.as_slice(),
+            [END_CHAR, ESC_CHAR, ESC_END_CHAR, 0x01, 0x02, END_CHAR]
+        );
+
+        writer.send(&[0x03_u8, 0x04]).unwrap();
+
+        assert_eq!(writer.frame.as_slice(), [END_CHAR, 0x03, 0x04, END_CHAR]);
+    }
+}
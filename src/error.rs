@@ -0,0 +1,363 @@
+use core::error::Error;
+use core::fmt;
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+/// An error returned when encoding a SLIP frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipEncodeError {
+    /// The output buffer is too small to hold the encoded frame.
+    BufferTooSmall,
+    /// The output buffer holds `available` bytes, while the encoded frame takes `required` bytes.
+    ///
+    /// This is returned by encoders which size the frame before writing it, so that nothing is
+    /// written yet: the call can be retried as is with a buffer of `required` bytes.
+    InsufficientCapacity { required: usize, available: usize },
+    /// The region of the output buffer to write to does not fit in the buffer.
+    InvalidRegion,
+    /// The payload is not valid for the requested frame type.
+    InvalidPayload,
+}
+
+/// Implementation of `Display` for `SlipEncodeError`.
+impl Display for SlipEncodeError {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => f.write_str("buffer too small for the encoded frame"),
+            Self::InsufficientCapacity {
+                required,
+                available,
+            } => write!(
+                f,
+                "buffer too small for the encoded frame: {required} bytes required, {available} available"
+            ),
+            Self::InvalidRegion => f.write_str("output region out of the buffer bounds"),
+            Self::InvalidPayload => f.write_str("payload invalid for the frame type"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipEncodeError`.
+impl Error for SlipEncodeError {}
+
+/// An error returned when decoding a SLIP frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipDecodeError {
+    /// The decoded payload does not fit in the buffer.
+    BufferFull,
+    /// `ESC_CHAR` was followed by the given byte instead of an escaped value.
+    InvalidEscape(u8),
+    /// The frame does not follow the SLIP encoding.
+    MalformedFrame,
+    /// A complete frame is pending and the decoder must be reset first.
+    FrameCompleted,
+    /// The decoder buffer no longer matches its integrity guard.
+    IntegrityCheckFailed,
+    /// The checksum trailing the frame does not match its payload.
+    ChecksumMismatch,
+    /// The frame is longer than the maximum frame length of the decoder.
+    FrameTooLong,
+    /// The given byte was received outside a frame, while waiting for its opening terminator.
+    UnexpectedByte(u8),
+    /// The frame transform rejected the frame, e.g. as its authentication failed.
+    TransformFailed,
+    /// The frame is longer than the MTU of the decoder configuration.
+    MtuExceeded,
+    /// No frame is complete yet.
+    FrameIncomplete,
+    /// The output buffer is smaller than the given length of the completed frame, which stays
+    /// pending until it is read with a large enough buffer.
+    OutputTooSmall(usize),
+    /// The validator of the decoder rejected the frame.
+    InvalidFrame,
+}
+
+/// Implementation of `Display` for `SlipDecodeError`.
+impl Display for SlipDecodeError {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferFull => f.write_str("buffer full"),
+            Self::InvalidEscape(value) => write!(f, "invalid escape sequence: 0x{value:02X}"),
+            Self::MalformedFrame => f.write_str("malformed frame"),
+            Self::FrameCompleted => f.write_str("frame already completed"),
+            Self::IntegrityCheckFailed => f.write_str("integrity check failed"),
+            Self::ChecksumMismatch => f.write_str("checksum mismatch"),
+            Self::FrameTooLong => f.write_str("frame too long"),
+            Self::UnexpectedByte(value) => {
+                write!(f, "unexpected byte outside a frame: 0x{value:02X}")
+            }
+            Self::TransformFailed => f.write_str("frame transform failed"),
+            Self::MtuExceeded => f.write_str("frame longer than the MTU"),
+            Self::FrameIncomplete => f.write_str("no frame completed yet"),
+            Self::OutputTooSmall(length) => {
+                write!(f, "output buffer too small for a frame of {length} bytes")
+            }
+            Self::InvalidFrame => f.write_str("frame rejected by the validator"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipDecodeError`.
+impl Error for SlipDecodeError {}
+
+/// An error returned by the frame readers and writers over a transport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipIoError<E> {
+    /// The transport returned an error.
+    Io(E),
+    /// The received frame could not be decoded.
+    Decode(SlipDecodeError),
+    /// The transport reached the end of the stream before a frame was complete.
+    UnexpectedEof,
+    /// The output buffer is smaller than the given length of the received frame, which stays
+    /// pending until it is read with a large enough buffer.
+    BufferTooSmall(usize),
+    /// The frame to send could not be encoded.
+    Encode(SlipEncodeError),
+    /// The transport stayed silent for longer than the timeout before a frame was complete. The
+    /// bytes of a partial frame stay pending, to be completed by the next read.
+    Timeout,
+}
+
+/// Implementation of `Display` for `SlipIoError`.
+impl<E: Display> Display for SlipIoError<E> {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "transport error: {error}"),
+            Self::Decode(error) => write!(f, "decode error: {error}"),
+            Self::UnexpectedEof => f.write_str("unexpected end of stream"),
+            Self::BufferTooSmall(length) => {
+                write!(f, "buffer too small for a frame of {length} bytes")
+            }
+            Self::Encode(error) => write!(f, "encode error: {error}"),
+            Self::Timeout => f.write_str("timed out waiting for a frame"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipIoError`.
+impl<E: Error + 'static> Error for SlipIoError<E> {
+    /// Returns the underlying transport, decode or encode error.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Decode(error) => Some(error),
+            Self::Encode(error) => Some(error),
+            Self::UnexpectedEof | Self::BufferTooSmall(_) | Self::Timeout => None,
+        }
+    }
+}
+
+/// Conversion from `SlipDecodeError` to `SlipIoError`.
+impl<E> From<SlipDecodeError> for SlipIoError<E> {
+    /// Wraps a decode error.
+    fn from(error: SlipDecodeError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+/// Conversion from `SlipEncodeError` to `SlipIoError`.
+impl<E> From<SlipEncodeError> for SlipIoError<E> {
+    /// Wraps an encode error.
+    fn from(error: SlipEncodeError) -> Self {
+        Self::Encode(error)
+    }
+}
+
+/// An error returned when reassembling a payload from its fragments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipFragmentError {
+    /// The fragment is shorter than its header.
+    Truncated,
+    /// A fragment of the payload is missing, or arrived out of order.
+    OutOfOrder,
+    /// The reassembled payload does not fit in the buffer.
+    PayloadTooLong,
+}
+
+/// Implementation of `Display` for `SlipFragmentError`.
+impl Display for SlipFragmentError {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("fragment shorter than its header"),
+            Self::OutOfOrder => f.write_str("fragment missing or out of order"),
+            Self::PayloadTooLong => f.write_str("reassembled payload too long"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipFragmentError`.
+impl Error for SlipFragmentError {}
+
+/// An error returned by the stop-and-wait reliability layer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipArqError {
+    /// A payload is still awaiting its acknowledgment.
+    Busy,
+    /// The payload and its header do not fit in the buffer.
+    PayloadTooLong,
+    /// The frame is shorter than its header, or of an unknown kind.
+    MalformedFrame,
+    /// The payload was not acknowledged within the allowed retransmissions, and was dropped.
+    Unacknowledged,
+}
+
+/// Implementation of `Display` for `SlipArqError`.
+impl Display for SlipArqError {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Busy => f.write_str("payload awaiting acknowledgment"),
+            Self::PayloadTooLong => f.write_str("payload too long"),
+            Self::MalformedFrame => f.write_str("malformed frame"),
+            Self::Unacknowledged => f.write_str("payload not acknowledged"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipArqError`.
+impl Error for SlipArqError {}
+
+/// An error returned when sending or receiving a serialized message as a SLIP frame.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipMessageError {
+    /// The message could not be serialized or deserialized.
+    Serde(postcard::Error),
+    /// The serialized message could not be encoded as a frame.
+    Encode(SlipEncodeError),
+    /// The frame could not be decoded.
+    Decode(SlipDecodeError),
+}
+
+/// Implementation of `Display` for `SlipMessageError`.
+#[cfg(feature = "serde")]
+impl Display for SlipMessageError {
+    /// Formats the error as a human-readable message.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serde(error) => write!(f, "serialization error: {error}"),
+            Self::Encode(error) => write!(f, "encode error: {error}"),
+            Self::Decode(error) => write!(f, "decode error: {error}"),
+        }
+    }
+}
+
+/// Implementation of `Error` for `SlipMessageError`.
+#[cfg(feature = "serde")]
+impl Error for SlipMessageError {
+    /// Returns the underlying serialization, encode or decode error.
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Serde(error) => Some(error),
+            Self::Encode(error) => Some(error),
+            Self::Decode(error) => Some(error),
+        }
+    }
+}
+
+/// Conversion from `postcard::Error` to `SlipMessageError`.
+#[cfg(feature = "serde")]
+impl From<postcard::Error> for SlipMessageError {
+    /// Wraps a serialization error, reporting a full buffer as an encode error.
+    fn from(error: postcard::Error) -> Self {
+        match error {
+            postcard::Error::SerializeBufferFull => Self::Encode(SlipEncodeError::BufferTooSmall),
+            error => Self::Serde(error),
+        }
+    }
+}
+
+/// Conversion from `SlipEncodeError` to `SlipMessageError`.
+#[cfg(feature = "serde")]
+impl From<SlipEncodeError> for SlipMessageError {
+    /// Wraps an encode error.
+    fn from(error: SlipEncodeError) -> Self {
+        Self::Encode(error)
+    }
+}
+
+/// Conversion from `SlipDecodeError` to `SlipMessageError`.
+#[cfg(feature = "serde")]
+impl From<SlipDecodeError> for SlipMessageError {
+    /// Wraps a decode error.
+    fn from(error: SlipDecodeError) -> Self {
+        Self::Decode(error)
+    }
+}
+
+/// Conversion from `std::io::Error` to `SlipIoError`.
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SlipIoError<std::io::Error> {
+    /// Wraps a transport error.
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::error::SlipArqError;
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::error::SlipFragmentError;
+    use noalloc_vec_rs::vec::Vec;
+
+    /// A fixed-capacity string used to render errors in tests.
+    #[derive(Default)]
+    struct Message(Vec<u8, 128>);
+
+    impl Write for Message {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            self.0.extend(s.bytes());
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let mut message = Message::default();
+        write!(message, "{}", SlipEncodeError::BufferTooSmall).unwrap();
+        assert_eq!(&*message.0, b"buffer too small for the encoded frame");
+
+        let mut message = Message::default();
+        let error = SlipEncodeError::InsufficientCapacity {
+            required: 12,
+            available: 8,
+        };
+        write!(message, "{error}").unwrap();
+        assert_eq!(
+            &*message.0,
+            b"buffer too small for the encoded frame: 12 bytes required, 8 available"
+        );
+
+        let mut message = Message::default();
+        write!(message, "{}", SlipDecodeError::InvalidEscape(0x0A)).unwrap();
+        assert_eq!(&*message.0, b"invalid escape sequence: 0x0A");
+
+        let mut message = Message::default();
+        write!(message, "{}", SlipDecodeError::UnexpectedByte(0x0A)).unwrap();
+        assert_eq!(&*message.0, b"unexpected byte outside a frame: 0x0A");
+
+        let mut message = Message::default();
+        write!(message, "{}", SlipFragmentError::OutOfOrder).unwrap();
+        assert_eq!(&*message.0, b"fragment missing or out of order");
+
+        let mut message = Message::default();
+        write!(message, "{}", SlipArqError::Unacknowledged).unwrap();
+        assert_eq!(&*message.0, b"payload not acknowledged");
+    }
+}
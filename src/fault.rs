@@ -0,0 +1,342 @@
+use embedded_io::ErrorType;
+use embedded_io::Read;
+use embedded_io::Write;
+
+/// Number of parts in the rates of a `FaultConfig`, i.e. rates are in parts per million.
+const RATE_PARTS: u32 = 1_000_000;
+
+/// Seed standing in for zero, which xorshift never leaves.
+const ZERO_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Faults injected by a `FaultyTransport` into the bytes it reads.
+///
+/// Rates are in parts per million of the bytes read: a rate of `1_000` corrupts one byte in a
+/// thousand on average.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultConfig {
+    bit_flips: u32,
+    drops: u32,
+    duplicates: u32,
+    splits: bool,
+}
+
+impl FaultConfig {
+    /// Creates a configuration injecting no fault.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bit_flips: 0,
+            drops: 0,
+            duplicates: 0,
+            splits: false,
+        }
+    }
+
+    /// Sets the rate of bytes with a single bit flipped.
+    #[must_use]
+    pub const fn with_bit_flips(mut self, rate: u32) -> Self {
+        self.bit_flips = rate;
+        self
+    }
+
+    /// Sets the rate of bytes dropped.
+    #[must_use]
+    pub const fn with_drops(mut self, rate: u32) -> Self {
+        self.drops = rate;
+        self
+    }
+
+    /// Sets the rate of bytes read twice in a row.
+    #[must_use]
+    pub const fn with_duplicates(mut self, rate: u32) -> Self {
+        self.duplicates = rate;
+        self
+    }
+
+    /// Sets whether reads are split at random points, each of them returning a random number of
+    /// bytes, instead of filling the buffer as far as the transport allows.
+    #[must_use]
+    pub const fn with_splits(mut self, splits: bool) -> Self {
+        self.splits = splits;
+        self
+    }
+}
+
+/// Counters of the faults injected by a `FaultyTransport`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FaultStats {
+    /// Number of bytes with a bit flipped.
+    pub bit_flips: usize,
+    /// Number of bytes dropped.
+    pub drops: usize,
+    /// Number of bytes duplicated.
+    pub duplicates: usize,
+}
+
+/// A transport injecting line noise into the bytes read from another transport, for testing.
+///
+/// This struct stands between the encoder and the decoder, e.g. over a `LoopbackTransport`, to
+/// check that the recovery settings of a decoder survive realistic line noise: bytes written go
+/// through untouched, while bytes read get bits flipped, are dropped or duplicated, and are
+/// handed out in chunks split at random points, as set by a `FaultConfig`. Faults are drawn from
+/// a pseudo-random generator seeded at creation, so that a run is reproduced by its seed.
+pub struct FaultyTransport<T> {
+    inner: T,
+    config: FaultConfig,
+    state: u64,
+    duplicate: Option<u8>,
+    stats: FaultStats,
+}
+
+impl<T> FaultyTransport<T> {
+    /// Creates a transport over `inner`, injecting the faults of `config` drawn from `seed`.
+    #[must_use]
+    pub const fn new(inner: T, config: FaultConfig, seed: u64) -> Self {
+        Self {
+            inner,
+            config,
+            state: if seed == 0 { ZERO_SEED } else { seed },
+            duplicate: None,
+            stats: FaultStats {
+                bit_flips: 0,
+                drops: 0,
+                duplicates: 0,
+            },
+        }
+    }
+
+    /// Returns the counters of the faults injected so far.
+    #[must_use]
+    pub const fn stats(&self) -> FaultStats {
+        self.stats
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the transport, returning the underlying transport.
+    ///
+    /// A duplicated byte not read yet is lost.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the next pseudo-random number, from a xorshift64 generator.
+    const fn next_random(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Returns whether a fault of `rate` parts per million happens.
+    const fn happens(&mut self, rate: u32) -> bool {
+        rate > 0 && self.next_random() % (RATE_PARTS as u64) < rate as u64
+    }
+
+    /// Returns a pseudo-random number of bytes, from 1 to `max`, for a non-zero `max`.
+    #[allow(clippy::cast_possible_truncation)]
+    const fn split_length(&mut self, max: usize) -> usize {
+        // The remainder is below `max`, which fits in `usize`
+        (self.next_random() % max as u64) as usize + 1
+    }
+}
+
+/// Implementation of `ErrorType` for `FaultyTransport`.
+impl<T: ErrorType> ErrorType for FaultyTransport<T> {
+    type Error = T::Error;
+}
+
+/// Implementation of `Read` for `FaultyTransport`.
+impl<T: Read> Read for FaultyTransport<T> {
+    /// Reads bytes from the transport, one at a time, injecting the faults of the configuration.
+    ///
+    /// Returns `Ok(0)` once the transport does, and no duplicated byte is left to read.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let limit = if self.config.splits && !buf.is_empty() {
+            self.split_length(buf.len())
+        } else {
+            buf.len()
+        };
+
+        let mut length = 0;
+        while length < limit {
+            if let Some(value) = self.duplicate.take() {
+                buf[length] = value;
+                length += 1;
+                continue;
+            }
+
+            let mut value = [0x00];
+            if self.inner.read(&mut value)? == 0 {
+                break;
+            }
+            let [mut value] = value;
+
+            if self.happens(self.config.drops) {
+                self.stats.drops += 1;
+                continue;
+            }
+            if self.happens(self.config.bit_flips) {
+                value ^= 1 << (self.next_random() % 8);
+                self.stats.bit_flips += 1;
+            }
+            if self.happens(self.config.duplicates) {
+                self.duplicate = Some(value);
+                self.stats.duplicates += 1;
+            }
+
+            buf[length] = value;
+            length += 1;
+        }
+
+        Ok(length)
+    }
+}
+
+/// Implementation of `Write` for `FaultyTransport`.
+impl<T: Write> Write for FaultyTransport<T> {
+    /// Writes bytes to the transport untouched.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.inner.write(buf)
+    }
+
+    /// Flushes the transport.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_io::Read;
+    use embedded_io::Write;
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::fault::FaultConfig;
+    use crate::fault::FaultStats;
+    use crate::fault::FaultyTransport;
+    use crate::loopback::LoopbackTransport;
+    use crate::slip::EmptyFramePolicy;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipConfig;
+    use crate::slip::SlipDecoder;
+    use crate::slip::SlipEncoder;
+
+    /// Number of frames sent through the noisy transport.
+    const FRAMES: u8 = 200;
+
+    /// Reads every byte of `transport`, and returns how many were read.
+    fn read_all<T: Read>(transport: &mut T, output: &mut [u8]) -> usize {
+        let mut length = 0;
+        loop {
+            let read = transport.read(&mut output[length..]).unwrap();
+            if read == 0 {
+                return length;
+            }
+            length += read;
+        }
+    }
+
+    /// Sends `FRAMES` frames through a transport injecting the faults of `config`, and returns
+    /// the bytes read back.
+    fn noisy_stream(config: FaultConfig, seed: u64, output: &mut [u8]) -> usize {
+        let mut transport = FaultyTransport::new(LoopbackTransport::<4096>::new(), config, seed);
+        let encoder = SlipEncoder::new();
+        let mut frame = [0x00; 16];
+
+        for index in 0..FRAMES {
+            let length = encoder.encode_to_slice(&[index; 4], &mut frame).unwrap();
+            transport.write_all(&frame[..length]).unwrap();
+        }
+
+        read_all(&mut transport, output)
+    }
+
+    #[test]
+    fn test_clean_transport() {
+        let mut transport = FaultyTransport::new(
+            LoopbackTransport::<16>::new(),
+            FaultConfig::new().with_splits(true),
+            1,
+        );
+        transport
+            .write_all(&[0x01, 0x02, 0x03, 0x04, 0x05])
+            .unwrap();
+
+        let mut output = [0x00; 16];
+        let first = transport.read(&mut output).unwrap();
+        assert!((1..=5).contains(&first));
+
+        let length = first + read_all(&mut transport, &mut output[first..]);
+        assert_eq!(output[..length], [0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(transport.stats(), FaultStats::default());
+    }
+
+    #[test]
+    fn test_faults_are_reproducible() {
+        let config = FaultConfig::new()
+            .with_bit_flips(10_000)
+            .with_drops(10_000)
+            .with_duplicates(10_000)
+            .with_splits(true);
+        let mut first = [0x00; 4096];
+        let mut second = [0x00; 4096];
+
+        let length = noisy_stream(config, 42, &mut first);
+
+        assert_eq!(noisy_stream(config, 42, &mut second), length);
+        assert_eq!(first[..length], second[..length]);
+        assert_ne!(noisy_stream(config, 43, &mut second), 0);
+        assert_ne!(first[..length], second[..length]);
+    }
+
+    #[test]
+    fn test_decoder_survives_line_noise() {
+        let config = FaultConfig::new()
+            .with_bit_flips(2_000)
+            .with_drops(2_000)
+            .with_duplicates(2_000)
+            .with_splits(true);
+        let mut stream = [0x00; 4096];
+        let length = noisy_stream(config, 7, &mut stream);
+
+        // Once a terminator is dropped, a decoder reporting empty frames takes the terminators
+        // between frames the wrong way round, and drops every following frame as stray bytes
+        let mut slip_decoder = SlipDecoder::<8>::with_config(
+            SlipConfig::new()
+                .with_empty_frames(EmptyFramePolicy::Skip)
+                .with_resync(true),
+        );
+        let mut intact: Vec<u8, { FRAMES as usize }> = Vec::new();
+        let mut data = &stream[..length];
+        while !data.is_empty() {
+            let result = slip_decoder.feed(data);
+            data = &data[result.consumed..];
+
+            if result.status == FeedStatus::Completed {
+                let frame = slip_decoder.get_buffer();
+                if frame.len() == 4 && frame.iter().all(|&value| value == frame[0]) {
+                    let _ = intact.push(frame[0]);
+                }
+                slip_decoder.reset();
+            }
+        }
+
+        // About one frame in 20 is hit by a fault, which only costs that frame and its neighbour
+        assert!(intact.len() >= usize::from(FRAMES) * 9 / 10);
+        assert_eq!(intact.as_slice().last(), Some(&(FRAMES - 1)));
+    }
+}
@@ -0,0 +1,448 @@
+use core::mem::align_of;
+use core::mem::size_of;
+use core::ptr;
+use core::slice;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::FeedStatus;
+use crate::slip::SlipDecoderRef;
+use crate::slip::SlipEncoder;
+
+// The storage of a `SlipFfiDecoder` must fit the decoder it holds
+const _: () = assert!(
+    size_of::<SlipDecoderRef<'static>>() <= size_of::<SlipFfiDecoder>()
+        && align_of::<SlipDecoderRef<'static>>() <= align_of::<SlipFfiDecoder>()
+);
+
+/// The storage of a SLIP decoder handed to C code.
+///
+/// This struct is opaque to C code, which only allocates it, statically or on the stack, and
+/// passes it to the `slip_decoder_*` functions. It must be initialized with `slip_decoder_init`
+/// before any other use. Its 192 bytes are aligned to 8 bytes, which fits the decoder on any
+/// target.
+#[repr(C)]
+pub struct SlipFfiDecoder {
+    storage: [u64; 24],
+}
+
+/// The status returned by the functions of the C API.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlipFfiStatus {
+    /// The call succeeded; for `slip_decoder_feed`, a frame is complete.
+    Ok = 0,
+    /// No frame is complete yet.
+    Incomplete = 1,
+    /// The output buffer is too small to hold the result.
+    BufferTooSmall = 2,
+    /// The decoded frame does not fit in the decoder buffer, or exceeds its maximum length.
+    BufferFull = 3,
+    /// The frame holds an invalid escape sequence.
+    InvalidEscape = 4,
+    /// The frame does not follow the SLIP encoding.
+    MalformedFrame = 5,
+    /// A pointer argument is null.
+    InvalidArgument = 6,
+    /// The call failed for another reason.
+    Failed = 7,
+}
+
+/// Conversion from `SlipDecodeError` to `SlipFfiStatus`.
+impl From<SlipDecodeError> for SlipFfiStatus {
+    /// Maps the error to the closest status.
+    fn from(error: SlipDecodeError) -> Self {
+        match error {
+            SlipDecodeError::BufferFull
+            | SlipDecodeError::FrameTooLong
+            | SlipDecodeError::MtuExceeded => Self::BufferFull,
+            SlipDecodeError::InvalidEscape(_) => Self::InvalidEscape,
+            SlipDecodeError::FrameIncomplete => Self::Incomplete,
+            SlipDecodeError::OutputTooSmall(_) => Self::BufferTooSmall,
+            SlipDecodeError::MalformedFrame | SlipDecodeError::UnexpectedByte(_) => {
+                Self::MalformedFrame
+            }
+            SlipDecodeError::FrameCompleted
+            | SlipDecodeError::IntegrityCheckFailed
+            | SlipDecodeError::ChecksumMismatch
+            | SlipDecodeError::TransformFailed
+            | SlipDecodeError::InvalidFrame => Self::Failed,
+        }
+    }
+}
+
+/// Conversion from `SlipEncodeError` to `SlipFfiStatus`.
+impl From<SlipEncodeError> for SlipFfiStatus {
+    /// Maps the error to the closest status.
+    fn from(error: SlipEncodeError) -> Self {
+        match error {
+            SlipEncodeError::BufferTooSmall | SlipEncodeError::InsufficientCapacity { .. } => {
+                Self::BufferTooSmall
+            }
+            SlipEncodeError::InvalidRegion | SlipEncodeError::InvalidPayload => Self::Failed,
+        }
+    }
+}
+
+/// Returns the slice of `len` bytes at `data`, or `None` if `data` is null.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes for the lifetime `'a`.
+const unsafe fn input<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        return Some(&[]);
+    }
+    if data.is_null() {
+        return None;
+    }
+
+    // SAFETY: guaranteed by the caller
+    Some(unsafe { slice::from_raw_parts(data, len) })
+}
+
+/// Returns the mutable slice of `len` bytes at `data`, or `None` if `data` is null.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads and writes of `len` bytes for the lifetime `'a`, and
+/// not aliased.
+const unsafe fn output<'a>(data: *mut u8, len: usize) -> Option<&'a mut [u8]> {
+    if len == 0 {
+        return Some(&mut []);
+    }
+    if data.is_null() {
+        return None;
+    }
+
+    // SAFETY: guaranteed by the caller
+    Some(unsafe { slice::from_raw_parts_mut(data, len) })
+}
+
+/// Returns the decoder held by `decoder`, or `None` if `decoder` is null.
+///
+/// # Safety
+///
+/// `decoder` must be null or point to a decoder initialized with `slip_decoder_init`, and not
+/// aliased.
+const unsafe fn decoder<'a>(
+    decoder: *mut SlipFfiDecoder,
+) -> Option<&'a mut SlipDecoderRef<'static>> {
+    // SAFETY: guaranteed by the caller, and the storage fits the decoder
+    unsafe { decoder.cast::<SlipDecoderRef<'static>>().as_mut() }
+}
+
+/// Initializes `decoder` to decode frames of up to `capacity` bytes into `buffer`, using the
+/// default SLIP configuration.
+///
+/// # Safety
+///
+/// `decoder` must be valid for writes. `buffer` must be valid for reads and writes of `capacity`
+/// bytes for as long as the decoder is used, and not accessed by anything else meanwhile.
+#[unsafe(no_mangle)]
+pub const unsafe extern "C" fn slip_decoder_init(
+    decoder: *mut SlipFfiDecoder,
+    buffer: *mut u8,
+    capacity: usize,
+) -> SlipFfiStatus {
+    // SAFETY: guaranteed by the caller
+    let Some(buffer) = (unsafe { output(buffer, capacity) }) else {
+        return SlipFfiStatus::InvalidArgument;
+    };
+    if decoder.is_null() {
+        return SlipFfiStatus::InvalidArgument;
+    }
+
+    // SAFETY: `decoder` is valid for writes, and its storage fits the decoder
+    unsafe {
+        ptr::write(
+            decoder.cast::<SlipDecoderRef<'static>>(),
+            SlipDecoderRef::new(buffer),
+        );
+    }
+
+    SlipFfiStatus::Ok
+}
+
+/// Feeds the `len` bytes at `data` into `decoder`.
+///
+/// Bytes are consumed up to the end of a frame or to a rejected byte, and their number is written
+/// to `consumed`. Returns `SlipFfiStatus::Ok` if a frame is complete, `SlipFfiStatus::Incomplete`
+/// if all bytes were consumed without completing one, or the error that rejected the last
+/// consumed byte. After a complete frame or an error, the decoder must be reset before feeding
+/// the leftover bytes.
+///
+/// # Safety
+///
+/// `decoder` must point to an initialized decoder, `data` must be valid for reads of `len` bytes,
+/// and `consumed` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slip_decoder_feed(
+    decoder: *mut SlipFfiDecoder,
+    data: *const u8,
+    len: usize,
+    consumed: *mut usize,
+) -> SlipFfiStatus {
+    // SAFETY: guaranteed by the caller
+    let (Some(decoder), Some(data)) = (unsafe { self::decoder(decoder) }, unsafe {
+        input(data, len)
+    }) else {
+        return SlipFfiStatus::InvalidArgument;
+    };
+    if consumed.is_null() {
+        return SlipFfiStatus::InvalidArgument;
+    }
+
+    let result = decoder.feed(data);
+    // SAFETY: `consumed` is valid for writes
+    unsafe { consumed.write(result.consumed) };
+
+    match result.status {
+        FeedStatus::Completed => SlipFfiStatus::Ok,
+        FeedStatus::Incomplete => SlipFfiStatus::Incomplete,
+        FeedStatus::Rejected(error) => error.into(),
+    }
+}
+
+/// Copies the completed frame of `decoder` into the `capacity` bytes at `output`, then resets
+/// the decoder.
+///
+/// The length of the frame is written to `len`. Returns `SlipFfiStatus::Incomplete` if no frame
+/// is complete, or `SlipFfiStatus::BufferTooSmall` if the frame does not fit in `output`; the
+/// decoder is then left untouched. Returns `SlipFfiStatus::Failed` if the frame failed the
+/// integrity check, in which case it is dropped.
+///
+/// # Safety
+///
+/// `decoder` must point to an initialized decoder, `output` must be valid for writes of
+/// `capacity` bytes, and `len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slip_decoder_take_frame(
+    decoder: *mut SlipFfiDecoder,
+    output: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> SlipFfiStatus {
+    // SAFETY: guaranteed by the caller
+    let (Some(decoder), Some(output)) = (unsafe { self::decoder(decoder) }, unsafe {
+        self::output(output, capacity)
+    }) else {
+        return SlipFfiStatus::InvalidArgument;
+    };
+    if len.is_null() {
+        return SlipFfiStatus::InvalidArgument;
+    }
+
+    match decoder.read_frame_into(output) {
+        Ok(length) => {
+            // SAFETY: `len` is valid for writes
+            unsafe { len.write(length) };
+
+            SlipFfiStatus::Ok
+        }
+        Err(SlipDecodeError::FrameIncomplete) => SlipFfiStatus::Incomplete,
+        Err(SlipDecodeError::OutputTooSmall(_)) => SlipFfiStatus::BufferTooSmall,
+        Err(error) => error.into(),
+    }
+}
+
+/// Resets `decoder`, dropping the current frame.
+///
+/// # Safety
+///
+/// `decoder` must point to an initialized decoder.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slip_decoder_reset(decoder: *mut SlipFfiDecoder) -> SlipFfiStatus {
+    // SAFETY: guaranteed by the caller
+    let Some(decoder) = (unsafe { self::decoder(decoder) }) else {
+        return SlipFfiStatus::InvalidArgument;
+    };
+
+    decoder.reset();
+
+    SlipFfiStatus::Ok
+}
+
+/// Encodes the `input_len` bytes at `input` as a SLIP frame into the `capacity` bytes at
+/// `output`, using the default SLIP configuration.
+///
+/// The length of the frame is written to `len`. Returns `SlipFfiStatus::BufferTooSmall` if the
+/// frame does not fit in `output`; nothing is written then.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes, `output` must be valid for writes of
+/// `capacity` bytes and not overlap `input`, and `len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn slip_encode(
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+    capacity: usize,
+    len: *mut usize,
+) -> SlipFfiStatus {
+    // SAFETY: guaranteed by the caller
+    let (Some(input), Some(output)) = (unsafe { self::input(input, input_len) }, unsafe {
+        self::output(output, capacity)
+    }) else {
+        return SlipFfiStatus::InvalidArgument;
+    };
+    if len.is_null() {
+        return SlipFfiStatus::InvalidArgument;
+    }
+
+    match SlipEncoder::default().encode_to_slice(input, output) {
+        Ok(length) => {
+            // SAFETY: `len` is valid for writes
+            unsafe { len.write(length) };
+
+            SlipFfiStatus::Ok
+        }
+        Err(error) => error.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+    use core::ptr;
+
+    use crate::ffi::SlipFfiDecoder;
+    use crate::ffi::SlipFfiStatus;
+    use crate::ffi::slip_decoder_feed;
+    use crate::ffi::slip_decoder_init;
+    use crate::ffi::slip_decoder_reset;
+    use crate::ffi::slip_decoder_take_frame;
+    use crate::ffi::slip_encode;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    #[test]
+    fn test_decode() {
+        let mut decoder = MaybeUninit::<SlipFfiDecoder>::uninit();
+        let mut buffer = [0x00; 4];
+        let data = [
+            END_CHAR,
+            0x01,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x02,
+        ];
+        let mut frame = [0x00; 4];
+        let mut consumed = 0;
+        let mut len = 0;
+
+        unsafe {
+            assert_eq!(
+                slip_decoder_init(decoder.as_mut_ptr(), buffer.as_mut_ptr(), buffer.len()),
+                SlipFfiStatus::Ok
+            );
+            let decoder = decoder.as_mut_ptr();
+
+            assert_eq!(
+                slip_decoder_take_frame(decoder, frame.as_mut_ptr(), frame.len(), &raw mut len),
+                SlipFfiStatus::Incomplete
+            );
+            assert_eq!(
+                slip_decoder_feed(decoder, data.as_ptr(), data.len(), &raw mut consumed),
+                SlipFfiStatus::Ok
+            );
+            assert_eq!(consumed, 5);
+            assert_eq!(
+                slip_decoder_take_frame(decoder, frame.as_mut_ptr(), 1, &raw mut len),
+                SlipFfiStatus::BufferTooSmall
+            );
+            assert_eq!(
+                slip_decoder_take_frame(decoder, frame.as_mut_ptr(), frame.len(), &raw mut len),
+                SlipFfiStatus::Ok
+            );
+            assert_eq!(frame[..len], [0x01, END_CHAR]);
+
+            assert_eq!(
+                slip_decoder_feed(decoder, data[5..].as_ptr(), 2, &raw mut consumed),
+                SlipFfiStatus::Incomplete
+            );
+            assert_eq!(slip_decoder_reset(decoder), SlipFfiStatus::Ok);
+        }
+    }
+
+    #[test]
+    fn test_decode_with_bad_escape_character() {
+        let mut decoder = MaybeUninit::<SlipFfiDecoder>::uninit();
+        let mut buffer = [0x00; 4];
+        let data = [END_CHAR, ESC_CHAR, 0x00];
+        let mut consumed = 0;
+
+        unsafe {
+            slip_decoder_init(decoder.as_mut_ptr(), buffer.as_mut_ptr(), buffer.len());
+
+            assert_eq!(
+                slip_decoder_feed(
+                    decoder.as_mut_ptr(),
+                    data.as_ptr(),
+                    data.len(),
+                    &raw mut consumed
+                ),
+                SlipFfiStatus::InvalidEscape
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let input = [0x01, END_CHAR];
+        let mut output = [0x00; 5];
+        let mut len = 0;
+
+        unsafe {
+            assert_eq!(
+                slip_encode(
+                    input.as_ptr(),
+                    input.len(),
+                    output.as_mut_ptr(),
+                    output.len(),
+                    &raw mut len
+                ),
+                SlipFfiStatus::Ok
+            );
+            assert_eq!(
+                output[..len],
+                [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+            );
+
+            assert_eq!(
+                slip_encode(
+                    input.as_ptr(),
+                    input.len(),
+                    output.as_mut_ptr(),
+                    4,
+                    &raw mut len
+                ),
+                SlipFfiStatus::BufferTooSmall
+            );
+        }
+    }
+
+    #[test]
+    fn test_null_arguments() {
+        let mut len = 0;
+
+        unsafe {
+            assert_eq!(
+                slip_decoder_init(ptr::null_mut(), ptr::null_mut(), 0),
+                SlipFfiStatus::InvalidArgument
+            );
+            assert_eq!(
+                slip_decoder_reset(ptr::null_mut()),
+                SlipFfiStatus::InvalidArgument
+            );
+            assert_eq!(
+                slip_encode(ptr::null(), 1, ptr::null_mut(), 0, &raw mut len),
+                SlipFfiStatus::InvalidArgument
+            );
+        }
+    }
+}
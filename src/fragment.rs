@@ -0,0 +1,357 @@
+use core::iter::FusedIterator;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipEncodeError;
+use crate::error::SlipFragmentError;
+use crate::wcet::WorkBound;
+
+/// Length of the header starting every fragment.
+///
+/// The first byte is the sequence number of the payload, and the second one holds the index of
+/// the fragment within the payload, along with `LAST_FRAGMENT_FLAG`.
+pub const FRAGMENT_HEADER_LENGTH: usize = 2;
+
+/// Flag set in the second header byte of the last fragment of a payload.
+pub const LAST_FRAGMENT_FLAG: u8 = 0x80;
+
+/// Maximum number of fragments of a payload.
+pub const MAX_FRAGMENTS: usize = LAST_FRAGMENT_FLAG as usize;
+
+/// A splitter of payloads larger than the link MTU into fragments.
+///
+/// This struct cuts each payload into fragments of up to `MTU` bytes, header included, to send as
+/// as many SLIP frames. Every payload gets the next sequence number, so that the receiving
+/// `Reassembler` tells payloads apart and detects lost or repeated fragments.
+pub struct Fragmenter<const MTU: usize> {
+    // Sequence number of the next payload
+    sequence: u8,
+}
+
+impl<const MTU: usize> Fragmenter<MTU> {
+    /// Creates a fragmenter, starting with sequence number zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `MTU` leaves no room for data after the fragment header.
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(
+            MTU > FRAGMENT_HEADER_LENGTH,
+            "the MTU must leave room for data after the fragment header"
+        );
+
+        Self { sequence: 0 }
+    }
+
+    /// Returns an iterator over the fragments of `payload`, each to be sent as a frame.
+    ///
+    /// An empty payload still takes one fragment. Returns
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the payload needs more than
+    /// `MAX_FRAGMENTS` fragments, with the length of the payload and the longest payload that
+    /// can be sent.
+    pub const fn fragments<'a>(
+        &mut self,
+        payload: &'a [u8],
+    ) -> Result<Fragments<'a, MTU>, SlipEncodeError> {
+        let available = MAX_FRAGMENTS * (MTU - FRAGMENT_HEADER_LENGTH);
+        if payload.len() > available {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required: payload.len(),
+                available,
+            });
+        }
+
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+
+        Ok(Fragments {
+            payload,
+            sequence,
+            index: 0,
+            done: false,
+        })
+    }
+}
+
+/// Default implementation for `Fragmenter`.
+impl<const MTU: usize> Default for Fragmenter<MTU> {
+    /// Creates a fragmenter, starting with sequence number zero.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator over the fragments of a payload, created by `Fragmenter::fragments`.
+#[derive(Clone, Debug)]
+pub struct Fragments<'a, const MTU: usize> {
+    // Data not sent in a fragment yet
+    payload: &'a [u8],
+    sequence: u8,
+    index: u8,
+    done: bool,
+}
+
+impl<const MTU: usize> Fragments<'_, MTU> {
+    /// Returns the worst-case work of `next`.
+    #[must_use]
+    pub const fn next_work_bound() -> WorkBound {
+        WorkBound::linear(MTU)
+    }
+}
+
+/// Implementation of `Iterator` for `Fragments`.
+impl<const MTU: usize> Iterator for Fragments<'_, MTU> {
+    type Item = Vec<u8, MTU>;
+
+    /// Returns the next fragment, header included.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let (data, rest) = self
+            .payload
+            .split_at(self.payload.len().min(MTU - FRAGMENT_HEADER_LENGTH));
+        self.payload = rest;
+        self.done = rest.is_empty();
+
+        let flags = if self.done {
+            self.index | LAST_FRAGMENT_FLAG
+        } else {
+            self.index
+        };
+        self.index += 1;
+
+        let mut fragment = Vec::new();
+        // The header and the data fit, as the data is cut to the MTU
+        let _ = fragment.write_slice(0, &[self.sequence, flags]);
+        let _ = fragment.write_slice(FRAGMENT_HEADER_LENGTH, data);
+
+        Some(fragment)
+    }
+}
+
+/// Implementation of `FusedIterator` for `Fragments`.
+impl<const MTU: usize> FusedIterator for Fragments<'_, MTU> {}
+
+/// The outcome of pushing a fragment into a `Reassembler`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReassemblyEvent<'a> {
+    /// The fragment was stored, and the payload awaits further fragments.
+    Pending,
+    /// The fragment completed the given payload.
+    Complete(&'a [u8]),
+    /// The fragment was already received, and was ignored.
+    Duplicate,
+}
+
+/// A reassembler of payloads of up to `MAX_LENGTH` bytes from their fragments.
+///
+/// This struct expects the fragments of a payload in order, as sent by a `Fragmenter` over a
+/// SLIP link. Repeated fragments, of the payload in progress or of the last completed one, are
+/// ignored. A missing fragment drops the payload in progress with
+/// `SlipFragmentError::OutOfOrder`, and so does the first fragment of another payload, without
+/// error.
+pub struct Reassembler<const MAX_LENGTH: usize> {
+    buffer: Vec<u8, MAX_LENGTH>,
+    // Sequence number of the payload in progress, if any
+    current: Option<u8>,
+    // Sequence number of the last completed payload, if any
+    completed: Option<u8>,
+    // Index of the next fragment of the payload in progress
+    next_index: u8,
+}
+
+impl<const MAX_LENGTH: usize> Reassembler<MAX_LENGTH> {
+    /// Creates an empty reassembler.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            current: None,
+            completed: None,
+            next_index: 0,
+        }
+    }
+
+    /// Stores `fragment`, as received in a frame, and returns the payload once it is complete.
+    ///
+    /// Returns an error if the fragment is truncated, out of order, or does not fit in the
+    /// buffer. The payload in progress is then dropped.
+    pub fn push(&mut self, fragment: &[u8]) -> Result<ReassemblyEvent<'_>, SlipFragmentError> {
+        let &[sequence, flags, ref data @ ..] = fragment else {
+            return Err(SlipFragmentError::Truncated);
+        };
+        let index = flags & !LAST_FRAGMENT_FLAG;
+
+        match self.current {
+            Some(current) if current == sequence => {
+                if index < self.next_index {
+                    return Ok(ReassemblyEvent::Duplicate);
+                }
+                if index > self.next_index {
+                    self.reset();
+
+                    return Err(SlipFragmentError::OutOfOrder);
+                }
+            }
+            _ if self.completed == Some(sequence) => return Ok(ReassemblyEvent::Duplicate),
+            _ => {
+                if index != 0 {
+                    self.reset();
+
+                    return Err(SlipFragmentError::OutOfOrder);
+                }
+
+                // Start the new payload, dropping the one in progress
+                self.buffer.clear();
+                self.current = Some(sequence);
+                self.next_index = 0;
+            }
+        }
+
+        if self.buffer.write_slice(self.buffer.len(), data).is_err() {
+            self.reset();
+
+            return Err(SlipFragmentError::PayloadTooLong);
+        }
+        self.next_index += 1;
+
+        if flags & LAST_FRAGMENT_FLAG == 0 {
+            return Ok(ReassemblyEvent::Pending);
+        }
+
+        self.current = None;
+        self.completed = Some(sequence);
+
+        Ok(ReassemblyEvent::Complete(self.buffer.as_slice()))
+    }
+
+    /// Returns the worst-case work of `push`.
+    #[must_use]
+    pub const fn push_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Drops the payload in progress, if any.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.current = None;
+        self.next_index = 0;
+    }
+}
+
+/// Default implementation for `Reassembler`.
+impl<const MAX_LENGTH: usize> Default for Reassembler<MAX_LENGTH> {
+    /// Creates an empty reassembler.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipEncodeError;
+    use crate::error::SlipFragmentError;
+    use crate::fragment::Fragmenter;
+    use crate::fragment::LAST_FRAGMENT_FLAG;
+    use crate::fragment::Reassembler;
+    use crate::fragment::ReassemblyEvent;
+
+    #[test]
+    fn test_fragments() {
+        let mut fragmenter = Fragmenter::<4>::new();
+        let payload = [0x01, 0x02, 0x03, 0x04, 0x05];
+
+        let mut fragments = fragmenter.fragments(&payload).unwrap();
+
+        assert_eq!(
+            fragments.next().unwrap().as_slice(),
+            [0x00, 0x00, 0x01, 0x02]
+        );
+        assert_eq!(
+            fragments.next().unwrap().as_slice(),
+            [0x00, 0x01, 0x03, 0x04]
+        );
+        assert_eq!(
+            fragments.next().unwrap().as_slice(),
+            [0x00, 0x02 | LAST_FRAGMENT_FLAG, 0x05]
+        );
+        assert!(fragments.next().is_none());
+
+        let mut fragments = fragmenter.fragments(&[]).unwrap();
+
+        assert_eq!(
+            fragments.next().unwrap().as_slice(),
+            [0x01, LAST_FRAGMENT_FLAG]
+        );
+        assert!(fragments.next().is_none());
+    }
+
+    #[test]
+    fn test_fragments_with_payload_too_long() {
+        let mut fragmenter = Fragmenter::<3>::new();
+
+        assert_eq!(
+            fragmenter.fragments(&[0x00; 129]).err(),
+            Some(SlipEncodeError::InsufficientCapacity {
+                required: 129,
+                available: 128
+            })
+        );
+    }
+
+    #[test]
+    fn test_reassemble() {
+        let mut fragmenter = Fragmenter::<4>::new();
+        let mut reassembler = Reassembler::<8>::new();
+        let payload = [0x01, 0x02, 0x03, 0x04, 0x05];
+
+        let mut fragments = fragmenter.fragments(&payload).unwrap();
+
+        assert_eq!(
+            reassembler.push(&fragments.next().unwrap()),
+            Ok(ReassemblyEvent::Pending)
+        );
+        let second = fragments.next().unwrap();
+        assert_eq!(reassembler.push(&second), Ok(ReassemblyEvent::Pending));
+        assert_eq!(reassembler.push(&second), Ok(ReassemblyEvent::Duplicate));
+
+        let third = fragments.next().unwrap();
+        assert_eq!(
+            reassembler.push(&third),
+            Ok(ReassemblyEvent::Complete(&payload))
+        );
+        assert_eq!(reassembler.push(&third), Ok(ReassemblyEvent::Duplicate));
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let mut fragmenter = Fragmenter::<4>::new();
+        let mut reassembler = Reassembler::<8>::new();
+
+        let mut fragments = fragmenter
+            .fragments(&[0x01, 0x02, 0x03, 0x04, 0x05])
+            .unwrap();
+        let first = fragments.next().unwrap();
+        let _ = fragments.next();
+
+        assert_eq!(reassembler.push(&first), Ok(ReassemblyEvent::Pending));
+        assert_eq!(
+            reassembler.push(&fragments.next().unwrap()),
+            Err(SlipFragmentError::OutOfOrder)
+        );
+        assert_eq!(reassembler.push(&[0x00]), Err(SlipFragmentError::Truncated));
+
+        let mut fragments = fragmenter.fragments(&[0x06; 9]).unwrap();
+        for fragment in fragments.by_ref().take(4) {
+            assert_eq!(reassembler.push(&fragment), Ok(ReassemblyEvent::Pending));
+        }
+
+        assert_eq!(
+            reassembler.push(&fragments.next().unwrap()),
+            Err(SlipFragmentError::PayloadTooLong)
+        );
+    }
+}
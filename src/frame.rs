@@ -0,0 +1,309 @@
+use core::iter::FusedIterator;
+use core::slice;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipEncodeError;
+use crate::slip::SlipConfig;
+use crate::slip::SlipEncoder;
+use crate::stuffing::ByteStuffingCodec;
+use crate::wcet::WorkBound;
+
+/// An encoded SLIP frame of up to `MAX_LENGTH` bytes, terminators included.
+///
+/// This struct holds a frame ready for transmission, so that queues, loggers and other APIs can
+/// pass frames around as a type of their own rather than as raw vectors. A frame is built from
+/// its payload with `TryFrom<&[u8]>` or `with_config`, which perform the encoding, and gives
+/// access to both the bytes on the wire and the payload they carry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlipFrame<const MAX_LENGTH: usize> {
+    bytes: Vec<u8, MAX_LENGTH>,
+    config: SlipConfig,
+}
+
+impl<const MAX_LENGTH: usize> SlipFrame<MAX_LENGTH> {
+    /// Encodes `payload` as a frame, using the framing options of `config`.
+    ///
+    /// Returns `Err(SlipEncodeError::InsufficientCapacity)` if the frame is longer than
+    /// `MAX_LENGTH` bytes, with the length of the frame.
+    pub fn with_config(payload: &[u8], config: SlipConfig) -> Result<Self, SlipEncodeError> {
+        let encoder = SlipEncoder::with_config(config);
+        let required = encoder.encoded_len(payload);
+        let error = SlipEncodeError::InsufficientCapacity {
+            required,
+            available: MAX_LENGTH,
+        };
+        if required > MAX_LENGTH {
+            return Err(error);
+        }
+
+        // The payload fits, as it is shorter than the frame
+        let mut bytes = Vec::try_from(payload).map_err(|()| error)?;
+        encoder.encode(&mut bytes)?;
+
+        Ok(Self { bytes, config })
+    }
+
+    /// Returns the bytes of the frame, as sent on the wire.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Returns an iterator over the bytes of the payload carried by the frame.
+    #[must_use]
+    pub fn payload(&self) -> SlipFramePayload<'_> {
+        let codec = self.config.codec();
+        let bytes = self.as_bytes();
+
+        // Leave out the terminators, which an encoded frame always has
+        let start = usize::from(codec.leading_delimiter());
+        let end = bytes.len() - 1;
+
+        SlipFramePayload {
+            bytes: bytes[start..end].iter(),
+            codec,
+        }
+    }
+
+    /// Returns the framing options the frame was encoded with.
+    #[must_use]
+    pub const fn config(&self) -> SlipConfig {
+        self.config
+    }
+
+    /// Consumes the frame, returning its bytes.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8, MAX_LENGTH> {
+        self.bytes
+    }
+}
+
+/// Conversion from a payload to `SlipFrame`.
+impl<const MAX_LENGTH: usize> TryFrom<&[u8]> for SlipFrame<MAX_LENGTH> {
+    type Error = SlipEncodeError;
+
+    /// Encodes `payload` as a frame, using the default SLIP configuration.
+    fn try_from(payload: &[u8]) -> Result<Self, Self::Error> {
+        Self::with_config(payload, SlipConfig::new())
+    }
+}
+
+/// Implementation of `AsRef<[u8]>` for `SlipFrame`.
+impl<const MAX_LENGTH: usize> AsRef<[u8]> for SlipFrame<MAX_LENGTH> {
+    /// Returns the bytes of the frame, as sent on the wire.
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// An iterator over the payload of a `SlipFrame`.
+///
+/// This struct restores the escaped bytes of the frame as it goes, so that the payload is never
+/// stored next to the frame.
+#[derive(Clone, Debug)]
+pub struct SlipFramePayload<'a> {
+    bytes: slice::Iter<'a, u8>,
+    codec: ByteStuffingCodec,
+}
+
+/// Implementation of `Iterator` for `SlipFramePayload`.
+impl Iterator for SlipFramePayload<'_> {
+    type Item = u8;
+
+    /// Returns the next byte of the payload.
+    fn next(&mut self) -> Option<Self::Item> {
+        let &value = self.bytes.next()?;
+        if value != self.codec.escape() {
+            return Some(value);
+        }
+
+        // The frame was encoded by `SlipFrame`, so that an escape byte is always followed by a
+        // valid substitute
+        let &substitute = self.bytes.next()?;
+
+        Some(self.codec.restore(substitute).unwrap_or(substitute))
+    }
+}
+
+/// Implementation of `FusedIterator` for `SlipFramePayload`.
+impl FusedIterator for SlipFramePayload<'_> {}
+
+/// A builder of a SLIP frame, assembling its payload piece by piece inside the output buffer.
+///
+/// Pieces are appended with `push` and `extend_from_slice` to the start of the buffer, and
+/// `finish` escapes and frames the payload in place, in a single pass, so that the payload never
+/// needs a buffer of its own. The buffer should hold the frame, that is up to twice the payload
+/// plus the terminators, as given by `max_encoded_len`.
+#[derive(Debug)]
+pub struct FrameBuilder<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+    config: SlipConfig,
+}
+
+impl<'a> FrameBuilder<'a> {
+    /// Creates a builder writing to `buffer`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(buffer: &'a mut [u8]) -> Self {
+        Self::with_config(buffer, SlipConfig::new())
+    }
+
+    /// Creates a builder writing to `buffer`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(buffer: &'a mut [u8], config: SlipConfig) -> Self {
+        Self {
+            buffer,
+            len: 0,
+            config,
+        }
+    }
+
+    /// Appends `value` to the payload.
+    ///
+    /// Returns `Err(SlipEncodeError::BufferTooSmall)` if the buffer is full.
+    pub fn push(&mut self, value: u8) -> Result<(), SlipEncodeError> {
+        let slot = self
+            .buffer
+            .get_mut(self.len)
+            .ok_or(SlipEncodeError::BufferTooSmall)?;
+        *slot = value;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Appends all of `values` to the payload.
+    ///
+    /// Returns `Err(SlipEncodeError::BufferTooSmall)`, leaving the payload untouched, if the
+    /// buffer cannot hold them all.
+    pub fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipEncodeError> {
+        let slots = self
+            .buffer
+            .get_mut(self.len..self.len + values.len())
+            .ok_or(SlipEncodeError::BufferTooSmall)?;
+        slots.copy_from_slice(values);
+        self.len += values.len();
+
+        Ok(())
+    }
+
+    /// Returns the payload appended so far.
+    #[must_use]
+    pub fn payload(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    /// Returns the number of bytes of the payload appended so far.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no byte of the payload was appended yet.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Escapes and frames the payload in place, at the start of the buffer.
+    ///
+    /// Returns the encoded frame, or `Err(SlipEncodeError::InsufficientCapacity)` if the buffer
+    /// lacks room for the framing overhead, with the length of the frame.
+    pub fn finish(self) -> Result<&'a [u8], SlipEncodeError> {
+        let frame_len = self.config.codec().encode_slice(self.buffer, self.len)?;
+
+        Ok(&self.buffer[..frame_len])
+    }
+
+    /// Returns the worst-case work of `finish` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn finish_work_bound(payload_len: usize) -> WorkBound {
+        SlipEncoder::encode_to_slice_work_bound(payload_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipEncodeError;
+    use crate::frame::FrameBuilder;
+    use crate::frame::SlipFrame;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::SlipConfig;
+
+    #[test]
+    fn test_frame() {
+        let frame = SlipFrame::<8>::try_from([0x01, END_CHAR].as_slice()).unwrap();
+
+        assert_eq!(
+            frame.as_bytes(),
+            [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+        assert!(frame.payload().eq([0x01, END_CHAR]));
+    }
+
+    #[test]
+    fn test_frame_with_config() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let frame = SlipFrame::<8>::with_config(&[0x01], config).unwrap();
+
+        assert_eq!(frame.as_bytes(), [0x01, END_CHAR]);
+        assert!(frame.payload().eq([0x01]));
+
+        let frame = SlipFrame::<8>::with_config(&[], config).unwrap();
+
+        assert_eq!(frame.as_bytes(), [END_CHAR]);
+        assert_eq!(frame.payload().next(), None);
+    }
+
+    #[test]
+    fn test_frame_with_not_enough_space() {
+        let result = SlipFrame::<4>::try_from([0x01, END_CHAR].as_slice());
+
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 5,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_frame_builder() {
+        let mut buffer = [0x00; 8];
+        let mut builder = FrameBuilder::new(&mut buffer);
+
+        builder.push(0x01).unwrap();
+        builder.extend_from_slice(&[END_CHAR, 0x02]).unwrap();
+
+        assert_eq!(builder.payload(), [0x01, END_CHAR, 0x02]);
+        assert_eq!(
+            builder.finish(),
+            Ok([END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, 0x02, END_CHAR].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_frame_builder_with_not_enough_space() {
+        let mut buffer = [0x00; 4];
+        let mut builder = FrameBuilder::new(&mut buffer);
+
+        assert_eq!(
+            builder.extend_from_slice(&[0x01; 5]),
+            Err(SlipEncodeError::BufferTooSmall)
+        );
+
+        builder.extend_from_slice(&[0x01, END_CHAR, 0x02]).unwrap();
+
+        assert_eq!(
+            builder.finish(),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 6,
+                available: 4
+            })
+        );
+    }
+}
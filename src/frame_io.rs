@@ -0,0 +1,122 @@
+#[cfg(feature = "futures")]
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipIoError;
+use crate::slip::FeedStatus;
+use crate::slip::FrameResync;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+
+/// Number of bytes requested from the transport by each read of a frame reader.
+const READ_CHUNK_LENGTH: usize = 64;
+
+/// The transport-agnostic state of a frame reader.
+///
+/// This struct holds the decoder and the bytes read from the transport but not decoded yet. The
+/// blocking and async frame readers only differ in how they refill it, which keeps their framing
+/// behavior identical. Its state is consistent whenever the transport is being read, so that an
+/// async reader dropped at that point loses nothing. Once a frame is rejected, the rest of it is
+/// skipped, as by `FrameResync`.
+pub struct FrameReadState<const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    resync: FrameResync,
+    chunk: [u8; READ_CHUNK_LENGTH],
+    chunk_start: usize,
+    chunk_end: usize,
+}
+
+impl<const MAX_LENGTH: usize> FrameReadState<MAX_LENGTH> {
+    /// Creates the state of a reader using the framing options of `config`.
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            resync: FrameResync::with_config(config),
+            chunk: [0; READ_CHUNK_LENGTH],
+            chunk_start: 0,
+            chunk_end: 0,
+        }
+    }
+
+    /// Decodes the buffered bytes and copies a completed frame into `buf`.
+    ///
+    /// Returns `None` if more bytes must be read from the transport, or the outcome of the read
+    /// otherwise: the length of the payload, or the error that interrupted the frame.
+    pub fn decode<E>(&mut self, buf: &mut [u8]) -> Option<Result<usize, SlipIoError<E>>> {
+        self.feed_chunk()
+            .map(|result| result.and_then(|()| self.take_frame(buf)))
+    }
+
+    /// Decodes the buffered bytes and returns a completed frame.
+    ///
+    /// Returns `None` if more bytes must be read from the transport, or the outcome of the read
+    /// otherwise: the payload, or the error that interrupted the frame.
+    #[cfg(feature = "futures")]
+    pub fn decode_frame<E>(&mut self) -> Option<Result<Vec<u8, MAX_LENGTH>, SlipIoError<E>>> {
+        match self.feed_chunk()? {
+            Ok(()) => Some(self.decoder.take_frame().map_err(SlipIoError::Decode)),
+            Err(error) => Some(Err(error)),
+        }
+    }
+
+    /// Feeds the buffered bytes to the decoder.
+    ///
+    /// Returns `None` if more bytes must be read from the transport, `Some(Ok(()))` once a frame
+    /// is complete, or the error that interrupted the frame.
+    fn feed_chunk<E>(&mut self) -> Option<Result<(), SlipIoError<E>>> {
+        let pending = &self.chunk[self.chunk_start..self.chunk_end];
+        self.chunk_start += self.resync.skip(&mut self.decoder, pending);
+        if self.resync.is_pending() {
+            return None;
+        }
+
+        let result = self
+            .decoder
+            .feed(&self.chunk[self.chunk_start..self.chunk_end]);
+        self.chunk_start += result.consumed;
+
+        match result.status {
+            FeedStatus::Incomplete => None,
+            FeedStatus::Completed => Some(Ok(())),
+            FeedStatus::Rejected(error) => {
+                self.resync.rejected(&mut self.decoder, error);
+
+                Some(Err(SlipIoError::Decode(error)))
+            }
+        }
+    }
+
+    /// Copies the completed frame into `buf` and resets the decoder.
+    ///
+    /// The frame stays pending if `buf` is too small, and is dropped if it failed the integrity
+    /// check.
+    fn take_frame<E>(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<E>> {
+        self.decoder
+            .read_frame_into(buf)
+            .map_err(|error| match error {
+                SlipDecodeError::OutputTooSmall(length) => SlipIoError::BufferTooSmall(length),
+                error => SlipIoError::Decode(error),
+            })
+    }
+
+    /// Returns the buffer to read the next chunk of bytes into.
+    ///
+    /// This must only be called once `decode` asked for more bytes.
+    pub const fn chunk_mut(&mut self) -> &mut [u8] {
+        &mut self.chunk
+    }
+
+    /// Records that `length` bytes have been read into the chunk buffer.
+    ///
+    /// Returns `Err(SlipIoError::UnexpectedEof)` if the transport reached the end of the stream.
+    pub const fn fill<E>(&mut self, length: usize) -> Result<(), SlipIoError<E>> {
+        if length == 0 {
+            return Err(SlipIoError::UnexpectedEof);
+        }
+
+        self.chunk_start = 0;
+        self.chunk_end = length;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use core::slice;
+
+use arbitrary::Arbitrary;
+use arbitrary::Error;
+use arbitrary::Result;
+use arbitrary::Unstructured;
+use noalloc_vec_rs::vec::Vec;
+
+use crate::frame::SlipFrame;
+use crate::slip::END_CHAR;
+use crate::slip::ESC_CHAR;
+use crate::slip::ESC_END_CHAR;
+use crate::slip::ESC_ESC_CHAR;
+use crate::slip::SlipConfig;
+use crate::slip::SlipEncoder;
+
+/// Maximum number of frames held by an arbitrary `SlipStream`.
+pub const MAX_STREAM_FRAMES: usize = 16;
+
+/// Maximum number of bytes of a payload held by an arbitrary `SlipStream`.
+pub const MAX_STREAM_PAYLOAD_LENGTH: usize = 64;
+
+/// Maximum number of noise bytes injected at once into an arbitrary `SlipStream`.
+pub const MAX_NOISE_LENGTH: usize = 16;
+
+/// The bytes given a special meaning by SLIP, which arbitrary payloads hold more often than
+/// other bytes.
+const SPECIAL_BYTES: [u8; 4] = [END_CHAR, ESC_CHAR, ESC_END_CHAR, ESC_ESC_CHAR];
+
+/// Builds an arbitrary payload of up to `max_length` bytes, whose frame encoded with the default
+/// SLIP configuration takes up to `max_encoded_len` bytes.
+///
+/// One byte out of four is drawn from the special bytes of SLIP, so that the escapes are
+/// exercised far more often than by uniform bytes.
+fn arbitrary_payload(
+    u: &mut Unstructured<'_>,
+    max_length: usize,
+    max_encoded_len: usize,
+) -> Result<alloc::vec::Vec<u8>> {
+    let codec = SlipConfig::new().codec();
+    let mut encoded_len = SlipEncoder::new().encoded_len(&[]);
+    let mut payload = alloc::vec::Vec::new();
+
+    for _ in 0..u.arbitrary_len::<u8>()?.min(max_length) {
+        let value = if u.ratio(1_u8, 4)? {
+            *u.choose(&SPECIAL_BYTES)?
+        } else {
+            u8::arbitrary(u)?
+        };
+
+        encoded_len += 1 + usize::from(codec.is_special(value));
+        if encoded_len > max_encoded_len {
+            break;
+        }
+        payload.push(value);
+    }
+
+    Ok(payload)
+}
+
+/// A payload of up to `MAX_LENGTH` bytes, for fuzzing the protocols carried over SLIP.
+///
+/// This struct is built by its `Arbitrary` implementation, which favours the bytes that SLIP
+/// escapes, so that payloads of a downstream protocol go through every escape sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlipPayload<const MAX_LENGTH: usize> {
+    bytes: Vec<u8, MAX_LENGTH>,
+}
+
+impl<const MAX_LENGTH: usize> SlipPayload<MAX_LENGTH> {
+    /// Returns the bytes of the payload.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Consumes the payload, returning its bytes.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<u8, MAX_LENGTH> {
+        self.bytes
+    }
+}
+
+/// Implementation of `AsRef<[u8]>` for `SlipPayload`.
+impl<const MAX_LENGTH: usize> AsRef<[u8]> for SlipPayload<MAX_LENGTH> {
+    /// Returns the bytes of the payload.
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Implementation of `Arbitrary` for `SlipPayload`.
+impl<'a, const MAX_LENGTH: usize> Arbitrary<'a> for SlipPayload<MAX_LENGTH> {
+    /// Builds a payload of up to `MAX_LENGTH` bytes, one out of four being a special byte.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let payload = arbitrary_payload(u, MAX_LENGTH, usize::MAX)?;
+
+        // The payload fits, as its length is bounded by `MAX_LENGTH`
+        let bytes = payload
+            .as_slice()
+            .try_into()
+            .map_err(|()| Error::IncorrectFormat)?;
+
+        Ok(Self { bytes })
+    }
+}
+
+/// Implementation of `Arbitrary` for `SlipFrame`.
+impl<'a, const MAX_LENGTH: usize> Arbitrary<'a> for SlipFrame<MAX_LENGTH> {
+    /// Encodes an arbitrary payload with the default SLIP configuration, cut short so that the
+    /// frame takes up to `MAX_LENGTH` bytes.
+    ///
+    /// Returns `Err(Error::IncorrectFormat)` if even an empty frame is longer than `MAX_LENGTH`
+    /// bytes.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let payload = arbitrary_payload(u, MAX_LENGTH, MAX_LENGTH)?;
+
+        Self::try_from(payload.as_slice()).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+/// A stream of SLIP frames as received from a noisy line, for fuzzing the protocols carried over
+/// SLIP.
+///
+/// This struct is built by its `Arbitrary` implementation: up to `MAX_STREAM_FRAMES` arbitrary
+/// payloads, of up to `MAX_STREAM_PAYLOAD_LENGTH` bytes, are encoded with the default SLIP configuration, bursts of up to `MAX_NOISE_LENGTH`
+/// random bytes are injected between the frames, and the stream is split into chunks of random
+/// lengths, as handed out by a transport. The bursts stand for the noise of an idle line, such as
+/// a peer booting: they never hold `END_CHAR`, so that a decoder skips them as stray bytes, and
+/// every payload is decoded from the stream, in order, without any other frame.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlipStream {
+    payloads: alloc::vec::Vec<alloc::vec::Vec<u8>>,
+    bytes: alloc::vec::Vec<u8>,
+    chunk_lengths: alloc::vec::Vec<usize>,
+}
+
+impl SlipStream {
+    /// Returns the payloads carried by the stream, in order.
+    #[must_use]
+    pub fn payloads(&self) -> &[alloc::vec::Vec<u8>] {
+        &self.payloads
+    }
+
+    /// Returns the bytes of the stream, as received from the line.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns an iterator over the chunks of the stream, as handed out by the transport.
+    #[must_use]
+    pub fn chunks(&self) -> SlipStreamChunks<'_> {
+        SlipStreamChunks {
+            bytes: &self.bytes,
+            chunk_lengths: self.chunk_lengths.iter(),
+        }
+    }
+}
+
+/// Implementation of `Arbitrary` for `SlipStream`.
+impl<'a> Arbitrary<'a> for SlipStream {
+    /// Builds a stream of arbitrary frames, noise bursts and chunk boundaries.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let encoder = SlipEncoder::new();
+        let mut payloads = alloc::vec::Vec::new();
+        let mut bytes = alloc::vec::Vec::new();
+
+        for _ in 0..u.int_in_range(0..=MAX_STREAM_FRAMES)? {
+            if u.ratio(1_u8, 4)? {
+                let length = u.int_in_range(1..=MAX_NOISE_LENGTH)?.min(u.len());
+                let noise = u.bytes(length)?;
+                bytes.extend(noise.iter().filter(|&&value| value != END_CHAR));
+            }
+
+            let payload = arbitrary_payload(u, MAX_STREAM_PAYLOAD_LENGTH, usize::MAX)?;
+            bytes.extend(encoder.encode_to_vec(&payload));
+            payloads.push(payload);
+        }
+
+        let mut chunk_lengths = alloc::vec::Vec::new();
+        let mut remaining = bytes.len();
+        while remaining > 0 {
+            // Once the data runs out, the rest of the stream makes up a single chunk
+            let length = remaining - u.int_in_range(0..=remaining - 1)?;
+            chunk_lengths.push(length);
+            remaining -= length;
+        }
+
+        Ok(Self {
+            payloads,
+            bytes,
+            chunk_lengths,
+        })
+    }
+}
+
+/// An iterator over the chunks of a `SlipStream`.
+#[derive(Clone, Debug)]
+pub struct SlipStreamChunks<'a> {
+    bytes: &'a [u8],
+    chunk_lengths: slice::Iter<'a, usize>,
+}
+
+/// Implementation of `Iterator` for `SlipStreamChunks`.
+impl<'a> Iterator for SlipStreamChunks<'a> {
+    type Item = &'a [u8];
+
+    /// Returns the next chunk of the stream.
+    fn next(&mut self) -> Option<Self::Item> {
+        let &length = self.chunk_lengths.next()?;
+        let (chunk, bytes) = self.bytes.split_at_checked(length)?;
+        self.bytes = bytes;
+
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+
+    use crate::frame::SlipFrame;
+    use crate::fuzzing::SlipStream;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipVecDecoder;
+
+    #[test]
+    fn test_frame_fits() {
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1024).collect();
+        let frame = SlipFrame::<16>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+        assert!(frame.as_bytes().len() <= 16);
+        assert!(SlipFrame::<1>::arbitrary(&mut Unstructured::new(&data)).is_err());
+    }
+
+    #[test]
+    fn test_stream_delivers_payloads() {
+        for seed in 0..64_u32 {
+            let mut state = seed + 1;
+            let data: Vec<u8> = (0..2048)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state.to_le_bytes()[0]
+                })
+                .collect();
+            let stream = SlipStream::arbitrary(&mut Unstructured::new(&data)).unwrap();
+
+            assert_eq!(
+                stream.chunks().collect::<Vec<_>>().concat(),
+                stream.as_bytes()
+            );
+
+            let mut decoder = SlipVecDecoder::new();
+            let mut frames: Vec<Vec<u8>> = Vec::new();
+            for mut chunk in stream.chunks() {
+                while !chunk.is_empty() {
+                    let result = decoder.feed(chunk);
+                    chunk = &chunk[result.consumed..];
+
+                    if result.status == FeedStatus::Completed {
+                        frames.push(decoder.get_buffer().to_vec());
+                        decoder.reset();
+                    } else {
+                        assert_eq!(result.status, FeedStatus::Incomplete);
+                    }
+                }
+            }
+
+            assert_eq!(frames, stream.payloads());
+        }
+    }
+}
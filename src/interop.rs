@@ -0,0 +1,145 @@
+//! Wire-level vectors of the reference SLIP implementations, checked byte for byte against the
+//! encoder and the decoder.
+//!
+//! The vectors follow the framing of the Linux `slip.c` driver, of the lwIP `slipif` interface
+//! and of the Python `sliplib` package. All three open and terminate every frame with `END_CHAR`,
+//! so that their frames are the ones of the default configuration. They differ on the edge cases
+//! of the receiving side, most of which map to a decoder configuration.
+
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::slip::EmptyFramePolicy;
+use crate::slip::EscapePolicy;
+use crate::slip::SlipConfig;
+use crate::slip::SlipEncoder;
+use crate::slip::SlipFrameIter;
+
+/// Frames sent by `slip_esc` in Linux `slip.c`, `slipif_output` in lwIP and `sliplib.encode`,
+/// along with their payload.
+const FRAMES: &[(&[u8], &[u8])] = &[
+    (&[], &[0xC0, 0xC0]),
+    (
+        &[0x45, 0x00, 0x00, 0x1C],
+        &[0xC0, 0x45, 0x00, 0x00, 0x1C, 0xC0],
+    ),
+    (&[0xC0], &[0xC0, 0xDB, 0xDC, 0xC0]),
+    (&[0xDB], &[0xC0, 0xDB, 0xDD, 0xC0]),
+    (&[0xDB, 0xDC], &[0xC0, 0xDB, 0xDD, 0xDC, 0xC0]),
+    (&[0xDC, 0xDD], &[0xC0, 0xDC, 0xDD, 0xC0]),
+    (
+        &[0x01, 0xC0, 0xC0, 0xDB, 0xDB, 0x02],
+        &[
+            0xC0, 0x01, 0xDB, 0xDC, 0xDB, 0xDC, 0xDB, 0xDD, 0xDB, 0xDD, 0x02, 0xC0,
+        ],
+    ),
+    (&[0xDB, 0xC0], &[0xC0, 0xDB, 0xDD, 0xDB, 0xDC, 0xC0]),
+];
+
+/// Decodes every frame of `stream` using the framing options of `config`.
+fn decode(stream: &[u8], config: SlipConfig) -> Vec<Result<Vec<u8>, SlipDecodeError>> {
+    SlipFrameIter::<16>::with_config(stream, config)
+        .map(|frame| frame.map(|frame| frame.to_vec()))
+        .collect()
+}
+
+#[test]
+fn test_encode_reference_frames() {
+    let mut output = [0x00; 16];
+
+    for &(payload, frame) in FRAMES {
+        let length = SlipEncoder::default()
+            .encode_to_slice(payload, &mut output)
+            .unwrap();
+
+        assert_eq!(output[..length], *frame, "payload {payload:02X?}");
+    }
+}
+
+#[test]
+fn test_decode_reference_frames() {
+    for &(payload, frame) in FRAMES {
+        assert_eq!(
+            decode(frame, SlipConfig::new()),
+            [Ok(payload.to_vec())],
+            "frame {frame:02X?}"
+        );
+    }
+}
+
+#[test]
+fn test_decode_back_to_back_frames() {
+    // Every sender opens its frames with `END_CHAR`, so that consecutive frames are separated by
+    // an empty one, which none of the receivers reports
+    let stream = [0xC0, 0x01, 0xC0, 0xC0, 0x02, 0xDB, 0xDC, 0xC0, 0xC0, 0xC0];
+    let config = SlipConfig::new().with_empty_frames(EmptyFramePolicy::Skip);
+
+    assert_eq!(
+        decode(&stream, config),
+        [Ok([0x01].to_vec()), Ok([0x02, 0xC0].to_vec())]
+    );
+}
+
+#[test]
+fn test_decode_trailing_end_only_framing() {
+    // Some senders only terminate their frames, which all the receivers accept
+    let stream = [0x01, 0x02, 0xC0, 0xDB, 0xDD, 0xC0];
+    let config = SlipConfig::new().with_leading_end(false);
+
+    assert_eq!(
+        decode(&stream, config),
+        [Ok([0x01, 0x02].to_vec()), Ok([0xDB].to_vec())]
+    );
+}
+
+#[test]
+fn test_decode_invalid_escape() {
+    // `sliplib` raises `ProtocolError`, while Linux `slip.c` and lwIP `slipif` drop `ESC_CHAR`
+    // and keep the byte following it
+    let stream = [0xC0, 0x01, 0xDB, 0x02, 0xC0];
+
+    assert_eq!(
+        decode(&stream, SlipConfig::new()),
+        [Err(SlipDecodeError::InvalidEscape(0x02))]
+    );
+    assert_eq!(
+        decode(
+            &stream,
+            SlipConfig::new().with_escapes(EscapePolicy::Lenient)
+        ),
+        [Ok([0x01, 0x02].to_vec())]
+    );
+}
+
+#[test]
+fn test_decode_escape_at_end_of_frame() {
+    // `sliplib` raises `ProtocolError`, while lwIP `slipif` keeps `END_CHAR` as a payload byte
+    // once escaped, and goes on with the frame
+    let stream = [0xC0, 0x01, 0xDB, 0xC0, 0x02, 0xC0];
+
+    assert_eq!(
+        decode(&stream, SlipConfig::new()).first(),
+        Some(&Err(SlipDecodeError::InvalidEscape(0xC0)))
+    );
+    assert_eq!(
+        decode(
+            &stream,
+            SlipConfig::new().with_escapes(EscapePolicy::Lenient)
+        ),
+        [Ok([0x01, 0xC0, 0x02].to_vec())]
+    );
+}
+
+#[test]
+fn test_decode_unescaped_substitutes() {
+    // `ESC_END_CHAR` and `ESC_ESC_CHAR` are plain bytes outside an escape sequence, for all the
+    // receivers
+    let stream = [0xC0, 0xDC, 0xDD, 0xDC, 0xC0];
+
+    assert_eq!(
+        decode(&stream, SlipConfig::new()),
+        [Ok([0xDC, 0xDD, 0xDC].to_vec())]
+    );
+}
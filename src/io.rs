@@ -0,0 +1,535 @@
+use core::fmt;
+
+use embedded_io::Read;
+use embedded_io::Write;
+
+use crate::encode::FrameWrite;
+use crate::error::SlipIoError;
+use crate::frame_io::FrameReadState;
+use crate::slip::ESC_CHAR;
+use crate::slip::EscapedSegments;
+use crate::slip::SlipConfig;
+use crate::transform::FrameTransform;
+
+/// A SLIP frame writer over an `embedded_io::Write` transport.
+///
+/// This struct escapes payload bytes as they pass through and writes them directly to the
+/// underlying transport, so that a frame never needs to be staged in memory. A frame is written
+/// with one call to `start_frame`, any number of calls to `write`, and one call to `end_frame`.
+pub struct SlipFrameWriter<W> {
+    inner: W,
+    config: SlipConfig,
+}
+
+impl<W: Write> SlipFrameWriter<W> {
+    /// Creates a writer over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: W) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a writer over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: W, config: SlipConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Begins a new frame, writing the opening terminator if the configuration requires one.
+    pub fn start_frame(&mut self) -> Result<(), W::Error> {
+        if self.config.leading_end() {
+            self.inner.write_all(&[self.config.end_char()])?;
+        }
+
+        Ok(())
+    }
+
+    /// Escapes `data` and writes it as part of the current frame.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), W::Error> {
+        for segment in EscapedSegments::new(data, self.config) {
+            self.inner.write_all(segment)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current frame, writing the closing terminator.
+    pub fn end_frame(&mut self) -> Result<(), W::Error> {
+        self.inner.write_all(&[self.config.end_char()])
+    }
+
+    /// Aborts the current frame, writing an escape byte followed by the closing terminator.
+    ///
+    /// Decoders using `EscapePolicy::Strict` reject the frame as `SlipDecodeError::InvalidEscape`,
+    /// so that its partial payload is never delivered. A new frame can be started right after.
+    pub fn abort_frame(&mut self) -> Result<(), W::Error> {
+        self.inner.write_all(&[ESC_CHAR, self.config.end_char()])
+    }
+
+    /// Writes `payload` as a complete frame.
+    pub fn write_frame(&mut self, payload: &[u8]) -> Result<(), W::Error> {
+        self.start_frame()?;
+        self.write(payload)?;
+        self.end_frame()
+    }
+
+    /// Transforms the payload held by the first `len` bytes of `buf` with `transform`, then
+    /// writes it as a complete frame.
+    ///
+    /// The payload is transformed in place, and may grow up to the length of `buf`.
+    pub fn write_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        len: usize,
+        transform: &mut impl FrameTransform,
+    ) -> Result<(), SlipIoError<W::Error>> {
+        let len = transform.apply(buf, len)?;
+
+        self.write_frame(&buf[..len]).map_err(SlipIoError::Io)
+    }
+
+    /// Flushes the underlying transport.
+    pub fn flush(&mut self) -> Result<(), W::Error> {
+        self.inner.flush()
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the writer, returning the underlying transport.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Implementation of `fmt::Write` for `SlipFrameWriter`.
+///
+/// Formatted text is escaped and written as part of the current frame, so that diagnostics can
+/// be sent with `write!` without any formatting buffer. The frame is still opened and closed with
+/// `start_frame` and `end_frame`.
+impl<W: Write> fmt::Write for SlipFrameWriter<W> {
+    /// Escapes `s` and writes it as part of the current frame.
+    ///
+    /// The transport error is lost, as `fmt::Error` carries no detail.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Implementation of `FrameWrite` for `SlipFrameWriter`.
+impl<W: Write> FrameWrite for SlipFrameWriter<W> {
+    type Error = W::Error;
+
+    /// Begins a new frame, writing the opening terminator if the configuration requires one.
+    fn start_frame(&mut self) -> Result<(), Self::Error> {
+        Self::start_frame(self)
+    }
+
+    /// Escapes `data` and writes it as part of the current frame.
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        Self::write(self, data)
+    }
+
+    /// Ends the current frame, writing the closing terminator.
+    fn end_frame(&mut self) -> Result<(), Self::Error> {
+        Self::end_frame(self)
+    }
+}
+
+/// A SLIP frame reader over an `embedded_io::Read` transport.
+///
+/// This struct pulls bytes from the underlying transport in chunks and decodes them until a
+/// frame is complete. Bytes read past the end of a frame are kept for the next frame.
+pub struct SlipFrameReader<R, const MAX_LENGTH: usize> {
+    inner: R,
+    state: FrameReadState<MAX_LENGTH>,
+}
+
+impl<R: Read, const MAX_LENGTH: usize> SlipFrameReader<R, MAX_LENGTH> {
+    /// Creates a reader over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a reader over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: R, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            state: FrameReadState::with_config(config),
+        }
+    }
+
+    /// Reads the next frame and copies its payload into `buf`.
+    ///
+    /// Returns the length of the payload. On a decode error, the frame is dropped and the next
+    /// call starts over with the following bytes.
+    #[doc(alias = "read_frame_into")]
+    pub fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<R::Error>> {
+        loop {
+            if let Some(result) = self.state.decode(buf) {
+                return result;
+            }
+
+            let length = self
+                .inner
+                .read(self.state.chunk_mut())
+                .map_err(SlipIoError::Io)?;
+            self.state.fill(length)?;
+        }
+    }
+
+    /// Reads the next frame, like `read_frame`, then restores its payload in place with
+    /// `transform`.
+    ///
+    /// Returns the length of the restored payload, or the error of the transform if it rejects
+    /// the frame, which is then dropped.
+    pub fn read_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        transform: &mut impl FrameTransform,
+    ) -> Result<usize, SlipIoError<R::Error>> {
+        let len = self.read_frame(buf)?;
+
+        Ok(transform.invert(buf, len)?)
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the reader, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// A bidirectional SLIP link over an `embedded_io` transport.
+///
+/// This struct combines a frame reader and a frame writer over a single transport, such as a
+/// serial port, so that payloads can be sent and received without wiring both halves together.
+/// Received frames are at most `MAX_LENGTH` bytes long.
+pub struct SlipTransport<T, const MAX_LENGTH: usize> {
+    reader: SlipFrameReader<T, MAX_LENGTH>,
+    config: SlipConfig,
+}
+
+impl<T: Read + Write, const MAX_LENGTH: usize> SlipTransport<T, MAX_LENGTH> {
+    /// Creates a transport over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: T) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a transport over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: T, config: SlipConfig) -> Self {
+        Self {
+            reader: SlipFrameReader::with_config(inner, config),
+            config,
+        }
+    }
+
+    /// Writes `payload` as a complete frame, then flushes the transport.
+    pub fn send(&mut self, payload: &[u8]) -> Result<(), T::Error> {
+        let mut writer = SlipFrameWriter::with_config(self.reader.get_mut(), self.config);
+        writer.write_frame(payload)?;
+        writer.flush()
+    }
+
+    /// Reads the next frame and copies its payload into `buf`, like `SlipFrameReader::read_frame`.
+    pub fn recv(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<T::Error>> {
+        self.reader.read_frame(buf)
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &T {
+        self.reader.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.reader.get_mut()
+    }
+
+    /// Consumes the transport, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.reader.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::error::SlipIoError;
+    use crate::io::SlipFrameReader;
+    use crate::io::SlipFrameWriter;
+    use crate::io::SlipTransport;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::ESC_ESC_CHAR;
+    use crate::transform::FrameTransform;
+    use core::convert::Infallible;
+    use embedded_io::ErrorType;
+    use embedded_io::Read;
+    use embedded_io::SliceWriteError;
+    use noalloc_vec_rs::vec::Vec;
+
+    struct MockSerial<'a> {
+        input: &'a [u8],
+        output: Vec<u8, 16>,
+    }
+
+    impl ErrorType for MockSerial<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for MockSerial<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(self.input.read(buf).unwrap_or_default())
+        }
+    }
+
+    impl embedded_io::Write for MockSerial<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.output.extend(buf.iter().copied());
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let mut output = [0x00; 10];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        writer.start_frame().unwrap();
+        writer.write(&[0x00, END_CHAR]).unwrap();
+        writer.write(&[ESC_CHAR, 0x01]).unwrap();
+        writer.end_frame().unwrap();
+
+        let remaining = writer.into_inner().len();
+        assert_eq!(remaining, 2);
+        assert_eq!(
+            output[..8],
+            [
+                END_CHAR,
+                0x00,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                ESC_CHAR,
+                ESC_ESC_CHAR,
+                0x01,
+                END_CHAR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frame_writer_with_full_transport() {
+        let mut output = [0x00; 3];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        let result = writer.write_frame(&[0x00, 0x01]);
+
+        assert_eq!(result, Err(SliceWriteError::Full));
+    }
+
+    #[test]
+    fn test_frame_writer_abort() {
+        let mut output = [0x00; 8];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        writer.start_frame().unwrap();
+        writer.write(&[0x00]).unwrap();
+        writer.abort_frame().unwrap();
+        writer.write_frame(&[0x01]).unwrap();
+
+        let remaining = writer.into_inner().len();
+        let mut reader = SlipFrameReader::<_, 4>::new(&output[..output.len() - remaining]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(
+            reader.read_frame(&mut buf),
+            Err(SlipIoError::Decode(SlipDecodeError::InvalidEscape(
+                END_CHAR
+            )))
+        );
+        assert_eq!(reader.read_frame(&mut buf), Ok(1));
+        assert_eq!(buf[..1], [0x01]);
+    }
+
+    #[test]
+    fn test_frame_writer_with_formatted_text() {
+        let mut output = [0x00; 8];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        writer.start_frame().unwrap();
+        write!(writer, "T={}", 21).unwrap();
+        writer.end_frame().unwrap();
+
+        assert_eq!(output[..6], [END_CHAR, b'T', b'=', b'2', b'1', END_CHAR]);
+
+        let mut output = [0x00; 2];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        assert!(write!(writer, "T={}", 21).is_err());
+    }
+
+    #[test]
+    fn test_frame_reader() {
+        let data = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            END_CHAR,
+        ];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(reader.read_frame(&mut buf), Ok(2));
+        assert_eq!(buf[..2], [0x00, END_CHAR]);
+
+        assert_eq!(reader.read_frame(&mut buf), Ok(1));
+        assert_eq!(buf[..1], [0x01]);
+
+        assert_eq!(reader.read_frame(&mut buf), Err(SlipIoError::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_frame_reader_with_bad_escape_character() {
+        let data = [END_CHAR, ESC_CHAR, 0x00, END_CHAR, END_CHAR, 0x01, END_CHAR];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(
+            reader.read_frame(&mut buf),
+            Err(SlipIoError::Decode(SlipDecodeError::InvalidEscape(0x00)))
+        );
+        assert_eq!(reader.read_frame(&mut buf), Ok(1));
+        assert_eq!(buf[..1], [0x01]);
+        assert_eq!(reader.read_frame(&mut buf), Err(SlipIoError::UnexpectedEof));
+    }
+
+    #[test]
+    fn test_frame_reader_with_small_buffer() {
+        let data = [END_CHAR, 0x00, 0x01, END_CHAR];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+
+        assert_eq!(
+            reader.read_frame(&mut [0x00; 1]),
+            Err(SlipIoError::BufferTooSmall(2))
+        );
+
+        let mut buf = [0x00; 2];
+        assert_eq!(reader.read_frame(&mut buf), Ok(2));
+        assert_eq!(buf, [0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_transformed_frames() {
+        /// Reverses the payload, and rejects empty frames.
+        struct Reverse;
+
+        /// Implementation of `FrameTransform` for `Reverse`.
+        impl FrameTransform for Reverse {
+            /// Reverses the payload.
+            fn apply(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipEncodeError> {
+                buf[..len].reverse();
+
+                Ok(len)
+            }
+
+            /// Reverses the payload back, rejecting an empty one.
+            fn invert(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipDecodeError> {
+                if len == 0 {
+                    return Err(SlipDecodeError::TransformFailed);
+                }
+                buf[..len].reverse();
+
+                Ok(len)
+            }
+        }
+
+        let mut output = [0x00; 8];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+        let mut buf = [0x01, 0x02, 0x00, 0x00];
+
+        writer
+            .write_transformed_frame(&mut buf, 2, &mut Reverse)
+            .unwrap();
+        writer.write_frame(&[]).unwrap();
+
+        assert_eq!(
+            output[..6],
+            [END_CHAR, 0x02, 0x01, END_CHAR, END_CHAR, END_CHAR]
+        );
+
+        let mut reader = SlipFrameReader::<_, 4>::new(&output[..6]);
+
+        assert_eq!(reader.read_transformed_frame(&mut buf, &mut Reverse), Ok(2));
+        assert_eq!(buf[..2], [0x01, 0x02]);
+        assert_eq!(
+            reader.read_transformed_frame(&mut buf, &mut Reverse),
+            Err(SlipIoError::Decode(SlipDecodeError::TransformFailed))
+        );
+    }
+
+    #[test]
+    fn test_transport() {
+        let serial = MockSerial {
+            input: &[END_CHAR, 0x01, ESC_CHAR, ESC_ESC_CHAR, END_CHAR],
+            output: Vec::new(),
+        };
+        let mut transport = SlipTransport::<_, 4>::new(serial);
+        let mut buf = [0x00; 4];
+
+        transport.send(&[0x02, END_CHAR]).unwrap();
+
+        assert_eq!(transport.recv(&mut buf), Ok(2));
+        assert_eq!(buf[..2], [0x01, ESC_CHAR]);
+        assert_eq!(transport.recv(&mut buf), Err(SlipIoError::UnexpectedEof));
+        assert_eq!(
+            transport.into_inner().output.as_slice(),
+            [END_CHAR, 0x02, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use embedded_io_async::Read;
+use embedded_io_async::Write;
+
+use crate::error::SlipIoError;
+use crate::frame_io::FrameReadState;
+use crate::slip::ESC_CHAR;
+use crate::slip::EscapedSegments;
+use crate::slip::SlipConfig;
+use crate::transform::FrameTransform;
+
+/// A SLIP frame writer over an `embedded_io_async::Write` transport.
+///
+/// This struct is the async counterpart of `io::SlipFrameWriter`, escaping payload bytes as they
+/// pass through and writing them directly to the underlying transport.
+pub struct SlipFrameWriter<W> {
+    inner: W,
+    config: SlipConfig,
+}
+
+impl<W: Write> SlipFrameWriter<W> {
+    /// Creates a writer over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: W) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a writer over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: W, config: SlipConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Begins a new frame, writing the opening terminator if the configuration requires one.
+    pub async fn start_frame(&mut self) -> Result<(), W::Error> {
+        if self.config.leading_end() {
+            self.inner.write_all(&[self.config.end_char()]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Escapes `data` and writes it as part of the current frame.
+    pub async fn write(&mut self, data: &[u8]) -> Result<(), W::Error> {
+        for segment in EscapedSegments::new(data, self.config) {
+            self.inner.write_all(segment).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current frame, writing the closing terminator.
+    pub async fn end_frame(&mut self) -> Result<(), W::Error> {
+        self.inner.write_all(&[self.config.end_char()]).await
+    }
+
+    /// Aborts the current frame, writing an escape byte followed by the closing terminator.
+    ///
+    /// Decoders using `EscapePolicy::Strict` reject the frame as `SlipDecodeError::InvalidEscape`,
+    /// so that its partial payload is never delivered. A new frame can be started right after.
+    pub async fn abort_frame(&mut self) -> Result<(), W::Error> {
+        self.inner
+            .write_all(&[ESC_CHAR, self.config.end_char()])
+            .await
+    }
+
+    /// Writes `payload` as a complete frame.
+    ///
+    /// # Cancellation safety
+    ///
+    /// This method is not cancellation safe: if the future is dropped, part of the frame may have
+    /// been written already. `abort_frame` then terminates it, so that the peer discards it.
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<(), W::Error> {
+        self.start_frame().await?;
+        self.write(payload).await?;
+        self.end_frame().await
+    }
+
+    /// Transforms the payload held by the first `len` bytes of `buf` with `transform`, then
+    /// writes it as a complete frame.
+    ///
+    /// The payload is transformed in place, and may grow up to the length of `buf`.
+    pub async fn write_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        len: usize,
+        transform: &mut impl FrameTransform,
+    ) -> Result<(), SlipIoError<W::Error>> {
+        let len = transform.apply(buf, len)?;
+
+        self.write_frame(&buf[..len]).await.map_err(SlipIoError::Io)
+    }
+
+    /// Flushes the underlying transport.
+    pub async fn flush(&mut self) -> Result<(), W::Error> {
+        self.inner.flush().await
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the writer, returning the underlying transport.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A SLIP frame reader over an `embedded_io_async::Read` transport.
+///
+/// This struct is the async counterpart of `io::SlipFrameReader`, pulling bytes from the
+/// underlying transport in chunks and decoding them until a frame is complete.
+pub struct SlipFrameReader<R, const MAX_LENGTH: usize> {
+    inner: R,
+    state: FrameReadState<MAX_LENGTH>,
+}
+
+impl<R: Read, const MAX_LENGTH: usize> SlipFrameReader<R, MAX_LENGTH> {
+    /// Creates a reader over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a reader over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: R, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            state: FrameReadState::with_config(config),
+        }
+    }
+
+    /// Reads the next frame and copies its payload into `buf`.
+    ///
+    /// Returns the length of the payload. On a decode error, the frame is dropped and the next
+    /// call starts over with the following bytes.
+    ///
+    /// # Cancellation safety
+    ///
+    /// This method is cancellation safe, provided that `read` of the transport is: the reader only
+    /// waits on the transport, with every byte read so far decoded or buffered. If the future is
+    /// dropped, e.g. as the losing branch of a `select!`, the partial frame is kept, and the next
+    /// call resumes decoding it.
+    #[doc(alias = "read_frame_into")]
+    pub async fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<R::Error>> {
+        loop {
+            if let Some(result) = self.state.decode(buf) {
+                return result;
+            }
+
+            let length = self
+                .inner
+                .read(self.state.chunk_mut())
+                .await
+                .map_err(SlipIoError::Io)?;
+            self.state.fill(length)?;
+        }
+    }
+
+    /// Reads the next frame, like `read_frame`, then restores its payload in place with
+    /// `transform`.
+    ///
+    /// Returns the length of the restored payload, or the error of the transform if it rejects
+    /// the frame, which is then dropped.
+    pub async fn read_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        transform: &mut impl FrameTransform,
+    ) -> Result<usize, SlipIoError<R::Error>> {
+        let len = self.read_frame(buf).await?;
+
+        Ok(transform.invert(buf, len)?)
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the reader, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use core::pin::Pin;
+    use core::pin::pin;
+    use core::task::Context;
+    use core::task::Poll;
+    use core::task::Waker;
+    use embedded_io_async::ErrorType;
+    use embedded_io_async::Read;
+
+    use crate::error::SlipIoError;
+    use crate::io_async::SlipFrameReader;
+    use crate::io_async::SlipFrameWriter;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    /// Polls `future` to completion, assuming it never has to wait.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// A future which is pending on its first poll.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+
+            Poll::Pending
+        }
+    }
+
+    /// A transport delivering one chunk per read, each after having been pending once.
+    struct MockSerial<'a> {
+        chunks: &'a [&'a [u8]],
+    }
+
+    impl ErrorType for MockSerial<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for MockSerial<'_> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            YieldOnce(false).await;
+
+            // Chunks are taken only once the read completes, so that a cancelled read loses none
+            let Some((chunk, rest)) = self.chunks.split_first() else {
+                return Ok(0);
+            };
+            buf[..chunk.len()].copy_from_slice(chunk);
+            self.chunks = rest;
+
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let mut output = [0x00; 6];
+        let mut writer = SlipFrameWriter::new(&mut output[..]);
+
+        block_on(writer.write_frame(&[0x00, END_CHAR, 0x01])).unwrap();
+
+        assert_eq!(
+            output,
+            [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, 0x01, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_frame_reader() {
+        let data = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            END_CHAR,
+        ];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(block_on(reader.read_frame(&mut buf)), Ok(2));
+        assert_eq!(buf[..2], [0x00, END_CHAR]);
+
+        assert_eq!(block_on(reader.read_frame(&mut buf)), Ok(1));
+        assert_eq!(buf[..1], [0x01]);
+
+        assert_eq!(
+            block_on(reader.read_frame(&mut buf)),
+            Err(SlipIoError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn test_frame_reader_cancellation() {
+        let serial = MockSerial {
+            chunks: &[&[END_CHAR, 0x01, ESC_CHAR], &[ESC_END_CHAR, END_CHAR]],
+        };
+        let mut reader = SlipFrameReader::<_, 4>::new(serial);
+        let mut buf = [0x00; 4];
+        let mut context = Context::from_waker(Waker::noop());
+
+        // Drop the future while it waits for the second chunk, after the first one was decoded
+        {
+            let mut future = pin!(reader.read_frame(&mut buf));
+
+            assert!(future.as_mut().poll(&mut context).is_pending());
+            assert!(future.as_mut().poll(&mut context).is_pending());
+        }
+
+        assert_eq!(reader.get_ref().chunks.len(), 1);
+        assert_eq!(block_on(reader.read_frame(&mut buf)), Ok(2));
+        assert_eq!(buf[..2], [0x01, END_CHAR]);
+    }
+}
@@ -0,0 +1,278 @@
+use core::pin::Pin;
+use core::task::Context;
+use core::task::Poll;
+use core::task::ready;
+use std::io::ErrorKind;
+
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use futures_io::AsyncWrite;
+use futures_sink::Sink;
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipIoError;
+use crate::frame_io::FrameReadState;
+use crate::slip::EscapedSegments;
+use crate::slip::SlipConfig;
+
+/// A `Stream` of the SLIP frames read from a `futures_io::AsyncRead` transport.
+///
+/// This struct yields the decoded payloads, or the error that interrupted a frame, in which case
+/// decoding resumes with the following bytes. The stream ends with the transport, and the bytes
+/// of a trailing unfinished frame are dropped. The partial frame lives in the stream rather than
+/// in a future, so that a `next` future dropped before completion loses no bytes.
+pub struct SlipFrameStream<R, const MAX_LENGTH: usize> {
+    inner: R,
+    state: FrameReadState<MAX_LENGTH>,
+}
+
+impl<R: AsyncRead + Unpin, const MAX_LENGTH: usize> SlipFrameStream<R, MAX_LENGTH> {
+    /// Creates a stream over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a stream over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: R, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            state: FrameReadState::with_config(config),
+        }
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the stream, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Implementation of `Stream` for `SlipFrameStream`.
+impl<R: AsyncRead + Unpin, const MAX_LENGTH: usize> Stream for SlipFrameStream<R, MAX_LENGTH> {
+    type Item = Result<Vec<u8, MAX_LENGTH>, SlipIoError<std::io::Error>>;
+
+    /// Reads from the transport until the next frame is complete.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(result) = this.state.decode_frame() {
+                return Poll::Ready(Some(result));
+            }
+
+            let length =
+                match ready!(Pin::new(&mut this.inner).poll_read(cx, this.state.chunk_mut())) {
+                    Ok(length) => length,
+                    Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                    Err(error) => return Poll::Ready(Some(Err(SlipIoError::Io(error)))),
+                };
+            if this.state.fill::<std::io::Error>(length).is_err() {
+                return Poll::Ready(None);
+            }
+        }
+    }
+}
+
+/// A `Sink` of payloads written as SLIP frames to a `futures_io::AsyncWrite` transport.
+///
+/// This struct encodes each payload into an internal buffer when it is sent, and writes the
+/// buffer out to the transport before accepting the next payload.
+pub struct SlipFrameSink<W> {
+    inner: W,
+    config: SlipConfig,
+    pending: std::vec::Vec<u8>,
+    written: usize,
+}
+
+impl<W: AsyncWrite + Unpin> SlipFrameSink<W> {
+    /// Creates a sink over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: W) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a sink over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: W, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            config,
+            pending: std::vec::Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the sink, returning the underlying transport.
+    ///
+    /// Encoded bytes not written to the transport yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the pending encoded bytes to the transport.
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while self.written < self.pending.len() {
+            let written = match ready!(
+                Pin::new(&mut self.inner).poll_write(cx, &self.pending[self.written..])
+            ) {
+                Ok(0) => return Poll::Ready(Err(ErrorKind::WriteZero.into())),
+                Ok(written) => written,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Poll::Ready(Err(error)),
+            };
+            self.written += written;
+        }
+        self.pending.clear();
+        self.written = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Implementation of `Sink` for `SlipFrameSink`.
+impl<T: AsRef<[u8]>, W: AsyncWrite + Unpin> Sink<T> for SlipFrameSink<W> {
+    type Error = std::io::Error;
+
+    /// Writes out the previous frame before accepting a new one.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_write_pending(cx)
+    }
+
+    /// Encodes `item` as a complete frame.
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let end = this.config.end_char();
+
+        if this.config.leading_end() {
+            this.pending.push(end);
+        }
+        for segment in EscapedSegments::new(item.as_ref(), this.config) {
+            this.pending.extend_from_slice(segment);
+        }
+        this.pending.push(end);
+
+        Ok(())
+    }
+
+    /// Writes out the pending frame and flushes the transport.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    /// Writes out the pending frame and closes the transport.
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+
+        Pin::new(&mut this.inner).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::pin::Pin;
+    use core::pin::pin;
+    use core::task::Context;
+    use core::task::Poll;
+    use core::task::Waker;
+
+    use futures_core::Stream;
+    use futures_sink::Sink;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipIoError;
+    use crate::io_futures::SlipFrameSink;
+    use crate::io_futures::SlipFrameStream;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    /// Polls `f` until it is ready, assuming it never has to wait.
+    fn block_on<T>(mut f: impl FnMut(&mut Context<'_>) -> Poll<T>) -> T {
+        let mut context = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(output) = f(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_frame_sink() {
+        let mut sink = pin!(SlipFrameSink::new(std::vec::Vec::new()));
+
+        block_on(|cx| Sink::<&[u8]>::poll_ready(sink.as_mut(), cx)).unwrap();
+        sink.as_mut()
+            .start_send(&[0x00, END_CHAR, 0x01][..])
+            .unwrap();
+        block_on(|cx| Sink::<&[u8]>::poll_flush(sink.as_mut(), cx)).unwrap();
+
+        assert_eq!(
+            sink.get_ref()[..],
+            [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, 0x01, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_frame_stream() {
+        let data = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            0x01,
+            END_CHAR,
+            0x02,
+            END_CHAR,
+            0x03,
+        ];
+        let mut stream = SlipFrameStream::<_, 4>::new(&data[..]);
+        let mut next = || block_on(|cx| Pin::new(&mut stream).poll_next(cx));
+
+        assert_eq!(next().unwrap().unwrap()[..], [0x00, END_CHAR]);
+        assert!(matches!(
+            next(),
+            Some(Err(SlipIoError::Decode(SlipDecodeError::InvalidEscape(
+                0x01
+            ))))
+        ));
+        assert_eq!(next().unwrap().unwrap()[..], [0x02]);
+        assert!(next().is_none());
+    }
+}
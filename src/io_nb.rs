@@ -0,0 +1,340 @@
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial::Read;
+use embedded_hal_nb::serial::Write;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipIoError;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+
+/// The progress of a frame written by a non-blocking frame writer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NbWriteState {
+    /// The opening terminator is next, if the configuration requires one.
+    Start,
+    /// The payload byte at `index` is next, preceded by `pending` if an escape sequence is
+    /// half-written.
+    Payload { index: usize, pending: Option<u8> },
+    /// The closing terminator is next.
+    End,
+}
+
+/// A SLIP frame writer over an `embedded_hal_nb::serial::Write` port.
+///
+/// This struct writes a frame one byte at a time, remembering its progress when the port would
+/// block, so that the same call can be repeated until the frame is out, e.g. with `nb::block!`
+/// or from a polling loop.
+pub struct SlipFrameWriter<S> {
+    serial: S,
+    config: SlipConfig,
+    state: NbWriteState,
+}
+
+impl<S: Write> SlipFrameWriter<S> {
+    /// Creates a writer over `serial`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(serial: S) -> Self {
+        Self::with_config(serial, SlipConfig::new())
+    }
+
+    /// Creates a writer over `serial`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(serial: S, config: SlipConfig) -> Self {
+        Self {
+            serial,
+            config,
+            state: NbWriteState::Start,
+        }
+    }
+
+    /// Writes `payload` as a complete frame, resuming where the previous call stopped.
+    ///
+    /// Returns `Err(nb::Error::WouldBlock)` if the port is busy: the call must then be repeated
+    /// with the same payload. On a port error, the frame is abandoned and the next call starts a
+    /// new one.
+    pub fn write_frame(&mut self, payload: &[u8]) -> nb::Result<(), S::Error> {
+        let result = self.advance(payload);
+        if let Err(nb::Error::Other(_)) = result {
+            self.state = NbWriteState::Start;
+        }
+
+        result
+    }
+
+    /// Writes the remaining bytes of the frame of `payload`.
+    fn advance(&mut self, payload: &[u8]) -> nb::Result<(), S::Error> {
+        let codec = self.config.codec();
+
+        loop {
+            match self.state {
+                NbWriteState::Start => {
+                    if self.config.leading_end() {
+                        self.serial.write(self.config.end_char())?;
+                    }
+                    self.state = NbWriteState::Payload {
+                        index: 0,
+                        pending: None,
+                    };
+                }
+                NbWriteState::Payload {
+                    index,
+                    pending: Some(value),
+                } => {
+                    self.serial.write(value)?;
+                    self.state = NbWriteState::Payload {
+                        index,
+                        pending: None,
+                    };
+                }
+                NbWriteState::Payload {
+                    index,
+                    pending: None,
+                } => {
+                    let Some(&value) = payload.get(index) else {
+                        self.state = NbWriteState::End;
+                        continue;
+                    };
+
+                    let substitute = codec.substitute(value);
+                    self.serial
+                        .write(substitute.map_or(value, |_| codec.escape()))?;
+                    self.state = NbWriteState::Payload {
+                        index: index + 1,
+                        pending: substitute,
+                    };
+                }
+                NbWriteState::End => {
+                    self.serial.write(self.config.end_char())?;
+                    self.state = NbWriteState::Start;
+
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Flushes the port.
+    pub fn flush(&mut self) -> nb::Result<(), S::Error> {
+        self.serial.flush()
+    }
+
+    /// Returns a reference to the underlying port.
+    #[must_use]
+    pub const fn get_ref(&self) -> &S {
+        &self.serial
+    }
+
+    /// Returns a mutable reference to the underlying port.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    /// Consumes the writer, returning the underlying port.
+    ///
+    /// The rest of a partially written frame is lost.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.serial
+    }
+}
+
+/// A SLIP frame reader over an `embedded_hal_nb::serial::Read` port.
+///
+/// This struct decodes bytes as the port delivers them, keeping the partial frame between calls,
+/// so that a frame can be polled for without a retry loop of its own.
+pub struct SlipFrameReader<S, const MAX_LENGTH: usize> {
+    serial: S,
+    decoder: SlipDecoder<MAX_LENGTH>,
+}
+
+impl<S: Read, const MAX_LENGTH: usize> SlipFrameReader<S, MAX_LENGTH> {
+    /// Creates a reader over `serial`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(serial: S) -> Self {
+        Self::with_config(serial, SlipConfig::new())
+    }
+
+    /// Creates a reader over `serial`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(serial: S, config: SlipConfig) -> Self {
+        Self {
+            serial,
+            decoder: SlipDecoder::with_config(config),
+        }
+    }
+
+    /// Reads the available bytes and copies the payload of a completed frame into `buf`.
+    ///
+    /// Returns the length of the payload, or `Err(nb::Error::WouldBlock)` if no frame is complete
+    /// yet: the bytes read so far are kept for the next call. On a decode error, the frame is
+    /// dropped and the next call starts over with the following bytes. If `buf` is too small, the
+    /// frame stays pending until it is read with a large enough buffer, while a frame failing the
+    /// integrity check is dropped.
+    #[doc(alias = "read_frame_into")]
+    pub fn read_frame(&mut self, buf: &mut [u8]) -> nb::Result<usize, SlipIoError<S::Error>> {
+        while !self.decoder.is_buffer_completed() {
+            let value = self
+                .serial
+                .read()
+                .map_err(|error| error.map(SlipIoError::Io))?;
+
+            if let Err(error) = self.decoder.insert(value) {
+                self.decoder.reset();
+
+                return Err(nb::Error::Other(SlipIoError::Decode(error)));
+            }
+        }
+
+        self.decoder
+            .read_frame_into(buf)
+            .map_err(|error| match error {
+                SlipDecodeError::OutputTooSmall(length) => {
+                    nb::Error::Other(SlipIoError::BufferTooSmall(length))
+                }
+                error => nb::Error::Other(SlipIoError::Decode(error)),
+            })
+    }
+
+    /// Returns a reference to the underlying port.
+    #[must_use]
+    pub const fn get_ref(&self) -> &S {
+        &self.serial
+    }
+
+    /// Returns a mutable reference to the underlying port.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    /// Consumes the reader, returning the underlying port.
+    ///
+    /// The bytes of a partially received frame are lost.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.serial
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use embedded_hal_nb::nb;
+    use embedded_hal_nb::serial::ErrorType;
+    use embedded_hal_nb::serial::Read;
+    use embedded_hal_nb::serial::Write;
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipIoError;
+    use crate::io_nb::SlipFrameReader;
+    use crate::io_nb::SlipFrameWriter;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    /// A port blocking on every other call, and until more input is provided.
+    struct MockSerial<'a> {
+        input: &'a [u8],
+        output: Vec<u8, 16>,
+        busy: bool,
+    }
+
+    impl MockSerial<'_> {
+        fn poll(&mut self) -> nb::Result<(), Infallible> {
+            self.busy = !self.busy;
+            if self.busy {
+                return Err(nb::Error::WouldBlock);
+            }
+
+            Ok(())
+        }
+    }
+
+    impl ErrorType for MockSerial<'_> {
+        type Error = Infallible;
+    }
+
+    impl Read for MockSerial<'_> {
+        fn read(&mut self) -> nb::Result<u8, Self::Error> {
+            self.poll()?;
+            let (&value, rest) = self.input.split_first().ok_or(nb::Error::WouldBlock)?;
+            self.input = rest;
+
+            Ok(value)
+        }
+    }
+
+    impl Write for MockSerial<'_> {
+        fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+            self.poll()?;
+            let _ = self.output.push(word);
+
+            Ok(())
+        }
+
+        fn flush(&mut self) -> nb::Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_frame() {
+        let serial = MockSerial {
+            input: &[],
+            output: Vec::new(),
+            busy: false,
+        };
+        let mut writer = SlipFrameWriter::new(serial);
+
+        let mut attempts = 1;
+        while writer.write_frame(&[0x01, END_CHAR]) == Err(nb::Error::WouldBlock) {
+            attempts += 1;
+        }
+
+        assert_eq!(attempts, 6);
+        assert_eq!(
+            writer.into_inner().output.as_slice(),
+            [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_read_frame() {
+        let serial = MockSerial {
+            input: &[END_CHAR, 0x01],
+            output: Vec::new(),
+            busy: false,
+        };
+        let mut reader = SlipFrameReader::<_, 4>::new(serial);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(reader.read_frame(&mut buf), Err(nb::Error::WouldBlock));
+        assert_eq!(reader.read_frame(&mut buf), Err(nb::Error::WouldBlock));
+        assert_eq!(reader.read_frame(&mut buf), Err(nb::Error::WouldBlock));
+
+        reader.get_mut().input = &[ESC_CHAR, ESC_END_CHAR, END_CHAR];
+        let length = nb::block!(reader.read_frame(&mut buf)).unwrap();
+
+        assert_eq!(buf[..length], [0x01, END_CHAR]);
+    }
+
+    #[test]
+    fn test_read_frame_with_bad_escape_character() {
+        let serial = MockSerial {
+            input: &[END_CHAR, ESC_CHAR, 0x00, END_CHAR, 0x01, END_CHAR],
+            output: Vec::new(),
+            busy: false,
+        };
+        let mut reader = SlipFrameReader::<_, 4>::new(serial);
+        let mut buf = [0x00; 1];
+
+        assert_eq!(
+            nb::block!(reader.read_frame(&mut buf)),
+            Err(SlipIoError::Decode(SlipDecodeError::InvalidEscape(0x00)))
+        );
+        assert_eq!(nb::block!(reader.read_frame(&mut buf)), Ok(1));
+        assert_eq!(buf, [0x01]);
+    }
+}
@@ -0,0 +1,413 @@
+use core::fmt;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+
+use crate::encode::FrameWrite;
+use crate::error::SlipIoError;
+use crate::frame_io::FrameReadState;
+use crate::slip::ESC_CHAR;
+use crate::slip::EscapedSegments;
+use crate::slip::SlipConfig;
+use crate::transform::FrameTransform;
+
+/// A SLIP frame writer over a `std::io::Write` transport.
+///
+/// This struct is the hosted counterpart of `io::SlipFrameWriter`, escaping payload bytes as they
+/// pass through and writing them directly to the underlying transport.
+pub struct SlipFrameWriter<W> {
+    inner: W,
+    config: SlipConfig,
+}
+
+impl<W: Write> SlipFrameWriter<W> {
+    /// Creates a writer over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: W) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a writer over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: W, config: SlipConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Begins a new frame, writing the opening terminator if the configuration requires one.
+    pub fn start_frame(&mut self) -> std::io::Result<()> {
+        if self.config.leading_end() {
+            self.inner.write_all(&[self.config.end_char()])?;
+        }
+
+        Ok(())
+    }
+
+    /// Escapes `data` and writes it as part of the current frame.
+    pub fn write(&mut self, data: &[u8]) -> std::io::Result<()> {
+        for segment in EscapedSegments::new(data, self.config) {
+            self.inner.write_all(segment)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current frame, writing the closing terminator.
+    pub fn end_frame(&mut self) -> std::io::Result<()> {
+        self.inner.write_all(&[self.config.end_char()])
+    }
+
+    /// Aborts the current frame, writing an escape byte followed by the closing terminator.
+    ///
+    /// Decoders using `EscapePolicy::Strict` reject the frame as `SlipDecodeError::InvalidEscape`,
+    /// so that its partial payload is never delivered. A new frame can be started right after.
+    pub fn abort_frame(&mut self) -> std::io::Result<()> {
+        self.inner.write_all(&[ESC_CHAR, self.config.end_char()])
+    }
+
+    /// Writes `payload` as a complete frame.
+    pub fn write_frame(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        self.start_frame()?;
+        self.write(payload)?;
+        self.end_frame()
+    }
+
+    /// Transforms the payload held by the first `len` bytes of `buf` with `transform`, then
+    /// writes it as a complete frame.
+    ///
+    /// The payload is transformed in place, and may grow up to the length of `buf`.
+    pub fn write_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        len: usize,
+        transform: &mut impl FrameTransform,
+    ) -> Result<(), SlipIoError<std::io::Error>> {
+        let len = transform.apply(buf, len)?;
+
+        self.write_frame(&buf[..len]).map_err(SlipIoError::Io)
+    }
+
+    /// Flushes the underlying transport.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the writer, returning the underlying transport.
+    #[must_use]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Implementation of `fmt::Write` for `SlipFrameWriter`.
+///
+/// Formatted text is escaped and written as part of the current frame, so that diagnostics can
+/// be sent with `write!` without any formatting buffer. The frame is still opened and closed with
+/// `start_frame` and `end_frame`.
+impl<W: Write> fmt::Write for SlipFrameWriter<W> {
+    /// Escapes `s` and writes it as part of the current frame.
+    ///
+    /// The transport error is lost, as `fmt::Error` carries no detail.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Implementation of `FrameWrite` for `SlipFrameWriter`.
+impl<W: Write> FrameWrite for SlipFrameWriter<W> {
+    type Error = std::io::Error;
+
+    /// Begins a new frame, writing the opening terminator if the configuration requires one.
+    fn start_frame(&mut self) -> Result<(), Self::Error> {
+        Self::start_frame(self)
+    }
+
+    /// Escapes `data` and writes it as part of the current frame.
+    fn write(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        Self::write(self, data)
+    }
+
+    /// Ends the current frame, writing the closing terminator.
+    fn end_frame(&mut self) -> Result<(), Self::Error> {
+        Self::end_frame(self)
+    }
+}
+
+/// A SLIP frame reader over a `std::io::Read` transport.
+///
+/// This struct is the hosted counterpart of `io::SlipFrameReader`, pulling bytes from the
+/// underlying transport in chunks and decoding them until a frame is complete. Reads interrupted
+/// by a signal are retried.
+pub struct SlipFrameReader<R, const MAX_LENGTH: usize> {
+    inner: R,
+    state: FrameReadState<MAX_LENGTH>,
+}
+
+impl<R: Read, const MAX_LENGTH: usize> SlipFrameReader<R, MAX_LENGTH> {
+    /// Creates a reader over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a reader over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: R, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            state: FrameReadState::with_config(config),
+        }
+    }
+
+    /// Reads the next frame and copies its payload into `buf`.
+    ///
+    /// Returns the length of the payload. On a decode error, the frame is dropped and the next
+    /// call starts over with the following bytes.
+    #[doc(alias = "read_frame_into")]
+    pub fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<std::io::Error>> {
+        loop {
+            if let Some(result) = self.state.decode(buf) {
+                return result;
+            }
+
+            let length = match self.inner.read(self.state.chunk_mut()) {
+                Ok(length) => length,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(SlipIoError::Io(error)),
+            };
+            self.state.fill(length)?;
+        }
+    }
+
+    /// Reads the next frame, like `read_frame`, then restores its payload in place with
+    /// `transform`.
+    ///
+    /// Returns the length of the restored payload, or the error of the transform if it rejects
+    /// the frame, which is then dropped.
+    pub fn read_transformed_frame(
+        &mut self,
+        buf: &mut [u8],
+        transform: &mut impl FrameTransform,
+    ) -> Result<usize, SlipIoError<std::io::Error>> {
+        let len = self.read_frame(buf)?;
+
+        Ok(transform.invert(buf, len)?)
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the reader, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// A bidirectional SLIP link over a `std::io` transport.
+///
+/// This struct combines a frame reader and a frame writer over a single transport, such as a
+/// serial port, so that payloads can be sent and received without wiring both halves together.
+/// Received frames are at most `MAX_LENGTH` bytes long.
+pub struct SlipTransport<T, const MAX_LENGTH: usize> {
+    reader: SlipFrameReader<T, MAX_LENGTH>,
+    config: SlipConfig,
+}
+
+impl<T: Read + Write, const MAX_LENGTH: usize> SlipTransport<T, MAX_LENGTH> {
+    /// Creates a transport over `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: T) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates a transport over `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: T, config: SlipConfig) -> Self {
+        Self {
+            reader: SlipFrameReader::with_config(inner, config),
+            config,
+        }
+    }
+
+    /// Writes `payload` as a complete frame, then flushes the transport.
+    pub fn send(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        let mut writer = SlipFrameWriter::with_config(self.reader.get_mut(), self.config);
+        writer.write_frame(payload)?;
+        writer.flush()
+    }
+
+    /// Reads the next frame and copies its payload into `buf`, like `SlipFrameReader::read_frame`.
+    pub fn recv(&mut self, buf: &mut [u8]) -> Result<usize, SlipIoError<std::io::Error>> {
+        self.reader.read_frame(buf)
+    }
+
+    /// Returns a reference to the underlying transport.
+    #[must_use]
+    pub const fn get_ref(&self) -> &T {
+        self.reader.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying transport.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut T {
+        self.reader.get_mut()
+    }
+
+    /// Consumes the transport, returning the underlying transport.
+    ///
+    /// Bytes read from the transport but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.reader.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::encode::FrameWrite;
+    use crate::error::SlipIoError;
+    use crate::io_std::SlipFrameReader;
+    use crate::io_std::SlipFrameWriter;
+    use crate::io_std::SlipTransport;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    struct MockSerial<'a> {
+        input: &'a [u8],
+        output: std::vec::Vec<u8>,
+    }
+
+    impl std::io::Read for MockSerial<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl std::io::Write for MockSerial<'_> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_frame_writer() {
+        let mut writer = SlipFrameWriter::new(std::vec::Vec::new());
+
+        writer.write_frame(&[0x00, END_CHAR, 0x01]).unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, 0x01, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_frame_writer_with_formatted_text() {
+        let mut writer = SlipFrameWriter::new(std::vec::Vec::new());
+
+        writer.start_frame().unwrap();
+        write!(writer, "{:.1}", 21.5).unwrap();
+        writer.end_frame().unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            [END_CHAR, b'2', b'1', b'.', b'5', END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_frame_writer_send() {
+        let mut writer = SlipFrameWriter::new(std::vec::Vec::new());
+
+        writer.send(&[0x00, END_CHAR]).unwrap();
+
+        assert_eq!(
+            writer.into_inner(),
+            [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_frame_reader() {
+        let data = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            END_CHAR,
+        ];
+        let mut reader = SlipFrameReader::<_, 4>::new(&data[..]);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(reader.read_frame(&mut buf).unwrap(), 2);
+        assert_eq!(buf[..2], [0x00, END_CHAR]);
+
+        assert_eq!(reader.read_frame(&mut buf).unwrap(), 1);
+        assert_eq!(buf[..1], [0x01]);
+
+        assert!(matches!(
+            reader.read_frame(&mut buf),
+            Err(SlipIoError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn test_error_is_std_error() {
+        let error: std::boxed::Box<dyn std::error::Error> =
+            std::boxed::Box::new(SlipIoError::Io(std::io::Error::other("link down")));
+
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_transport() {
+        let serial = MockSerial {
+            input: &[END_CHAR, 0x01, END_CHAR],
+            output: std::vec::Vec::new(),
+        };
+        let mut transport = SlipTransport::<_, 4>::new(serial);
+        let mut buf = [0x00; 4];
+
+        transport.send(&[0x02, END_CHAR]).unwrap();
+
+        assert_eq!(transport.recv(&mut buf).unwrap(), 1);
+        assert_eq!(buf[..1], [0x01]);
+        assert_eq!(
+            transport.into_inner().output,
+            [END_CHAR, 0x02, ESC_CHAR, ESC_END_CHAR, END_CHAR]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::slip::SlipConfig;
+use crate::wcet::WorkBound;
+
+/// What a `Keepalive` sends over an idle link.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeepaliveKind {
+    /// A single terminator byte, which the peer skips between frames. It also terminates a frame
+    /// the peer was left receiving after line noise.
+    #[default]
+    IdleEnd,
+    /// An empty frame, which a peer reporting empty frames completes as a valid frame, so that it
+    /// can tell a live idle link from a dead one with `SlipDecoder::time_since_last_frame`.
+    EmptyFrame,
+}
+
+/// A timer emitting keepalives over a SLIP link left idle.
+///
+/// The link is idle once nothing was sent for `interval` ticks. Ticks are in the unit of the
+/// timestamps given to `record_sent` and `poll`, which only need to increase monotonically, and
+/// may wrap around.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Keepalive {
+    interval: u64,
+    kind: KeepaliveKind,
+    bytes: [u8; 2],
+    len: usize,
+    last_sent_at: u64,
+}
+
+impl Keepalive {
+    /// Creates a timer sending a terminator byte of the default SLIP configuration once the link
+    /// was idle for `interval` ticks.
+    #[must_use]
+    pub const fn new(interval: u64) -> Self {
+        Self::with_config(interval, KeepaliveKind::IdleEnd, SlipConfig::new())
+    }
+
+    /// Creates a timer sending `kind` keepalives, framed with the options of `config`, once the
+    /// link was idle for `interval` ticks.
+    #[must_use]
+    pub const fn with_config(interval: u64, kind: KeepaliveKind, config: SlipConfig) -> Self {
+        let end = config.end_char();
+        let len = match kind {
+            KeepaliveKind::EmptyFrame if config.leading_end() => 2,
+            KeepaliveKind::IdleEnd | KeepaliveKind::EmptyFrame => 1,
+        };
+
+        Self {
+            interval,
+            kind,
+            bytes: [end; 2],
+            len,
+            last_sent_at: 0,
+        }
+    }
+
+    /// Returns the number of idle ticks after which a keepalive is sent.
+    #[must_use]
+    pub const fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// Returns what the timer sends over an idle link.
+    #[must_use]
+    pub const fn kind(&self) -> KeepaliveKind {
+        self.kind
+    }
+
+    /// Records that bytes were sent at `now`, which postpones the next keepalive.
+    pub const fn record_sent(&mut self, now: u64) {
+        self.last_sent_at = now;
+    }
+
+    /// Returns the bytes of the keepalive to send at `now`, or `None` if the link is not idle
+    /// yet.
+    ///
+    /// The keepalive is recorded as sent, so that the next one comes after another `interval`
+    /// idle ticks. Calling this periodically, e.g. from a timer, keeps the link alive.
+    pub fn poll(&mut self, now: u64) -> Option<&[u8]> {
+        if now.wrapping_sub(self.last_sent_at) < self.interval {
+            return None;
+        }
+
+        self.last_sent_at = now;
+
+        self.bytes.get(..self.len)
+    }
+
+    /// Returns the worst-case work of `poll`.
+    #[must_use]
+    pub const fn poll_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keepalive::Keepalive;
+    use crate::keepalive::KeepaliveKind;
+    use crate::slip::END_CHAR;
+    use crate::slip::SlipConfig;
+    use crate::slip::SlipDecoder;
+
+    #[test]
+    fn test_keepalive_on_idle_link() {
+        let mut keepalive = Keepalive::new(10);
+
+        assert_eq!(keepalive.poll(9), None);
+        assert_eq!(keepalive.poll(10), Some([END_CHAR].as_slice()));
+        assert_eq!(keepalive.poll(15), None);
+
+        keepalive.record_sent(18);
+
+        assert_eq!(keepalive.poll(27), None);
+        assert_eq!(keepalive.poll(28), Some([END_CHAR].as_slice()));
+    }
+
+    #[test]
+    fn test_keepalive_refreshes_liveness() {
+        let mut keepalive =
+            Keepalive::with_config(10, KeepaliveKind::EmptyFrame, SlipConfig::new());
+        let mut slip_decoder = SlipDecoder::<4>::new();
+
+        assert_eq!(slip_decoder.time_since_last_frame(5), None);
+
+        let frame = keepalive.poll(10).unwrap();
+        assert_eq!(frame, [END_CHAR, END_CHAR]);
+
+        slip_decoder.feed_at(frame, 12);
+        slip_decoder.reset();
+
+        assert_eq!(slip_decoder.last_frame_at(), Some(12));
+        assert_eq!(slip_decoder.time_since_last_frame(40), Some(28));
+    }
+}
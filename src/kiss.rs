@@ -0,0 +1,363 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::EmptyFramePolicy;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+use crate::wcet::WorkBound;
+
+/// Number of ports a KISS TNC can address.
+pub const KISS_PORT_COUNT: u8 = 16;
+
+/// Type byte of the `Return` command, which leaves KISS mode.
+const RETURN_TYPE: u8 = 0xFF;
+
+/// A KISS command, carried by the low nibble of the type byte of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KissCommand {
+    /// The payload is a frame to transmit, or a received frame.
+    Data,
+    /// The payload sets the keyup delay, in steps of 10 ms.
+    TxDelay,
+    /// The payload sets the persistence parameter of the channel access.
+    Persistence,
+    /// The payload sets the slot interval, in steps of 10 ms.
+    SlotTime,
+    /// The payload sets the time to hold the transmitter up after a frame, in steps of 10 ms.
+    TxTail,
+    /// The payload selects full duplex when non-zero, half duplex otherwise.
+    FullDuplex,
+    /// The payload is a hardware specific command.
+    SetHardware,
+    /// Leaves KISS mode. This command is not addressed to a port.
+    Return,
+}
+
+impl KissCommand {
+    /// Returns the code of the command, as found in the low nibble of the type byte.
+    const fn code(self) -> u8 {
+        match self {
+            Self::Data => 0x00,
+            Self::TxDelay => 0x01,
+            Self::Persistence => 0x02,
+            Self::SlotTime => 0x03,
+            Self::TxTail => 0x04,
+            Self::FullDuplex => 0x05,
+            Self::SetHardware => 0x06,
+            Self::Return => 0x0F,
+        }
+    }
+
+    /// Returns the command of the given code, if any.
+    const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x00 => Some(Self::Data),
+            0x01 => Some(Self::TxDelay),
+            0x02 => Some(Self::Persistence),
+            0x03 => Some(Self::SlotTime),
+            0x04 => Some(Self::TxTail),
+            0x05 => Some(Self::FullDuplex),
+            0x06 => Some(Self::SetHardware),
+            _ => None,
+        }
+    }
+}
+
+/// The port and command of a KISS frame, carried by its type byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KissHeader {
+    port: u8,
+    command: KissCommand,
+}
+
+impl KissHeader {
+    /// Creates the header of a frame sending `command` to `port`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not lower than `KISS_PORT_COUNT`.
+    #[must_use]
+    pub const fn new(port: u8, command: KissCommand) -> Self {
+        assert!(port < KISS_PORT_COUNT, "a KISS port must fit in 4 bits");
+
+        Self { port, command }
+    }
+
+    /// Creates the header of a data frame on `port`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not lower than `KISS_PORT_COUNT`.
+    #[must_use]
+    pub const fn data(port: u8) -> Self {
+        Self::new(port, KissCommand::Data)
+    }
+
+    /// Returns the port the frame is addressed to.
+    #[must_use]
+    pub const fn port(&self) -> u8 {
+        self.port
+    }
+
+    /// Returns the command of the frame.
+    #[must_use]
+    pub const fn command(&self) -> KissCommand {
+        self.command
+    }
+
+    /// Returns the type byte encoding the header.
+    const fn to_byte(self) -> u8 {
+        match self.command {
+            KissCommand::Return => RETURN_TYPE,
+            command => (self.port << 4) | command.code(),
+        }
+    }
+
+    /// Returns the header encoded by the type byte `value`, if it is valid.
+    const fn from_byte(value: u8) -> Option<Self> {
+        if value == RETURN_TYPE {
+            return Some(Self {
+                port: value >> 4,
+                command: KissCommand::Return,
+            });
+        }
+
+        match KissCommand::from_code(value & 0x0F) {
+            Some(command) => Some(Self {
+                port: value >> 4,
+                command,
+            }),
+            None => None,
+        }
+    }
+}
+
+/// A KISS frame encoder.
+///
+/// KISS frames are SLIP frames whose first byte, the type byte, holds the port and command of the
+/// frame.
+#[derive(Clone, Copy, Default)]
+pub struct KissEncoder {
+    encoder: SlipEncoder,
+}
+
+impl KissEncoder {
+    /// Creates a KISS encoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            encoder: SlipEncoder::with_config(SlipConfig::new()),
+        }
+    }
+
+    /// Prepends the type byte of `header` to the payload held by `vec`, then encodes it in place
+    /// as a KISS frame.
+    ///
+    /// Returns `Err(SlipEncodeError::BufferTooSmall)` or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the vector is too small to hold the frame,
+    /// in which case it is left untouched.
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        header: KissHeader,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        vec.insert(0, header.to_byte())
+            .map_err(|_| SlipEncodeError::BufferTooSmall)?;
+
+        self.encoder.encode(vec).inspect_err(|_| {
+            let _ = vec.remove(0);
+        })
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to shift the payload, then the encoding itself
+        WorkBound::linear(MAX_LENGTH).then(SlipEncoder::encode_work_bound::<MAX_LENGTH>())
+    }
+}
+
+/// A KISS frame decoder.
+///
+/// `MAX_LENGTH` bounds the decoded frame, the type byte included. Empty frames, which KISS hosts
+/// send to resynchronize the link, are skipped, and a frame with an unknown command is rejected
+/// as `SlipDecodeError::MalformedFrame`.
+pub struct KissDecoder<const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    header: Option<KissHeader>,
+}
+
+impl<const MAX_LENGTH: usize> KissDecoder<MAX_LENGTH> {
+    /// Creates a KISS decoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(
+                SlipConfig::new().with_empty_frames(EmptyFramePolicy::Skip),
+            ),
+            header: None,
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected, the frame is too long
+    /// to be buffered, or the frame it completes has an invalid type byte.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+
+        if self.decoder.is_buffer_completed() {
+            self.parse_header()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound()
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, as for
+    /// `SlipDecoder::feed`. A completed frame with an invalid type byte is rejected.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+
+        let status = match result.status {
+            FeedStatus::Completed => match self.parse_header() {
+                Ok(()) => FeedStatus::Completed,
+                Err(error) => FeedStatus::Rejected(error),
+            },
+            status => status,
+        };
+
+        FeedResult {
+            consumed: result.consumed,
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len)
+    }
+
+    /// Parses the type byte of the completed frame.
+    ///
+    /// The frame is never empty, as the underlying decoder skips empty frames.
+    fn parse_header(&mut self) -> Result<(), SlipDecodeError> {
+        let value = self.decoder.get_buffer().first().copied();
+
+        self.header = value.and_then(KissHeader::from_byte);
+        if self.header.is_none() {
+            self.decoder.reset();
+
+            return Err(SlipDecodeError::MalformedFrame);
+        }
+
+        Ok(())
+    }
+
+    /// Resets the decoder for the next frame.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.header = None;
+    }
+
+    /// Returns the header of the completed frame, or `None` if no frame is complete.
+    #[must_use]
+    pub const fn header(&self) -> Option<KissHeader> {
+        self.header
+    }
+
+    /// Returns the decoded payload, without its type byte.
+    ///
+    /// The payload is only meaningful once the frame is complete.
+    #[must_use]
+    pub fn get_buffer(&self) -> &[u8] {
+        self.decoder.get_buffer().get(1..).unwrap_or_default()
+    }
+}
+
+/// Default implementation for `KissDecoder`.
+impl<const MAX_LENGTH: usize> Default for KissDecoder<MAX_LENGTH> {
+    /// Creates a KISS decoder.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::kiss::KissCommand;
+    use crate::kiss::KissDecoder;
+    use crate::kiss::KissEncoder;
+    use crate::kiss::KissHeader;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_encode() {
+        let mut vec = Vec::<u8, 8>::from([0x01, 0x02]);
+        KissEncoder::new()
+            .encode(KissHeader::new(12, KissCommand::Data), &mut vec)
+            .unwrap();
+
+        assert_eq!(
+            vec.as_slice(),
+            [END_CHAR, ESC_CHAR, ESC_END_CHAR, 0x01, 0x02, END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_encode_return() {
+        let mut vec = Vec::<u8, 4>::new();
+        KissEncoder::new()
+            .encode(KissHeader::new(0, KissCommand::Return), &mut vec)
+            .unwrap();
+
+        assert_eq!(vec.as_slice(), [END_CHAR, 0xFF, END_CHAR]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = [END_CHAR, END_CHAR, 0x21, 0x32, END_CHAR];
+        let mut decoder = KissDecoder::<8>::new();
+
+        let result = decoder.feed(&data);
+
+        assert_eq!(result.consumed, data.len());
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(
+            decoder.header(),
+            Some(KissHeader::new(2, KissCommand::TxDelay))
+        );
+        assert_eq!(decoder.get_buffer(), [0x32]);
+    }
+
+    #[test]
+    fn test_decode_with_unknown_command() {
+        let mut decoder = KissDecoder::<8>::new();
+
+        assert_eq!(decoder.insert(END_CHAR), Ok(()));
+        assert_eq!(decoder.insert(0x08), Ok(()));
+        assert_eq!(
+            decoder.insert(END_CHAR),
+            Err(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(decoder.header(), None);
+    }
+}
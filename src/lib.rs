@@ -1,3 +1,93 @@
 #![no_std]
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[macro_use]
+mod trace;
+
+pub mod arq;
+pub mod buffer;
+pub mod bus;
+#[cfg(feature = "std")]
+pub mod capture;
+pub mod cdc;
+pub mod checksum;
+pub mod cobs;
+pub mod codec;
+pub mod constant_time;
+pub mod crc;
+#[cfg(feature = "cslip")]
+pub mod cslip;
+pub mod dedup;
+#[cfg(feature = "smoltcp")]
+pub mod device;
+pub mod dispatch;
+pub mod dump;
+#[cfg(feature = "embassy")]
+pub mod embassy;
+pub mod encode;
+pub mod error;
+#[cfg(feature = "embedded-io")]
+pub mod fault;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fragment;
+pub mod frame;
+#[cfg(any(
+    feature = "embedded-io",
+    feature = "async",
+    feature = "futures",
+    feature = "std"
+))]
+mod frame_io;
+#[cfg(feature = "arbitrary")]
+pub mod fuzzing;
+#[cfg(test)]
+mod interop;
+#[cfg(feature = "embedded-io")]
+pub mod io;
+#[cfg(feature = "async")]
+pub mod io_async;
+#[cfg(feature = "futures")]
+pub mod io_futures;
+#[cfg(feature = "embedded-hal-nb")]
+pub mod io_nb;
+#[cfg(feature = "std")]
+pub mod io_std;
+pub mod keepalive;
+pub mod kiss;
+#[cfg(feature = "embedded-io")]
+pub mod loopback;
+pub mod mailbox;
+#[cfg(feature = "serde")]
+pub mod message;
+pub mod mux;
+pub mod observer;
+pub mod pingpong;
+pub mod pool;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod queue;
+mod scan;
+#[cfg(feature = "serialport")]
+pub mod serial;
+#[cfg(feature = "critical-section")]
+pub mod shared;
 pub mod slip;
+pub mod slipmux;
+pub mod split;
+pub mod stream;
+pub mod stuffing;
+pub mod symbol;
+pub mod transform;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod tun;
+pub mod tunnel;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod wcet;
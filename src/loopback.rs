@@ -0,0 +1,314 @@
+use core::cell::RefCell;
+
+use embedded_io::ErrorKind;
+use embedded_io::ErrorType;
+use embedded_io::Read;
+use embedded_io::Write;
+use noalloc_vec_rs::vec::Vec;
+
+/// A queue of up to `N` bytes, handing them out in the order they were written.
+struct Pipe<const N: usize> {
+    data: Vec<u8, N>,
+}
+
+impl<const N: usize> Pipe<N> {
+    /// Creates an empty pipe.
+    const fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    /// Moves up to `limit` of the oldest bytes into `buf`, and returns how many were moved.
+    fn read(&mut self, buf: &mut [u8], limit: usize) -> usize {
+        let length = buf.len().min(limit).min(self.data.len());
+        buf[..length].copy_from_slice(&self.data.as_slice()[..length]);
+
+        let remaining = self.data.len() - length;
+        self.data.as_mut_slice().copy_within(length.., 0);
+        self.data.truncate(remaining);
+
+        length
+    }
+
+    /// Appends up to `limit` bytes of `buf`, and returns how many were appended.
+    ///
+    /// Returns `Err(ErrorKind::WriteZero)` if `buf` is not empty and the pipe is full.
+    fn write(&mut self, buf: &[u8], limit: usize) -> Result<usize, ErrorKind> {
+        let length = buf.len().min(limit).min(N - self.data.len());
+        if length == 0 && !buf.is_empty() {
+            return Err(ErrorKind::WriteZero);
+        }
+
+        // The bytes fit, as their length is bounded by the room left
+        let _ = self.data.write_slice(self.data.len(), &buf[..length]);
+
+        Ok(length)
+    }
+}
+
+/// An in-memory transport reading back the bytes written to it, for testing.
+///
+/// This struct stands in for a serial port looped back on itself, so that SLIP-based protocols
+/// can be exercised without hardware: the bytes written are held, up to `N` of them, until they
+/// are read. Each call transfers at most `chunk_size` bytes, so that frames are delivered in
+/// pieces as by a real transport. Reading while no byte is held returns `Ok(0)`, the end of
+/// stream, rather than blocking.
+pub struct LoopbackTransport<const N: usize> {
+    pipe: Pipe<N>,
+    chunk_size: usize,
+}
+
+impl<const N: usize> LoopbackTransport<N> {
+    /// Creates an empty transport, transferring any number of bytes per call.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_chunk_size(usize::MAX)
+    }
+
+    /// Creates an empty transport, transferring at most `chunk_size` bytes per call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[must_use]
+    pub const fn with_chunk_size(chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "the chunk size must not be zero");
+
+        Self {
+            pipe: Pipe::new(),
+            chunk_size,
+        }
+    }
+
+    /// Returns the bytes written and not read yet.
+    #[must_use]
+    pub const fn pending(&self) -> &[u8] {
+        self.pipe.data.as_slice()
+    }
+}
+
+/// Default implementation for `LoopbackTransport`.
+impl<const N: usize> Default for LoopbackTransport<N> {
+    /// Creates an empty transport, transferring any number of bytes per call.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementation of `ErrorType` for `LoopbackTransport`.
+impl<const N: usize> ErrorType for LoopbackTransport<N> {
+    type Error = ErrorKind;
+}
+
+/// Implementation of `Read` for `LoopbackTransport`.
+impl<const N: usize> Read for LoopbackTransport<N> {
+    /// Reads up to `chunk_size` of the bytes written, returning `Ok(0)` if there is none.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.pipe.read(buf, self.chunk_size))
+    }
+}
+
+/// Implementation of `Write` for `LoopbackTransport`.
+impl<const N: usize> Write for LoopbackTransport<N> {
+    /// Holds up to `chunk_size` bytes of `buf` to be read back.
+    ///
+    /// Returns `Err(ErrorKind::WriteZero)` if no byte fits.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.pipe.write(buf, self.chunk_size)
+    }
+
+    /// Does nothing, as written bytes are readable right away.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Implementation of `embedded_io_async::Read` for `LoopbackTransport`.
+#[cfg(feature = "async")]
+impl<const N: usize> embedded_io_async::Read for LoopbackTransport<N> {
+    /// Reads up to `chunk_size` of the bytes written, returning `Ok(0)` if there is none.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Read::read(self, buf)
+    }
+}
+
+/// Implementation of `embedded_io_async::Write` for `LoopbackTransport`.
+#[cfg(feature = "async")]
+impl<const N: usize> embedded_io_async::Write for LoopbackTransport<N> {
+    /// Holds up to `chunk_size` bytes of `buf` to be read back.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Write::write(self, buf)
+    }
+
+    /// Does nothing, as written bytes are readable right away.
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A pair of in-memory transports connected to each other, for testing.
+///
+/// This struct stands in for a serial link between two devices: the bytes written to one end,
+/// up to `N` in each direction, are read from the other one. Both ends are borrowed from the
+/// duplex with `ends`, so that each side of a protocol can own one of them. As for
+/// `LoopbackTransport`, each call transfers at most `chunk_size` bytes, and reading while no byte
+/// is held returns `Ok(0)`.
+pub struct Duplex<const N: usize> {
+    pipes: [RefCell<Pipe<N>>; 2],
+    chunk_size: usize,
+}
+
+impl<const N: usize> Duplex<N> {
+    /// Creates an empty duplex, transferring any number of bytes per call.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_chunk_size(usize::MAX)
+    }
+
+    /// Creates an empty duplex, transferring at most `chunk_size` bytes per call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[must_use]
+    pub const fn with_chunk_size(chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "the chunk size must not be zero");
+
+        Self {
+            pipes: [RefCell::new(Pipe::new()), RefCell::new(Pipe::new())],
+            chunk_size,
+        }
+    }
+
+    /// Returns both ends of the duplex.
+    #[must_use]
+    pub const fn ends(&self) -> (DuplexEnd<'_, N>, DuplexEnd<'_, N>) {
+        let [first, second] = &self.pipes;
+
+        (
+            DuplexEnd {
+                rx: first,
+                tx: second,
+                chunk_size: self.chunk_size,
+            },
+            DuplexEnd {
+                rx: second,
+                tx: first,
+                chunk_size: self.chunk_size,
+            },
+        )
+    }
+}
+
+/// Default implementation for `Duplex`.
+impl<const N: usize> Default for Duplex<N> {
+    /// Creates an empty duplex, transferring any number of bytes per call.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One end of a `Duplex`, reading the bytes written to the other end.
+pub struct DuplexEnd<'a, const N: usize> {
+    rx: &'a RefCell<Pipe<N>>,
+    tx: &'a RefCell<Pipe<N>>,
+    chunk_size: usize,
+}
+
+/// Implementation of `ErrorType` for `DuplexEnd`.
+impl<const N: usize> ErrorType for DuplexEnd<'_, N> {
+    type Error = ErrorKind;
+}
+
+/// Implementation of `Read` for `DuplexEnd`.
+impl<const N: usize> Read for DuplexEnd<'_, N> {
+    /// Reads up to `chunk_size` of the bytes written to the other end, returning `Ok(0)` if there
+    /// is none.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(self.rx.borrow_mut().read(buf, self.chunk_size))
+    }
+}
+
+/// Implementation of `Write` for `DuplexEnd`.
+impl<const N: usize> Write for DuplexEnd<'_, N> {
+    /// Holds up to `chunk_size` bytes of `buf` to be read from the other end.
+    ///
+    /// Returns `Err(ErrorKind::WriteZero)` if no byte fits.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.tx.borrow_mut().write(buf, self.chunk_size)
+    }
+
+    /// Does nothing, as written bytes are readable right away.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Implementation of `embedded_io_async::Read` for `DuplexEnd`.
+#[cfg(feature = "async")]
+#[allow(clippy::future_not_send)]
+impl<const N: usize> embedded_io_async::Read for DuplexEnd<'_, N> {
+    /// Reads up to `chunk_size` of the bytes written to the other end, returning `Ok(0)` if there
+    /// is none.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Read::read(self, buf)
+    }
+}
+
+/// Implementation of `embedded_io_async::Write` for `DuplexEnd`.
+#[cfg(feature = "async")]
+#[allow(clippy::future_not_send)]
+impl<const N: usize> embedded_io_async::Write for DuplexEnd<'_, N> {
+    /// Holds up to `chunk_size` bytes of `buf` to be read from the other end.
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Write::write(self, buf)
+    }
+
+    /// Does nothing, as written bytes are readable right away.
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_io::ErrorKind;
+    use embedded_io::Read;
+    use embedded_io::Write;
+
+    use crate::io::SlipTransport;
+    use crate::loopback::Duplex;
+    use crate::loopback::LoopbackTransport;
+    use crate::slip::END_CHAR;
+
+    #[test]
+    fn test_loopback() {
+        let mut transport = LoopbackTransport::<4>::with_chunk_size(3);
+        let mut buf = [0x00; 4];
+
+        assert_eq!(transport.write(&[0x01, 0x02, 0x03, 0x04]), Ok(3));
+        assert_eq!(transport.write(&[0x05, 0x06]), Ok(1));
+        assert_eq!(transport.write(&[0x06]), Err(ErrorKind::WriteZero));
+        assert_eq!(transport.read(&mut buf), Ok(3));
+        assert_eq!(buf[..3], [0x01, 0x02, 0x03]);
+        assert_eq!(transport.pending(), [0x05]);
+        assert_eq!(transport.read(&mut buf), Ok(1));
+        assert_eq!(transport.read(&mut buf), Ok(0));
+    }
+
+    #[test]
+    fn test_duplex() {
+        let duplex = Duplex::<16>::with_chunk_size(2);
+        let (first, second) = duplex.ends();
+        let mut first = SlipTransport::<_, 8>::new(first);
+        let mut second = SlipTransport::<_, 8>::new(second);
+        let mut buf = [0x00; 8];
+
+        first.send(&[0x01, END_CHAR, 0x02]).unwrap();
+        second.send(&[0x03]).unwrap();
+
+        assert_eq!(second.recv(&mut buf), Ok(3));
+        assert_eq!(buf[..3], [0x01, END_CHAR, 0x02]);
+        assert_eq!(first.recv(&mut buf), Ok(1));
+        assert_eq!(buf[0], 0x03);
+    }
+}
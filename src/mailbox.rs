@@ -0,0 +1,275 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::dispatch::FrameSink;
+use crate::error::SlipDecodeError;
+
+/// Index standing for no slot.
+const NO_SLOT: usize = usize::MAX;
+
+/// A slot of a mailbox, holding a frame and its sequence number.
+struct Slot<const MAX_LENGTH: usize> {
+    frame: Vec<u8, MAX_LENGTH>,
+    sequence: usize,
+}
+
+/// A lock-free single-slot mailbox, holding the latest frame of up to `MAX_LENGTH` bytes.
+///
+/// This struct suits telemetry, where only the most recent sample matters: every frame posted
+/// overwrites the previous one, so that a slow consumer reads the latest frame instead of working
+/// through a backlog, as it would with a `FrameQueue`. Frames are numbered in the order they are
+/// posted, which tells the consumer how many it missed, and whether the latest one was already
+/// read. The mailbox is split into a `MailboxWriter` and a `MailboxReader`, each usable from its
+/// own execution context, e.g. an interrupt handler and the main loop.
+///
+/// Posting never waits for the reader, as frames are triple-buffered. Like `FrameQueue`, it only
+/// relies on atomic loads and stores, which every target supports.
+pub struct FrameMailbox<const MAX_LENGTH: usize> {
+    slots: [UnsafeCell<Slot<MAX_LENGTH>>; 3],
+    // Slot of the latest frame posted
+    latest: AtomicUsize,
+    // Slot claimed by the reader
+    reading: AtomicUsize,
+    // Sequence number of the latest frame posted
+    posted: AtomicUsize,
+}
+
+// SAFETY: the writer only writes a slot which is neither the latest one nor the one claimed by
+// the reader, and the reader only reads the slot it claimed while it still was the latest one.
+// All the accesses to `latest` and `reading` are sequentially consistent, so that once the reader
+// sees its claim still holding the latest slot, the writer sees the claim before picking that
+// slot again. There is at most one writer and one reader, as both borrow the mailbox mutably
+// when it is split.
+unsafe impl<const MAX_LENGTH: usize> Sync for FrameMailbox<MAX_LENGTH> {}
+
+impl<const MAX_LENGTH: usize> FrameMailbox<MAX_LENGTH> {
+    /// Creates an empty mailbox.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const {
+                UnsafeCell::new(Slot {
+                    frame: Vec::new(),
+                    sequence: 0,
+                })
+            }; 3],
+            latest: AtomicUsize::new(NO_SLOT),
+            reading: AtomicUsize::new(NO_SLOT),
+            posted: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the mailbox into its writer and reader halves.
+    pub const fn split(
+        &mut self,
+    ) -> (MailboxWriter<'_, MAX_LENGTH>, MailboxReader<'_, MAX_LENGTH>) {
+        let mailbox = &*self;
+
+        (
+            MailboxWriter {
+                mailbox,
+                latest: NO_SLOT,
+                sequence: 0,
+            },
+            MailboxReader { mailbox, seen: 0 },
+        )
+    }
+
+    /// Returns the sequence number of the latest frame posted, or `0` if none was.
+    #[must_use]
+    pub fn sequence(&self) -> usize {
+        self.posted.load(Ordering::Acquire)
+    }
+}
+
+/// Default implementation for `FrameMailbox`.
+impl<const MAX_LENGTH: usize> Default for FrameMailbox<MAX_LENGTH> {
+    /// Creates an empty mailbox.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The writer half of a `FrameMailbox`.
+pub struct MailboxWriter<'a, const MAX_LENGTH: usize> {
+    mailbox: &'a FrameMailbox<MAX_LENGTH>,
+    latest: usize,
+    sequence: usize,
+}
+
+impl<const MAX_LENGTH: usize> MailboxWriter<'_, MAX_LENGTH> {
+    /// Copies `frame` into the mailbox, overwriting the frame held.
+    ///
+    /// Returns `Err(SlipDecodeError::BufferFull)` if `frame` is longer than `MAX_LENGTH`. The
+    /// frame is then dropped, and the mailbox left untouched.
+    pub fn post(&mut self, frame: &[u8]) -> Result<(), SlipDecodeError> {
+        if frame.len() > MAX_LENGTH {
+            return Err(SlipDecodeError::BufferFull);
+        }
+
+        let reading = self.mailbox.reading.load(Ordering::SeqCst);
+        // Out of three slots, one is always neither the latest one nor the claimed one
+        let index = (0..3)
+            .find(|&index| index != self.latest && index != reading)
+            .unwrap_or_default();
+
+        // SAFETY: the slot is neither the latest one nor the claimed one, so the reader does not
+        // access it until the store of the new latest slot below
+        let slot = unsafe { &mut *self.mailbox.slots[index].get() };
+        self.sequence = self.sequence.wrapping_add(1);
+        slot.frame.clear();
+        let _ = slot.frame.write_slice(0, frame);
+        slot.sequence = self.sequence;
+
+        self.latest = index;
+        self.mailbox.latest.store(index, Ordering::SeqCst);
+        self.mailbox.posted.store(self.sequence, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Returns the sequence number of the latest frame posted, or `0` if none was.
+    #[must_use]
+    pub const fn sequence(&self) -> usize {
+        self.sequence
+    }
+}
+
+/// Implementation of `FrameSink` for `MailboxWriter`.
+impl<const MAX_LENGTH: usize> FrameSink for MailboxWriter<'_, MAX_LENGTH> {
+    /// Posts `frame`, dropping it if it is too long.
+    fn on_frame(&mut self, frame: &[u8]) {
+        let _ = self.post(frame);
+    }
+}
+
+/// The reader half of a `FrameMailbox`.
+pub struct MailboxReader<'a, const MAX_LENGTH: usize> {
+    mailbox: &'a FrameMailbox<MAX_LENGTH>,
+    seen: usize,
+}
+
+impl<const MAX_LENGTH: usize> MailboxReader<'_, MAX_LENGTH> {
+    /// Calls `f` with the latest frame posted and its sequence number, marking it as read.
+    ///
+    /// Returns the result of `f`, or `None` without calling it if no frame was posted yet. The
+    /// latest frame is handed out again until a newer one is posted: `is_fresh` tells whether it
+    /// was already read. The frame is borrowed straight from its slot, without being copied.
+    pub fn read_with<R>(&mut self, f: impl FnOnce(&[u8], usize) -> R) -> Option<R> {
+        let index = loop {
+            let index = self.mailbox.latest.load(Ordering::SeqCst);
+            if index == NO_SLOT {
+                return None;
+            }
+
+            // The claim holds once the slot is still the latest one, which only fails if the
+            // writer posted meanwhile
+            self.mailbox.reading.store(index, Ordering::SeqCst);
+            if self.mailbox.latest.load(Ordering::SeqCst) == index {
+                break index;
+            }
+        };
+
+        // SAFETY: the slot is claimed, so the writer does not access it until the claim is
+        // released below
+        let slot = unsafe { &*self.mailbox.slots[index].get() };
+        self.seen = slot.sequence;
+        let result = f(slot.frame.as_slice(), slot.sequence);
+
+        self.mailbox.reading.store(NO_SLOT, Ordering::SeqCst);
+
+        Some(result)
+    }
+
+    /// Returns a copy of the latest frame posted, marking it as read.
+    ///
+    /// Returns `None` if no frame was posted yet.
+    pub fn read(&mut self) -> Option<Vec<u8, MAX_LENGTH>> {
+        self.read_with(|frame, _| frame.iter().copied().collect())
+    }
+
+    /// Returns `true` if a frame was posted since the last one read.
+    #[must_use]
+    pub fn is_fresh(&self) -> bool {
+        self.mailbox.sequence() != self.seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use crate::dispatch::SlipDispatcher;
+    use crate::error::SlipDecodeError;
+    use crate::mailbox::FrameMailbox;
+    use crate::slip::END_CHAR;
+
+    #[test]
+    fn test_post_overwrites() {
+        let mut mailbox = FrameMailbox::<2>::new();
+        let (mut writer, mut reader) = mailbox.split();
+
+        assert_eq!(reader.read(), None);
+        assert!(!reader.is_fresh());
+
+        writer.post(&[0x01]).unwrap();
+        writer.post(&[0x02, 0x03]).unwrap();
+
+        assert_eq!(
+            writer.post(&[0x04, 0x05, 0x06]),
+            Err(SlipDecodeError::BufferFull)
+        );
+        assert!(reader.is_fresh());
+        assert_eq!(
+            reader.read_with(|frame, sequence| (frame == [0x02, 0x03], sequence)),
+            Some((true, 2))
+        );
+        assert!(!reader.is_fresh());
+        assert_eq!(reader.read().unwrap().as_slice(), [0x02, 0x03]);
+
+        writer.post(&[0x04]).unwrap();
+
+        assert!(reader.is_fresh());
+        assert_eq!(reader.read().unwrap().as_slice(), [0x04]);
+        assert_eq!(writer.sequence(), 3);
+    }
+
+    #[test]
+    fn test_post_while_reading() {
+        let mut mailbox = FrameMailbox::<2>::new();
+        let (writer, mut reader) = mailbox.split();
+        let writer = RefCell::new(writer);
+
+        writer.borrow_mut().post(&[0x01]).unwrap();
+
+        // Frames posted while a frame is read go to the other slots
+        let result = reader.read_with(|frame, _| {
+            writer.borrow_mut().post(&[0x02]).unwrap();
+            writer.borrow_mut().post(&[0x03]).unwrap();
+            writer.borrow_mut().post(&[0x04]).unwrap();
+
+            frame == [0x01]
+        });
+
+        assert_eq!(result, Some(true));
+        assert_eq!(
+            reader.read_with(|frame, sequence| (frame == [0x04], sequence)),
+            Some((true, 4))
+        );
+    }
+
+    #[test]
+    fn test_post_from_dispatcher() {
+        let mut mailbox = FrameMailbox::<4>::new();
+        let (writer, mut reader) = mailbox.split();
+
+        let mut dispatcher = SlipDispatcher::<_, 4>::new(writer);
+        dispatcher.feed(&[END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, END_CHAR]);
+        drop(dispatcher);
+
+        assert_eq!(reader.read().unwrap().as_slice(), [0x02]);
+    }
+}
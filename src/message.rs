@@ -0,0 +1,153 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::SlipMessageError;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+
+/// Serializes `value` with postcard and encodes it as a SLIP frame into `buf`, using the default
+/// SLIP configuration.
+///
+/// Returns the length of the frame, or `Err(SlipMessageError::Encode(_))` if `buf` is too small
+/// to hold it.
+pub fn encode_frame<T: Serialize + ?Sized>(
+    value: &T,
+    buf: &mut [u8],
+) -> Result<usize, SlipMessageError> {
+    encode_frame_with_config(value, buf, SlipConfig::new())
+}
+
+/// Serializes `value` with postcard and encodes it as a SLIP frame into `buf`, using the framing
+/// options of `config`.
+///
+/// The message is serialized at the start of `buf`, then escaped in place, so that no other
+/// buffer is needed. Returns the length of the frame, or `Err(SlipMessageError::Encode(_))` if
+/// `buf` is too small to hold it.
+pub fn encode_frame_with_config<T: Serialize + ?Sized>(
+    value: &T,
+    buf: &mut [u8],
+    config: SlipConfig,
+) -> Result<usize, SlipMessageError> {
+    let payload_len = postcard::to_slice(value, buf)?.len();
+
+    Ok(config.codec().encode_slice(buf, payload_len)?)
+}
+
+/// Decodes the SLIP frame held by `frame` and deserializes its payload with postcard, using the
+/// default SLIP configuration.
+///
+/// The frame is unescaped in place, so its content is left undefined. Payloads already decoded,
+/// e.g. by a `SlipDecoder`, can be deserialized with `postcard::from_bytes` directly.
+pub fn decode_frame<T: DeserializeOwned>(frame: &mut [u8]) -> Result<T, SlipMessageError> {
+    decode_frame_with_config(frame, SlipConfig::new())
+}
+
+/// Decodes the SLIP frame held by `frame` and deserializes its payload with postcard, using the
+/// framing options of `config`.
+///
+/// The frame is unescaped in place, so its content is left undefined.
+pub fn decode_frame_with_config<T: DeserializeOwned>(
+    frame: &mut [u8],
+    config: SlipConfig,
+) -> Result<T, SlipMessageError> {
+    // Decoding in place leaves the decoder buffer untouched, so it needs no capacity
+    let payload = SlipDecoder::<0>::with_config(config).decode_in_place(frame)?;
+
+    Ok(postcard::from_bytes(payload)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde::Serialize;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::error::SlipMessageError;
+    use crate::message::decode_frame;
+    use crate::message::decode_frame_with_config;
+    use crate::message::encode_frame;
+    use crate::message::encode_frame_with_config;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::SlipConfig;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: u8,
+        value: i16,
+    }
+
+    #[test]
+    fn test_encode_decode_frame() {
+        let reading = Reading {
+            sensor: END_CHAR,
+            value: -2,
+        };
+        let mut buf = [0x00; 8];
+
+        let length = encode_frame(&reading, &mut buf).unwrap();
+
+        assert_eq!(
+            buf[..length],
+            [END_CHAR, ESC_CHAR, ESC_END_CHAR, 0x03, END_CHAR]
+        );
+        assert_eq!(decode_frame::<Reading>(&mut buf[..length]), Ok(reading));
+    }
+
+    #[test]
+    fn test_encode_decode_frame_with_config() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let mut buf = [0x00; 4];
+
+        let length = encode_frame_with_config(&[0x01_u8, 0x02], &mut buf, config).unwrap();
+
+        assert_eq!(buf[..length], [0x01, 0x02, END_CHAR]);
+        assert_eq!(
+            decode_frame_with_config::<[u8; 2]>(&mut buf[..length], config),
+            Ok([0x01, 0x02])
+        );
+    }
+
+    #[test]
+    fn test_encode_frame_with_small_buffer() {
+        let reading = Reading {
+            sensor: END_CHAR,
+            value: -2,
+        };
+
+        assert_eq!(
+            encode_frame(&reading, &mut [0x00; 2]),
+            Err(SlipMessageError::Encode(
+                SlipEncodeError::InsufficientCapacity {
+                    required: 5,
+                    available: 2
+                }
+            ))
+        );
+        assert_eq!(
+            encode_frame(&reading, &mut [0x00; 4]),
+            Err(SlipMessageError::Encode(
+                SlipEncodeError::InsufficientCapacity {
+                    required: 5,
+                    available: 4
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_frame_with_bad_frame() {
+        assert_eq!(
+            decode_frame::<Reading>(&mut [END_CHAR, ESC_CHAR, 0x00, END_CHAR]),
+            Err(SlipMessageError::Decode(SlipDecodeError::InvalidEscape(
+                0x00
+            )))
+        );
+        assert!(matches!(
+            decode_frame::<Reading>(&mut [END_CHAR, 0x01, END_CHAR]),
+            Err(SlipMessageError::Serde(_))
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::slip::FeedResult;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A set of SLIP decoders, one per serial port.
+///
+/// This struct keeps the state of `PORTS` links side by side, e.g. for a gateway terminating
+/// several SLIP UARTs. Bytes are fed per port, and completed frames are polled for across all
+/// ports, tagged with the port they came from. Ports are polled in turn, starting after the port
+/// of the last polled frame, so that a busy port cannot starve the others.
+pub struct SlipPortMux<const PORTS: usize, const MAX_LENGTH: usize> {
+    decoders: [SlipDecoder<MAX_LENGTH>; PORTS],
+    // Port to poll first for the next frame
+    next: usize,
+}
+
+impl<const PORTS: usize, const MAX_LENGTH: usize> SlipPortMux<PORTS, MAX_LENGTH> {
+    /// Creates a set of decoders using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decoders: [const { SlipDecoder::new() }; PORTS],
+            next: 0,
+        }
+    }
+
+    /// Creates a set of decoders all using the framing options of `config`.
+    #[must_use]
+    pub fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoders: core::array::from_fn(|_| SlipDecoder::with_config(config)),
+            next: 0,
+        }
+    }
+
+    /// Returns the number of ports.
+    #[must_use]
+    pub const fn ports(&self) -> usize {
+        PORTS
+    }
+
+    /// Feeds the bytes of `data`, received on `port`, into the decoder of that port.
+    ///
+    /// As with `SlipDecoder::feed`, bytes are consumed up to the end of a frame: once the frame
+    /// has been polled for, the leftover bytes must be fed again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not lower than `PORTS`.
+    pub fn feed(&mut self, port: usize, data: &[u8]) -> FeedResult {
+        self.decoders[port].feed(data)
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len)
+    }
+
+    /// Returns a copy of a completed frame along with its port, and resets the decoder of that
+    /// port for the next frame.
+    ///
+    /// Returns `None` if no port has a frame complete yet. A frame failing the integrity check is
+    /// dropped, and counted in the statistics of its port.
+    pub fn poll_frame(&mut self) -> Option<(usize, Vec<u8, MAX_LENGTH>)> {
+        for offset in 0..PORTS {
+            let port = (self.next + offset) % PORTS;
+            if let Ok(frame) = self.decoders[port].take_frame() {
+                self.next = (port + 1) % PORTS;
+
+                return Some((port, frame));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the worst-case work of `poll_frame`.
+    #[must_use]
+    pub const fn poll_frame_work_bound() -> WorkBound {
+        WorkBound::linear(PORTS).then(SlipDecoder::<MAX_LENGTH>::take_frame_work_bound())
+    }
+
+    /// Resets the decoder of `port` to its initial state, dropping its current frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not lower than `PORTS`.
+    pub fn reset(&mut self, port: usize) {
+        self.decoders[port].reset();
+    }
+
+    /// Returns the statistics of the link on `port`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` is not lower than `PORTS`.
+    #[must_use]
+    pub const fn stats(&self, port: usize) -> SlipStats {
+        self.decoders[port].stats()
+    }
+
+    /// Returns a reference to the decoder of `port`, or `None` if there is no such port.
+    #[must_use]
+    pub fn decoder(&self, port: usize) -> Option<&SlipDecoder<MAX_LENGTH>> {
+        self.decoders.get(port)
+    }
+
+    /// Returns a mutable reference to the decoder of `port`, or `None` if there is no such port.
+    #[must_use]
+    pub fn decoder_mut(&mut self, port: usize) -> Option<&mut SlipDecoder<MAX_LENGTH>> {
+        self.decoders.get_mut(port)
+    }
+}
+
+/// Default implementation for `SlipPortMux`.
+impl<const PORTS: usize, const MAX_LENGTH: usize> Default for SlipPortMux<PORTS, MAX_LENGTH> {
+    /// Creates a set of decoders using the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mux::SlipPortMux;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_poll_frame() {
+        let mut mux = SlipPortMux::<4, 4>::new();
+
+        assert_eq!(
+            mux.feed(1, &[END_CHAR, 0x01]).status,
+            FeedStatus::Incomplete
+        );
+        assert_eq!(
+            mux.feed(3, &[END_CHAR, 0x03, END_CHAR]).status,
+            FeedStatus::Completed
+        );
+        assert_eq!(
+            mux.feed(0, &[END_CHAR, 0x00, END_CHAR]).status,
+            FeedStatus::Completed
+        );
+        assert_eq!(mux.feed(1, &[END_CHAR]).status, FeedStatus::Completed);
+
+        let (port, frame) = mux.poll_frame().unwrap();
+        assert_eq!((port, frame.as_slice()), (0, [0x00].as_slice()));
+        let (port, frame) = mux.poll_frame().unwrap();
+        assert_eq!((port, frame.as_slice()), (1, [0x01].as_slice()));
+        let (port, frame) = mux.poll_frame().unwrap();
+        assert_eq!((port, frame.as_slice()), (3, [0x03].as_slice()));
+        assert!(mux.poll_frame().is_none());
+        assert_eq!(mux.stats(3).frames_completed, 1);
+    }
+
+    #[test]
+    fn test_poll_frame_round_robin() {
+        let mut mux = SlipPortMux::<2, 4>::new();
+
+        mux.feed(0, &[END_CHAR, 0x00, END_CHAR]);
+        mux.feed(1, &[END_CHAR, 0x01, END_CHAR]);
+        assert_eq!(mux.poll_frame().unwrap().0, 0);
+
+        // The first port must not be served twice while the second one waits
+        mux.feed(0, &[END_CHAR, 0x02, END_CHAR]);
+        assert_eq!(mux.poll_frame().unwrap().0, 1);
+        assert_eq!(mux.poll_frame().unwrap().0, 0);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::SlipDecodeError;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+
+/// A hook notified of the frames going through a codec, to feed metrics.
+///
+/// The codec calls these methods as it encodes and decodes, so that an application can update
+/// Prometheus counters or log to RTT without the crate depending on any metrics framework. Every
+/// method does nothing by default, so that an observer only implements what it counts. Methods
+/// take `&self`, as the codec encodes through `&self`: observers keep their counts in atomics or
+/// `Cell`s.
+pub trait SlipObserver {
+    /// Called once a payload of `len` bytes is encoded into a frame.
+    fn frame_encoded(&self, len: usize) {
+        let _ = len;
+    }
+
+    /// Called once a frame carrying a payload of `len` bytes is decoded.
+    fn frame_decoded(&self, len: usize) {
+        let _ = len;
+    }
+
+    /// Called once a frame is dropped, with the error that rejected it.
+    fn frame_dropped(&self, reason: SlipDecodeError) {
+        let _ = reason;
+    }
+
+    /// Called once `count` bytes received outside a frame are discarded.
+    fn bytes_discarded(&self, count: usize) {
+        let _ = count;
+    }
+}
+
+/// Implementation of `SlipObserver` for `()`, observing nothing.
+impl SlipObserver for () {}
+
+/// Implementation of `SlipObserver` for references to observers.
+impl<T: SlipObserver + ?Sized> SlipObserver for &T {
+    /// Forwards to the referenced observer.
+    fn frame_encoded(&self, len: usize) {
+        (**self).frame_encoded(len);
+    }
+
+    /// Forwards to the referenced observer.
+    fn frame_decoded(&self, len: usize) {
+        (**self).frame_decoded(len);
+    }
+
+    /// Forwards to the referenced observer.
+    fn frame_dropped(&self, reason: SlipDecodeError) {
+        (**self).frame_dropped(reason);
+    }
+
+    /// Forwards to the referenced observer.
+    fn bytes_discarded(&self, count: usize) {
+        (**self).bytes_discarded(count);
+    }
+}
+
+/// Reports the outcome of feeding bytes into a decoder to `observer`.
+///
+/// `discarded` is the number of bytes the decoder discarded meanwhile, and `frame_len` the length
+/// of the frame it completed, if any.
+pub(crate) fn observe_feed(
+    observer: &impl SlipObserver,
+    result: FeedResult,
+    discarded: u32,
+    frame_len: usize,
+) {
+    if discarded > 0 {
+        observer.bytes_discarded(discarded as usize);
+    }
+
+    match result.status {
+        FeedStatus::Incomplete => {}
+        FeedStatus::Completed => observer.frame_decoded(frame_len),
+        FeedStatus::Rejected(error) => observer.frame_dropped(error),
+    }
+}
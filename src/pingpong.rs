@@ -0,0 +1,199 @@
+use core::mem;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::slip::DecoderStatus;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A SLIP decoder with two buffers, decoding the next frame while the previous one is handled.
+///
+/// When a frame completes, its buffer is handed out through `frame`, and decoding goes on into
+/// the other buffer, so that the bytes following a frame can be fed before the frame has been
+/// processed or copied. Buffers are swapped rather than copied. Once the frame has been handled,
+/// `release` gives its buffer back to the decoder. Should a second frame complete before then,
+/// it is held in the decoder, which rejects further bytes with `SlipDecodeError::FrameCompleted`
+/// until the first frame is released.
+pub struct PingPongDecoder<const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    ready: Vec<u8, MAX_LENGTH>,
+    // Whether `ready` holds a frame handed out and not released yet
+    handed_out: bool,
+}
+
+impl<const MAX_LENGTH: usize> PingPongDecoder<MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            ready: Vec::new(),
+            handed_out: false,
+        }
+    }
+
+    /// Hands out the frame completed by the decoder, if the other buffer is free.
+    fn swap(&mut self) {
+        if self.handed_out || !self.decoder.is_buffer_completed() {
+            return;
+        }
+
+        mem::swap(self.decoder.buffer_mut(), &mut self.ready);
+        self.handed_out = true;
+        self.decoder.reset();
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected or the buffer is full.
+    /// A completed frame is handed out right away if the other buffer is free.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+        self.swap();
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound()
+            .then(SlipDecoder::<MAX_LENGTH>::reset_work_bound())
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed across frame boundaries while the other buffer is free, so that a
+    /// chunk holding a frame and the beginning of the next one is consumed whole. Consumption
+    /// stops at the end of a second frame, with `FeedStatus::Completed`, or at a rejected byte.
+    /// The status is also `FeedStatus::Completed` whenever a frame is handed out.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+        if result.status != FeedStatus::Completed || self.handed_out {
+            return result;
+        }
+
+        self.swap();
+        let rest = self.decoder.feed(&data[result.consumed..]);
+        self.swap();
+
+        let status = match rest.status {
+            FeedStatus::Incomplete => FeedStatus::Completed,
+            status => status,
+        };
+
+        FeedResult {
+            consumed: result.consumed + rest.consumed,
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len)
+            .then(SlipDecoder::<MAX_LENGTH>::reset_work_bound())
+    }
+
+    /// Returns the frame handed out, or `None` if there is none.
+    #[must_use]
+    pub fn frame(&self) -> Option<&[u8]> {
+        self.handed_out.then(|| self.ready.as_slice())
+    }
+
+    /// Gives the buffer of the frame handed out back to the decoder.
+    ///
+    /// A frame held in the decoder meanwhile is handed out in turn, and decoding resumes.
+    pub fn release(&mut self) {
+        self.handed_out = false;
+        self.swap();
+    }
+
+    /// Returns the worst-case work of `release`.
+    #[must_use]
+    pub const fn release_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::reset_work_bound()
+    }
+
+    /// Resets the decoder to its initial state, dropping the frame in progress and the frame
+    /// handed out.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.handed_out = false;
+    }
+
+    /// Returns where the decoder stands in the byte stream, regardless of the frame handed out.
+    #[must_use]
+    pub fn status(&self) -> DecoderStatus {
+        self.decoder.status()
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+}
+
+/// Default implementation for `PingPongDecoder`.
+impl<const MAX_LENGTH: usize> Default for PingPongDecoder<MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipDecodeError;
+    use crate::pingpong::PingPongDecoder;
+    use crate::slip::DecoderStatus;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_ping_pong() {
+        let mut decoder = PingPongDecoder::<4>::new();
+        let data = [END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, END_CHAR, 0x03];
+
+        let result = decoder.feed(&data);
+
+        assert_eq!(result.consumed, 6);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.frame(), Some([0x01].as_slice()));
+        assert_eq!(decoder.status(), DecoderStatus::FrameReady);
+        assert_eq!(decoder.insert(0x03), Err(SlipDecodeError::FrameCompleted));
+
+        decoder.release();
+
+        assert_eq!(decoder.frame(), Some([0x02].as_slice()));
+        assert_eq!(decoder.status(), DecoderStatus::Idle);
+
+        decoder.release();
+
+        assert_eq!(decoder.frame(), None);
+    }
+
+    #[test]
+    fn test_ping_pong_insert() {
+        let mut decoder = PingPongDecoder::<4>::new();
+
+        for value in [END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02] {
+            assert_eq!(decoder.insert(value), Ok(()));
+        }
+
+        assert_eq!(decoder.frame(), Some([0x01].as_slice()));
+        assert_eq!(decoder.status(), DecoderStatus::InFrame);
+    }
+}
@@ -0,0 +1,245 @@
+use core::cell::Cell;
+use core::cell::UnsafeCell;
+use core::ops::Deref;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::buffer::FrameBuffer;
+use crate::error::SlipDecodeError;
+use crate::slip::FeedResult;
+use crate::slip::SlipBufferDecoder;
+use crate::slip::SlipConfig;
+use crate::slip::SlipStats;
+
+/// A fixed-size pool of `SLOTS` frame buffers of up to `MAX_LENGTH` bytes each.
+///
+/// Buffers are checked out as `PoolBuffer` handles, which return them to the pool once dropped,
+/// so that several frames can be in flight at once, e.g. one being decoded while others are
+/// processed, without any allocation or copy. The pool is meant for a single execution context:
+/// it is neither `Sync` nor are its handles `Send`.
+pub struct FramePool<const SLOTS: usize, const MAX_LENGTH: usize> {
+    slots: [UnsafeCell<Vec<u8, MAX_LENGTH>>; SLOTS],
+    used: [Cell<bool>; SLOTS],
+}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> FramePool<SLOTS, MAX_LENGTH> {
+    /// Creates a pool with every buffer free.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(Vec::new()) }; SLOTS],
+            used: [const { Cell::new(false) }; SLOTS],
+        }
+    }
+
+    /// Checks out a free buffer, emptied.
+    ///
+    /// Returns `None` if every buffer is checked out.
+    pub fn checkout(&self) -> Option<PoolBuffer<'_, MAX_LENGTH>> {
+        let index = self.used.iter().position(|used| !used.get())?;
+        self.used[index].set(true);
+
+        // SAFETY: the slot was free, so that no other handle refers to it until this one is
+        // dropped
+        let data = unsafe { &mut *self.slots[index].get() };
+        data.clear();
+
+        Some(PoolBuffer {
+            data,
+            used: &self.used[index],
+        })
+    }
+
+    /// Returns the number of free buffers.
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.used.iter().filter(|used| !used.get()).count()
+    }
+}
+
+/// Default implementation for `FramePool`.
+impl<const SLOTS: usize, const MAX_LENGTH: usize> Default for FramePool<SLOTS, MAX_LENGTH> {
+    /// Creates a pool with every buffer free.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A buffer checked out of a `FramePool`, returned to it once dropped.
+pub struct PoolBuffer<'a, const MAX_LENGTH: usize> {
+    data: &'a mut Vec<u8, MAX_LENGTH>,
+    used: &'a Cell<bool>,
+}
+
+/// Implementation of `FrameBuffer` for `PoolBuffer`.
+impl<const MAX_LENGTH: usize> FrameBuffer for PoolBuffer<'_, MAX_LENGTH> {
+    /// Appends `value`, unless `MAX_LENGTH` bytes are already held.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        FrameBuffer::push(self.data, value)
+    }
+
+    /// Copies `values` at once, unless they do not all fit.
+    fn extend_from_slice(&mut self, values: &[u8]) -> Result<(), SlipDecodeError> {
+        FrameBuffer::extend_from_slice(self.data, values)
+    }
+
+    /// Removes every byte from the buffer.
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the bytes held by the buffer.
+    fn as_slice(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Returns `MAX_LENGTH`.
+    fn capacity(&self) -> usize {
+        MAX_LENGTH
+    }
+}
+
+/// Implementation of `Deref` for `PoolBuffer`.
+impl<const MAX_LENGTH: usize> Deref for PoolBuffer<'_, MAX_LENGTH> {
+    type Target = [u8];
+
+    /// Dereferences to the bytes held by the buffer.
+    fn deref(&self) -> &Self::Target {
+        self.data.as_slice()
+    }
+}
+
+/// Implementation of `Drop` for `PoolBuffer`.
+impl<const MAX_LENGTH: usize> Drop for PoolBuffer<'_, MAX_LENGTH> {
+    /// Returns the buffer to its pool.
+    fn drop(&mut self) {
+        self.used.set(false);
+    }
+}
+
+/// A SLIP decoder storing every frame in a buffer checked out of a `FramePool`.
+///
+/// Once a frame is complete, its buffer is handed to the consumer as is, and the decoder checks
+/// out another one for the next frame, so that frames are pipelined without being copied. While
+/// every buffer is checked out, incoming frames are dropped as `SlipDecodeError::BufferFull`,
+/// until a consumer drops a buffer.
+pub struct PooledDecoder<'a, const SLOTS: usize, const MAX_LENGTH: usize> {
+    pool: &'a FramePool<SLOTS, MAX_LENGTH>,
+    decoder: SlipBufferDecoder<Option<PoolBuffer<'a, MAX_LENGTH>>>,
+}
+
+impl<'a, const SLOTS: usize, const MAX_LENGTH: usize> PooledDecoder<'a, SLOTS, MAX_LENGTH> {
+    /// Creates a decoder checking buffers out of `pool`, using the default SLIP configuration.
+    #[must_use]
+    pub fn new(pool: &'a FramePool<SLOTS, MAX_LENGTH>) -> Self {
+        Self::with_config(pool, SlipConfig::new())
+    }
+
+    /// Creates a decoder checking buffers out of `pool`, using the framing options of `config`.
+    #[must_use]
+    pub fn with_config(pool: &'a FramePool<SLOTS, MAX_LENGTH>, config: SlipConfig) -> Self {
+        Self {
+            pool,
+            decoder: SlipBufferDecoder::with_buffer(pool.checkout(), config),
+        }
+    }
+
+    /// Feeds the bytes of `data` into the decoder, as `SlipDecoder::feed` does.
+    ///
+    /// A decoder left without a buffer first checks out one freed meanwhile.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        if self.decoder.capacity() == 0
+            && let Some(buffer) = self.pool.checkout()
+        {
+            self.decoder.replace_buffer(Some(buffer));
+        }
+
+        self.decoder.feed(data)
+    }
+
+    /// Takes the buffer holding the completed frame, and resets the decoder for the next frame.
+    ///
+    /// Returns the error of `SlipDecoder::check_frame` if no frame is complete yet, or if the frame
+    /// failed the integrity check. Returns `Err(SlipDecodeError::BufferFull)` for an empty frame
+    /// completed while every buffer was checked out, which is then dropped.
+    pub fn take_frame(&mut self) -> Result<PoolBuffer<'a, MAX_LENGTH>, SlipDecodeError> {
+        self.decoder.check_frame()?;
+
+        self.decoder
+            .replace_buffer(self.pool.checkout())
+            .ok_or(SlipDecodeError::BufferFull)
+    }
+
+    /// Drops the frame in progress, if any.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipDecodeError;
+    use crate::pool::FramePool;
+    use crate::pool::PooledDecoder;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+
+    #[test]
+    fn test_checkout_and_return() {
+        let pool = FramePool::<2, 4>::new();
+
+        let first = pool.checkout().unwrap();
+        let second = pool.checkout().unwrap();
+
+        assert!(pool.checkout().is_none());
+        assert_eq!(pool.available(), 0);
+
+        drop(first);
+
+        assert_eq!(pool.available(), 1);
+        assert!(pool.checkout().unwrap().is_empty());
+
+        drop(second);
+
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    fn test_frames_in_flight() {
+        let pool = FramePool::<2, 4>::new();
+        let mut slip_decoder = PooledDecoder::new(&pool);
+        let data = [END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, 0x03, END_CHAR];
+
+        let result = slip_decoder.feed(&data);
+        assert_eq!(result.status, FeedStatus::Completed);
+        let first = slip_decoder.take_frame().unwrap();
+
+        let result = slip_decoder.feed(&data[result.consumed..]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        let second = slip_decoder.take_frame().unwrap();
+
+        assert_eq!(*first, [0x01]);
+        assert_eq!(*second, [0x02, 0x03]);
+        assert_eq!(pool.available(), 0);
+
+        // The decoder has no buffer left, until a frame is dropped
+        let result = slip_decoder.feed(&[END_CHAR, 0x04, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+
+        drop(first);
+        slip_decoder.reset();
+
+        let result = slip_decoder.feed(&[END_CHAR, 0x05, END_CHAR]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(*slip_decoder.take_frame().unwrap(), [0x05]);
+    }
+}
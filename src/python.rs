@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+
+use pyo3::prelude::Bound;
+use pyo3::prelude::PyModule;
+use pyo3::prelude::PyModuleMethods;
+use pyo3::prelude::PyResult;
+use pyo3::prelude::Python;
+use pyo3::prelude::pyclass;
+use pyo3::prelude::pyfunction;
+use pyo3::prelude::pymethods;
+use pyo3::prelude::pymodule;
+use pyo3::prelude::wrap_pyfunction;
+use pyo3::types::PyBytes;
+
+use crate::slip::FeedStatus;
+use crate::slip::SlipEncoder;
+use crate::slip::SlipVecDecoder;
+
+/// Encodes `payload` as a SLIP frame, using the default SLIP configuration.
+#[pyfunction]
+#[must_use]
+pub fn slip_encode<'py>(py: Python<'py>, payload: &[u8]) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, &SlipEncoder::new().encode_to_vec(payload))
+}
+
+/// A SLIP decoder for Python code, such as a test rig.
+///
+/// This struct wraps a `SlipVecDecoder`, using the default SLIP configuration, so that Python
+/// code decodes frames exactly as the device does: the bytes read from the port are fed as they
+/// arrive, and every frame they complete is returned as `bytes`. Frames rejected on the way, e.g.
+/// for an invalid escape sequence, are dropped and counted by `frames_dropped`.
+#[pyclass(name = "SlipDecoder")]
+pub struct PySlipDecoder {
+    decoder: SlipVecDecoder,
+}
+
+#[pymethods]
+impl PySlipDecoder {
+    /// Creates a decoder of frames of up to `max_frame_len` decoded bytes, if given.
+    #[new]
+    #[pyo3(signature = (max_frame_len = None))]
+    #[must_use]
+    pub const fn new(max_frame_len: Option<usize>) -> Self {
+        let mut decoder = SlipVecDecoder::new();
+        if let Some(max_frame_len) = max_frame_len {
+            decoder.set_max_frame_len(max_frame_len);
+        }
+
+        Self { decoder }
+    }
+
+    /// Feeds the bytes of `data` into the decoder, and returns the frames they complete.
+    ///
+    /// A frame left incomplete by `data` is continued by the next call.
+    pub fn feed<'py>(&mut self, py: Python<'py>, data: &[u8]) -> Vec<Bound<'py, PyBytes>> {
+        let mut frames = Vec::new();
+        self.decode(data, |frame| frames.push(PyBytes::new(py, frame)));
+
+        frames
+    }
+
+    /// Drops the frame in progress, if any.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns the number of frames dropped since the decoder was created.
+    #[getter]
+    #[must_use]
+    pub const fn frames_dropped(&self) -> u32 {
+        self.decoder.stats().frames_dropped
+    }
+}
+
+impl PySlipDecoder {
+    /// Feeds the bytes of `data` into the decoder, handing every frame they complete to
+    /// `on_frame`.
+    fn decode(&mut self, mut data: &[u8], mut on_frame: impl FnMut(&[u8])) {
+        while !data.is_empty() {
+            let result = self.decoder.feed(data);
+            data = data.get(result.consumed..).unwrap_or_default();
+
+            match result.status {
+                FeedStatus::Completed => {
+                    on_frame(self.decoder.get_buffer());
+                    self.decoder.reset();
+                }
+                FeedStatus::Rejected(_) => self.decoder.reset(),
+                FeedStatus::Incomplete => {}
+            }
+        }
+    }
+}
+
+/// Registers `slip_encode` and `SlipDecoder` in the `noalloc_slip_rs` Python module.
+#[pymodule]
+pub fn noalloc_slip_rs(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(slip_encode, module)?)?;
+    module.add_class::<PySlipDecoder>()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::python::PySlipDecoder;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    #[test]
+    fn test_decode() {
+        let mut decoder = PySlipDecoder::new(Some(2));
+        let mut frames = Vec::new();
+        let data = [
+            END_CHAR,
+            0x01,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            0x02,
+            0x03,
+            END_CHAR,
+            0x04,
+        ];
+        decoder.decode(&data, |frame| frames.push(frame.to_vec()));
+
+        assert_eq!(frames, [[0x01, END_CHAR]]);
+        assert_eq!(decoder.frames_dropped(), 1);
+
+        decoder.decode(&[END_CHAR], |frame| frames.push(frame.to_vec()));
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1], [0x04]);
+    }
+}
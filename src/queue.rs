@@ -0,0 +1,239 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+
+/// A lock-free single-producer single-consumer queue of frames.
+///
+/// This struct holds up to `SLOTS` frames of up to `MAX_LENGTH` bytes each, so that an interrupt
+/// handler can push the frames it decodes while the main loop pops them. It only relies on
+/// atomic loads and stores, which every target supports. The slots are left uninitialized until
+/// frames are pushed, so that a queue of large frames takes no time to create. The queue is
+/// split into a `FrameProducer` and a `FrameConsumer`, each usable from its own execution
+/// context.
+pub struct FrameQueue<const SLOTS: usize, const MAX_LENGTH: usize> {
+    slots: [UnsafeCell<Vec<u8, MAX_LENGTH>>; SLOTS],
+    // Index of the next frame to pop, modulo twice the number of slots
+    head: AtomicUsize,
+    // Index of the next frame to push, modulo twice the number of slots
+    tail: AtomicUsize,
+}
+
+// SAFETY: a slot is only written by the producer while it is free, and only read by the
+// consumer while it is occupied. Ownership of a slot changes hands through the release stores
+// and acquire loads of `head` and `tail`, and there is at most one producer and one consumer,
+// as both borrow the queue mutably when it is split.
+unsafe impl<const SLOTS: usize, const MAX_LENGTH: usize> Sync for FrameQueue<SLOTS, MAX_LENGTH> {}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> FrameQueue<SLOTS, MAX_LENGTH> {
+    /// Creates an empty queue.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(Vec::new()) }; SLOTS],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the queue into its producer and consumer halves.
+    pub const fn split(
+        &mut self,
+    ) -> (
+        FrameProducer<'_, SLOTS, MAX_LENGTH>,
+        FrameConsumer<'_, SLOTS, MAX_LENGTH>,
+    ) {
+        let queue = &*self;
+
+        (FrameProducer { queue }, FrameConsumer { queue })
+    }
+
+    /// Returns the number of frames in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        // Loading the head first ensures it is never ahead of the tail
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        Self::distance(head, tail)
+    }
+
+    /// Returns the number of frames between the indices `head` and `tail`.
+    ///
+    /// Indices run modulo twice the number of slots, which tells a full queue from an empty one
+    /// without ever wrapping around an integer.
+    const fn distance(head: usize, tail: usize) -> usize {
+        if SLOTS == 0 {
+            return 0;
+        }
+
+        (tail + 2 * SLOTS - head) % (2 * SLOTS)
+    }
+
+    /// Returns the index following `index`.
+    const fn next(index: usize) -> usize {
+        (index + 1) % (2 * SLOTS)
+    }
+
+    /// Returns `true` if the queue holds no frame.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the slot of the frame at `index`.
+    const fn slot(&self, index: usize) -> *mut Vec<u8, MAX_LENGTH> {
+        self.slots[index % SLOTS].get()
+    }
+}
+
+/// Default implementation for `FrameQueue`.
+impl<const SLOTS: usize, const MAX_LENGTH: usize> Default for FrameQueue<SLOTS, MAX_LENGTH> {
+    /// Creates an empty queue.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The producer half of a `FrameQueue`.
+pub struct FrameProducer<'a, const SLOTS: usize, const MAX_LENGTH: usize> {
+    queue: &'a FrameQueue<SLOTS, MAX_LENGTH>,
+}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> FrameProducer<'_, SLOTS, MAX_LENGTH> {
+    /// Copies `frame` into the next free slot of the queue.
+    ///
+    /// Returns `Err(SlipDecodeError::BufferFull)` if the queue is full, or if `frame` is longer
+    /// than `MAX_LENGTH`. The frame is then dropped, and the queue left untouched.
+    pub fn push(&mut self, frame: &[u8]) -> Result<(), SlipDecodeError> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if FrameQueue::<SLOTS, MAX_LENGTH>::distance(head, tail) >= SLOTS
+            || frame.len() > MAX_LENGTH
+        {
+            return Err(SlipDecodeError::BufferFull);
+        }
+
+        // SAFETY: the slot is free, so the consumer does not access it until the store of the
+        // new tail below
+        let slot = unsafe { &mut *self.queue.slot(tail) };
+        slot.clear();
+        let _ = slot.write_slice(0, frame);
+
+        self.queue.tail.store(
+            FrameQueue::<SLOTS, MAX_LENGTH>::next(tail),
+            Ordering::Release,
+        );
+
+        Ok(())
+    }
+
+    /// Returns `true` if the queue has no free slot.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= SLOTS
+    }
+}
+
+/// The consumer half of a `FrameQueue`.
+pub struct FrameConsumer<'a, const SLOTS: usize, const MAX_LENGTH: usize> {
+    queue: &'a FrameQueue<SLOTS, MAX_LENGTH>,
+}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> FrameConsumer<'_, SLOTS, MAX_LENGTH> {
+    /// Calls `f` with the oldest frame of the queue, then frees its slot.
+    ///
+    /// Returns the result of `f`, or `None` without calling it if the queue is empty. The frame
+    /// is borrowed straight from its slot, without being copied.
+    pub fn pop_with<R>(&mut self, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        // SAFETY: the slot is occupied, so the producer does not access it until the store of
+        // the new head below
+        let slot = unsafe { &*self.queue.slot(head) };
+        let result = f(slot.as_slice());
+
+        self.queue.head.store(
+            FrameQueue::<SLOTS, MAX_LENGTH>::next(head),
+            Ordering::Release,
+        );
+
+        Some(result)
+    }
+
+    /// Removes the oldest frame of the queue and returns a copy of it.
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn pop(&mut self) -> Option<Vec<u8, MAX_LENGTH>> {
+        self.pop_with(|frame| frame.iter().copied().collect())
+    }
+
+    /// Returns `true` if the queue holds no frame.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipDecodeError;
+    use crate::queue::FrameQueue;
+    use crate::slip::END_CHAR;
+    use crate::slip::SlipDecoder;
+
+    #[test]
+    fn test_push_pop() {
+        let mut queue = FrameQueue::<2, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        assert_eq!(consumer.pop(), None);
+
+        producer.push(&[0x01]).unwrap();
+        producer.push(&[0x02, 0x03]).unwrap();
+
+        assert!(producer.is_full());
+        assert_eq!(producer.push(&[0x04]), Err(SlipDecodeError::BufferFull));
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x01]);
+
+        producer.push(&[0x04]).unwrap();
+
+        assert_eq!(consumer.pop_with(<[u8]>::len), Some(2));
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x04]);
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_push_too_long() {
+        let mut queue = FrameQueue::<2, 1>::new();
+        let (mut producer, consumer) = queue.split();
+
+        assert_eq!(
+            producer.push(&[0x01, 0x02]),
+            Err(SlipDecodeError::BufferFull)
+        );
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_push_from_decoder() {
+        let mut queue = FrameQueue::<2, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut decoder = SlipDecoder::<4>::default();
+
+        decoder.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+        decoder
+            .take_frame_with(|frame| producer.push(frame))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x01, 0x02]);
+    }
+}
@@ -0,0 +1,75 @@
+//! Word-at-a-time scanning for the special bytes of a frame.
+//!
+//! Payloads are mostly made of bytes which are neither delimiters nor escape bytes, so the bulk
+//! encoding and decoding paths look for the next special byte a machine word at a time, then copy
+//! the run of ordinary bytes before it with a single `copy_from_slice`.
+
+/// Number of bytes tested at once.
+const WORD_LENGTH: usize = size_of::<usize>();
+
+/// A word holding `0x01` in every byte.
+const LOW_BITS: usize = usize::from_ne_bytes([0x01; WORD_LENGTH]);
+
+/// A word holding `0x80` in every byte.
+const HIGH_BITS: usize = usize::from_ne_bytes([0x80; WORD_LENGTH]);
+
+/// Returns `true` if a byte of `word` is zero.
+const fn has_zero_byte(word: usize) -> bool {
+    word.wrapping_sub(LOW_BITS) & !word & HIGH_BITS != 0
+}
+
+/// Returns the index of the first byte of `data` equal to `first` or `second`, or the length of
+/// `data` if there is none.
+pub fn find_special(data: &[u8], first: u8, second: u8) -> usize {
+    let first_mask = usize::from_ne_bytes([first; WORD_LENGTH]);
+    let second_mask = usize::from_ne_bytes([second; WORD_LENGTH]);
+
+    let mut index = 0;
+    for chunk in data.chunks_exact(WORD_LENGTH) {
+        let Ok(bytes) = <[u8; WORD_LENGTH]>::try_from(chunk) else {
+            break;
+        };
+        let word = usize::from_ne_bytes(bytes);
+
+        if has_zero_byte(word ^ first_mask) || has_zero_byte(word ^ second_mask) {
+            break;
+        }
+        index += WORD_LENGTH;
+    }
+
+    // Locate the match within its word, or scan the bytes left over after the last word
+    data.get(index..)
+        .unwrap_or_default()
+        .iter()
+        .position(|&value| value == first || value == second)
+        .map_or(data.len(), |position| index + position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scan::find_special;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+
+    #[test]
+    fn test_find_special() {
+        let mut data = [0x00; 37];
+
+        assert_eq!(find_special(&data, END_CHAR, ESC_CHAR), data.len());
+        assert_eq!(find_special(&[], END_CHAR, ESC_CHAR), 0);
+
+        for index in (0..data.len()).rev() {
+            data[index] = if index % 2 == 0 { END_CHAR } else { ESC_CHAR };
+
+            assert_eq!(find_special(&data, END_CHAR, ESC_CHAR), index);
+        }
+    }
+
+    #[test]
+    fn test_find_special_with_close_bytes() {
+        // Bytes one bit away from the special ones must not match
+        let data = [0xC1, 0xDA, 0x40, 0x5B, 0xC0 ^ 0x80, 0xDC, 0xBF, 0xDB];
+
+        assert_eq!(find_special(&data, END_CHAR, ESC_CHAR), 7);
+    }
+}
@@ -0,0 +1,124 @@
+use std::boxed::Box;
+use std::io;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use serialport::SerialPort;
+
+use crate::error::SlipIoError;
+use crate::io_std::SlipTransport;
+use crate::slip::SlipConfig;
+
+/// A SLIP link over a serial port opened with the `serialport` crate, for host-side tools.
+///
+/// This struct wraps the port in a `SlipTransport`, receiving frames of up to `MAX_LENGTH`
+/// bytes, and maps the timeouts of the port to `SlipIoError::Timeout`. The timeout of a receive
+/// bounds the silence of the port, not the duration of the whole frame: it elapses once no byte
+/// arrived for that long.
+pub struct SerialLink<const MAX_LENGTH: usize> {
+    transport: SlipTransport<Box<dyn SerialPort>, MAX_LENGTH>,
+}
+
+impl<const MAX_LENGTH: usize> SerialLink<MAX_LENGTH> {
+    /// Opens the serial port at `path` with the given baud rate, using the default SLIP
+    /// configuration.
+    pub fn open(path: &str, baud_rate: u32) -> serialport::Result<Self> {
+        Ok(Self::new(serialport::new(path, baud_rate).open()?))
+    }
+
+    /// Creates a link over `port`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(port: Box<dyn SerialPort>) -> Self {
+        Self::with_config(port, SlipConfig::new())
+    }
+
+    /// Creates a link over `port`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(port: Box<dyn SerialPort>, config: SlipConfig) -> Self {
+        Self {
+            transport: SlipTransport::with_config(port, config),
+        }
+    }
+
+    /// Writes `payload` as a complete frame, then flushes the port.
+    pub fn send_frame(&mut self, payload: &[u8]) -> Result<(), SlipIoError<io::Error>> {
+        self.transport.send(payload).map_err(SlipIoError::Io)
+    }
+
+    /// Reads the next frame and copies its payload into `buf`, waiting at most `timeout` for
+    /// each byte.
+    ///
+    /// Returns the length of the payload, or `Err(SlipIoError::Timeout)` if the port stayed
+    /// silent for `timeout`. The bytes of a partial frame then stay pending, to be completed by
+    /// the next call. On a decode error, the frame is dropped and the next call starts over with
+    /// the following bytes.
+    pub fn recv_frame(
+        &mut self,
+        buf: &mut [u8],
+        timeout: Duration,
+    ) -> Result<usize, SlipIoError<io::Error>> {
+        self.transport
+            .get_mut()
+            .set_timeout(timeout)
+            .map_err(|error| SlipIoError::Io(error.into()))?;
+
+        match self.transport.recv(buf) {
+            Err(SlipIoError::Io(error)) if error.kind() == ErrorKind::TimedOut => {
+                Err(SlipIoError::Timeout)
+            }
+            result => result,
+        }
+    }
+
+    /// Returns a reference to the serial port.
+    #[must_use]
+    pub const fn get_ref(&self) -> &dyn SerialPort {
+        &**self.transport.get_ref()
+    }
+
+    /// Returns a mutable reference to the serial port, e.g. to change its baud rate.
+    #[must_use]
+    pub fn get_mut(&mut self) -> &mut dyn SerialPort {
+        &mut **self.transport.get_mut()
+    }
+
+    /// Consumes the link, returning the serial port.
+    ///
+    /// Bytes read from the port but not decoded yet are lost.
+    #[must_use]
+    pub fn into_inner(self) -> Box<dyn SerialPort> {
+        self.transport.into_inner()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::boxed::Box;
+    use std::time::Duration;
+
+    use serialport::TTYPort;
+
+    use crate::error::SlipIoError;
+    use crate::serial::SerialLink;
+    use crate::slip::END_CHAR;
+
+    #[test]
+    fn test_send_and_recv() {
+        let (first, second) = TTYPort::pair().unwrap();
+        let mut first = SerialLink::<8>::new(Box::new(first));
+        let mut second = SerialLink::<8>::new(Box::new(second));
+        let mut buf = [0x00; 8];
+
+        first.send_frame(&[0x01, END_CHAR, 0x02]).unwrap();
+
+        assert!(matches!(
+            second.recv_frame(&mut buf, Duration::from_secs(1)),
+            Ok(3)
+        ));
+        assert_eq!(buf[..3], [0x01, END_CHAR, 0x02]);
+        assert!(matches!(
+            second.recv_frame(&mut buf, Duration::from_millis(10)),
+            Err(SlipIoError::Timeout)
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::slip::DecoderStatus;
+use crate::slip::FeedResult;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A SLIP decoder shared between an interrupt handler and a task.
+///
+/// Every method takes `&self` and runs within a critical section of the `critical-section` crate,
+/// so that the decoder can sit in a `static`: the interrupt handler inserts the received bytes,
+/// while a task reads out completed frames. No method ever blocks or waits for the other side;
+/// each one only holds the critical section for its own, bounded, work.
+///
+/// Once a frame is complete, further bytes are rejected with `SlipDecodeError::FrameCompleted`
+/// until the frame is taken, so the task must drain frames faster than they arrive. Links with
+/// bursts of frames should queue them, e.g. in a `FrameQueue`, from the interrupt handler instead.
+pub struct SharedSlipDecoder<const MAX_LENGTH: usize> {
+    decoder: Mutex<RefCell<SlipDecoder<MAX_LENGTH>>>,
+}
+
+impl<const MAX_LENGTH: usize> SharedSlipDecoder<MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: Mutex::new(RefCell::new(SlipDecoder::with_config(config))),
+        }
+    }
+
+    /// Runs `f` on the decoder within a critical section.
+    fn with<R>(&self, f: impl FnOnce(&mut SlipDecoder<MAX_LENGTH>) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.decoder.borrow_ref_mut(cs)))
+    }
+
+    /// Feeds `value` into the decoder state machine, as `SlipDecoder::insert` does.
+    ///
+    /// This is meant to be called from the receive interrupt handler.
+    pub fn insert(&self, value: u8) -> Result<(), SlipDecodeError> {
+        self.with(|decoder| decoder.insert(value))
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound()
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine, as `SlipDecoder::feed` does.
+    ///
+    /// The critical section lasts for the whole of `data`, so DMA or FIFO chunks should be kept
+    /// short.
+    pub fn feed(&self, data: &[u8]) -> FeedResult {
+        self.with(|decoder| decoder.feed(data))
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len)
+    }
+
+    /// Feeds the bytes of `first` then `second` into the decoder state machine, as
+    /// `SlipDecoder::feed_split` does.
+    ///
+    /// This is meant for the two regions of a wrapped-around DMA ring buffer, which are fed
+    /// within a single critical section, without being copied into a linear buffer first.
+    pub fn feed_split(&self, first: &[u8], second: &[u8]) -> FeedResult {
+        self.with(|decoder| decoder.feed_split(first, second))
+    }
+
+    /// Returns the worst-case work of `feed_split` for `data_len` bytes across both slices.
+    #[must_use]
+    pub const fn feed_split_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_split_work_bound(data_len)
+    }
+
+    /// Returns a copy of the completed frame and resets the decoder for the next frame.
+    ///
+    /// Returns the error of `SlipDecoder::take_frame` if no frame can be handed over.
+    pub fn take_frame(&self) -> Result<Vec<u8, MAX_LENGTH>, SlipDecodeError> {
+        self.with(SlipDecoder::take_frame)
+    }
+
+    /// Returns the worst-case work of `take_frame`.
+    #[must_use]
+    pub const fn take_frame_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::take_frame_work_bound()
+    }
+
+    /// Calls `f` with the completed frame, then resets the decoder for the next frame.
+    ///
+    /// Returns the result of `f`, or the error of `SlipDecoder::take_frame_with` without calling
+    /// it if no frame can be handed over. `f` runs within the critical section, so it should only
+    /// copy or inspect the frame.
+    pub fn take_frame_with<R>(&self, f: impl FnOnce(&[u8]) -> R) -> Result<R, SlipDecodeError> {
+        self.with(|decoder| decoder.take_frame_with(f))
+    }
+
+    /// Copies the completed frame into `out`, then resets the decoder for the next frame.
+    ///
+    /// Returns the length of the frame, or the error of `SlipDecoder::read_frame_into`.
+    pub fn read_frame_into(&self, out: &mut [u8]) -> Result<usize, SlipDecodeError> {
+        self.with(|decoder| decoder.read_frame_into(out))
+    }
+
+    /// Returns the worst-case work of `read_frame_into`.
+    #[must_use]
+    pub const fn read_frame_into_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::read_frame_into_work_bound()
+    }
+
+    /// Resets the decoder to its initial state, dropping the current frame.
+    pub fn reset(&self) {
+        self.with(SlipDecoder::reset);
+    }
+
+    /// Returns where the decoder stands in the byte stream.
+    #[must_use]
+    pub fn status(&self) -> DecoderStatus {
+        self.with(|decoder| decoder.status())
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub fn stats(&self) -> SlipStats {
+        self.with(|decoder| decoder.stats())
+    }
+}
+
+/// Default implementation for `SharedSlipDecoder`.
+impl<const MAX_LENGTH: usize> Default for SharedSlipDecoder<MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::thread;
+
+    use crate::error::SlipDecodeError;
+    use crate::shared::SharedSlipDecoder;
+    use crate::slip::DecoderStatus;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+
+    static DECODER: SharedSlipDecoder<4> = SharedSlipDecoder::new();
+
+    #[test]
+    fn test_shared_decoder() {
+        thread::spawn(|| {
+            for value in [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR] {
+                DECODER.insert(value).unwrap();
+            }
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(DECODER.status(), DecoderStatus::FrameReady);
+        assert_eq!(DECODER.insert(0x02), Err(SlipDecodeError::FrameCompleted));
+        assert_eq!(DECODER.take_frame().unwrap().as_slice(), [0x01, END_CHAR]);
+        assert_eq!(DECODER.status(), DecoderStatus::Idle);
+        assert_eq!(DECODER.stats().frames_completed, 1);
+
+        let result = DECODER.feed_split(&[END_CHAR, 0x03], &[END_CHAR, 0x04]);
+
+        assert_eq!(result.consumed, 3);
+        assert_eq!(DECODER.take_frame().unwrap().as_slice(), [0x03]);
+    }
+}
@@ -1,7 +1,44 @@
+use core::any::type_name;
+use core::fmt;
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::iter::Copied;
+use core::iter::FusedIterator;
 use core::ops::Deref;
+use core::ops::Range;
+use core::slice;
 
+#[cfg(feature = "bbqueue")]
+use bbqueue::prod_cons::stream::StreamGrantR;
+#[cfg(feature = "bbqueue")]
+use bbqueue::prod_cons::stream::StreamGrantW;
+#[cfg(feature = "bbqueue")]
+use bbqueue::traits::bbqhdl::BbqHandle;
+#[cfg(feature = "bytes")]
+use bytes::Buf;
+#[cfg(feature = "bytes")]
+use bytes::BufMut;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+#[cfg(feature = "bytes")]
+use bytes::BytesMut;
 use noalloc_vec_rs::vec::Vec;
 
+use crate::buffer::CountingBuffer;
+use crate::buffer::FrameBuffer;
+use crate::buffer::SliceBuffer;
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::observer::SlipObserver;
+use crate::observer::observe_feed;
+use crate::scan::find_special;
+use crate::stuffing::ByteStuffingCodec;
+use crate::stuffing::Substitution;
+use crate::stuffing::set;
+use crate::validate::AcceptAll;
+use crate::validate::FrameValidator;
+use crate::wcet::WorkBound;
+
 /// Marks the start and end of a SLIP frame.
 pub const END_CHAR: u8 = 0xC0;
 
@@ -14,51 +51,1118 @@ pub const ESC_END_CHAR: u8 = 0xDC;
 /// Escaped representation of `ESC_CHAR` inside a frame.
 pub const ESC_ESC_CHAR: u8 = 0xDD;
 
+/// Control byte resuming the transmission on links using software flow control.
+pub const XON: u8 = 0x11;
+
+/// Control byte pausing the transmission on links using software flow control.
+pub const XOFF: u8 = 0x13;
+
+/// Bitmap of the control bytes to escape on links using software flow control, for
+/// `SlipConfig::with_escaped_controls`.
+pub const XON_XOFF_CONTROLS: u32 = (1 << XON) | (1 << XOFF);
+
+/// Returns the largest number of bytes a payload of `payload_len` bytes occupies once encoded.
+///
+/// This holds for any configuration: every payload byte is escaped, and the frame is opened and
+/// terminated. It is meant to size static buffers, as in `[u8; max_encoded_len(64)]`.
+#[must_use]
+pub const fn max_encoded_len(payload_len: usize) -> usize {
+    payload_len.saturating_mul(2).saturating_add(2)
+}
+
+/// MTU of SLIP links as set by RFC 1055, in payload bytes, after the Berkeley UNIX
+/// implementation and as still enforced by `slattach` peers.
+pub const RFC1055_MTU: usize = 1006;
+
+/// MTU commonly used on slow SLIP links for interactive traffic, in payload bytes, keeping a
+/// frame short enough not to delay a keystroke behind a bulk transfer.
+pub const LOW_DELAY_MTU: usize = 296;
+
+/// Minimum MTU of IPv4, in payload bytes, that any IPv4 host accepts to reassemble.
+pub const IPV4_MIN_MTU: usize = 576;
+
+/// Minimum MTU of IPv6, in payload bytes, that any IPv6 link must carry.
+pub const IPV6_MIN_MTU: usize = 1280;
+
+/// Canary value held by the integrity guard of a decoder.
+const INTEGRITY_CANARY: u32 = 0x5A1F_C0DE;
+
+/// How a decoder handles a frame without any payload byte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EmptyFramePolicy {
+    /// Empty frames complete the buffer, like any other frame.
+    #[default]
+    Report,
+    /// Empty frames are skipped.
+    ///
+    /// Many senders open and terminate every frame with a terminator, so that back-to-back
+    /// frames are separated by an empty one. A terminator closing an empty frame then opens the
+    /// next frame instead of completing the buffer.
+    Skip,
+}
+
+/// How a decoder handles `ESC_CHAR` followed by a byte other than an escaped value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EscapePolicy {
+    /// The byte is rejected as `SlipDecodeError::InvalidEscape`.
+    #[default]
+    Strict,
+    /// The byte is kept as is, as the reference implementation of RFC 1055 does.
+    Lenient,
+    /// Both `ESC_CHAR` and the byte are kept, as RFC 1055 suggests to leave protocol violations in
+    /// the packet, for peers relying on this behavior.
+    PassThrough,
+}
+
+/// How a decoder handles a frame longer than its buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OverflowPolicy {
+    /// The first byte that does not fit is rejected as `SlipDecodeError::BufferFull`.
+    #[default]
+    Reject,
+    /// The bytes that do not fit are dropped, and the frame completes truncated.
+    ///
+    /// The decoder flags the frame, see `SlipDecoder::is_truncated`.
+    Truncate,
+    /// The whole frame is dropped without reporting an error, and the rest of it is skipped up to
+    /// its terminator.
+    Discard,
+}
+
+/// How a decoder handles bytes received outside a frame, while waiting for its opening
+/// terminator.
+///
+/// Such bytes are always counted in `SlipStats::bytes_discarded`, for link-quality monitoring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StrayBytePolicy {
+    /// The bytes are skipped.
+    #[default]
+    Skip,
+    /// Each byte is skipped, but also rejected as `SlipDecodeError::UnexpectedByte`, which
+    /// reveals line noise or a peer speaking another protocol. The next frame is not affected.
+    Reject,
+}
+
+/// Framing options shared by the SLIP encoder and decoder.
+///
+/// The default configuration follows the usual SLIP dialect: frames are terminated by
+/// `END_CHAR` and also opened by one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlipConfig {
+    end: u8,
+    leading_end: bool,
+    integrity_check: bool,
+    resync: bool,
+    empty_frames: EmptyFramePolicy,
+    escapes: EscapePolicy,
+    overflow: OverflowPolicy,
+    stray_bytes: StrayBytePolicy,
+    mtu: Option<usize>,
+    controls: u32,
+}
+
+impl SlipConfig {
+    /// Creates the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            end: END_CHAR,
+            leading_end: true,
+            integrity_check: false,
+            resync: false,
+            empty_frames: EmptyFramePolicy::Report,
+            escapes: EscapePolicy::Strict,
+            overflow: OverflowPolicy::Reject,
+            stray_bytes: StrayBytePolicy::Skip,
+            mtu: None,
+            controls: 0,
+        }
+    }
+
+    /// Creates the configuration of RFC 1055: the default SLIP configuration, with frames limited
+    /// to `RFC1055_MTU` payload bytes.
+    #[must_use]
+    pub const fn rfc1055() -> Self {
+        Self::new().with_mtu(Some(RFC1055_MTU))
+    }
+
+    /// Sets the byte used as frame terminator in place of `END_CHAR`.
+    ///
+    /// The terminator is still escaped as `ESC_CHAR, ESC_END_CHAR` inside a frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is `ESC_CHAR`, or one of the bytes following it in an escape sequence,
+    /// escaped control bytes included.
+    #[must_use]
+    pub const fn with_end_char(mut self, end: u8) -> Self {
+        assert!(end != ESC_CHAR, "the frame terminator cannot be ESC_CHAR");
+        assert!(
+            self.codec().restore(end).is_none(),
+            "the frame terminator cannot be part of an escape sequence"
+        );
+
+        self.end = end;
+        self
+    }
+
+    /// Sets whether frames are opened by a terminator byte.
+    ///
+    /// When disabled, the encoder only terminates frames and the decoder starts accumulating
+    /// payload bytes without waiting for an opening delimiter.
+    #[must_use]
+    pub const fn with_leading_end(mut self, leading_end: bool) -> Self {
+        self.leading_end = leading_end;
+        self
+    }
+
+    /// Sets whether the decoder guards its buffer against memory corruption.
+    ///
+    /// When enabled, the decoder maintains a running checksum of its buffer alongside a canary
+    /// value, both verified by `SlipDecoder::get_verified_buffer`. This turns silent RAM
+    /// corruption into a detectable error at the cost of a few operations per decoded byte.
+    #[must_use]
+    pub const fn with_integrity_check(mut self, integrity_check: bool) -> Self {
+        self.integrity_check = integrity_check;
+        self
+    }
+
+    /// Sets whether the decoder recovers on its own from a corrupted frame.
+    ///
+    /// When enabled, a byte rejected by the decoder still yields its error, but the frame is
+    /// discarded and the rest of it is skipped up to its terminator instead of waiting for a
+    /// reset, so that the next frame is decoded whether it is opened by a terminator of its own
+    /// or not. A completed frame is never discarded, and must still be consumed before the next
+    /// one is decoded.
+    #[must_use]
+    pub const fn with_resync(mut self, resync: bool) -> Self {
+        self.resync = resync;
+        self
+    }
+
+    /// Sets how the decoder handles empty frames.
+    #[must_use]
+    pub const fn with_empty_frames(mut self, empty_frames: EmptyFramePolicy) -> Self {
+        self.empty_frames = empty_frames;
+        self
+    }
+
+    /// Sets how the decoder handles invalid escape sequences.
+    #[must_use]
+    pub const fn with_escapes(mut self, escapes: EscapePolicy) -> Self {
+        self.escapes = escapes;
+        self
+    }
+
+    /// Sets how the decoder handles frames longer than its buffer.
+    #[must_use]
+    pub const fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets how the decoder handles bytes received outside a frame.
+    #[must_use]
+    pub const fn with_stray_bytes(mut self, stray_bytes: StrayBytePolicy) -> Self {
+        self.stray_bytes = stray_bytes;
+        self
+    }
+
+    /// Sets the MTU of the link, in payload bytes, or lifts it with `None`.
+    ///
+    /// Frames with more payload bytes are dropped by the decoder with
+    /// `SlipDecodeError::MtuExceeded`, whatever the capacity of its buffer and the overflow
+    /// policy, and counted in `SlipStats::frames_oversized`. This matches peers enforcing a fixed
+    /// MTU, such as `RFC1055_MTU` for `slattach`.
+    #[must_use]
+    pub const fn with_mtu(mut self, mtu: Option<usize>) -> Self {
+        self.mtu = mtu;
+        self
+    }
+
+    /// Sets the control bytes, from `0x00` to `0x1F`, escaped inside a frame on top of the
+    /// terminator and `ESC_CHAR`, e.g. `XON_XOFF_CONTROLS` for links using software flow control.
+    ///
+    /// `controls` is a bitmap, where bit `n` stands for byte `n`. An escaped control byte is sent
+    /// as `ESC_CHAR` followed by the control byte XOR-ed with `0x20`, so that it never shows on the
+    /// wire, and the decoder restores it. A decoder still takes a control byte received as is as a
+    /// payload byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame terminator is a substituted control byte.
+    #[must_use]
+    pub const fn with_escaped_controls(mut self, controls: u32) -> Self {
+        let _ = self.codec().with_escaped_controls(controls);
+
+        self.controls = controls;
+        self
+    }
+
+    /// Returns the byte used as frame terminator.
+    #[must_use]
+    pub const fn end_char(&self) -> u8 {
+        self.end
+    }
+
+    /// Returns `true` if frames are opened by a terminator byte.
+    #[must_use]
+    pub const fn leading_end(&self) -> bool {
+        self.leading_end
+    }
+
+    /// Returns `true` if the decoder guards its buffer against memory corruption.
+    #[must_use]
+    pub const fn integrity_check(&self) -> bool {
+        self.integrity_check
+    }
+
+    /// Returns `true` if the decoder recovers on its own from a corrupted frame.
+    #[must_use]
+    pub const fn resync(&self) -> bool {
+        self.resync
+    }
+
+    /// Returns how the decoder handles empty frames.
+    #[must_use]
+    pub const fn empty_frames(&self) -> EmptyFramePolicy {
+        self.empty_frames
+    }
+
+    /// Returns how the decoder handles invalid escape sequences.
+    #[must_use]
+    pub const fn escapes(&self) -> EscapePolicy {
+        self.escapes
+    }
+
+    /// Returns how the decoder handles frames longer than its buffer.
+    #[must_use]
+    pub const fn overflow(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Returns how the decoder handles bytes received outside a frame.
+    #[must_use]
+    pub const fn stray_bytes(&self) -> StrayBytePolicy {
+        self.stray_bytes
+    }
+
+    /// Returns the bitmap of the control bytes escaped inside a frame, as set by
+    /// `with_escaped_controls`.
+    #[must_use]
+    pub const fn escaped_controls(&self) -> u32 {
+        self.controls
+    }
+
+    /// Returns the MTU of the link, in payload bytes, if any.
+    #[must_use]
+    pub const fn mtu(&self) -> Option<usize> {
+        self.mtu
+    }
+
+    /// Restores the byte following `ESC_CHAR` according to the escape policy.
+    const fn restore(self, value: u8) -> Result<u8, SlipDecodeError> {
+        match (self.codec().restore(value), self.escapes) {
+            (Some(value), _) => Ok(value),
+            (None, EscapePolicy::Lenient | EscapePolicy::PassThrough) => Ok(value),
+            (None, EscapePolicy::Strict) => Err(SlipDecodeError::InvalidEscape(value)),
+        }
+    }
+
+    /// Returns `true` if `ESC_CHAR` is kept in front of `value` according to the escape policy.
+    const fn passes_through(self, value: u8) -> bool {
+        matches!(self.escapes, EscapePolicy::PassThrough) && self.codec().restore(value).is_none()
+    }
+
+    /// Returns the byte-stuffing rules of this configuration.
+    #[must_use]
+    pub const fn codec(&self) -> ByteStuffingCodec {
+        // The terminator is checked by `with_end_char`
+        ByteStuffingCodec::new_unchecked(
+            self.end,
+            ESC_CHAR,
+            Substitution::Table {
+                delimiter: ESC_END_CHAR,
+                escape: ESC_ESC_CHAR,
+            },
+        )
+        .with_leading_delimiter(self.leading_end)
+        .with_escaped_controls_unchecked(self.controls)
+    }
+}
+
+/// Default implementation for `SlipConfig`.
+impl Default for SlipConfig {
+    /// Creates the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A SLIP encoder.
 ///
 /// This struct provides a method to encode a packet using the SLIP protocol.
-pub struct SlipEncoder;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SlipEncoder {
+    config: SlipConfig,
+}
 
 impl SlipEncoder {
+    /// Creates an encoder using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates an encoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the number of bytes `payload` occupies once encoded as a SLIP frame, terminators
+    /// included.
+    ///
+    /// This tells whether a frame fits in a buffer before committing to an encode.
+    #[must_use]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encoded_len(&self, payload: &[u8]) -> usize {
+        self.config.codec().encoded_len(payload)
+    }
+
+    /// Returns the worst-case work of `encoded_len` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn encoded_len_work_bound(payload_len: usize) -> WorkBound {
+        WorkBound::linear(payload_len)
+    }
+
+    /// Returns the number of bytes `payload` occupies once encoded, like `encoded_len`, but in a
+    /// const context, to size the array of `encode_array`.
+    #[must_use]
+    pub const fn const_encoded_len(&self, payload: &[u8]) -> usize {
+        self.config.codec().const_encoded_len(payload)
+    }
+
+    /// Encodes `payload` as a SLIP frame into an array, in a const context.
+    ///
+    /// This pre-encodes the fixed frames of a protocol at compile time, such as handshakes and
+    /// keepalives, so that they are stored in flash rather than encoded at every boot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not the encoded length of `payload`, as returned by `const_encoded_len`,
+    /// which fails the build when evaluated in a const context.
+    #[must_use]
+    pub const fn encode_array<const N: usize>(&self, payload: &[u8]) -> [u8; N] {
+        self.config.codec().encode_array(payload)
+    }
+
+    /// Returns the worst-case work of `encode_array` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn encode_array_work_bound(payload_len: usize) -> WorkBound {
+        // One pass to check the encoded length, one pass to write the frame
+        WorkBound::linear(payload_len).then(WorkBound::linear(max_encoded_len(payload_len)))
+    }
+
     /// Encodes `vec` in place as a SLIP frame.
     ///
-    /// Returns `Ok(())` on success, or `Err(())` if `vec` lacks capacity for the framing overhead.
-    #[allow(clippy::result_unit_err)]
-    pub fn encode<const MAX_LENGTH: usize>(vec: &mut Vec<u8, MAX_LENGTH>) -> Result<(), ()> {
-        // Begin the SLIP frame
-        vec.insert(0, END_CHAR).map_err(|_| ())?;
-
-        let mut index = 1;
-        while index < vec.len() {
-            match vec[index] {
-                END_CHAR => {
-                    vec.insert(index, ESC_CHAR).map_err(|_| ())?;
-                    vec.write(index + 1, ESC_END_CHAR).map_err(|_| ())?;
-                    index += 2;
-                }
-                ESC_CHAR => {
-                    vec.insert(index, ESC_CHAR).map_err(|_| ())?;
-                    vec.write(index + 1, ESC_ESC_CHAR).map_err(|_| ())?;
-                    index += 2;
-                }
-                _ => {
-                    index += 1;
-                }
-            }
+    /// The encoded length is computed first, then the frame is filled back-to-front in a single
+    /// pass, so that each byte is moved at most once.
+    ///
+    /// Returns `Ok(())` on success, or `Err(SlipEncodeError::InsufficientCapacity)` if `vec`
+    /// lacks capacity for the framing overhead, with the capacity the frame requires. `vec` is
+    /// left untouched on error.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        self.config.codec().encode(vec)
+    }
+
+    /// Encodes `vec` in place as a SLIP frame, like `encode` does for the crate's own `Vec`.
+    ///
+    /// Returns `Ok(())` on success, or `Err(SlipEncodeError::InsufficientCapacity)` if `vec`
+    /// lacks capacity for the framing overhead, with the capacity the frame requires. `vec` is
+    /// left untouched on error.
+    #[cfg(feature = "heapless")]
+    pub fn encode_heapless<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut heapless::Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        self.config.codec().encode_heapless(vec)
+    }
+
+    /// Encodes `input` as a SLIP frame into `output`.
+    ///
+    /// Returns the number of bytes written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` is too small to hold the frame,
+    /// with the length of the frame. Nothing is written to `output` on error.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode_to_slice(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, SlipEncodeError> {
+        self.config.codec().encode_to_slice(input, output)
+    }
+
+    /// Returns the worst-case work of `encode_to_slice` for an input of `input_len` bytes.
+    #[must_use]
+    pub const fn encode_to_slice_work_bound(input_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one pass to write the frame
+        WorkBound::linear(input_len).then(WorkBound::linear(max_encoded_len(input_len)))
+    }
+
+    /// Encodes each packet of `packets` as a SLIP frame, back to back into `output`, e.g. to send
+    /// a burst of packets with a single DMA transfer.
+    ///
+    /// The range of `output` holding each frame is stored at the same index of `ranges`, and the
+    /// ranges of all frames are returned. Returns `Err(SlipEncodeError::InsufficientCapacity)` if
+    /// `output` is too small to hold the frames, with their total length, or
+    /// `Err(SlipEncodeError::BufferTooSmall)` if `ranges` is shorter than `packets`. Nothing is
+    /// written to `output` on error.
+    pub fn encode_many<'r>(
+        &self,
+        packets: &[&[u8]],
+        output: &mut [u8],
+        ranges: &'r mut [Range<usize>],
+    ) -> Result<&'r [Range<usize>], SlipEncodeError> {
+        let ranges = ranges
+            .get_mut(..packets.len())
+            .ok_or(SlipEncodeError::BufferTooSmall)?;
+
+        let mut end = 0;
+        for (packet, range) in packets.iter().zip(ranges.iter_mut()) {
+            let start = end;
+            end += self.encoded_len(packet);
+            *range = start..end;
+        }
+        if end > output.len() {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required: end,
+                available: output.len(),
+            });
+        }
+
+        for (packet, range) in packets.iter().zip(ranges.iter()) {
+            self.encode_to_slice(packet, &mut output[range.clone()])?;
         }
 
-        // End the SLIP frame
-        vec.insert(vec.len(), END_CHAR).map_err(|_| ())?;
+        Ok(ranges)
+    }
 
-        Ok(())
+    /// Returns the worst-case work of `encode_many` for `packet_count` packets holding
+    /// `input_len` bytes in total.
+    #[must_use]
+    pub const fn encode_many_work_bound(packet_count: usize, input_len: usize) -> WorkBound {
+        WorkBound::linear(packet_count)
+            .then(WorkBound::linear(input_len))
+            .then(Self::encode_to_slice_work_bound(input_len))
+    }
+
+    /// Encodes `input` as a SLIP frame into a `bbqueue` write grant, then commits the frame.
+    ///
+    /// Exactly the bytes of the frame are committed, so that the grant can be larger than the
+    /// frame. Returns the number of bytes committed, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the grant is too small to hold the frame.
+    /// Nothing is committed on error.
+    #[cfg(feature = "bbqueue")]
+    pub fn encode_to_grant<Q: BbqHandle>(
+        &self,
+        input: &[u8],
+        mut grant: StreamGrantW<Q>,
+    ) -> Result<usize, SlipEncodeError> {
+        let length = self.encode_to_slice(input, &mut grant)?;
+        grant.commit(length);
+
+        Ok(length)
+    }
+
+    /// Returns the worst-case work of `encode_to_grant` for an input of `input_len` bytes.
+    #[cfg(feature = "bbqueue")]
+    #[must_use]
+    pub const fn encode_to_grant_work_bound(input_len: usize) -> WorkBound {
+        Self::encode_to_slice_work_bound(input_len)
+    }
+
+    /// Encodes `input` as a SLIP frame at the end of `output`, e.g. a `BytesMut` of a `bytes`
+    /// based pipeline.
+    ///
+    /// Returns the number of bytes written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` cannot take the frame, with the
+    /// length of the frame. Nothing is written to `output` on error.
+    #[cfg(feature = "bytes")]
+    pub fn encode_to_buf<B: BufMut>(
+        &self,
+        input: &[u8],
+        output: &mut B,
+    ) -> Result<usize, SlipEncodeError> {
+        let required = self.encoded_len(input);
+        let available = output.remaining_mut();
+        if required > available {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required,
+                available,
+            });
+        }
+
+        if self.config.leading_end {
+            output.put_u8(self.config.end);
+        }
+        for segment in self.escape_segments(input) {
+            output.put_slice(segment);
+        }
+        output.put_u8(self.config.end);
+
+        Ok(required)
+    }
+
+    /// Returns the worst-case work of `encode_to_buf` for an input of `input_len` bytes.
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub const fn encode_to_buf_work_bound(input_len: usize) -> WorkBound {
+        Self::encode_to_slice_work_bound(input_len)
+    }
+
+    /// Encodes `input` as a SLIP frame into freshly allocated `Bytes`.
+    ///
+    /// The frame is allocated once, with its exact length.
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub fn encode_to_bytes(&self, input: &[u8]) -> Bytes {
+        let mut output = BytesMut::with_capacity(self.encoded_len(input));
+        let _ = self.encode_to_buf(input, &mut output);
+
+        output.freeze()
+    }
+
+    /// Encodes `input` as a SLIP frame into a freshly allocated vector.
+    ///
+    /// The vector is allocated once, with the exact length of the frame.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn encode_to_vec(&self, input: &[u8]) -> alloc::vec::Vec<u8> {
+        let codec = self.config.codec();
+        let mut output = alloc::vec::Vec::with_capacity(codec.encoded_len(input));
+        codec.for_each_encoded(input, |value| output.push(value));
+
+        output
+    }
+
+    /// Returns the worst-case work of `encode_to_vec` for an input of `input_len` bytes.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub const fn encode_to_vec_work_bound(input_len: usize) -> WorkBound {
+        Self::encode_to_slice_work_bound(input_len)
+    }
+
+    /// Encodes `input` as a SLIP frame into a vector allocated from `alloc`.
+    ///
+    /// The vector is allocated once, with the exact length of the frame, so that frames can be
+    /// taken from a pool or an arena rather than from the global heap.
+    #[cfg(feature = "allocator-api")]
+    #[must_use]
+    pub fn encode_to_vec_in<A: core::alloc::Allocator>(
+        &self,
+        input: &[u8],
+        alloc: A,
+    ) -> alloc::vec::Vec<u8, A> {
+        let codec = self.config.codec();
+        let mut output = alloc::vec::Vec::with_capacity_in(codec.encoded_len(input), alloc);
+        codec.for_each_encoded(input, |value| output.push(value));
+
+        output
+    }
+
+    /// Returns an iterator yielding the SLIP frame encoding the bytes of `payload`.
+    #[must_use]
+    pub fn encode_iter<I: IntoIterator<Item = u8>>(
+        &self,
+        payload: I,
+    ) -> SlipEncodeIter<I::IntoIter> {
+        SlipEncodeIter::with_config(payload.into_iter(), self.config)
+    }
+
+    /// Returns an iterator over the escaped segments of `payload`, without frame delimiters.
+    ///
+    /// Unescaped runs are borrowed from `payload`, so writing each segment to a transport
+    /// avoids staging the escaped payload in memory.
+    #[must_use]
+    pub const fn escape_segments<'a>(&self, payload: &'a [u8]) -> EscapedSegments<'a> {
+        EscapedSegments::new(payload, self.config)
+    }
+
+    /// Encodes `payload` as a SLIP frame, handing the bytes of the frame to `sink` in chunks.
+    ///
+    /// Unescaped runs are borrowed from `payload`, so that a transmit function, e.g. of a vendor
+    /// HAL, can be fed without staging the frame in memory. Stops at the first error of `sink`,
+    /// which is returned as is.
+    pub fn encode_with<E>(
+        &self,
+        payload: &[u8],
+        mut sink: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let end = [self.config.end];
+        if self.config.leading_end {
+            sink(&end)?;
+        }
+
+        for segment in self.escape_segments(payload) {
+            sink(segment)?;
+        }
+
+        sink(&end)
+    }
+
+    /// Returns the worst-case work of `encode_with` for a payload of `payload_len` bytes,
+    /// excluding the time spent in the sink.
+    #[must_use]
+    pub const fn encode_with_work_bound(payload_len: usize) -> WorkBound {
+        WorkBound::linear(max_encoded_len(payload_len))
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to compute the encoded length, one to grow the vector, one to fill the frame
+        WorkBound::linear(MAX_LENGTH)
+            .then(WorkBound::linear(MAX_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Encodes `payload` into the circular buffer `ring`, starting at `start`.
+    ///
+    /// `available` is the number of free bytes following `start`, i.e. the room left before
+    /// reaching data the DMA controller has not transmitted yet. The frame is split across the end
+    /// of `ring` when needed, escape sequences included.
+    ///
+    /// Returns the regions to program into the DMA controller,
+    /// `Err(SlipEncodeError::InvalidRegion)` if `start` or `available` do not fit in `ring`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the frame does not fit in `available`.
+    /// Nothing is written to `ring` on error.
+    pub fn encode_to_ring(
+        &self,
+        payload: &[u8],
+        ring: &mut [u8],
+        start: usize,
+        available: usize,
+    ) -> Result<RingFrame, SlipEncodeError> {
+        if start >= ring.len() || available > ring.len() {
+            return Err(SlipEncodeError::InvalidRegion);
+        }
+
+        let codec = self.config.codec();
+        let frame_len = codec.encoded_len(payload);
+        if frame_len > available {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available,
+            });
+        }
+
+        let mut index = start;
+        codec.for_each_encoded(payload, |value| {
+            ring[index] = value;
+            index = if index + 1 == ring.len() {
+                0
+            } else {
+                index + 1
+            };
+        });
+
+        let first_len = frame_len.min(ring.len() - start);
+
+        Ok(RingFrame {
+            first: RingRegion {
+                start,
+                len: first_len,
+            },
+            second: RingRegion {
+                start: 0,
+                len: frame_len - first_len,
+            },
+            next: index,
+        })
+    }
+
+    /// Returns the worst-case work of `encode_to_ring` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn encode_to_ring_work_bound(payload_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one pass to write the frame
+        WorkBound::linear(payload_len).then(WorkBound::linear(max_encoded_len(payload_len)))
+    }
+
+    /// Encodes `payload` as a SLIP frame into `first`, then into `second` once `first` is full.
+    ///
+    /// This writes straight into the writable region of a circular buffer that wraps, such as the
+    /// two slices of a DMA transmit ring, without staging the frame in a bounce buffer. The frame
+    /// is split across both slices when needed, escape sequences included.
+    ///
+    /// Returns the number of bytes written to `first` and to `second`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the frame does not fit in both slices, with
+    /// the length of the frame. Nothing is written on error.
+    pub fn encode_to_slices(
+        &self,
+        payload: &[u8],
+        first: &mut [u8],
+        second: &mut [u8],
+    ) -> Result<(usize, usize), SlipEncodeError> {
+        let codec = self.config.codec();
+        let frame_len = codec.encoded_len(payload);
+        let available = first.len().saturating_add(second.len());
+        if frame_len > available {
+            return Err(SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available,
+            });
+        }
+
+        let first_len = frame_len.min(first.len());
+        let mut output = first[..first_len]
+            .iter_mut()
+            .chain(second[..frame_len - first_len].iter_mut());
+        codec.for_each_encoded(payload, |value| {
+            if let Some(byte) = output.next() {
+                *byte = value;
+            }
+        });
+
+        Ok((first_len, frame_len - first_len))
+    }
+
+    /// Returns the worst-case work of `encode_to_slices` for a payload of `payload_len` bytes.
+    #[must_use]
+    pub const fn encode_to_slices_work_bound(payload_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one pass to write the frame
+        WorkBound::linear(payload_len).then(WorkBound::linear(max_encoded_len(payload_len)))
     }
 }
 
-/// The state of the SLIP decoder.
-#[derive(Debug, Default, PartialEq)]
-enum SlipDecoderState {
-    /// Waiting for the opening `END_CHAR` of a frame.
-    #[default]
+/// Escape sequence replacing the frame terminator inside a frame.
+const ESCAPED_END: [u8; 2] = [ESC_CHAR, ESC_END_CHAR];
+
+/// Escape sequence replacing `ESC_CHAR` inside a frame.
+const ESCAPED_ESC: [u8; 2] = [ESC_CHAR, ESC_ESC_CHAR];
+
+/// Escape sequences replacing the control bytes inside a frame, when they are escaped.
+const ESCAPED_CONTROLS: [[u8; 2]; 32] = {
+    let mut escapes = [[ESC_CHAR, 0]; 32];
+    let mut value: u8 = 0;
+    while value < 0x20 {
+        escapes[value as usize][1] = value ^ 0x20;
+        value += 1;
+    }
+
+    escapes
+};
+
+/// An iterator over the escaped segments of a payload.
+///
+/// This struct yields runs of bytes that need no escaping, borrowed from the payload, interleaved
+/// with escape sequences. It is the transport-agnostic core of the frame writers: blocking and
+/// async adapters only differ in how they push each segment out.
+#[derive(Clone, Debug)]
+pub struct EscapedSegments<'a> {
+    payload: &'a [u8],
+    codec: ByteStuffingCodec,
+}
+
+impl<'a> EscapedSegments<'a> {
+    /// Creates an iterator over the escaped segments of `payload`.
+    pub(crate) const fn new(payload: &'a [u8], config: SlipConfig) -> Self {
+        Self {
+            payload,
+            codec: config.codec(),
+        }
+    }
+}
+
+/// Implementation of `Iterator` for `EscapedSegments`.
+impl<'a> Iterator for EscapedSegments<'a> {
+    type Item = &'a [u8];
+
+    /// Returns the next escaped segment.
+    fn next(&mut self) -> Option<Self::Item> {
+        let codec = self.codec;
+        let position = self
+            .payload
+            .iter()
+            .position(|&value| codec.is_special(value));
+
+        match position {
+            Some(0) => {
+                let value = self.payload[0];
+                let escape: &'static [u8] = if value == codec.delimiter() {
+                    &ESCAPED_END
+                } else if value == ESC_CHAR {
+                    &ESCAPED_ESC
+                } else {
+                    &ESCAPED_CONTROLS[usize::from(value)]
+                };
+                self.payload = &self.payload[1..];
+
+                Some(escape)
+            }
+            Some(position) => {
+                let (run, rest) = self.payload.split_at(position);
+                self.payload = rest;
+
+                Some(run)
+            }
+            None if self.payload.is_empty() => None,
+            None => Some(core::mem::take(&mut self.payload)),
+        }
+    }
+}
+
+/// The state of the SLIP encoding iterator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SlipEncodeIterState {
+    /// The opening terminator has not been yielded yet.
+    Start,
+    /// Yielding the escaped payload bytes.
+    Payload,
+    /// The closing terminator has been yielded.
+    Done,
+}
+
+/// An iterator adapter encoding a byte iterator as a SLIP frame.
+///
+/// This struct yields the framed bytes on the fly, opening terminator, escape sequences and
+/// closing terminator included, without any intermediate buffer.
+#[derive(Clone, Debug)]
+pub struct SlipEncodeIter<I> {
+    inner: I,
+    config: SlipConfig,
+    state: SlipEncodeIterState,
+    pending: Option<u8>,
+}
+
+impl<I: Iterator<Item = u8>> SlipEncodeIter<I> {
+    /// Creates an iterator encoding the bytes of `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: I) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates an iterator encoding the bytes of `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: I, config: SlipConfig) -> Self {
+        let state = if config.leading_end {
+            SlipEncodeIterState::Start
+        } else {
+            SlipEncodeIterState::Payload
+        };
+
+        Self {
+            inner,
+            config,
+            state,
+            pending: None,
+        }
+    }
+}
+
+/// Implementation of `Iterator` for `SlipEncodeIter`.
+impl<I: Iterator<Item = u8>> Iterator for SlipEncodeIter<I> {
+    type Item = u8;
+
+    /// Returns the next byte of the SLIP frame.
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.config.end;
+
+        match self.state {
+            SlipEncodeIterState::Start => {
+                self.state = SlipEncodeIterState::Payload;
+
+                Some(end)
+            }
+            SlipEncodeIterState::Payload => {
+                if let Some(value) = self.pending.take() {
+                    return Some(value);
+                }
+
+                let Some(value) = self.inner.next() else {
+                    self.state = SlipEncodeIterState::Done;
+
+                    return Some(end);
+                };
+
+                if let Some(substitute) = self.config.codec().substitute(value) {
+                    self.pending = Some(substitute);
+
+                    return Some(ESC_CHAR);
+                }
+
+                Some(value)
+            }
+            SlipEncodeIterState::Done => None,
+        }
+    }
+
+    /// Returns the bounds on the number of bytes left in the SLIP frame.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let framing = match self.state {
+            SlipEncodeIterState::Start => 2,
+            SlipEncodeIterState::Payload => 1,
+            SlipEncodeIterState::Done => return (0, Some(0)),
+        } + usize::from(self.pending.is_some());
+
+        let (lower, upper) = self.inner.size_hint();
+
+        (
+            lower.saturating_add(framing),
+            upper.and_then(|upper| upper.checked_mul(2)?.checked_add(framing)),
+        )
+    }
+}
+
+/// Implementation of `FusedIterator` for `SlipEncodeIter`.
+///
+/// The iterator keeps returning `None` once the closing terminator has been yielded.
+impl<I: Iterator<Item = u8>> FusedIterator for SlipEncodeIter<I> {}
+
+/// A resumable SLIP encoder emitting a frame in chunks.
+///
+/// This struct encodes a payload into output buffers of any size, remembering its position
+/// between calls, so that a frame can be written straight into a transmit FIFO as room frees up.
+/// No staging buffer for the whole frame is needed.
+#[derive(Clone, Debug)]
+pub struct ChunkedSlipEncoder<'a> {
+    inner: SlipEncodeIter<Copied<slice::Iter<'a, u8>>>,
+}
+
+impl<'a> ChunkedSlipEncoder<'a> {
+    /// Creates an encoder emitting the frame of `payload`, using the default SLIP configuration.
+    #[must_use]
+    pub fn new(payload: &'a [u8]) -> Self {
+        Self::with_config(payload, SlipConfig::new())
+    }
+
+    /// Creates an encoder emitting the frame of `payload`, using the framing options of `config`.
+    #[must_use]
+    pub fn with_config(payload: &'a [u8], config: SlipConfig) -> Self {
+        Self {
+            inner: SlipEncodeIter::with_config(payload.iter().copied(), config),
+        }
+    }
+
+    /// Writes the next bytes of the frame into `output`.
+    ///
+    /// Returns the number of bytes written, which is less than the length of `output` only once
+    /// the end of the frame is reached. An escape sequence may be split across two chunks.
+    pub fn encode_chunk(&mut self, output: &mut [u8]) -> usize {
+        output
+            .iter_mut()
+            .zip(&mut self.inner)
+            .map(|(slot, value)| *slot = value)
+            .count()
+    }
+
+    /// Returns the worst-case work of `encode_chunk` for an output of `output_len` bytes.
+    #[must_use]
+    pub const fn encode_chunk_work_bound(output_len: usize) -> WorkBound {
+        WorkBound::linear(output_len)
+    }
+
+    /// Returns `true` once the whole frame has been emitted.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.inner.state == SlipEncodeIterState::Done
+    }
+}
+
+/// A contiguous region of a circular buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RingRegion {
+    /// Index of the first byte of the region.
+    pub start: usize,
+    /// Number of bytes in the region.
+    pub len: usize,
+}
+
+/// The location of a frame written into a circular buffer.
+///
+/// `second` is empty unless the frame wraps around the end of the buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RingFrame {
+    /// Region from the start index up to the end of the buffer at most.
+    pub first: RingRegion,
+    /// Region from the beginning of the buffer, after the wrap point.
+    pub second: RingRegion,
+    /// Index following the last byte written.
+    pub next: usize,
+}
+
+/// The status of a `SlipDecoder` after feeding it a chunk of bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FeedStatus {
+    /// All bytes were consumed and no frame is complete yet.
+    Incomplete,
+    /// A frame is complete; the remaining bytes were left unconsumed.
+    Completed,
+    /// The last consumed byte was rejected by the decoder.
+    Rejected(SlipDecodeError),
+}
+
+/// The outcome of feeding a chunk of bytes into a `SlipDecoder`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FeedResult {
+    /// Number of bytes consumed from the input, including a rejected byte.
+    pub consumed: usize,
+    /// Status of the decoder after the last consumed byte.
+    pub status: FeedStatus,
+}
+
+/// What a byte fed into a decoder changed in the current frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlipEvent {
+    /// The byte was consumed without reaching a frame boundary.
+    None,
+    /// The byte opened a frame.
+    ///
+    /// This is only reported when frames are opened by a terminator byte.
+    FrameStarted,
+    /// The byte completed a frame, which is ready to be consumed.
+    FrameCompleted,
+    /// The byte was rejected with the given error.
+    ///
+    /// The current frame is dropped, except on `SlipDecodeError::FrameCompleted`: the completed
+    /// frame is then still pending and must be consumed first. There is no frame to drop either on
+    /// `SlipDecodeError::UnexpectedByte`, received between frames.
+    FrameAborted(SlipDecodeError),
+}
+
+/// Where a decoder stands in the byte stream, as reported by `SlipBufferDecoder::status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecoderStatus {
+    /// No frame byte is pending: the decoder waits for a frame, or has only seen its opening
+    /// terminator.
+    Idle,
+    /// A frame is being received.
+    InFrame,
+    /// A frame is being received, and the next byte completes an escape sequence.
+    Escaping,
+    /// A complete frame is pending, and the decoder must be reset before the next one.
+    FrameReady,
+}
+
+/// The state of the SLIP decoder.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum SlipDecoderState {
+    /// Waiting for the opening `END_CHAR` of a frame.
+    #[default]
     Start,
     /// Received the closing `END_CHAR`; frame is complete.
     End,
@@ -66,134 +1170,3915 @@ enum SlipDecoderState {
     Append,
     /// Received `ESC_CHAR`; next byte is an escaped value.
     Escape,
+    /// Skipping the rest of a dropped frame up to its terminator.
+    Skip,
+    /// Received the terminator of a dropped frame, which also opens the next frame unless that
+    /// one has a terminator of its own.
+    Resume,
+}
+
+impl SlipDecoderState {
+    /// Returns the state a decoder using `config` starts from.
+    const fn initial(config: SlipConfig) -> Self {
+        if config.leading_end {
+            Self::Start
+        } else {
+            Self::Append
+        }
+    }
+
+    /// Returns the state a decoder using `config` resumes from once the terminator of a dropped
+    /// frame is received.
+    const fn resumed(config: SlipConfig) -> Self {
+        if config.leading_end {
+            Self::Resume
+        } else {
+            Self::Append
+        }
+    }
+
+    /// Returns the state stored as `value` in a snapshot.
+    const fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Start),
+            1 => Some(Self::End),
+            2 => Some(Self::Append),
+            3 => Some(Self::Escape),
+            4 => Some(Self::Skip),
+            5 => Some(Self::Resume),
+            _ => None,
+        }
+    }
+}
+
+/// Length of the header preceding the buffered bytes in a serialized snapshot.
+pub const SNAPSHOT_HEADER_LENGTH: usize = 6;
+
+/// Snapshot flag set when bytes of the frame were dropped for lack of space.
+const SNAPSHOT_TRUNCATED: u8 = 0x01;
+
+/// Snapshot flag set when the frame was rejected.
+const SNAPSHOT_REJECTED: u8 = 0x02;
+
+/// The state of a `SlipDecoder`, as plain data.
+///
+/// A snapshot holds the position of the decoder in the current frame and the bytes decoded so
+/// far, so that a device entering deep sleep mid-frame can persist it, e.g. in retention RAM or
+/// flash, and resume the frame on wake-up. The configuration and the link statistics are not
+/// part of it: a snapshot is restored into a decoder created with the same configuration.
+///
+/// `to_bytes` serializes it as a `SNAPSHOT_HEADER_LENGTH`-byte header, holding the state, flags
+/// and length of the frame, followed by the buffered bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlipDecoderSnapshot<const MAX_LENGTH: usize> {
+    state: SlipDecoderState,
+    flags: u8,
+    len: usize,
+    data: [u8; MAX_LENGTH],
+}
+
+impl<const MAX_LENGTH: usize> SlipDecoderSnapshot<MAX_LENGTH> {
+    /// Returns the decoded bytes held by the snapshot.
+    #[must_use]
+    pub fn get_buffer(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns the number of bytes `to_bytes` writes.
+    #[must_use]
+    pub const fn serialized_len(&self) -> usize {
+        SNAPSHOT_HEADER_LENGTH + self.len
+    }
+
+    /// Serializes the snapshot into `output`.
+    ///
+    /// Returns the number of bytes written, or `Err(SlipEncodeError::BufferTooSmall)` if `output`
+    /// is shorter than `serialized_len`.
+    pub fn to_bytes(&self, output: &mut [u8]) -> Result<usize, SlipEncodeError> {
+        let length = self.serialized_len();
+        let output = output
+            .get_mut(..length)
+            .ok_or(SlipEncodeError::BufferTooSmall)?;
+        let len = u32::try_from(self.len).map_err(|_| SlipEncodeError::BufferTooSmall)?;
+
+        // The state is stored as its declaration index
+        output[0] = self.state as u8;
+        output[1] = self.flags;
+        output[2..SNAPSHOT_HEADER_LENGTH].copy_from_slice(&len.to_le_bytes());
+        output[SNAPSHOT_HEADER_LENGTH..].copy_from_slice(self.get_buffer());
+
+        Ok(length)
+    }
+
+    /// Deserializes a snapshot written by `to_bytes`.
+    ///
+    /// Returns `Err(SlipDecodeError::MalformedFrame)` if `bytes` is not a valid snapshot, or
+    /// `Err(SlipDecodeError::BufferFull)` if it holds more than `MAX_LENGTH` bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SlipDecodeError> {
+        let (header, buffer) = bytes
+            .split_at_checked(SNAPSHOT_HEADER_LENGTH)
+            .ok_or(SlipDecodeError::MalformedFrame)?;
+        let state =
+            SlipDecoderState::from_byte(header[0]).ok_or(SlipDecodeError::MalformedFrame)?;
+        let flags = header[1];
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+
+        if flags & !(SNAPSHOT_TRUNCATED | SNAPSHOT_REJECTED) != 0
+            || usize::try_from(len).ok() != Some(buffer.len())
+        {
+            return Err(SlipDecodeError::MalformedFrame);
+        }
+
+        let mut data = [0; MAX_LENGTH];
+        data.get_mut(..buffer.len())
+            .ok_or(SlipDecodeError::BufferFull)?
+            .copy_from_slice(buffer);
+
+        Ok(Self {
+            state,
+            flags,
+            len: buffer.len(),
+            data,
+        })
+    }
+}
+
+/// Statistics of a link, as seen by a decoder.
+///
+/// Counters wrap around on overflow. They survive decoder resets, so that they cover the whole
+/// life of a link.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SlipStats {
+    /// Number of frames completed.
+    pub frames_completed: u32,
+    /// Number of frames dropped on an invalid escape sequence, for lack of space, for being too
+    /// long, on a timeout, or by the validator of the decoder.
+    pub frames_dropped: u32,
+    /// Number of bytes skipped while waiting for the opening terminator of a frame.
+    pub bytes_discarded: u32,
+    /// Number of escape sequences received.
+    pub escapes: u32,
+    /// Number of frames dropped for exceeding the maximum frame length of the decoder, or the MTU
+    /// of its configuration.
+    pub frames_oversized: u32,
+    /// Number of frames aborted for lack of bytes within the inter-byte timeout.
+    pub frames_timed_out: u32,
+    /// Number of frames rejected by the validator of the decoder.
+    pub frames_invalid: u32,
+}
+
+impl SlipStats {
+    /// Creates statistics with all counters at zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frames_completed: 0,
+            frames_dropped: 0,
+            bytes_discarded: 0,
+            escapes: 0,
+            frames_oversized: 0,
+            frames_timed_out: 0,
+            frames_invalid: 0,
+        }
+    }
+}
+
+/// Metadata of a frame, as received on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameMetadata {
+    /// Number of bytes the frame took on the wire, escape sequences and terminators included.
+    pub wire_len: usize,
+    /// Number of escape sequences in the frame.
+    pub escapes: usize,
+    /// Whether bytes were discarded since the previous frame, such as garbage before the
+    /// opening terminator or a dropped frame.
+    pub preceded_by_garbage: bool,
+}
+
+impl FrameMetadata {
+    /// Creates the metadata of a frame not received yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            wire_len: 0,
+            escapes: 0,
+            preceded_by_garbage: false,
+        }
+    }
+}
+
+/// A running Fletcher-16 checksum of the decoder buffer, next to a canary value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct IntegrityGuard {
+    canary: u32,
+    sum1: u16,
+    sum2: u16,
+}
+
+impl IntegrityGuard {
+    /// Creates the guard of an empty buffer.
+    const fn new() -> Self {
+        Self {
+            canary: INTEGRITY_CANARY,
+            sum1: 0,
+            sum2: 0,
+        }
+    }
+
+    /// Creates the guard of `bytes`.
+    fn of(bytes: &[u8]) -> Self {
+        let mut guard = Self::new();
+        for &value in bytes {
+            guard.update(value);
+        }
+
+        guard
+    }
+
+    /// Accounts for `value` being appended to the buffer.
+    const fn update(&mut self, value: u8) {
+        self.sum1 = (self.sum1 + value as u16) % 255;
+        self.sum2 = (self.sum2 + self.sum1) % 255;
+    }
+}
+
+/// A SLIP decoder over the frame buffer `B`.
+///
+/// This struct provides methods to decode a packet using the SLIP protocol. The decoding logic
+/// does not depend on where the decoded bytes are stored, so that the same state machine serves
+/// fixed-capacity and growable buffers: any `FrameBuffer` implementation can back it. See
+/// `SlipDecoder` for the usual fixed-capacity decoder.
+///
+/// A decoder over a cloneable buffer is itself cloneable, so that its state can be forked for
+/// speculative parsing, or checkpointed before feeding untrusted bytes and restored afterwards.
+///
+/// Completed frames are checked by the validator `V`, set with `with_validator`, which accepts
+/// every frame by default.
+#[derive(Clone)]
+pub struct SlipBufferDecoder<B, V = AcceptAll> {
+    state: SlipDecoderState,
+    buffer: B,
+    config: SlipConfig,
+    integrity: IntegrityGuard,
+    truncated: bool,
+    rejected: bool,
+    max_frame_len: usize,
+    inter_byte_timeout: Option<u64>,
+    last_byte_at: u64,
+    last_frame_at: Option<u64>,
+    validator: V,
+    stats: SlipStats,
+    // Metadata of the frame in progress, and of the last completed frame
+    metadata: FrameMetadata,
+    completed: FrameMetadata,
+    // Whether bytes were discarded since the last frame started
+    garbage: bool,
+}
+
+/// A SLIP decoder storing up to `MAX_LENGTH` decoded bytes.
+///
+/// The bytes are held in `MaybeUninit` storage, which neither the creation nor the reset of the
+/// decoder writes to, so that a large decoder, e.g. a `SlipDecoder<4096>` in a `static`, costs no
+/// zeroing at startup or between frames.
+pub type SlipDecoder<const MAX_LENGTH: usize> = SlipBufferDecoder<Vec<u8, MAX_LENGTH>>;
+
+/// A SLIP decoder sized for the frames of RFC 1055, of up to `RFC1055_MTU` decoded bytes.
+///
+/// Created with `SlipConfig::rfc1055`, it rejects longer frames with
+/// `SlipDecodeError::MtuExceeded`, as legacy `slattach` peers do.
+pub type Rfc1055Decoder = SlipDecoder<RFC1055_MTU>;
+
+/// A SLIP decoder storing the decoded bytes in a borrowed slice.
+///
+/// The capacity is the length of the slice, so that a single type serves buffers of any size.
+pub type SlipDecoderRef<'a> = SlipBufferDecoder<SliceBuffer<'a>>;
+
+/// A SLIP decoder storing the decoded bytes in a growable `alloc::vec::Vec`.
+///
+/// Frames are only bounded by the available memory, which suits host-side tools.
+#[cfg(feature = "alloc")]
+pub type SlipVecDecoder = SlipBufferDecoder<alloc::vec::Vec<u8>>;
+
+/// A SLIP decoder checking frames without storing their payload.
+///
+/// The state machine runs as for any decoder, so that escape sequences, frame lengths and
+/// terminators are validated, but decoded bytes are only counted: `frame_len` gives the length
+/// of the current frame, while `get_buffer` is always empty. This suits sniffers and link
+/// monitors, which do not need the payloads, with no `MAX_LENGTH` to size.
+pub type SlipValidator = SlipBufferDecoder<CountingBuffer>;
+
+impl<const MAX_LENGTH: usize> SlipDecoder<MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    ///
+    /// Like every decoder constructor, this is a `const fn`, so that a decoder can be placed in
+    /// a `static` without any initialization at runtime.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self::with_buffer(Vec::new(), config)
+    }
+
+    /// Returns an iterator over the frames completed by the bytes of `data`, starting with a frame
+    /// left pending by a previous call to `feed`.
+    ///
+    /// Each frame is copied out and the decoder reset for the next one, so that the whole
+    /// receive path is a `for` loop. The bytes of a trailing unfinished frame stay in the decoder,
+    /// to be completed by the next chunk.
+    pub const fn frames<'a>(&'a mut self, data: &'a [u8]) -> SlipFrames<'a, MAX_LENGTH> {
+        SlipFrames::with_observer(self, data, ())
+    }
+}
+
+impl<const MAX_LENGTH: usize, V: FrameValidator> SlipBufferDecoder<Vec<u8, MAX_LENGTH>, V> {
+    /// Returns a copy of the completed frame and resets the decoder for the next frame.
+    ///
+    /// Returns the error of `check_frame` if no frame is complete yet, or if the frame failed the
+    /// integrity check.
+    pub fn take_frame(&mut self) -> Result<Vec<u8, MAX_LENGTH>, SlipDecodeError> {
+        self.check_frame()?;
+
+        let frame = self.buffer.clone();
+        self.reset();
+
+        Ok(frame)
+    }
+
+    /// Returns the worst-case work of `take_frame`.
+    #[must_use]
+    pub const fn take_frame_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Returns the worst-case work of `read_frame_into`.
+    #[must_use]
+    pub const fn read_frame_into_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Returns the state of the decoder, with the bytes of the current frame.
+    #[must_use]
+    pub fn snapshot(&self) -> SlipDecoderSnapshot<MAX_LENGTH> {
+        let buffer = self.buffer.as_slice();
+        let mut data = [0; MAX_LENGTH];
+        data[..buffer.len()].copy_from_slice(buffer);
+
+        let mut flags = 0;
+        if self.truncated {
+            flags |= SNAPSHOT_TRUNCATED;
+        }
+        if self.rejected {
+            flags |= SNAPSHOT_REJECTED;
+        }
+
+        SlipDecoderSnapshot {
+            state: self.state,
+            flags,
+            len: buffer.len(),
+            data,
+        }
+    }
+
+    /// Returns the worst-case work of `snapshot`.
+    #[must_use]
+    pub const fn snapshot_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Restores the state saved by `snapshot`, so that decoding resumes where it stopped.
+    ///
+    /// The current frame is replaced, while the configuration and the statistics of the decoder
+    /// are kept.
+    pub fn restore(&mut self, snapshot: &SlipDecoderSnapshot<MAX_LENGTH>) {
+        // The snapshot never holds more than `MAX_LENGTH` bytes
+        self.buffer.clear();
+        for &value in snapshot.get_buffer() {
+            let _ = self.buffer.push(value);
+        }
+        self.state = snapshot.state;
+        self.truncated = snapshot.flags & SNAPSHOT_TRUNCATED != 0;
+        self.rejected = snapshot.flags & SNAPSHOT_REJECTED != 0;
+        self.integrity = if self.config.integrity_check {
+            IntegrityGuard::of(self.buffer.as_slice())
+        } else {
+            IntegrityGuard::new()
+        };
+    }
+
+    /// Returns the worst-case work of `restore`.
+    #[must_use]
+    pub const fn restore_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+
+    /// Returns a mutable slice of the decoded bytes accumulated so far.
+    ///
+    /// This allows transforming a completed frame in place, e.g. to decrypt it, without copying
+    /// it out first. If the integrity check is enabled, changed bytes make
+    /// `get_verified_buffer` fail until the decoder is reset.
+    #[must_use]
+    pub const fn get_buffer_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut_slice()
+    }
+
+    /// Returns the worst-case work of `get_verified_buffer`.
+    #[must_use]
+    pub const fn get_verified_buffer_work_bound() -> WorkBound {
+        WorkBound::linear(MAX_LENGTH)
+    }
+}
+
+impl<'a> SlipDecoderRef<'a> {
+    /// Creates a decoder storing the decoded bytes in `buffer`, using the default SLIP
+    /// configuration.
+    #[must_use]
+    pub const fn new(buffer: &'a mut [u8]) -> Self {
+        Self::with_config(buffer, SlipConfig::new())
+    }
+
+    /// Creates a decoder storing the decoded bytes in `buffer`, using the framing options of
+    /// `config`.
+    #[must_use]
+    pub const fn with_config(buffer: &'a mut [u8], config: SlipConfig) -> Self {
+        Self::with_buffer(SliceBuffer::new(buffer), config)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SlipVecDecoder {
+    /// Creates a decoder using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self::with_buffer(alloc::vec::Vec::new(), config)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V: FrameValidator> SlipBufferDecoder<alloc::vec::Vec<u8>, V> {
+    /// Hands over the completed frame and resets the decoder for the next frame.
+    ///
+    /// Returns the error of `check_frame` if no frame is complete yet, or if the frame failed the
+    /// integrity check.
+    #[cfg(not(feature = "allocator-api"))]
+    pub fn take_frame(&mut self) -> Result<alloc::vec::Vec<u8>, SlipDecodeError> {
+        self.check_frame()?;
+
+        let frame = core::mem::take(&mut self.buffer);
+        self.reset();
+
+        Ok(frame)
+    }
+}
+
+#[cfg(feature = "allocator-api")]
+impl<A: core::alloc::Allocator> SlipBufferDecoder<alloc::vec::Vec<u8, A>> {
+    /// Creates a decoder allocating its frames from `alloc`, using the default SLIP
+    /// configuration.
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_config_in(SlipConfig::new(), alloc)
+    }
+
+    /// Creates a decoder allocating its frames from `alloc`, using the framing options of
+    /// `config`.
+    #[must_use]
+    pub const fn with_config_in(config: SlipConfig, alloc: A) -> Self {
+        Self::with_buffer(alloc::vec::Vec::new_in(alloc), config)
+    }
 }
 
-/// A SLIP decoder.
-///
-/// This struct provides methods to decode a packet using the SLIP protocol.
-#[derive(Default)]
-pub struct SlipDecoder<const MAX_LENGTH: usize> {
-    state: SlipDecoderState,
-    buffer: Vec<u8, MAX_LENGTH>,
-}
+#[cfg(feature = "allocator-api")]
+impl<A: core::alloc::Allocator, V: FrameValidator> SlipBufferDecoder<alloc::vec::Vec<u8, A>, V> {
+    /// Hands over the completed frame and resets the decoder for the next frame.
+    ///
+    /// The next frame is allocated from a clone of the allocator of the frame handed over.
+    /// Returns the error of `check_frame` if no frame is complete yet, or if the frame failed the
+    /// integrity check.
+    pub fn take_frame(&mut self) -> Result<alloc::vec::Vec<u8, A>, SlipDecodeError>
+    where
+        A: Clone,
+    {
+        self.check_frame()?;
+
+        let buffer = alloc::vec::Vec::new_in(self.buffer.allocator().clone());
+        let frame = core::mem::replace(&mut self.buffer, buffer);
+        self.reset();
+
+        Ok(frame)
+    }
+}
+
+impl SlipValidator {
+    /// Creates a validator accepting frames of any length, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a validator accepting frames of any length, using the framing options of
+    /// `config`.
+    ///
+    /// The integrity check is turned off, as there is no stored byte to guard.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self::with_max_length(usize::MAX, config)
+    }
+
+    /// Creates a validator rejecting frames longer than `max_length` decoded bytes, as a
+    /// `SlipDecoder<MAX_LENGTH>` would, using the framing options of `config`.
+    ///
+    /// The integrity check is turned off, as there is no stored byte to guard.
+    #[must_use]
+    pub const fn with_max_length(max_length: usize, config: SlipConfig) -> Self {
+        Self::with_buffer(
+            CountingBuffer::with_capacity(max_length),
+            config.with_integrity_check(false),
+        )
+    }
+}
+
+impl<B: FrameBuffer> SlipBufferDecoder<B> {
+    /// Creates a decoder storing the decoded bytes in `buffer`, using the framing options of
+    /// `config`.
+    ///
+    /// `buffer` should be empty, as the first frame is appended to its content.
+    #[must_use]
+    pub const fn with_buffer(buffer: B, config: SlipConfig) -> Self {
+        Self {
+            state: SlipDecoderState::initial(config),
+            buffer,
+            config,
+            integrity: IntegrityGuard::new(),
+            truncated: false,
+            rejected: false,
+            max_frame_len: usize::MAX,
+            inter_byte_timeout: None,
+            last_byte_at: 0,
+            last_frame_at: None,
+            validator: AcceptAll,
+            stats: SlipStats::new(),
+            metadata: FrameMetadata::new(),
+            completed: FrameMetadata::new(),
+            garbage: false,
+        }
+    }
+}
+
+impl<B: FrameBuffer, V: FrameValidator> SlipBufferDecoder<B, V> {
+    /// Returns the decoder running `validator` on every frame once its terminator is received.
+    ///
+    /// A frame rejected by the validator is dropped with `SlipDecodeError::InvalidFrame` instead
+    /// of being completed, and counted in `SlipStats::frames_invalid`. As its terminator was
+    /// received, the decoder then goes on with the next frame, whether resynchronization is
+    /// enabled or not. The validator runs exactly once per frame, and may borrow local state,
+    /// e.g. to count the frames it saw.
+    #[must_use]
+    pub fn with_validator<W: FrameValidator>(self, validator: W) -> SlipBufferDecoder<B, W> {
+        SlipBufferDecoder {
+            state: self.state,
+            buffer: self.buffer,
+            config: self.config,
+            integrity: self.integrity,
+            truncated: self.truncated,
+            rejected: self.rejected,
+            max_frame_len: self.max_frame_len,
+            inter_byte_timeout: self.inter_byte_timeout,
+            last_byte_at: self.last_byte_at,
+            last_frame_at: self.last_frame_at,
+            validator,
+            stats: self.stats,
+            metadata: self.metadata,
+            completed: self.completed,
+            garbage: self.garbage,
+        }
+    }
+
+    /// Returns the validator run on every frame.
+    #[must_use]
+    pub const fn validator(&self) -> &V {
+        &self.validator
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected or the buffer is full.
+    /// If the configuration enables resynchronization, the current frame is then discarded, and
+    /// the rest of it is skipped up to its terminator.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        let result = self.advance(value);
+        // Neither a completed frame nor a byte outside a frame leaves a frame to drop
+        if let Err(error) = result
+            && !matches!(
+                error,
+                SlipDecodeError::FrameCompleted | SlipDecodeError::UnexpectedByte(_)
+            )
+        {
+            if !self.rejected {
+                self.rejected = true;
+                self.stats.frames_dropped = self.stats.frames_dropped.wrapping_add(1);
+                trace!("SLIP frame dropped: {}", error);
+            }
+
+            // The terminator ended the rejected frame, so that the next one starts afresh
+            if error == SlipDecodeError::InvalidFrame || self.config.resync {
+                self.drop_frame(error);
+            }
+        }
+
+        result
+    }
+
+    /// Moves the state machine forward on `value`.
+    fn advance(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        match self.state {
+            SlipDecoderState::Start => {
+                if value == self.config.end {
+                    self.state = SlipDecoderState::Append;
+                    self.count_wire(1);
+                    trace!("SLIP frame started");
+                } else {
+                    self.stats.bytes_discarded = self.stats.bytes_discarded.wrapping_add(1);
+                    self.garbage = true;
+
+                    if self.config.stray_bytes == StrayBytePolicy::Reject {
+                        return Err(SlipDecodeError::UnexpectedByte(value));
+                    }
+                }
+
+                Ok(())
+            }
+            SlipDecoderState::Append => {
+                self.count_wire(1);
+
+                self.append(value)
+            }
+            SlipDecoderState::Escape => {
+                self.state = SlipDecoderState::Append;
+                self.count_wire(1);
+
+                if self.config.passes_through(value) {
+                    self.push(ESC_CHAR)?;
+                }
+                let value = self.config.restore(value)?;
+
+                self.push(value)
+            }
+            SlipDecoderState::End => Err(SlipDecodeError::FrameCompleted),
+            SlipDecoderState::Skip => {
+                if value == self.config.end {
+                    self.state = SlipDecoderState::resumed(self.config);
+                } else {
+                    self.stats.bytes_discarded = self.stats.bytes_discarded.wrapping_add(1);
+                }
+
+                Ok(())
+            }
+            SlipDecoderState::Resume => {
+                self.state = SlipDecoderState::Append;
+                self.count_wire(1);
+                trace!("SLIP frame started");
+
+                if value == self.config.end {
+                    Ok(())
+                } else {
+                    self.append(value)
+                }
+            }
+        }
+    }
+
+    /// Moves the state machine forward on `value`, received inside a frame.
+    fn append(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        match value {
+            value if value == self.config.end => {
+                if self.config.empty_frames == EmptyFramePolicy::Report || !self.buffer.is_empty() {
+                    if !self.rejected && !self.accepts(self.buffer.as_slice()) {
+                        self.stats.frames_invalid = self.stats.frames_invalid.wrapping_add(1);
+
+                        return Err(SlipDecodeError::InvalidFrame);
+                    }
+
+                    self.state = SlipDecoderState::End;
+                    self.completed = self.metadata;
+                    trace!("SLIP frame completed: {} bytes", self.buffer.len());
+
+                    if !self.rejected {
+                        self.stats.frames_completed = self.stats.frames_completed.wrapping_add(1);
+                    }
+                } else {
+                    // The skipped empty frame ends where the next one starts
+                    self.metadata.wire_len = 1;
+                }
+            }
+            ESC_CHAR => {
+                self.state = SlipDecoderState::Escape;
+                self.stats.escapes = self.stats.escapes.wrapping_add(1);
+                self.metadata.escapes += 1;
+            }
+            _ => {
+                self.push(value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Feeds `value` into the decoder state machine, skipping the length checks of the frame.
+    ///
+    /// This is a fast path for interrupt handlers that checked `remaining_capacity` once for a
+    /// whole FIFO or DMA chunk: an ordinary byte inside a frame is appended straight to the
+    /// buffer, without checking the MTU, the maximum frame length or the overflow policy. Other
+    /// bytes go through `insert`, so that frame boundaries and escape sequences are checked as
+    /// usual.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the frame has no room left. In release builds, the byte is then
+    /// handled by the buffer, e.g. dropped by a buffer using the default
+    /// `FrameBuffer::push_unchecked`, or panicking with a `Vec`.
+    pub fn insert_unchecked(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        if self.state != SlipDecoderState::Append || value == self.config.end || value == ESC_CHAR {
+            return self.insert(value);
+        }
+
+        debug_assert!(self.remaining_capacity() > 0, "no room left in the frame");
+
+        self.count_wire(1);
+        self.buffer.push_unchecked(value);
+        if self.config.integrity_check {
+            self.integrity.update(value);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert_unchecked`.
+    #[must_use]
+    pub const fn insert_unchecked_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Feeds `value` into the decoder state machine, and returns what it changed in the frame.
+    ///
+    /// This is an alternative to `insert` for event-driven code, such as interrupt handlers, that
+    /// reacts to frame boundaries without polling the decoder after every byte.
+    pub fn insert_event(&mut self, value: u8) -> SlipEvent {
+        let started = matches!(
+            self.state,
+            SlipDecoderState::Start | SlipDecoderState::Resume
+        );
+
+        if let Err(error) = self.insert(value) {
+            return SlipEvent::FrameAborted(error);
+        }
+
+        if self.is_buffer_completed() {
+            SlipEvent::FrameCompleted
+        } else if started && self.state == SlipDecoderState::Append {
+            SlipEvent::FrameStarted
+        } else {
+            SlipEvent::None
+        }
+    }
+
+    /// Returns the worst-case work of `insert_event`.
+    #[must_use]
+    pub const fn insert_event_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Counts `len` bytes of the frame in progress on the wire.
+    ///
+    /// The first bytes counted start the frame, which takes over the garbage discarded before.
+    const fn count_wire(&mut self, len: usize) {
+        if self.metadata.wire_len == 0 {
+            self.metadata.preceded_by_garbage = self.garbage;
+            self.garbage = false;
+        }
+
+        self.metadata.wire_len += len;
+    }
+
+    /// Appends `value` to the buffer, keeping the integrity guard up to date.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        let error = if self.config.mtu.is_some_and(|mtu| self.buffer.len() >= mtu) {
+            Some(SlipDecodeError::MtuExceeded)
+        } else if self.buffer.len() >= self.max_frame_len {
+            Some(SlipDecodeError::FrameTooLong)
+        } else {
+            None
+        };
+        if let Some(error) = error {
+            if !self.rejected {
+                self.stats.frames_oversized = self.stats.frames_oversized.wrapping_add(1);
+            }
+
+            return Err(error);
+        }
+
+        if let Err(error) = self.buffer.push(value) {
+            match self.config.overflow {
+                OverflowPolicy::Reject => return Err(error),
+                OverflowPolicy::Truncate => self.truncated = true,
+                OverflowPolicy::Discard => {
+                    trace!("SLIP frame dropped: {}", error);
+                    self.drop_frame(error);
+                    self.stats.frames_dropped = self.stats.frames_dropped.wrapping_add(1);
+                }
+            }
+
+            return Ok(());
+        }
+
+        if self.config.integrity_check {
+            self.integrity.update(value);
+        }
+
+        Ok(())
+    }
+
+    /// Feeds the bytes of `first` then `second` into the decoder state machine.
+    ///
+    /// This is meant for the two regions of a wrapped-around ring buffer. Bytes are consumed up
+    /// to the end of a frame or to a rejected byte; `consumed` counts across both slices.
+    pub fn feed_split(&mut self, first: &[u8], second: &[u8]) -> FeedResult {
+        let result = self.feed(first);
+        if result.status != FeedStatus::Incomplete {
+            return result;
+        }
+
+        let result = self.feed(second);
+
+        FeedResult {
+            consumed: first.len() + result.consumed,
+            status: result.status,
+        }
+    }
+
+    /// Feeds the bytes of `iter` into the decoder state machine, e.g. straight out of a ring
+    /// buffer, without collecting them into a slice first.
+    ///
+    /// Bytes are pulled up to the end of a frame or to a rejected byte, so that the rest of an
+    /// iterator passed with `by_ref` can be fed once the frame has been handled and the decoder
+    /// reset. Returns the number of bytes consumed, or the error of the rejected byte.
+    pub fn insert_iter<I: IntoIterator<Item = u8>>(
+        &mut self,
+        iter: I,
+    ) -> Result<usize, SlipDecodeError> {
+        let mut consumed = 0;
+        if self.is_buffer_completed() {
+            return Ok(consumed);
+        }
+
+        for value in iter {
+            self.insert(value)?;
+            consumed += 1;
+
+            if self.is_buffer_completed() {
+                break;
+            }
+        }
+
+        Ok(consumed)
+    }
+
+    /// Returns the worst-case work of `insert_iter` for an iterator of `iter_len` bytes, excluding
+    /// the time spent in the iterator.
+    #[must_use]
+    pub const fn insert_iter_work_bound(iter_len: usize) -> WorkBound {
+        WorkBound::linear(iter_len)
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Returns the worst-case work of `feed_split` for `data_len` bytes across both slices.
+    #[must_use]
+    pub const fn feed_split_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, so that the caller can
+    /// continue from the leftover bytes once the frame has been handled and the decoder reset.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let mut index = 0;
+        while let Some(&value) = data.get(index) {
+            if self.is_buffer_completed() {
+                return FeedResult {
+                    consumed: index,
+                    status: FeedStatus::Completed,
+                };
+            }
+
+            let run = self.append_run(data.get(index..).unwrap_or_default());
+            if run > 0 {
+                index += run;
+                continue;
+            }
+
+            if let Err(error) = self.insert(value) {
+                return FeedResult {
+                    consumed: index + 1,
+                    status: FeedStatus::Rejected(error),
+                };
+            }
+            index += 1;
+        }
+
+        let status = if self.is_buffer_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult {
+            consumed: data.len(),
+            status,
+        }
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine, like `feed`, through
+    /// `insert_unchecked`.
+    ///
+    /// The caller has checked that `remaining_capacity` is at least the length of `data`, e.g.
+    /// once per receive FIFO chunk. Bytes are consumed up to the end of a frame or to a rejected
+    /// byte, as with `feed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `data` holds more decoded bytes than the frame has room for.
+    pub fn feed_unchecked(&mut self, data: &[u8]) -> FeedResult {
+        for (index, &value) in data.iter().enumerate() {
+            if self.is_buffer_completed() {
+                return FeedResult {
+                    consumed: index,
+                    status: FeedStatus::Completed,
+                };
+            }
+
+            if let Err(error) = self.insert_unchecked(value) {
+                return FeedResult {
+                    consumed: index + 1,
+                    status: FeedStatus::Rejected(error),
+                };
+            }
+        }
+
+        let status = if self.is_buffer_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult {
+            consumed: data.len(),
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed_unchecked` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_unchecked_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine, like `feed`, but returns a frame
+    /// held as a whole by `data` as a subslice of it.
+    ///
+    /// When the decoder is between frames and `data` holds the next frame without any escape
+    /// sequence, which is the common case for small payloads, the frame is returned as
+    /// `DecodedFrame::Borrowed` without being copied, and the decoder is left ready for the next
+    /// frame. Otherwise, the bytes are buffered as by `feed`, and a completed frame is returned as
+    /// `DecodedFrame::Buffered`, which resets the decoder once dropped.
+    ///
+    /// The frame is `Some` exactly when the status is `FeedStatus::Completed`.
+    pub fn feed_borrowed<'a>(
+        &'a mut self,
+        data: &'a [u8],
+    ) -> (FeedResult, Option<DecodedFrame<'a, B, V>>) {
+        if let Some((begin, payload)) = self.borrowed_payload(data) {
+            // The bytes before the opening terminator, if any, are skipped
+            let discarded = u32::try_from(begin.saturating_sub(1)).unwrap_or(u32::MAX);
+            self.stats.bytes_discarded = self.stats.bytes_discarded.wrapping_add(discarded);
+            self.state = SlipDecoderState::initial(self.config);
+            if begin > 1 {
+                self.garbage = true;
+            }
+            self.count_wire(payload.len() + 1 + usize::from(begin > 0));
+            let consumed = begin + payload.len() + 1;
+
+            // The frame is dropped as `insert` would, without running the validator again
+            if !self.accepts(payload) {
+                self.stats.frames_invalid = self.stats.frames_invalid.wrapping_add(1);
+                self.stats.frames_dropped = self.stats.frames_dropped.wrapping_add(1);
+                self.metadata = FrameMetadata::new();
+                self.garbage = true;
+                trace!("SLIP frame dropped: {}", SlipDecodeError::InvalidFrame);
+
+                let result = FeedResult {
+                    consumed,
+                    status: FeedStatus::Rejected(SlipDecodeError::InvalidFrame),
+                };
+
+                return (result, None);
+            }
+
+            self.stats.frames_completed = self.stats.frames_completed.wrapping_add(1);
+            self.completed = self.metadata;
+            self.metadata = FrameMetadata::new();
+            trace!("SLIP frame completed: {} bytes", payload.len());
+
+            let result = FeedResult {
+                consumed,
+                status: FeedStatus::Completed,
+            };
+
+            return (result, Some(DecodedFrame::Borrowed(payload)));
+        }
+
+        let result = self.feed(data);
+        if result.status != FeedStatus::Completed {
+            return (result, None);
+        }
+
+        match self.frame() {
+            Ok(frame) => (result, Some(DecodedFrame::Buffered(frame))),
+            Err(error) => {
+                let result = FeedResult {
+                    consumed: result.consumed,
+                    status: FeedStatus::Rejected(error),
+                };
+
+                (result, None)
+            }
+        }
+    }
+
+    /// Returns the worst-case work of `feed_borrowed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_borrowed_work_bound(data_len: usize) -> WorkBound {
+        // One pass looking for an unescaped frame, then the bytes are fed if there is none
+        WorkBound::linear(data_len).then(Self::feed_work_bound(data_len))
+    }
+
+    /// Returns the offset and the payload of a frame held as a whole by `data` without any escape
+    /// sequence, if the decoder is between frames and would take it, before any validation.
+    fn borrowed_payload<'a>(&self, data: &'a [u8]) -> Option<(usize, &'a [u8])> {
+        let end = self.config.end;
+        let begin = match self.state {
+            SlipDecoderState::Start => match data.iter().position(|&value| value == end)? {
+                0 => 1,
+                // The bytes skipped before the frame must be rejected one by one
+                _ if self.config.stray_bytes == StrayBytePolicy::Reject => return None,
+                position => position + 1,
+            },
+            SlipDecoderState::Append
+                if self.buffer.is_empty() && !self.rejected && !self.truncated =>
+            {
+                0
+            }
+            _ => return None,
+        };
+
+        let rest = data.get(begin..)?;
+        let length = find_special(rest, end, ESC_CHAR);
+        if rest.get(length) != Some(&end)
+            || length > self.length_limit()
+            || (length == 0 && self.config.empty_frames == EmptyFramePolicy::Skip)
+        {
+            return None;
+        }
+
+        Some((begin, &rest[..length]))
+    }
+
+    /// Returns the maximum number of decoded bytes a frame can hold, within the capacity of the
+    /// buffer, the maximum frame length and the MTU.
+    fn length_limit(&self) -> usize {
+        self.buffer
+            .capacity()
+            .min(self.max_frame_len)
+            .min(self.config.mtu.unwrap_or(usize::MAX))
+    }
+
+    /// Appends the ordinary bytes at the start of `data` at once, while a frame is being
+    /// accumulated and the frame has room for them.
+    ///
+    /// Returns the number of bytes appended, leaving special bytes and bytes which do not fit to
+    /// `insert`.
+    fn append_run(&mut self, data: &[u8]) -> usize {
+        if self.state != SlipDecoderState::Append {
+            return 0;
+        }
+
+        let room = self.length_limit().saturating_sub(self.buffer.len());
+        let window = data.get(..room).unwrap_or(data);
+        let run = window
+            .get(..find_special(window, self.config.end, ESC_CHAR))
+            .unwrap_or_default();
+
+        if run.is_empty() || self.buffer.extend_from_slice(run).is_err() {
+            return 0;
+        }
+        self.count_wire(run.len());
+
+        if self.config.integrity_check {
+            for &value in run {
+                self.integrity.update(value);
+            }
+        }
+
+        run.len()
+    }
+
+    /// Feeds the bytes of `buf` into the decoder state machine, like `feed`, across its chunks.
+    ///
+    /// Exactly the consumed bytes are advanced past in `buf`, so that the bytes following a
+    /// completed frame stay in `buf` for the next call, once the frame has been taken, e.g. with
+    /// `take_frame_bytes`.
+    #[cfg(feature = "bytes")]
+    pub fn feed_buf<T: Buf>(&mut self, buf: &mut T) -> FeedResult {
+        let mut consumed = 0;
+        while buf.has_remaining() {
+            let result = self.feed(buf.chunk());
+            buf.advance(result.consumed);
+            consumed += result.consumed;
+
+            if result.status != FeedStatus::Incomplete {
+                return FeedResult {
+                    consumed,
+                    status: result.status,
+                };
+            }
+        }
+
+        let status = if self.is_buffer_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult { consumed, status }
+    }
+
+    /// Returns the worst-case work of `feed_buf` for `data_len` bytes across all chunks.
+    #[cfg(feature = "bytes")]
+    #[must_use]
+    pub const fn feed_buf_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Returns a copy of the completed frame as `Bytes` and resets the decoder for the next
+    /// frame.
+    ///
+    /// Returns the error of `check_frame` if no frame is complete yet, or if the frame failed the
+    /// integrity check.
+    #[cfg(feature = "bytes")]
+    pub fn take_frame_bytes(&mut self) -> Result<Bytes, SlipDecodeError> {
+        self.take_frame_with(Bytes::copy_from_slice)
+    }
+
+    /// Feeds the bytes of a `bbqueue` read grant into the decoder state machine, like `feed`.
+    ///
+    /// Exactly the consumed bytes are released back to the queue, so that the bytes following a
+    /// completed frame stay queued for the next call, once the frame has been handled and the
+    /// decoder reset. This suits receive paths where DMA transfers land in a `bbqueue`.
+    #[cfg(feature = "bbqueue")]
+    pub fn feed_grant<Q: BbqHandle>(&mut self, grant: StreamGrantR<Q>) -> FeedResult {
+        let result = self.feed(&grant);
+        grant.release(result.consumed);
+
+        result
+    }
+
+    /// Returns the worst-case work of `feed_grant` for a grant of `grant_len` bytes.
+    #[cfg(feature = "bbqueue")]
+    #[must_use]
+    pub const fn feed_grant_work_bound(grant_len: usize) -> WorkBound {
+        WorkBound::linear(grant_len)
+    }
+
+    /// Decodes the frame held by `buf` in place, using the framing options of this decoder.
+    ///
+    /// Since decoding only ever shrinks data, the payload is unescaped within `buf` and the
+    /// decoder buffer is left untouched. `buf` holds a single frame, optionally delimited by
+    /// terminators: decoding stops at the first unescaped terminator after the payload.
+    ///
+    /// Returns the decoded payload, as a subslice of `buf`, or an error if the frame holds an
+    /// invalid escape sequence.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn decode_in_place<'b>(&self, buf: &'b mut [u8]) -> Result<&'b [u8], SlipDecodeError> {
+        let end = self.config.end;
+
+        // Skip the opening delimiters of the frame
+        let mut read_index = 0;
+        if self.config.leading_end {
+            while buf.get(read_index) == Some(&end) {
+                read_index += 1;
+            }
+        }
+
+        let mut write_index = 0;
+        let mut escaping = false;
+        while let Some(&value) = buf.get(read_index) {
+            read_index += 1;
+
+            if escaping {
+                // The escape byte was read as well, so that there is room for both
+                if self.config.passes_through(value) {
+                    set(buf, write_index, ESC_CHAR);
+                    write_index += 1;
+                }
+                set(buf, write_index, self.config.restore(value)?);
+                write_index += 1;
+                escaping = false;
+            } else if value == end {
+                break;
+            } else if value == ESC_CHAR {
+                escaping = true;
+            } else {
+                set(buf, write_index, value);
+                write_index += 1;
+            }
+        }
+
+        if escaping {
+            return Err(SlipDecodeError::MalformedFrame);
+        }
+
+        Ok(buf.get(..write_index).unwrap_or_default())
+    }
+
+    /// Returns the worst-case work of `decode_in_place` for a buffer of `buf_len` bytes.
+    #[must_use]
+    pub const fn decode_in_place_work_bound(buf_len: usize) -> WorkBound {
+        WorkBound::linear(buf_len)
+    }
+
+    /// Drops the current frame and waits for the next terminator.
+    fn discard(&mut self) {
+        self.state = SlipDecoderState::Start;
+        self.buffer.clear();
+        self.integrity = IntegrityGuard::new();
+        self.truncated = false;
+        self.rejected = false;
+        self.metadata = FrameMetadata::new();
+        self.garbage = true;
+    }
+
+    /// Drops the frame rejected with `error`, and skips the rest of it up to its terminator,
+    /// unless the terminator was the rejected byte.
+    fn drop_frame(&mut self, error: SlipDecodeError) {
+        self.discard();
+        self.state = match error {
+            SlipDecodeError::InvalidFrame => SlipDecoderState::resumed(self.config),
+            SlipDecodeError::InvalidEscape(value) if value == self.config.end => {
+                SlipDecoderState::resumed(self.config)
+            }
+            _ => SlipDecoderState::Skip,
+        };
+    }
+
+    /// Drops the frame `feed` rejected with `error`, so that the decoder resumes with the next
+    /// frame, even if the rest of the rejected frame only comes with the next chunk of bytes.
+    pub(crate) fn resync_rejected(&mut self, error: SlipDecodeError) {
+        if matches!(error, SlipDecodeError::UnexpectedByte(_)) {
+            // A byte outside a frame leaves no frame to drop
+            self.reset();
+        } else {
+            self.drop_frame(error);
+        }
+    }
+
+    /// Resets the decoder to its initial state.
+    pub fn reset(&mut self) {
+        self.state = SlipDecoderState::initial(self.config);
+        self.buffer.clear();
+        self.integrity = IntegrityGuard::new();
+        self.truncated = false;
+        self.rejected = false;
+        self.metadata = FrameMetadata::new();
+    }
+
+    /// Replaces the buffer of the decoder with `buffer`, and returns the previous one.
+    ///
+    /// The decoder is reset, as by `reset`, so that the next frame is stored in `buffer`, which
+    /// should be empty. This hands a completed frame over along with its buffer, without copying
+    /// it, e.g. to a consumer of a `FramePool`.
+    pub fn replace_buffer(&mut self, buffer: B) -> B {
+        let previous = core::mem::replace(&mut self.buffer, buffer);
+        self.reset();
+
+        previous
+    }
+
+    /// Returns the worst-case work of `reset`.
+    #[must_use]
+    pub const fn reset_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Returns where the decoder stands in the byte stream.
+    ///
+    /// Supervisory code can use it to know whether a frame is in flight, e.g. before powering down
+    /// the receiver: no byte would be lost while the decoder is `Idle`.
+    #[must_use]
+    pub fn status(&self) -> DecoderStatus {
+        match self.state {
+            SlipDecoderState::Start | SlipDecoderState::Resume => DecoderStatus::Idle,
+            SlipDecoderState::Append
+                if self.buffer.is_empty() && !self.rejected && !self.truncated =>
+            {
+                DecoderStatus::Idle
+            }
+            SlipDecoderState::Append | SlipDecoderState::Skip => DecoderStatus::InFrame,
+            SlipDecoderState::Escape => DecoderStatus::Escaping,
+            SlipDecoderState::End => DecoderStatus::FrameReady,
+        }
+    }
+
+    /// Returns `true` if the decoder has received a complete SLIP frame.
+    #[must_use]
+    pub fn is_buffer_completed(&self) -> bool {
+        self.state == SlipDecoderState::End
+    }
+
+    /// Returns the statistics of the link since the decoder was created or its statistics were
+    /// last reset.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.stats
+    }
+
+    /// Resets the statistics of the link.
+    ///
+    /// The decoder state is left untouched.
+    pub const fn reset_stats(&mut self) {
+        self.stats = SlipStats::new();
+    }
+
+    /// Returns the metadata of the last completed frame.
+    ///
+    /// The metadata stays available once the decoder is reset, until the next frame completes.
+    #[must_use]
+    pub const fn frame_metadata(&self) -> FrameMetadata {
+        self.completed
+    }
+
+    /// Returns `true` if bytes of the current frame were dropped for lack of space.
+    ///
+    /// This only happens with `OverflowPolicy::Truncate`.
+    #[must_use]
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Returns a slice of the decoded bytes accumulated so far.
+    #[must_use]
+    pub fn get_buffer(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    /// Consumes the decoder, returning its buffer with the decoded bytes accumulated so far.
+    #[must_use]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Returns a reference to the frame buffer.
+    pub(crate) const fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the frame buffer.
+    pub(crate) const fn buffer_mut(&mut self) -> &mut B {
+        &mut self.buffer
+    }
+
+    /// Checks that a frame is complete and, if the integrity check is enabled, that it still
+    /// matches its integrity guard, before it is handed over.
+    ///
+    /// Returns `Err(SlipDecodeError::FrameIncomplete)`, leaving the decoder untouched, if no frame
+    /// is complete yet. Returns `Err(SlipDecodeError::IntegrityCheckFailed)` if the frame was
+    /// corrupted since it was decoded, in which case it is dropped and the decoder reset.
+    pub fn check_frame(&mut self) -> Result<(), SlipDecodeError> {
+        if !self.is_buffer_completed() {
+            return Err(SlipDecodeError::FrameIncomplete);
+        }
+
+        if self.config.integrity_check
+            && self.integrity != IntegrityGuard::of(self.buffer.as_slice())
+        {
+            self.stats.frames_dropped = self.stats.frames_dropped.wrapping_add(1);
+            trace!(
+                "SLIP frame dropped: {}",
+                SlipDecodeError::IntegrityCheckFailed
+            );
+            self.reset();
+
+            return Err(SlipDecodeError::IntegrityCheckFailed);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a guard over the completed frame, which resets the decoder for the next frame
+    /// once dropped.
+    ///
+    /// Returns the error of `check_frame` if no frame is complete yet, or if the frame failed the
+    /// integrity check.
+    pub fn frame(&mut self) -> Result<FrameRef<'_, B, V>, SlipDecodeError> {
+        self.check_frame()?;
+
+        Ok(FrameRef { decoder: self })
+    }
+
+    /// Calls `f` with the completed frame, then resets the decoder for the next frame.
+    ///
+    /// Returns the result of `f`, or the error of `check_frame` without calling `f` if no frame
+    /// is complete yet, or if the frame failed the integrity check.
+    pub fn take_frame_with<R>(&mut self, f: impl FnOnce(&[u8]) -> R) -> Result<R, SlipDecodeError> {
+        self.check_frame()?;
+
+        let result = f(self.buffer.as_slice());
+        self.reset();
+
+        Ok(result)
+    }
+
+    /// Copies the completed frame into `out`, then resets the decoder for the next frame.
+    ///
+    /// Returns the length of the frame, or the error of `check_frame` if no frame is complete yet,
+    /// or if the frame failed the integrity check. Returns
+    /// `Err(SlipDecodeError::OutputTooSmall)` if `out` cannot hold the frame, which then stays
+    /// pending.
+    pub fn read_frame_into(&mut self, out: &mut [u8]) -> Result<usize, SlipDecodeError> {
+        self.check_frame()?;
+
+        let frame = self.buffer.as_slice();
+        let Some(output) = out.get_mut(..frame.len()) else {
+            return Err(SlipDecodeError::OutputTooSmall(frame.len()));
+        };
+        output.copy_from_slice(frame);
+        self.reset();
+
+        Ok(output.len())
+    }
+
+    /// Returns the number of decoded bytes accumulated so far.
+    ///
+    /// This matches the length of `get_buffer`, except for buffers which do not store the bytes,
+    /// such as the one of `SlipValidator`.
+    #[must_use]
+    pub fn frame_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the number of decoded bytes accumulated so far, as `frame_len`.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if no decoded byte was accumulated since the last frame.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Limits frames to `max_frame_len` decoded bytes, e.g. to enforce the MTU negotiated on a
+    /// link, below the capacity of the buffer.
+    ///
+    /// Longer frames are dropped with `SlipDecodeError::FrameTooLong`, whatever the overflow
+    /// policy, and counted in `SlipStats::frames_oversized`. The limit applies from the next
+    /// decoded byte on.
+    pub const fn set_max_frame_len(&mut self, max_frame_len: usize) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    /// Returns the maximum number of decoded bytes a frame can hold, as set by
+    /// `set_max_frame_len`, regardless of the capacity of the buffer.
+    #[must_use]
+    pub const fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Aborts frames left half-received for more than `timeout` ticks between two bytes, e.g. when
+    /// bytes are lost on the wire, or disables the timeout with `None`.
+    ///
+    /// Ticks are in the unit of the timestamps given to `insert_at`, `feed_at` and `tick`, which
+    /// only need to increase monotonically, and may wrap around. An aborted frame is counted in
+    /// `SlipStats::frames_timed_out`, and the decoder resynchronizes on the next terminator.
+    pub const fn set_inter_byte_timeout(&mut self, timeout: Option<u64>) {
+        self.inter_byte_timeout = timeout;
+    }
+
+    /// Returns the inter-byte timeout, as set by `set_inter_byte_timeout`.
+    #[must_use]
+    pub const fn inter_byte_timeout(&self) -> Option<u64> {
+        self.inter_byte_timeout
+    }
+
+    /// Returns `true` if `frame` is accepted by the validator.
+    fn accepts(&self, frame: &[u8]) -> bool {
+        validate_nounwind(&self.validator, frame)
+    }
+
+    /// Aborts the frame in progress if no byte was received within the inter-byte timeout before
+    /// `now`.
+    ///
+    /// Returns `true` if a frame was aborted. Calling this periodically, e.g. from a timer, also
+    /// aborts a frame when the line goes quiet altogether.
+    pub fn tick(&mut self, now: u64) -> bool {
+        let Some(timeout) = self.inter_byte_timeout else {
+            return false;
+        };
+
+        let in_frame = matches!(
+            self.status(),
+            DecoderStatus::InFrame | DecoderStatus::Escaping
+        );
+        if !in_frame || now.wrapping_sub(self.last_byte_at) <= timeout {
+            return false;
+        }
+
+        if !self.rejected {
+            self.stats.frames_dropped = self.stats.frames_dropped.wrapping_add(1);
+        }
+        self.stats.frames_timed_out = self.stats.frames_timed_out.wrapping_add(1);
+        trace!("SLIP frame timed out");
+        self.discard();
+
+        true
+    }
+
+    /// Returns the worst-case work of `tick`.
+    #[must_use]
+    pub const fn tick_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Feeds `value`, received at `now`, into the decoder state machine.
+    ///
+    /// This behaves as `insert`, after aborting the frame in progress if the inter-byte timeout
+    /// has elapsed since the previous byte.
+    pub fn insert_at(&mut self, value: u8, now: u64) -> Result<(), SlipDecodeError> {
+        self.tick(now);
+        self.last_byte_at = now;
+
+        let frames_completed = self.stats.frames_completed;
+        let result = self.insert(value);
+        self.record_frame_at(frames_completed, now);
+
+        result
+    }
+
+    /// Returns the worst-case work of `insert_at`.
+    #[must_use]
+    pub const fn insert_at_work_bound() -> WorkBound {
+        Self::tick_work_bound().then(Self::insert_work_bound())
+    }
+
+    /// Feeds the bytes of `data`, received at `now`, into the decoder state machine.
+    ///
+    /// This behaves as `feed`, after aborting the frame in progress if the inter-byte timeout has
+    /// elapsed since the previous bytes. The bytes of `data` are taken as received together.
+    pub fn feed_at(&mut self, data: &[u8], now: u64) -> FeedResult {
+        self.tick(now);
+        self.last_byte_at = now;
+
+        let frames_completed = self.stats.frames_completed;
+        let result = self.feed(data);
+        self.record_frame_at(frames_completed, now);
+
+        result
+    }
+
+    /// Returns the worst-case work of `feed_at` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_at_work_bound(data_len: usize) -> WorkBound {
+        Self::tick_work_bound().then(Self::feed_work_bound(data_len))
+    }
+
+    /// Records `now` as the time of the last valid frame, if frames were completed since the
+    /// count of `frames_completed`.
+    const fn record_frame_at(&mut self, frames_completed: u32, now: u64) {
+        if self.stats.frames_completed != frames_completed {
+            self.last_frame_at = Some(now);
+        }
+    }
+
+    /// Returns the timestamp of the last valid frame completed by `insert_at` or `feed_at`, or
+    /// `None` if no frame was received yet.
+    ///
+    /// Unlike the frame in progress, this survives `reset`, as it describes the link rather than
+    /// the frame.
+    #[must_use]
+    pub const fn last_frame_at(&self) -> Option<u64> {
+        self.last_frame_at
+    }
+
+    /// Returns the number of ticks elapsed since the last valid frame, at `now`, or `None` if no
+    /// frame was received yet.
+    ///
+    /// A supervisor can compare it against a deadline to detect a dead link, e.g. to reset a
+    /// radio. Ticks are in the unit of the timestamps given to `insert_at` and `feed_at`, and may
+    /// wrap around. A peer sending `keepalive::Keepalive` empty frames keeps it low on an idle
+    /// link, provided this decoder reports empty frames.
+    #[must_use]
+    pub const fn time_since_last_frame(&self, now: u64) -> Option<u64> {
+        match self.last_frame_at {
+            Some(last_frame_at) => Some(now.wrapping_sub(last_frame_at)),
+            None => None,
+        }
+    }
+
+    /// Returns the maximum number of decoded bytes a frame can hold.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Returns the number of decoded bytes the frame in progress can still take, within the
+    /// capacity of the buffer, the limit set by `set_max_frame_len` and the MTU of the
+    /// configuration.
+    ///
+    /// Flow-control code can check it against the bytes still in flight, e.g. to assert RTS or to
+    /// send XOFF before the frame overflows rather than after it was dropped.
+    #[must_use]
+    pub fn remaining_capacity(&self) -> usize {
+        self.length_limit().saturating_sub(self.len())
+    }
+
+    /// Returns a slice of the decoded bytes after verifying the integrity guard.
+    ///
+    /// Returns `Err(SlipDecodeError::IntegrityCheckFailed)` if the integrity check is enabled and
+    /// the buffer no longer matches its running checksum, or if the canary has been overwritten.
+    pub fn get_verified_buffer(&self) -> Result<&[u8], SlipDecodeError> {
+        if self.config.integrity_check
+            && self.integrity != IntegrityGuard::of(self.buffer.as_slice())
+        {
+            return Err(SlipDecodeError::IntegrityCheckFailed);
+        }
+
+        Ok(self.get_buffer())
+    }
+}
+
+/// A completed frame, borrowed from its decoder.
+///
+/// This guard dereferences to the payload of the frame, and resets the decoder when dropped, so
+/// that the decoder cannot be left holding a handled frame.
+pub struct FrameRef<'a, B: FrameBuffer, V: FrameValidator = AcceptAll> {
+    decoder: &'a mut SlipBufferDecoder<B, V>,
+}
+
+/// A completed frame returned by `SlipBufferDecoder::feed_borrowed`.
+pub enum DecodedFrame<'a, B: FrameBuffer, V: FrameValidator = AcceptAll> {
+    /// The frame held no escape sequence, and is borrowed from the input as is.
+    Borrowed(&'a [u8]),
+    /// The frame was decoded into the decoder buffer, and resets the decoder once dropped.
+    Buffered(FrameRef<'a, B, V>),
+}
+
+/// Implementation of `Deref` for `DecodedFrame`.
+impl<B: FrameBuffer, V: FrameValidator> Deref for DecodedFrame<'_, B, V> {
+    type Target = [u8];
+
+    /// Dereferences to the payload of the frame.
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(payload) => payload,
+            Self::Buffered(frame) => frame,
+        }
+    }
+}
+
+/// Implementation of `Deref` for `FrameRef`.
+impl<B: FrameBuffer, V: FrameValidator> Deref for FrameRef<'_, B, V> {
+    type Target = [u8];
+
+    /// Dereferences to the payload of the frame.
+    fn deref(&self) -> &Self::Target {
+        self.decoder.get_buffer()
+    }
+}
+
+/// Implementation of `Drop` for `FrameRef`.
+impl<B: FrameBuffer, V: FrameValidator> Drop for FrameRef<'_, B, V> {
+    /// Resets the decoder for the next frame.
+    fn drop(&mut self) {
+        self.decoder.reset();
+    }
+}
+
+/// Default implementation for `SlipBufferDecoder`.
+impl<B: FrameBuffer + Default> Default for SlipBufferDecoder<B> {
+    /// Creates a decoder over an empty buffer, using the default SLIP configuration.
+    fn default() -> Self {
+        Self::with_buffer(B::default(), SlipConfig::new())
+    }
+}
+
+/// Implementation of `Debug` for `SlipBufferDecoder`.
+impl<B: FrameBuffer, V: FrameValidator> Debug for SlipBufferDecoder<B, V> {
+    /// Formats the state of the decoder, with the bytes decoded so far.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlipBufferDecoder")
+            .field("state", &self.state)
+            .field("buffer", &self.buffer.as_slice())
+            .field("config", &self.config)
+            .field("integrity", &self.integrity)
+            .field("truncated", &self.truncated)
+            .field("rejected", &self.rejected)
+            .field("max_frame_len", &self.max_frame_len)
+            .field("inter_byte_timeout", &self.inter_byte_timeout)
+            .field("last_byte_at", &self.last_byte_at)
+            .field("last_frame_at", &self.last_frame_at)
+            .field("validator", &type_name::<V>())
+            .field("stats", &self.stats)
+            .field("metadata", &self.metadata)
+            .field("completed", &self.completed)
+            .field("garbage", &self.garbage)
+            .finish()
+    }
+}
+
+/// Runs `validator` on `frame`, behind a boundary that cannot unwind.
+///
+/// Validators are foreign to the decoding paths, which are checked not to panic: a panicking
+/// validator aborts instead of unwinding through the decoder. The function is never called from
+/// C code, so that the slice crossing the boundary is fine.
+#[allow(improper_ctypes_definitions)]
+extern "C" fn validate_nounwind<V: FrameValidator>(validator: &V, frame: &[u8]) -> bool {
+    validator.validate(frame)
+}
+
+/// Implementation of `Deref` for `SlipBufferDecoder`.
+///
+/// This allows treating a decoder as a byte slice of the decoded buffer.
+impl<B: FrameBuffer, V: FrameValidator> Deref for SlipBufferDecoder<B, V> {
+    type Target = [u8];
+
+    /// Dereferences to the decoded buffer slice.
+    fn deref(&self) -> &Self::Target {
+        self.get_buffer()
+    }
+}
+
+/// Implementation of `Extend` for `SlipBufferDecoder`.
+///
+/// This lets the decoder stand in for any byte sink in generic code. Errors cannot be returned
+/// through `Extend`: rejected bytes only show in `stats` and `status`, and the bytes following a
+/// completed frame are rejected until the decoder is reset. `insert_iter` is the fallible
+/// counterpart, which stops at the end of a frame.
+impl<B: FrameBuffer, V: FrameValidator> Extend<u8> for SlipBufferDecoder<B, V> {
+    /// Feeds every byte of `iter` into the decoder state machine.
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for value in iter {
+            let _ = self.insert(value);
+        }
+    }
+}
+
+/// Implementation of `Extend` over byte references for `SlipBufferDecoder`.
+impl<'a, B: FrameBuffer, V: FrameValidator> Extend<&'a u8> for SlipBufferDecoder<B, V> {
+    /// Feeds every byte of `iter` into the decoder state machine.
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Where a frame reader or iterator stands after a rejected frame.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Resync {
+    /// No byte of a rejected frame is left.
+    #[default]
+    Idle,
+    /// The rest of the rejected frame is skipped up to its terminator.
+    Skip,
+    /// The terminator of the rejected frame was skipped, and may also open the next frame.
+    Resume,
+}
+
+/// The resynchronization of a frame reader or iterator after a rejected frame.
+///
+/// Once a frame is rejected, the rest of it is skipped up to its terminator, so that decoding
+/// resumes with the next frame, whether it is opened by a terminator of its own or not, instead
+/// of taking the terminator for the start of a frame.
+#[derive(Default)]
+pub(crate) struct FrameResync {
+    config: SlipConfig,
+    state: Resync,
+}
+
+impl FrameResync {
+    /// Creates the resynchronization of a decoder using the framing options of `config`.
+    pub(crate) const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            config,
+            state: Resync::Idle,
+        }
+    }
+
+    /// Resets `decoder` after it rejected a frame with `error`, and starts skipping the rest of
+    /// the frame.
+    pub(crate) fn rejected<const MAX_LENGTH: usize>(
+        &mut self,
+        decoder: &mut SlipDecoder<MAX_LENGTH>,
+        error: SlipDecodeError,
+    ) {
+        decoder.reset();
+        self.state = match error {
+            // A byte outside a frame leaves no frame to skip
+            SlipDecodeError::UnexpectedByte(_) => Resync::Idle,
+            // The frame was rejected at its terminator
+            SlipDecodeError::InvalidEscape(value) if value == self.config.end_char() => {
+                Resync::Resume
+            }
+            SlipDecodeError::InvalidFrame => Resync::Resume,
+            _ => Resync::Skip,
+        };
+    }
+
+    /// Skips the bytes at the start of `data` left by a rejected frame, and prepares `decoder`
+    /// for the next frame.
+    ///
+    /// Returns the number of bytes skipped. More bytes are needed while `is_pending` returns
+    /// `true`.
+    pub(crate) fn skip<const MAX_LENGTH: usize>(
+        &mut self,
+        decoder: &mut SlipDecoder<MAX_LENGTH>,
+        data: &[u8],
+    ) -> usize {
+        let end = self.config.end_char();
+        let mut skipped = 0;
+        if self.state == Resync::Skip {
+            let Some(position) = data.iter().position(|&value| value == end) else {
+                return data.len();
+            };
+
+            skipped = position + 1;
+            self.state = Resync::Resume;
+        }
+        if self.state == Resync::Resume
+            && let Some(&value) = data.get(skipped)
+        {
+            // A frame without a terminator of its own is opened by the skipped one
+            if value != end && self.config.leading_end() {
+                let _ = decoder.insert(end);
+            }
+            self.state = Resync::Idle;
+        }
+
+        skipped
+    }
+
+    /// Returns `true` if bytes of a rejected frame may still have to be skipped.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.state != Resync::Idle
+    }
+}
+
+/// An iterator over the SLIP frames contained in a byte slice.
+///
+/// This struct decodes back-to-back frames, yielding each decoded payload or the error that
+/// interrupted it. Decoding resumes with the next frame once the rest of a rejected frame is
+/// skipped, and the bytes of a trailing unfinished frame are ignored.
+pub struct SlipFrameIter<'a, const MAX_LENGTH: usize> {
+    data: &'a [u8],
+    decoder: SlipDecoder<MAX_LENGTH>,
+    resync: FrameResync,
+}
+
+impl<'a, const MAX_LENGTH: usize> SlipFrameIter<'a, MAX_LENGTH> {
+    /// Creates an iterator over the frames of `data`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self::with_config(data, SlipConfig::new())
+    }
+
+    /// Creates an iterator over the frames of `data`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(data: &'a [u8], config: SlipConfig) -> Self {
+        Self {
+            data,
+            decoder: SlipDecoder::with_config(config),
+            resync: FrameResync::with_config(config),
+        }
+    }
+}
+
+/// Implementation of `Iterator` for `SlipFrameIter`.
+impl<const MAX_LENGTH: usize> Iterator for SlipFrameIter<'_, MAX_LENGTH> {
+    type Item = Result<Vec<u8, MAX_LENGTH>, SlipDecodeError>;
+
+    /// Decodes the next frame.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.resync.is_pending() {
+            let skipped = self.resync.skip(&mut self.decoder, self.data);
+            self.data = &self.data[skipped..];
+        }
+
+        let result = self.decoder.feed(self.data);
+        self.data = &self.data[result.consumed..];
+
+        let item = match result.status {
+            FeedStatus::Incomplete => return None,
+            FeedStatus::Completed => self.decoder.take_frame(),
+            FeedStatus::Rejected(error) => {
+                self.resync.rejected(&mut self.decoder, error);
+
+                Err(error)
+            }
+        };
+
+        Some(item)
+    }
+}
+
+/// An iterator over the frames completed by a chunk of bytes, as returned by
+/// `SlipDecoder::frames`.
+///
+/// This struct feeds the chunk into a borrowed decoder, yielding each decoded payload or the
+/// error that interrupted it. Decoding resumes with the next frame once the rest of a rejected
+/// frame is skipped, in this chunk or the next one.
+///
+/// The frames and errors are reported to the observer `O`, as for `SlipCodec::with_observer`.
+pub struct SlipFrames<'a, const MAX_LENGTH: usize, O = ()> {
+    decoder: &'a mut SlipDecoder<MAX_LENGTH>,
+    data: &'a [u8],
+    observer: O,
+}
+
+impl<'a, const MAX_LENGTH: usize, O: SlipObserver> SlipFrames<'a, MAX_LENGTH, O> {
+    /// Creates an iterator feeding `data` into `decoder`, reporting to `observer`.
+    pub(crate) const fn with_observer(
+        decoder: &'a mut SlipDecoder<MAX_LENGTH>,
+        data: &'a [u8],
+        observer: O,
+    ) -> Self {
+        Self {
+            decoder,
+            data,
+            observer,
+        }
+    }
+}
+
+/// Implementation of `Iterator` for `SlipFrames`.
+impl<const MAX_LENGTH: usize, O: SlipObserver> Iterator for SlipFrames<'_, MAX_LENGTH, O> {
+    type Item = Result<Vec<u8, MAX_LENGTH>, SlipDecodeError>;
+
+    /// Decodes the next frame.
+    fn next(&mut self) -> Option<Self::Item> {
+        let discarded = self.decoder.stats().bytes_discarded;
+        let result = self.decoder.feed(self.data);
+        self.data = &self.data[result.consumed..];
+        observe_feed(
+            &self.observer,
+            result,
+            self.decoder.stats().bytes_discarded.wrapping_sub(discarded),
+            self.decoder.frame_len(),
+        );
+
+        let item = match result.status {
+            FeedStatus::Incomplete => return None,
+            FeedStatus::Completed => self.decoder.take_frame(),
+            FeedStatus::Rejected(error) => {
+                self.decoder.resync_rejected(error);
+
+                Err(error)
+            }
+        };
+
+        Some(item)
+    }
+}
+
+/// Implementation of `FusedIterator` for `SlipFrames`.
+impl<const MAX_LENGTH: usize, O: SlipObserver> FusedIterator for SlipFrames<'_, MAX_LENGTH, O> {}
+
+/// An iterator over the boundaries of the SLIP frames contained in a byte slice.
+///
+/// This struct yields the range of every complete frame of `data`, terminators excluded, without
+/// decoding or copying it, so that frames can be processed where they were received, e.g. with
+/// `decode_in_place`. Frames are delimited by terminators only: escape sequences are left to the
+/// decoding step, and empty frames are skipped. `data` is expected to start at a frame boundary,
+/// which holds when the trailing bytes of a buffer are carried over in front of the next one.
+pub struct SlipFrameBounds<'a> {
+    data: &'a [u8],
+    end: u8,
+    position: usize,
+}
+
+impl<'a> SlipFrameBounds<'a> {
+    /// Creates an iterator over the frame boundaries of `data`, using the default SLIP
+    /// configuration.
+    #[must_use]
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self::with_config(data, SlipConfig::new())
+    }
+
+    /// Creates an iterator over the frame boundaries of `data`, using the framing options of
+    /// `config`.
+    #[must_use]
+    pub const fn with_config(data: &'a [u8], config: SlipConfig) -> Self {
+        Self {
+            data,
+            end: config.end,
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes following the last terminator of `data`.
+    ///
+    /// These are the bytes of an unfinished frame, which no range covers.
+    #[must_use]
+    pub fn trailing_len(&self) -> usize {
+        self.data
+            .iter()
+            .rev()
+            .position(|&value| value == self.end)
+            .unwrap_or(self.data.len())
+    }
+
+    /// Returns the worst-case work of scanning a buffer of `data_len` bytes, across all calls to
+    /// `next` and `trailing_len`.
+    #[must_use]
+    pub const fn scan_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len).then(WorkBound::linear(data_len))
+    }
+}
+
+/// Implementation of `Iterator` for `SlipFrameBounds`.
+impl Iterator for SlipFrameBounds<'_> {
+    type Item = Range<usize>;
+
+    /// Returns the range of the next complete frame.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.position;
+            let len = self.data[start..]
+                .iter()
+                .position(|&value| value == self.end)?;
+            self.position = start + len + 1;
+
+            if len > 0 {
+                return Some(start..start + len);
+            }
+        }
+    }
+}
+
+/// Implementation of `FusedIterator` for `SlipFrameBounds`.
+///
+/// The iterator keeps returning `None` once no terminator is left.
+impl FusedIterator for SlipFrameBounds<'_> {}
+
+/// An iterator adapter decoding the SLIP frames of a byte iterator.
+///
+/// This struct pulls bytes from the wrapped iterator until a frame is complete, then yields the
+/// decoded payload or the error that interrupted it. Decoding resumes with the next frame once
+/// the rest of a rejected frame is skipped, and the bytes of a trailing unfinished frame are
+/// dropped. A fallible byte source can be adapted with `map_while(Result::ok)`.
+pub struct SlipDecodeIter<I, const MAX_LENGTH: usize> {
+    inner: I,
+    decoder: SlipDecoder<MAX_LENGTH>,
+    resync: FrameResync,
+}
+
+impl<I: Iterator<Item = u8>, const MAX_LENGTH: usize> SlipDecodeIter<I, MAX_LENGTH> {
+    /// Creates an iterator decoding the bytes of `inner`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(inner: I) -> Self {
+        Self::with_config(inner, SlipConfig::new())
+    }
+
+    /// Creates an iterator decoding the bytes of `inner`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(inner: I, config: SlipConfig) -> Self {
+        Self {
+            inner,
+            decoder: SlipDecoder::with_config(config),
+            resync: FrameResync::with_config(config),
+        }
+    }
+}
+
+/// Implementation of `Iterator` for `SlipDecodeIter`.
+impl<I: Iterator<Item = u8>, const MAX_LENGTH: usize> Iterator for SlipDecodeIter<I, MAX_LENGTH> {
+    type Item = Result<Vec<u8, MAX_LENGTH>, SlipDecodeError>;
+
+    /// Decodes the next frame.
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in self.inner.by_ref() {
+            if self.resync.is_pending()
+                && self.resync.skip(&mut self.decoder, slice::from_ref(&value)) > 0
+            {
+                continue;
+            }
+
+            return match self.decoder.insert(value) {
+                Ok(()) if self.decoder.is_buffer_completed() => Some(self.decoder.take_frame()),
+                Ok(()) => continue,
+                Err(error) => {
+                    self.resync.rejected(&mut self.decoder, error);
+
+                    Some(Err(error))
+                }
+            };
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bbqueue")]
+    use bbqueue::nicknames::Churrasco;
+    use proptest::collection;
+    use proptest::prelude::any;
+    use proptest::prop_assert;
+    use proptest::prop_assert_eq;
+    use proptest::proptest;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::ChunkedSlipEncoder;
+    use crate::slip::DecodedFrame;
+    use crate::slip::DecoderStatus;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::ESC_ESC_CHAR;
+    use crate::slip::EmptyFramePolicy;
+    use crate::slip::EscapePolicy;
+    use crate::slip::FeedResult;
+    use crate::slip::FeedStatus;
+    use crate::slip::FrameMetadata;
+    use crate::slip::OverflowPolicy;
+    use crate::slip::RFC1055_MTU;
+    use crate::slip::Rfc1055Decoder;
+    use crate::slip::RingFrame;
+    use crate::slip::RingRegion;
+    use crate::slip::SNAPSHOT_HEADER_LENGTH;
+    #[cfg(any(feature = "allocator-api", feature = "heapless"))]
+    use crate::slip::SlipBufferDecoder;
+    use crate::slip::SlipConfig;
+    use crate::slip::SlipDecodeIter;
+    use crate::slip::SlipDecoder;
+    use crate::slip::SlipDecoderRef;
+    use crate::slip::SlipDecoderSnapshot;
+    use crate::slip::SlipDecoderState;
+    use crate::slip::SlipEncodeIter;
+    use crate::slip::SlipEncoder;
+    use crate::slip::SlipEvent;
+    use crate::slip::SlipFrameBounds;
+    use crate::slip::SlipFrameIter;
+    use crate::slip::SlipStats;
+    use crate::slip::SlipValidator;
+    #[cfg(feature = "alloc")]
+    use crate::slip::SlipVecDecoder;
+    use crate::slip::StrayBytePolicy;
+    use crate::slip::XOFF;
+    use crate::slip::XON;
+    use crate::slip::XON_XOFF_CONTROLS;
+    use crate::slip::max_encoded_len;
+    use crate::wcet::WorkBound;
+    use noalloc_vec_rs::vec::Vec;
+
+    #[test]
+    fn test_encode() {
+        let mut array = Vec::<u8, 12>::from([0x00, 0x01, 0x02, 0x03]);
+
+        let result = SlipEncoder::default().encode(&mut array);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(*array, [END_CHAR, 0x00, 0x01, 0x02, 0x03, END_CHAR]);
+    }
+
+    #[test]
+    fn test_encode_empty() {
+        let mut array = Vec::<u8, 12>::new();
+
+        let result = SlipEncoder::default().encode(&mut array);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(*array, [END_CHAR, END_CHAR]);
+    }
+
+    #[test]
+    fn test_encode_with_escape_characters() {
+        let mut array = Vec::<u8, 12>::from([END_CHAR, ESC_CHAR, ESC_END_CHAR, ESC_ESC_CHAR]);
+
+        let result = SlipEncoder::default().encode(&mut array);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *array,
+            [
+                END_CHAR,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                ESC_CHAR,
+                ESC_ESC_CHAR,
+                ESC_END_CHAR,
+                ESC_ESC_CHAR,
+                END_CHAR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut slip_decoder = SlipDecoder::<1>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+
+        assert!(slip_decoder.is_buffer_completed());
+
+        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+    }
+
+    #[test]
+    fn test_decode_with_escape_characters() {
+        let mut slip_decoder = SlipDecoder::<6>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(ESC_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+
+        let result = slip_decoder.insert(ESC_END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(ESC_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+
+        let result = slip_decoder.insert(ESC_ESC_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+
+        assert!(slip_decoder.is_buffer_completed());
+
+        assert_eq!(slip_decoder.get_buffer(), &[END_CHAR, ESC_CHAR]);
+    }
+
+    #[test]
+    fn test_decode_with_bad_escape_character() {
+        let mut slip_decoder = SlipDecoder::<1>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(ESC_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Err(SlipDecodeError::InvalidEscape(0x00)));
+    }
+
+    #[test]
+    fn test_decode_empty() {
+        let mut slip_decoder = SlipDecoder::<0>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+
+        assert!(slip_decoder.is_buffer_completed());
+
+        assert!(slip_decoder.get_buffer().is_empty());
+    }
+
+    #[test]
+    fn test_decode_and_reset() {
+        let mut slip_decoder = SlipDecoder::<1>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+
+        assert!(slip_decoder.is_buffer_completed());
+
+        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+
+        slip_decoder.reset();
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        assert!(!slip_decoder.is_buffer_completed());
+    }
+
+    #[test]
+    fn test_decode_with_not_enough_space() {
+        let mut slip_decoder = SlipDecoder::<1>::default();
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Ok(()));
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Err(SlipDecodeError::BufferFull));
+    }
+
+    #[test]
+    fn test_encode_with_newline_terminator() {
+        let config = SlipConfig::new().with_end_char(b'\n');
+        let mut array = Vec::<u8, 12>::from([b'a', b'\n', END_CHAR, ESC_CHAR]);
+
+        let result = SlipEncoder::with_config(config).encode(&mut array);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *array,
+            [
+                b'\n',
+                b'a',
+                ESC_CHAR,
+                ESC_END_CHAR,
+                END_CHAR,
+                ESC_CHAR,
+                ESC_ESC_CHAR,
+                b'\n'
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_without_leading_end() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let mut array = Vec::<u8, 12>::from([0x00, END_CHAR]);
+
+        let result = SlipEncoder::with_config(config).encode(&mut array);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(*array, [0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+    }
+
+    #[test]
+    fn test_decode_with_newline_terminator() {
+        let config = SlipConfig::new().with_end_char(b'\n');
+        let mut slip_decoder = SlipDecoder::<3>::with_config(config);
+
+        for value in [b'\n', END_CHAR, ESC_CHAR, ESC_END_CHAR, b'a', b'\n'] {
+            assert_eq!(slip_decoder.insert(value), Ok(()));
+        }
+
+        assert!(slip_decoder.is_buffer_completed());
+        assert_eq!(slip_decoder.get_buffer(), &[END_CHAR, b'\n', b'a']);
+    }
+
+    #[test]
+    fn test_decode_without_leading_end() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let mut slip_decoder = SlipDecoder::<1>::with_config(config);
+
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+
+        let result = slip_decoder.insert(0x00);
+        assert_eq!(result, Ok(()));
+
+        let result = slip_decoder.insert(END_CHAR);
+        assert_eq!(result, Ok(()));
+        assert!(slip_decoder.is_buffer_completed());
+        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+
+        slip_decoder.reset();
+        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    }
+
+    #[test]
+    fn test_encode_decode_with_escaped_controls() {
+        let config = SlipConfig::new().with_escaped_controls(XON_XOFF_CONTROLS);
+        let encoder = SlipEncoder::with_config(config);
+        let payload = [XON, 0x01, XOFF, END_CHAR];
+        let frame = [
+            END_CHAR,
+            ESC_CHAR,
+            0x31,
+            0x01,
+            ESC_CHAR,
+            0x33,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+        ];
+        let mut output = [0x00; 12];
+
+        assert_eq!(encoder.encode_to_slice(&payload, &mut output), Ok(9));
+        assert_eq!(output[..9], frame);
+        assert!(encoder.encode_iter(payload).eq(frame));
+        assert!(encoder.escape_segments(&payload).flatten().eq(&frame[1..8]));
+
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let result = slip_decoder.feed(&frame);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), payload);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the substituted control bytes must be distinct from the other escapes"
+    )]
+    fn test_escaped_controls_with_clashing_terminator() {
+        let _ = SlipConfig::new()
+            .with_end_char(0x31)
+            .with_escaped_controls(XON_XOFF_CONTROLS);
+    }
+
+    #[test]
+    fn test_encode_to_ring() {
+        let mut ring = [0x00; 8];
+
+        let result = SlipEncoder::default().encode_to_ring(&[0x01, 0x02], &mut ring, 2, 6);
+
+        assert_eq!(
+            result,
+            Ok(RingFrame {
+                first: RingRegion { start: 2, len: 4 },
+                second: RingRegion { start: 0, len: 0 },
+                next: 6,
+            })
+        );
+        assert_eq!(
+            ring,
+            [0x00, 0x00, END_CHAR, 0x01, 0x02, END_CHAR, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_encode_to_ring_with_wrap_around() {
+        let mut ring = [0x00; 8];
+
+        let result =
+            SlipEncoder::default().encode_to_ring(&[0x01, END_CHAR, 0x02], &mut ring, 5, 8);
+
+        assert_eq!(
+            result,
+            Ok(RingFrame {
+                first: RingRegion { start: 5, len: 3 },
+                second: RingRegion { start: 0, len: 3 },
+                next: 3,
+            })
+        );
+        assert_eq!(
+            ring,
+            [
+                ESC_END_CHAR,
+                0x02,
+                END_CHAR,
+                0x00,
+                0x00,
+                END_CHAR,
+                0x01,
+                ESC_CHAR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_to_ring_with_not_enough_space() {
+        let mut ring = [0x00; 8];
+
+        let result = SlipEncoder::default().encode_to_ring(&[0x01, ESC_CHAR], &mut ring, 0, 4);
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 5,
+                available: 4
+            })
+        );
+
+        let result = SlipEncoder::default().encode_to_ring(&[0x01], &mut ring, 8, 4);
+        assert_eq!(result, Err(SlipEncodeError::InvalidRegion));
+
+        assert_eq!(ring, [0x00; 8]);
+    }
+
+    #[test]
+    fn test_encode_to_slices() {
+        let mut ring = [0x00; 8];
+        let (second, first) = ring.split_at_mut(5);
+
+        let result =
+            SlipEncoder::default().encode_to_slices(&[0x01, END_CHAR, 0x02], first, second);
+
+        assert_eq!(result, Ok((3, 3)));
+        assert_eq!(
+            ring,
+            [
+                ESC_END_CHAR,
+                0x02,
+                END_CHAR,
+                0x00,
+                0x00,
+                END_CHAR,
+                0x01,
+                ESC_CHAR
+            ]
+        );
+
+        let result = SlipEncoder::default().encode_to_slices(&[0x01], &mut ring, &mut []);
+
+        assert_eq!(result, Ok((3, 0)));
+
+        let result =
+            SlipEncoder::default().encode_to_slices(&[0x01; 4], &mut [0x00; 3], &mut [0x00; 2]);
+
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 6,
+                available: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_feed_split() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+
+        let result =
+            slip_decoder.feed_split(&[END_CHAR, 0x00, ESC_CHAR], &[ESC_END_CHAR, END_CHAR, 0x01]);
+
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 5,
+                status: FeedStatus::Completed,
+            }
+        );
+        assert_eq!(slip_decoder.get_buffer(), &[0x00, END_CHAR]);
+    }
+
+    #[test]
+    fn test_decode_feed_split_with_frame_in_first_slice() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+
+        let result = slip_decoder.feed_split(&[END_CHAR, 0x00, END_CHAR, END_CHAR], &[0x01]);
+
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Completed,
+            }
+        );
+        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+    }
+
+    #[test]
+    fn test_decode_feed_split_with_bad_escape_character() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+
+        let result = slip_decoder.feed_split(&[END_CHAR], &[ESC_CHAR, 0x00, END_CHAR]);
+
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x00)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_with_integrity_check() {
+        let config = SlipConfig::new().with_integrity_check(true);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+
+        for value in [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR] {
+            assert_eq!(slip_decoder.insert(value), Ok(()));
+        }
+
+        assert_eq!(
+            slip_decoder.get_verified_buffer(),
+            Ok(&[0x01, END_CHAR][..])
+        );
+
+        // Simulate a bit flip in the buffer
+        slip_decoder.buffer[0] ^= 0x10;
+        assert_eq!(
+            slip_decoder.get_verified_buffer(),
+            Err(SlipDecodeError::IntegrityCheckFailed)
+        );
+
+        slip_decoder.reset();
+        assert_eq!(slip_decoder.get_verified_buffer(), Ok(&[][..]));
+
+        // Simulate a corrupted canary
+        slip_decoder.integrity.canary = 0;
+        assert_eq!(
+            slip_decoder.get_verified_buffer(),
+            Err(SlipDecodeError::IntegrityCheckFailed)
+        );
+    }
+
+    #[test]
+    fn test_take_frame_with_integrity_check() {
+        let config = SlipConfig::new().with_integrity_check(true);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let mut output = [0x00; 4];
+
+        slip_decoder.feed(&[END_CHAR, 0x01, END_CHAR]);
+        slip_decoder.integrity.canary = 0;
+        assert_eq!(
+            slip_decoder.take_frame(),
+            Err(SlipDecodeError::IntegrityCheckFailed)
+        );
+        assert!(!slip_decoder.is_buffer_completed());
+
+        slip_decoder.feed(&[END_CHAR, 0x02, END_CHAR]);
+        slip_decoder.integrity.canary = 0;
+        assert_eq!(
+            slip_decoder.read_frame_into(&mut output),
+            Err(SlipDecodeError::IntegrityCheckFailed)
+        );
+
+        slip_decoder.feed(&[END_CHAR, 0x03, END_CHAR]);
+        slip_decoder.buffer[0] ^= 0x10;
+        assert!(slip_decoder.frame().is_err());
+        assert_eq!(slip_decoder.stats().frames_dropped, 3);
+
+        slip_decoder.feed(&[END_CHAR, 0x04, END_CHAR]);
+        assert_eq!(slip_decoder.read_frame_into(&mut output), Ok(1));
+        assert_eq!(output[..1], [0x04]);
+    }
+
+    #[test]
+    fn test_work_bounds() {
+        assert_eq!(SlipEncoder::encode_work_bound::<4>(), WorkBound::linear(12));
+        assert_eq!(
+            SlipEncoder::encode_to_ring_work_bound(4),
+            WorkBound::linear(14)
+        );
+        assert_eq!(SlipDecoder::<4>::insert_work_bound(), WorkBound::CONSTANT);
+        assert_eq!(
+            SlipDecoder::<4>::feed_split_work_bound(8),
+            WorkBound::linear(8)
+        );
+        assert_eq!(
+            SlipDecoder::<4>::get_verified_buffer_work_bound(),
+            WorkBound::linear(4)
+        );
+    }
+
+    #[test]
+    fn test_encode_to_slice() {
+        let mut output = [0x00; 8];
+
+        let result =
+            SlipEncoder::default().encode_to_slice(&[0x00, END_CHAR, ESC_CHAR], &mut output);
+
+        assert_eq!(result, Ok(7));
+        assert_eq!(
+            output[..7],
+            [
+                END_CHAR,
+                0x00,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                ESC_CHAR,
+                ESC_ESC_CHAR,
+                END_CHAR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_with_resync() {
+        let config = SlipConfig::new().with_resync(true);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let data = [
+            END_CHAR, 0x00, ESC_CHAR, 0x01, 0x02, END_CHAR, END_CHAR, 0x03, END_CHAR, END_CHAR,
+            0x04, END_CHAR,
+        ];
+
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x01))
+        );
+        assert!(slip_decoder.get_buffer().is_empty());
+
+        let mut consumed = result.consumed;
+        for payload in [[0x03], [0x04]] {
+            let result = slip_decoder.feed(&data[consumed..]);
+            consumed += result.consumed;
+
+            assert_eq!(result.status, FeedStatus::Completed);
+            assert_eq!(slip_decoder.get_buffer(), payload);
+
+            slip_decoder.reset();
+        }
+
+        assert_eq!(consumed, data.len());
+    }
+
+    #[test]
+    fn test_decode_with_resync_after_overflow() {
+        let encoder = SlipEncoder::default();
+        let mut data = [0; 32];
+        let mut len = 0;
+        for payload in [&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06][..], &[0x07], &[0x08]] {
+            len += encoder.encode_to_slice(payload, &mut data[len..]).unwrap();
+        }
+
+        let config = SlipConfig::new().with_resync(true);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let result = slip_decoder.feed(&data[..len]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+
+        let mut consumed = result.consumed;
+        for payload in [[0x07], [0x08]] {
+            let result = slip_decoder.feed(&data[consumed..len]);
+            consumed += result.consumed;
+
+            assert_eq!(result.status, FeedStatus::Completed);
+            assert_eq!(slip_decoder.get_buffer(), payload);
+
+            slip_decoder.reset();
+        }
+
+        assert_eq!(consumed, len);
+        assert_eq!(slip_decoder.stats().frames_dropped, 1);
+    }
+
+    #[test]
+    fn test_decode_with_resync_and_no_leading_end() {
+        let config = SlipConfig::new()
+            .with_leading_end(false)
+            .with_empty_frames(EmptyFramePolicy::Skip)
+            .with_resync(true);
+        let mut slip_decoder = SlipDecoder::<1>::with_config(config);
+        let data = [
+            END_CHAR, 0x00, 0x01, 0x02, END_CHAR, END_CHAR, 0x03, END_CHAR, END_CHAR, 0x04,
+            END_CHAR,
+        ];
+
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+
+        let mut consumed = result.consumed;
+        for payload in [[0x03], [0x04]] {
+            let result = slip_decoder.feed(&data[consumed..]);
+            consumed += result.consumed;
+
+            assert_eq!(result.status, FeedStatus::Completed);
+            assert_eq!(slip_decoder.get_buffer(), payload);
+
+            slip_decoder.reset();
+        }
+
+        assert_eq!(consumed, data.len());
+    }
+
+    #[test]
+    fn test_decode_with_empty_frames_skipped() {
+        let config = SlipConfig::new().with_empty_frames(EmptyFramePolicy::Skip);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let data = [END_CHAR, 0x00, END_CHAR, END_CHAR, END_CHAR, 0x01, END_CHAR];
+
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x00]);
+
+        slip_decoder.reset();
+        let result = slip_decoder.feed(&data[result.consumed..]);
+
+        assert_eq!(result.consumed, 4);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01]);
+    }
+
+    #[test]
+    fn test_decode_with_lenient_escapes() {
+        let config = SlipConfig::new().with_escapes(EscapePolicy::Lenient);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+
+        let result =
+            slip_decoder.feed(&[END_CHAR, ESC_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR]);
+
+        let mut buf = [ESC_CHAR, 0x01, END_CHAR];
+
+        assert_eq!(slip_decoder.decode_in_place(&mut buf), Ok(&[0x01][..]));
+    }
+
+    #[test]
+    fn test_decode_with_passed_through_escapes() {
+        let config = SlipConfig::new().with_escapes(EscapePolicy::PassThrough);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+
+        let result =
+            slip_decoder.feed(&[END_CHAR, ESC_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [ESC_CHAR, 0x01, END_CHAR]);
+
+        let mut buf = [ESC_CHAR, 0x01, END_CHAR];
+
+        assert_eq!(
+            slip_decoder.decode_in_place(&mut buf),
+            Ok(&[ESC_CHAR, 0x01][..])
+        );
+    }
+
+    #[test]
+    fn test_decode_with_truncated_overflow() {
+        let config = SlipConfig::new().with_overflow(OverflowPolicy::Truncate);
+        let mut slip_decoder = SlipDecoder::<2>::with_config(config);
+
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, 0x01, 0x02, 0x03, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x00, 0x01]);
+        assert!(slip_decoder.is_truncated());
+
+        slip_decoder.reset();
+
+        assert!(!slip_decoder.is_truncated());
+    }
+
+    #[test]
+    fn test_decode_with_discarded_overflow() {
+        let config = SlipConfig::new().with_overflow(OverflowPolicy::Discard);
+        let mut slip_decoder = SlipDecoder::<2>::with_config(config);
+
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, 0x01, 0x02, END_CHAR, 0x03, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x03]);
+        assert!(!slip_decoder.is_truncated());
+    }
+
+    #[test]
+    fn test_decode_with_discarded_overflow_between_encoded_frames() {
+        let encoder = SlipEncoder::default();
+        let mut data = [0; 32];
+        let mut len = 0;
+        for payload in [&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06][..], &[0x07], &[0x08]] {
+            len += encoder.encode_to_slice(payload, &mut data[len..]).unwrap();
+        }
+
+        let config = SlipConfig::new().with_overflow(OverflowPolicy::Discard);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+
+        let mut consumed = 0;
+        for payload in [[0x07], [0x08]] {
+            let result = slip_decoder.feed(&data[consumed..len]);
+            consumed += result.consumed;
+
+            assert_eq!(result.status, FeedStatus::Completed);
+            assert_eq!(slip_decoder.get_buffer(), payload);
+
+            slip_decoder.reset();
+        }
+
+        assert_eq!(consumed, len);
+        assert_eq!(slip_decoder.stats().frames_dropped, 1);
+    }
+
+    #[test]
+    fn test_decode_with_max_frame_len() {
+        let mut slip_decoder = SlipDecoder::<8>::with_config(SlipConfig::new().with_resync(true));
+        slip_decoder.set_max_frame_len(2);
+
+        let data = [END_CHAR, 0x01, 0x02, 0x03, END_CHAR, 0x04, 0x05, END_CHAR];
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::FrameTooLong)
+        );
+
+        let result = slip_decoder.feed(&data[result.consumed..]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x04, 0x05]);
+        assert_eq!(slip_decoder.stats().frames_oversized, 1);
+        assert_eq!(slip_decoder.stats().frames_dropped, 1);
+    }
+
+    #[test]
+    fn test_decode_with_mtu() {
+        let mut slip_decoder = SlipDecoder::<8>::with_config(SlipConfig::new().with_mtu(Some(2)));
+
+        let data = [END_CHAR, 0x01, 0x02, 0x03, END_CHAR];
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::MtuExceeded)
+        );
+        assert_eq!(slip_decoder.stats().frames_oversized, 1);
+
+        slip_decoder.reset();
+        slip_decoder.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+
+        assert_eq!(slip_decoder.get_buffer(), [0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_decode_rfc1055() {
+        let mut slip_decoder = Rfc1055Decoder::with_config(SlipConfig::rfc1055());
+        let mut frame = [0x00; RFC1055_MTU + 2];
+        frame[0] = END_CHAR;
+        frame[RFC1055_MTU + 1] = END_CHAR;
+
+        assert_eq!(slip_decoder.feed(&frame).status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer().len(), RFC1055_MTU);
+
+        let mut slip_decoder = SlipDecoder::<2048>::with_config(SlipConfig::rfc1055());
+        slip_decoder.feed(&[END_CHAR]);
+
+        assert_eq!(slip_decoder.remaining_capacity(), RFC1055_MTU);
+        assert_eq!(
+            slip_decoder.feed(&[0x00; RFC1055_MTU + 1]).status,
+            FeedStatus::Rejected(SlipDecodeError::MtuExceeded)
+        );
+    }
+
+    #[test]
+    fn test_decode_unchecked() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+        let data = [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, 0x02, END_CHAR, 0x03];
+
+        assert_eq!(slip_decoder.remaining_capacity(), 4);
+
+        let result = slip_decoder.feed_unchecked(&data);
+
+        assert_eq!(result.consumed, 6);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR, 0x02]);
+        assert_eq!(slip_decoder.frame_metadata().wire_len, 6);
+        assert_eq!(
+            slip_decoder.insert_unchecked(0x03),
+            Err(SlipDecodeError::FrameCompleted)
+        );
+
+        slip_decoder.reset();
+
+        assert_eq!(
+            slip_decoder
+                .feed_unchecked(&[END_CHAR, ESC_CHAR, 0x01])
+                .status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x01))
+        );
+    }
+
+    #[test]
+    fn test_remaining_capacity() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+
+        assert!(slip_decoder.is_empty());
+        assert_eq!(slip_decoder.remaining_capacity(), 4);
+
+        slip_decoder.feed(&[END_CHAR, 0x01]);
+
+        assert_eq!(slip_decoder.len(), 1);
+        assert_eq!(slip_decoder.capacity(), 4);
+        assert_eq!(slip_decoder.remaining_capacity(), 3);
+
+        slip_decoder.set_max_frame_len(2);
+
+        assert_eq!(slip_decoder.remaining_capacity(), 1);
+
+        slip_decoder.feed(&[0x02]);
+
+        assert_eq!(slip_decoder.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn test_decode_with_inter_byte_timeout() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+        slip_decoder.set_inter_byte_timeout(Some(10));
+
+        assert_eq!(slip_decoder.feed_at(&[END_CHAR, 0x01], 0).consumed, 2);
+        assert!(!slip_decoder.tick(10));
+
+        // The end of the first frame was lost, so the decoder waits for the next terminator
+        let result = slip_decoder.feed_at(&[0x02, END_CHAR, 0x03, END_CHAR], 21);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x03]);
+        assert_eq!(slip_decoder.stats().frames_timed_out, 1);
+        assert_eq!(slip_decoder.stats().bytes_discarded, 1);
+
+        slip_decoder.reset();
+        assert_eq!(slip_decoder.insert_at(0x04, 100), Ok(()));
+        assert!(!slip_decoder.tick(200));
+        assert_eq!(slip_decoder.stats().frames_timed_out, 1);
+    }
+
+    #[test]
+    fn test_decode_with_stray_bytes_rejected() {
+        let config = SlipConfig::new().with_stray_bytes(StrayBytePolicy::Reject);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let data = [0x01, 0x02, END_CHAR, 0x03, END_CHAR];
+
+        let result = slip_decoder.feed(&data);
+
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 1,
+                status: FeedStatus::Rejected(SlipDecodeError::UnexpectedByte(0x01)),
+            }
+        );
+
+        let result = slip_decoder.feed_borrowed(&data[1..]).0;
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::UnexpectedByte(0x02))
+        );
+
+        let (result, frame) = slip_decoder.feed_borrowed(&data[2..]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(*frame.unwrap(), [0x03]);
+        assert_eq!(
+            slip_decoder.stats(),
+            SlipStats {
+                frames_completed: 1,
+                bytes_discarded: 2,
+                ..SlipStats::new()
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_long_runs() {
+        let mut payload = [0x00; 100];
+        for (index, value) in payload.iter_mut().enumerate() {
+            *value = u8::try_from(index).unwrap();
+        }
+        payload[40] = END_CHAR;
+        payload[41] = ESC_CHAR;
+
+        let mut output = [0x00; 110];
+        let length = SlipEncoder::default()
+            .encode_to_slice(&payload, &mut output)
+            .unwrap();
+
+        let config = SlipConfig::new().with_integrity_check(true);
+        let mut slip_decoder = SlipDecoder::<100>::with_config(config);
+
+        assert_eq!(
+            slip_decoder.feed(&output[..length]).status,
+            FeedStatus::Completed
+        );
+        assert_eq!(slip_decoder.get_verified_buffer(), Ok(&payload[..]));
+
+        let config = SlipConfig::new().with_overflow(OverflowPolicy::Truncate);
+        let mut slip_decoder = SlipDecoder::<50>::with_config(config);
+
+        assert_eq!(
+            slip_decoder.feed(&output[..length]).status,
+            FeedStatus::Completed
+        );
+        assert!(slip_decoder.is_truncated());
+        assert_eq!(slip_decoder.get_buffer(), &payload[..50]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let config = SlipConfig::new().with_resync(true);
+        let mut slip_decoder = SlipDecoder::<4>::with_config(config);
+        let data = [
+            0x01,
+            0x02,
+            END_CHAR,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            0x03,
+            END_CHAR,
+            0x04,
+            END_CHAR,
+        ];
+
+        let mut consumed = slip_decoder.feed(&data).consumed;
+        slip_decoder.reset();
+        consumed += slip_decoder.feed(&data[consumed..]).consumed;
+        let result = slip_decoder.feed(&data[consumed..]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(
+            slip_decoder.stats(),
+            SlipStats {
+                frames_completed: 2,
+                frames_dropped: 1,
+                bytes_discarded: 2,
+                escapes: 2,
+                frames_oversized: 0,
+                frames_timed_out: 0,
+                frames_invalid: 0,
+            }
+        );
+
+        slip_decoder.reset_stats();
+
+        assert_eq!(slip_decoder.stats(), SlipStats::new());
+        assert_eq!(slip_decoder.get_buffer(), [0x04]);
+    }
+
+    #[test]
+    fn test_frame_metadata() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+
+        let result = slip_decoder.feed(&[0x01, END_CHAR, 0x02, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(
+            slip_decoder.frame_metadata(),
+            FrameMetadata {
+                wire_len: 5,
+                escapes: 1,
+                preceded_by_garbage: true,
+            }
+        );
+
+        slip_decoder.reset();
+        let (result, frame) = slip_decoder.feed_borrowed(&[END_CHAR, 0x03, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(*frame.unwrap(), [0x03]);
+        assert_eq!(
+            slip_decoder.frame_metadata(),
+            FrameMetadata {
+                wire_len: 3,
+                escapes: 0,
+                preceded_by_garbage: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_insert_event() {
+        let mut slip_decoder = SlipDecoder::<1>::default();
+
+        assert_eq!(slip_decoder.insert_event(0x00), SlipEvent::None);
+        assert_eq!(slip_decoder.insert_event(END_CHAR), SlipEvent::FrameStarted);
+        assert_eq!(slip_decoder.insert_event(0x01), SlipEvent::None);
+        assert_eq!(
+            slip_decoder.insert_event(END_CHAR),
+            SlipEvent::FrameCompleted
+        );
+        assert_eq!(
+            slip_decoder.insert_event(0x02),
+            SlipEvent::FrameAborted(SlipDecodeError::FrameCompleted)
+        );
+
+        slip_decoder.reset();
+        slip_decoder.insert_event(END_CHAR);
+        slip_decoder.insert_event(0x01);
+
+        assert_eq!(
+            slip_decoder.insert_event(0x02),
+            SlipEvent::FrameAborted(SlipDecodeError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_take_frame() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.feed(&[END_CHAR, 0x00, 0x01]);
+
+        assert_eq!(
+            slip_decoder.take_frame(),
+            Err(SlipDecodeError::FrameIncomplete)
+        );
+        assert_eq!(slip_decoder.get_buffer(), [0x00, 0x01]);
+
+        slip_decoder.insert(END_CHAR).unwrap();
+
+        assert_eq!(slip_decoder.take_frame(), Ok(Vec::from([0x00, 0x01])));
+        assert!(!slip_decoder.is_buffer_completed());
+        assert!(slip_decoder.get_buffer().is_empty());
+    }
+
+    #[test]
+    fn test_get_buffer_mut() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+
+        for value in slip_decoder.get_buffer_mut() {
+            *value ^= 0xFF;
+        }
+
+        assert_eq!(slip_decoder.get_buffer(), [0xFE, 0xFD]);
+        assert_eq!(slip_decoder.into_inner().as_slice(), [0xFE, 0xFD]);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.feed(&[END_CHAR, 0x01, ESC_CHAR]);
+
+        let mut bytes = [0x00; SNAPSHOT_HEADER_LENGTH + 4];
+        let length = slip_decoder.snapshot().to_bytes(&mut bytes).unwrap();
+
+        assert_eq!(length, SNAPSHOT_HEADER_LENGTH + 1);
+
+        let snapshot = SlipDecoderSnapshot::<4>::from_bytes(&bytes[..length]).unwrap();
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.restore(&snapshot);
+        let result = slip_decoder.feed(&[ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR]);
+    }
+
+    #[test]
+    fn test_clone_forks_state() {
+        extern crate std;
+
+        use std::format;
+
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.feed(&[END_CHAR, 0x01, ESC_CHAR]);
+
+        let mut fork = slip_decoder.clone();
+        let result = fork.feed(&[0x02]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x02))
+        );
+
+        let result = slip_decoder.feed(&[ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR]);
+        assert!(format!("{slip_decoder:?}").contains("buffer: [1, 192]"));
+    }
+
+    #[test]
+    fn test_snapshot_from_bad_bytes() {
+        assert_eq!(
+            SlipDecoderSnapshot::<4>::from_bytes(&[0x02, 0x00, 0x01]),
+            Err(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(
+            SlipDecoderSnapshot::<4>::from_bytes(&[0x06, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            Err(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(
+            SlipDecoderSnapshot::<4>::from_bytes(&[0x02, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01]),
+            Err(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(
+            SlipDecoderSnapshot::<1>::from_bytes(&[0x02, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02]),
+            Err(SlipDecodeError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_frame() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        slip_decoder.feed(&[END_CHAR, 0x01]);
+
+        assert!(slip_decoder.frame().is_err());
+
+        slip_decoder.feed(&[END_CHAR]);
+        if let Ok(frame) = slip_decoder.frame() {
+            assert_eq!(*frame, [0x01]);
+        }
+
+        assert!(!slip_decoder.is_buffer_completed());
+        assert!(slip_decoder.get_buffer().is_empty());
+    }
+
+    #[test]
+    fn test_feed_borrowed() {
+        let data = [
+            0x00,
+            END_CHAR,
+            0x01,
+            0x02,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+        ];
+        let mut slip_decoder = SlipDecoder::<4>::default();
+
+        let (result, frame) = slip_decoder.feed_borrowed(&data);
+
+        assert_eq!(result.consumed, 5);
+        assert!(matches!(frame, Some(DecodedFrame::Borrowed(&[0x01, 0x02]))));
+
+        drop(frame);
+        let (result, frame) = slip_decoder.feed_borrowed(&data[5..]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert!(matches!(frame, Some(DecodedFrame::Buffered(_))));
+        assert_eq!(frame.as_deref(), Some(&[END_CHAR][..]));
+
+        drop(frame);
+
+        assert!(!slip_decoder.is_buffer_completed());
+        assert_eq!(slip_decoder.stats().frames_completed, 2);
+        assert_eq!(slip_decoder.stats().bytes_discarded, 1);
+    }
+
+    #[test]
+    fn test_status() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::Idle);
+
+        slip_decoder.feed(&[END_CHAR]);
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::Idle);
+
+        slip_decoder.feed(&[0x01]);
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::InFrame);
+
+        slip_decoder.feed(&[ESC_CHAR]);
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::Escaping);
+
+        slip_decoder.feed(&[ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::FrameReady);
+
+        slip_decoder.reset();
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::Idle);
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const ENCODER: SlipEncoder = SlipEncoder::new();
+        const DECODER: SlipDecoder<4> = SlipDecoder::new();
+
+        let mut vec = Vec::<u8, 4>::from([0x01]);
+        ENCODER.encode(&mut vec).unwrap();
+
+        let mut slip_decoder = DECODER;
+        slip_decoder.feed(vec.as_slice());
+
+        assert_eq!(slip_decoder.get_buffer(), [0x01]);
+    }
+
+    #[test]
+    fn test_take_frame_with() {
+        let mut buffer = [0x00; 4];
+        let mut slip_decoder = SlipDecoderRef::new(&mut buffer);
+        slip_decoder.feed(&[END_CHAR, 0x00, 0x01, END_CHAR]);
+
+        assert_eq!(slip_decoder.take_frame_with(<[u8]>::len), Ok(2));
+        assert_eq!(
+            slip_decoder.take_frame_with(<[u8]>::len),
+            Err(SlipDecodeError::FrameIncomplete)
+        );
+    }
+
+    #[test]
+    fn test_read_frame_into() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+        let mut out = [0x00; 2];
+
+        assert_eq!(
+            slip_decoder.read_frame_into(&mut out),
+            Err(SlipDecodeError::FrameIncomplete)
+        );
+
+        slip_decoder.feed(&[END_CHAR, 0x00, 0x01, 0x02, END_CHAR]);
+
+        assert_eq!(
+            slip_decoder.read_frame_into(&mut out),
+            Err(SlipDecodeError::OutputTooSmall(3))
+        );
+        assert!(slip_decoder.is_buffer_completed());
+
+        let mut out = [0x00; 4];
+
+        assert_eq!(slip_decoder.read_frame_into(&mut out), Ok(3));
+        assert_eq!(out[..3], [0x00, 0x01, 0x02]);
+        assert_eq!(slip_decoder.status(), DecoderStatus::Idle);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_encode_heapless() {
+        let mut vec = heapless::Vec::<u8, 8>::from_slice(&[0x00, END_CHAR]).unwrap();
+        SlipEncoder::default().encode_heapless(&mut vec).unwrap();
+
+        assert_eq!(vec, [END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        let mut vec = heapless::Vec::<u8, 4>::from_slice(&[0x00, END_CHAR]).unwrap();
+
+        assert_eq!(
+            SlipEncoder::default().encode_heapless(&mut vec),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 5,
+                available: 4
+            })
+        );
+        assert_eq!(vec, [0x00, END_CHAR]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_decode_with_heapless_buffer() {
+        let mut slip_decoder = SlipBufferDecoder::<heapless::Vec<u8, 2>>::default();
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x00, END_CHAR]);
+
+        slip_decoder.reset();
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, 0x01, 0x02]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+    }
+
+    #[cfg(feature = "bbqueue")]
+    #[test]
+    fn test_grants() {
+        let queue = Churrasco::<16>::new();
+        let producer = queue.stream_producer();
+        let consumer = queue.stream_consumer();
+        let encoder = SlipEncoder::default();
+
+        let grant = producer.grant_max_remaining(16).unwrap();
+        assert_eq!(encoder.encode_to_grant(&[0x01, END_CHAR], grant), Ok(5));
+
+        let grant = producer.grant_max_remaining(16).unwrap();
+        assert_eq!(encoder.encode_to_grant(&[0x02], grant), Ok(3));
+
+        let grant = producer.grant_max_remaining(16).unwrap();
+        assert_eq!(
+            encoder.encode_to_grant(&[END_CHAR; 4], grant),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 10,
+                available: 8
+            })
+        );
+
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        let result = slip_decoder.feed_grant(consumer.read().unwrap());
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR]);
+        assert_eq!(*consumer.read().unwrap(), [END_CHAR, 0x02, END_CHAR]);
+
+        slip_decoder.reset();
+        let result = slip_decoder.feed_grant(consumer.read().unwrap());
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x02]);
+        assert!(consumer.read().is_err());
+    }
+
+    #[test]
+    fn test_decode_with_borrowed_buffer() {
+        let mut buffer = [0x00; 2];
+        let mut slip_decoder = SlipDecoderRef::new(&mut buffer);
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x00, END_CHAR]);
+
+        slip_decoder.reset();
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, 0x01, 0x02]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_encode_to_vec() {
+        let output = SlipEncoder::default().encode_to_vec(&[0x00, END_CHAR, ESC_CHAR]);
+
+        assert_eq!(
+            output,
+            [
+                END_CHAR,
+                0x00,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                ESC_CHAR,
+                ESC_ESC_CHAR,
+                END_CHAR
+            ]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_decode_with_growable_buffer() {
+        let payload = [0x01; 300];
+        let frame = SlipEncoder::default().encode_to_vec(&payload);
+
+        let mut slip_decoder = SlipVecDecoder::default();
+        let result = slip_decoder.feed(&frame);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.take_frame().unwrap(), payload);
+        assert!(slip_decoder.get_buffer().is_empty());
+    }
+
+    #[cfg(feature = "allocator-api")]
+    #[test]
+    fn test_encode_decode_in_allocator() {
+        extern crate std;
+
+        use std::alloc::System;
+
+        let payload = [0x01, END_CHAR, 0x02];
+        let frame = SlipEncoder::default().encode_to_vec_in(&payload, System);
+
+        let mut slip_decoder = SlipBufferDecoder::new_in(System);
+        let result = slip_decoder.feed(&frame);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.take_frame().unwrap(), payload);
+        assert!(slip_decoder.get_buffer().is_empty());
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_encode_decode_bytes() {
+        use bytes::Buf;
+        use bytes::BytesMut;
+
+        let encoder = SlipEncoder::default();
+        let mut output = BytesMut::new();
+
+        assert_eq!(encoder.encode_to_buf(&[0x01, END_CHAR], &mut output), Ok(5));
+
+        let frame = encoder.encode_to_bytes(&[0x02]);
+        let mut input = output.freeze().chain(frame);
+        let mut slip_decoder = SlipDecoder::<4>::new();
+
+        assert_eq!(
+            slip_decoder.feed_buf(&mut input).status,
+            FeedStatus::Completed
+        );
+        assert_eq!(
+            slip_decoder.take_frame_bytes().unwrap(),
+            [0x01, END_CHAR][..]
+        );
+        assert_eq!(input.remaining(), 3);
+        assert_eq!(
+            slip_decoder.feed_buf(&mut input),
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Completed
+            }
+        );
+        assert_eq!(slip_decoder.take_frame_bytes().unwrap(), [0x02][..]);
+        assert_eq!(
+            slip_decoder.take_frame_bytes(),
+            Err(SlipDecodeError::FrameIncomplete)
+        );
+        assert_eq!(
+            encoder.encode_to_buf(&[0x00; 4], &mut [0x00; 4].as_mut_slice()),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 6,
+                available: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut validator = SlipValidator::new();
+        let result = validator.feed(&[END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(validator.frame_len(), 2);
+        assert!(validator.get_buffer().is_empty());
+
+        validator.reset();
+        let result = validator.feed(&[END_CHAR, ESC_CHAR, 0x00, END_CHAR]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x00))
+        );
+    }
+
+    #[test]
+    fn test_validate_with_max_length() {
+        let config = SlipConfig::new().with_integrity_check(true);
+        let mut validator = SlipValidator::with_max_length(2, config);
+
+        let result = validator.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(validator.get_verified_buffer(), Ok(&[][..]));
+
+        validator.reset();
+        let result = validator.feed(&[END_CHAR, 0x01, 0x02, 0x03, END_CHAR]);
+
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let payload = [0x00, END_CHAR, ESC_CHAR];
+        let mut output = [0x00; max_encoded_len(3)];
+
+        assert_eq!(output.len(), 8);
+        assert_eq!(SlipEncoder::default().encoded_len(&payload), 7);
+        assert_eq!(
+            SlipEncoder::with_config(SlipConfig::new().with_leading_end(false))
+                .encoded_len(&payload),
+            6
+        );
+        assert_eq!(
+            SlipEncoder::default().encode_to_slice(&payload, &mut output),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn test_encode_array() {
+        const PAYLOAD: &[u8] = &[0x00, END_CHAR, ESC_CHAR];
+        const ENCODER: SlipEncoder = SlipEncoder::new();
+        const FRAME: [u8; ENCODER.const_encoded_len(PAYLOAD)] = ENCODER.encode_array(PAYLOAD);
+
+        let mut output = [0x00; max_encoded_len(3)];
+        let len = ENCODER.encode_to_slice(PAYLOAD, &mut output).unwrap();
+
+        assert_eq!(FRAME, output[..len]);
+    }
+
+    #[test]
+    fn test_insert_iter() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+        let mut data = [END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, ESC_CHAR, 0x03].into_iter();
+
+        assert_eq!(slip_decoder.insert_iter(data.by_ref()), Ok(3));
+        assert_eq!(slip_decoder.insert_iter(data.by_ref()), Ok(0));
+        assert_eq!(slip_decoder.get_buffer(), [0x01]);
+
+        slip_decoder.reset();
+
+        assert_eq!(
+            slip_decoder.insert_iter(data.by_ref()),
+            Err(SlipDecodeError::InvalidEscape(0x03))
+        );
+        assert_eq!(data.next(), None);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
+
+        slip_decoder.extend([END_CHAR, 0x01, ESC_CHAR]);
+        slip_decoder.extend(&[ESC_END_CHAR, END_CHAR, 0x02]);
+
+        assert_eq!(slip_decoder.status(), DecoderStatus::FrameReady);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, END_CHAR]);
+        assert_eq!(slip_decoder.stats().frames_completed, 1);
+    }
 
-impl<const MAX_LENGTH: usize> SlipDecoder<MAX_LENGTH> {
-    /// Feeds `value` into the decoder state machine.
-    ///
-    /// Returns `Ok(())` on success, or `Err(value)` if the byte is unexpected or the buffer is full.
-    #[allow(clippy::result_unit_err)]
-    pub fn insert(&mut self, value: u8) -> Result<(), u8> {
-        match self.state {
-            SlipDecoderState::Start => {
-                if value == END_CHAR {
-                    self.state = SlipDecoderState::Append;
-                }
+    #[test]
+    fn test_frames() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
 
-                Ok(())
-            }
-            SlipDecoderState::Append => {
-                match value {
-                    END_CHAR => {
-                        self.state = SlipDecoderState::End;
-                    }
-                    ESC_CHAR => {
-                        self.state = SlipDecoderState::Escape;
-                    }
-                    _ => {
-                        self.buffer.push(value)?;
-                    }
-                }
+        assert_eq!(slip_decoder.feed(&[END_CHAR, 0x01, END_CHAR]).consumed, 3);
 
-                Ok(())
-            }
-            SlipDecoderState::Escape => {
-                self.state = SlipDecoderState::Append;
+        let data = [
+            END_CHAR, ESC_CHAR, 0x00, END_CHAR, 0x02, END_CHAR, END_CHAR, 0x03,
+        ];
+        let mut frames = slip_decoder.frames(&data);
 
-                match value {
-                    ESC_END_CHAR => {
-                        self.buffer.push(END_CHAR)?;
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x01]))));
+        assert_eq!(
+            frames.next(),
+            Some(Err(SlipDecodeError::InvalidEscape(0x00)))
+        );
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x02]))));
+        assert_eq!(frames.next(), None);
+        assert_eq!(frames.next(), None);
 
-                        Ok(())
-                    }
-                    ESC_ESC_CHAR => {
-                        self.buffer.push(ESC_CHAR)?;
+        let mut frames = slip_decoder.frames(&[END_CHAR]);
 
-                        Ok(())
-                    }
-                    _ => Err(value),
-                }
-            }
-            SlipDecoderState::End => Err(value),
-        }
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x03]))));
     }
 
-    /// Resets the decoder to its initial state.
-    pub fn reset(&mut self) {
-        self.state = SlipDecoderState::Start;
-        self.buffer.clear();
-    }
+    #[test]
+    fn test_frames_resume_after_bad_escape() {
+        let mut slip_decoder = SlipDecoder::<4>::new();
 
-    /// Returns `true` if the decoder has received a complete SLIP frame.
-    #[must_use]
-    pub fn is_buffer_completed(&self) -> bool {
-        self.state == SlipDecoderState::End
-    }
+        let mut frames = slip_decoder.frames(&[END_CHAR, 0x01, ESC_CHAR, 0x05]);
 
-    /// Returns a slice of the decoded bytes accumulated so far.
-    #[must_use]
-    pub const fn get_buffer(&self) -> &[u8] {
-        self.buffer.as_slice()
+        assert_eq!(
+            frames.next(),
+            Some(Err(SlipDecodeError::InvalidEscape(0x05)))
+        );
+        assert_eq!(frames.next(), None);
+
+        let data = [
+            0x02, END_CHAR, END_CHAR, 0x07, END_CHAR, END_CHAR, 0x08, END_CHAR,
+        ];
+        let mut frames = slip_decoder.frames(&data);
+
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x07]))));
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x08]))));
+        assert_eq!(frames.next(), None);
+        assert_eq!(slip_decoder.stats().frames_completed, 2);
     }
-}
 
-/// Implementation of `Deref` for `SlipDecoder`.
-///
-/// This allows treating a `SlipDecoder` as a byte slice of the decoded buffer.
-impl<const MAX_LENGTH: usize> Deref for SlipDecoder<MAX_LENGTH> {
-    type Target = [u8];
+    #[test]
+    fn test_encode_many() {
+        let mut output = [0x00; 9];
+        let mut ranges = [0..0, 0..0, 0..0];
 
-    /// Dereferences to the decoded buffer slice.
-    fn deref(&self) -> &Self::Target {
-        self.get_buffer()
+        let result = SlipEncoder::default().encode_many(
+            &[&[0x00], &[], &[END_CHAR]],
+            &mut output,
+            &mut ranges,
+        );
+
+        assert_eq!(result, Ok([0..3, 3..5, 5..9].as_slice()));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::slip::END_CHAR;
-    use crate::slip::ESC_CHAR;
-    use crate::slip::ESC_END_CHAR;
-    use crate::slip::ESC_ESC_CHAR;
-    use crate::slip::SlipDecoder;
-    use crate::slip::SlipDecoderState;
-    use crate::slip::SlipEncoder;
-    use noalloc_vec_rs::vec::Vec;
+    #[test]
+    fn test_encode_many_with_not_enough_space() {
+        let mut output = [0x00; 8];
+        let mut ranges = [0..0, 0..0];
+        let encoder = SlipEncoder::default();
+
+        let result = encoder.encode_many(&[&[0x00], &[END_CHAR]], &mut output[..6], &mut ranges);
+
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 7,
+                available: 6
+            })
+        );
+        assert_eq!(output, [0x00; 8]);
+
+        let result = encoder.encode_many(&[&[0x00], &[0x01], &[0x02]], &mut output, &mut ranges);
+
+        assert_eq!(result, Err(SlipEncodeError::BufferTooSmall));
+
+        let result = encoder.encode_many(&[&[0x00], &[END_CHAR]], &mut output, &mut ranges);
+
+        assert_eq!(result, Ok([0..3, 3..7].as_slice()));
+        assert_eq!(
+            output[..7],
+            [
+                END_CHAR,
+                0x00,
+                END_CHAR,
+                END_CHAR,
+                ESC_CHAR,
+                ESC_END_CHAR,
+                END_CHAR
+            ]
+        );
+    }
 
     #[test]
-    fn test_encode() {
-        let mut array = Vec::<u8, 12>::from([0x00, 0x01, 0x02, 0x03]);
+    fn test_encode_to_slice_with_not_enough_space() {
+        let mut output = [0x00; 6];
 
-        let result = SlipEncoder::encode(&mut array);
+        let result =
+            SlipEncoder::default().encode_to_slice(&[0x00, END_CHAR, ESC_CHAR], &mut output);
 
-        assert_eq!(result, Ok(()));
-        assert_eq!(*array, [END_CHAR, 0x00, 0x01, 0x02, 0x03, END_CHAR]);
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 7,
+                available: 6
+            })
+        );
+        assert_eq!(output, [0x00; 6]);
     }
 
     #[test]
-    fn test_encode_empty() {
-        let mut array = Vec::<u8, 12>::new();
+    fn test_encode_with_not_enough_space() {
+        let mut array = Vec::<u8, 5>::from([0x00, END_CHAR, 0x01]);
 
-        let result = SlipEncoder::encode(&mut array);
+        let result = SlipEncoder::default().encode(&mut array);
 
-        assert_eq!(result, Ok(()));
-        assert_eq!(*array, [END_CHAR, END_CHAR]);
+        assert_eq!(
+            result,
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 6,
+                available: 5
+            })
+        );
+        assert_eq!(*array, [0x00, END_CHAR, 0x01]);
     }
 
     #[test]
-    fn test_encode_with_escape_characters() {
-        let mut array = Vec::<u8, 12>::from([END_CHAR, ESC_CHAR, ESC_END_CHAR, ESC_ESC_CHAR]);
+    fn test_encode_with_only_escape_characters() {
+        let mut array = Vec::<u8, 8>::from([ESC_CHAR, ESC_CHAR, END_CHAR]);
 
-        let result = SlipEncoder::encode(&mut array);
+        let result = SlipEncoder::default().encode(&mut array);
 
         assert_eq!(result, Ok(()));
         assert_eq!(
@@ -201,153 +5086,383 @@ mod tests {
             [
                 END_CHAR,
                 ESC_CHAR,
-                ESC_END_CHAR,
+                ESC_ESC_CHAR,
                 ESC_CHAR,
                 ESC_ESC_CHAR,
+                ESC_CHAR,
                 ESC_END_CHAR,
-                ESC_ESC_CHAR,
                 END_CHAR
             ]
         );
     }
 
     #[test]
-    fn test_decode() {
-        let mut slip_decoder = SlipDecoder::<1>::default();
+    fn test_decode_feed() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
+        let data = [END_CHAR, 0x00, END_CHAR, END_CHAR, 0x01, END_CHAR];
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        let result = slip_decoder.feed(&data);
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Completed,
+            }
+        );
+        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+        // A completed decoder does not consume anything until it is reset
+        let result = slip_decoder.feed(&data[3..]);
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 0,
+                status: FeedStatus::Completed,
+            }
+        );
 
-        let result = slip_decoder.insert(0x00);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+        slip_decoder.reset();
+        let result = slip_decoder.feed(&data[3..]);
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Completed,
+            }
+        );
+        assert_eq!(slip_decoder.get_buffer(), &[0x01]);
+    }
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+    #[test]
+    fn test_decode_feed_incomplete() {
+        let mut slip_decoder = SlipDecoder::<4>::default();
 
-        assert!(slip_decoder.is_buffer_completed());
+        let result = slip_decoder.feed(&[END_CHAR, 0x00, ESC_CHAR]);
 
-        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+        assert_eq!(
+            result,
+            FeedResult {
+                consumed: 3,
+                status: FeedStatus::Incomplete,
+            }
+        );
+        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
     }
 
     #[test]
-    fn test_decode_with_escape_characters() {
-        let mut slip_decoder = SlipDecoder::<6>::default();
+    fn test_decode_frame_iter() {
+        let data = [
+            END_CHAR,
+            0x00,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            0x01,
+            END_CHAR,
+            0x02,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x03,
+        ];
+        let mut frames = SlipFrameIter::<4>::new(&data);
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x00]))));
+        assert_eq!(
+            frames.next(),
+            Some(Err(SlipDecodeError::InvalidEscape(0x01)))
+        );
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x02]))));
+        assert_eq!(frames.next(), Some(Ok(Vec::from([END_CHAR]))));
+        assert_eq!(frames.next(), None);
+    }
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    #[test]
+    fn test_frame_iter_resumes_after_bad_escape() {
+        let data = [
+            END_CHAR, 0x01, ESC_CHAR, 0x05, 0x02, END_CHAR, END_CHAR, 0x07, END_CHAR, END_CHAR,
+            0x08, END_CHAR,
+        ];
+        let mut frames = SlipFrameIter::<4>::new(&data);
 
-        let result = slip_decoder.insert(ESC_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+        assert_eq!(
+            frames.next(),
+            Some(Err(SlipDecodeError::InvalidEscape(0x05)))
+        );
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x07]))));
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x08]))));
+        assert_eq!(frames.next(), None);
+    }
 
-        let result = slip_decoder.insert(ESC_END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    #[test]
+    fn test_frame_bounds() {
+        let data = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            END_CHAR,
+            0x02,
+            0x03,
+        ];
+        let mut bounds = SlipFrameBounds::new(&data);
 
-        let result = slip_decoder.insert(ESC_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+        assert_eq!(bounds.next(), Some(1..4));
+        assert_eq!(bounds.next(), Some(6..7));
+        assert_eq!(bounds.next(), None);
+        assert_eq!(bounds.next(), None);
+        assert_eq!(bounds.trailing_len(), 2);
+    }
 
-        let result = slip_decoder.insert(ESC_ESC_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    #[test]
+    fn test_frame_bounds_decode_in_place() {
+        let slip_decoder = SlipDecoder::<0>::default();
+        let mut data = [0x00, ESC_CHAR, ESC_ESC_CHAR, END_CHAR, 0x01];
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+        let ranges = SlipFrameBounds::new(&data).collect::<Vec<_, 2>>();
+        assert_eq!(ranges.len(), 1);
 
-        assert!(slip_decoder.is_buffer_completed());
+        let frame = slip_decoder.decode_in_place(&mut data[ranges[0].clone()]);
+        assert_eq!(frame, Ok([0x00, ESC_CHAR].as_slice()));
+        assert_eq!(SlipFrameBounds::new(&[]).trailing_len(), 0);
+        assert_eq!(SlipFrameBounds::new(&[0x01, 0x02]).trailing_len(), 2);
+    }
 
-        assert_eq!(slip_decoder.get_buffer(), &[END_CHAR, ESC_CHAR]);
+    #[test]
+    fn test_encode_chunks() {
+        let mut encoder = ChunkedSlipEncoder::new(&[0x00, END_CHAR, 0x01]);
+        let mut output = [0x00; 2];
+
+        assert_eq!(encoder.encode_chunk(&mut output), 2);
+        assert_eq!(output, [END_CHAR, 0x00]);
+        assert_eq!(encoder.encode_chunk(&mut output), 2);
+        assert_eq!(output, [ESC_CHAR, ESC_END_CHAR]);
+        assert_eq!(encoder.encode_chunk(&mut output[..1]), 1);
+        assert_eq!(output[0], 0x01);
+        assert!(!encoder.is_done());
+        assert_eq!(encoder.encode_chunk(&mut output), 1);
+        assert_eq!(output[0], END_CHAR);
+        assert!(encoder.is_done());
+        assert_eq!(encoder.encode_chunk(&mut output), 0);
     }
 
     #[test]
-    fn test_decode_with_bad_escape_character() {
-        let mut slip_decoder = SlipDecoder::<1>::default();
+    fn test_encode_chunks_split_escape() {
+        let config = SlipConfig::new().with_leading_end(false);
+        let mut encoder = ChunkedSlipEncoder::with_config(&[ESC_CHAR], config);
+        let mut output = [0x00; 1];
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        assert_eq!(encoder.encode_chunk(&mut output), 1);
+        assert_eq!(output, [ESC_CHAR]);
+        assert_eq!(encoder.encode_chunk(&mut output), 1);
+        assert_eq!(output, [ESC_ESC_CHAR]);
+        assert_eq!(encoder.encode_chunk(&mut output), 1);
+        assert_eq!(output, [END_CHAR]);
+        assert!(encoder.is_done());
+    }
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    #[test]
+    fn test_decode_in_place() {
+        let slip_decoder = SlipDecoder::<0>::default();
+        let mut buf = [
+            END_CHAR,
+            0x00,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            ESC_CHAR,
+            ESC_ESC_CHAR,
+            END_CHAR,
+            0x01,
+        ];
 
-        let result = slip_decoder.insert(ESC_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Escape);
+        let result = slip_decoder.decode_in_place(&mut buf);
 
-        let result = slip_decoder.insert(0x00);
-        assert_eq!(result, Err(0x00));
+        assert_eq!(result, Ok(&[0x00, END_CHAR, ESC_CHAR][..]));
     }
 
     #[test]
-    fn test_decode_empty() {
-        let mut slip_decoder = SlipDecoder::<0>::default();
+    fn test_decode_in_place_without_delimiters() {
+        let slip_decoder = SlipDecoder::<0>::default();
+        let mut buf = [0x00, 0x01];
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        let result = slip_decoder.decode_in_place(&mut buf);
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+        assert_eq!(result, Ok(&[0x00, 0x01][..]));
+    }
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+    #[test]
+    fn test_decode_in_place_with_bad_escape_character() {
+        let slip_decoder = SlipDecoder::<0>::default();
 
-        assert!(slip_decoder.is_buffer_completed());
+        let mut buf = [END_CHAR, ESC_CHAR, 0x00, END_CHAR];
+        let result = slip_decoder.decode_in_place(&mut buf);
+        assert_eq!(result, Err(SlipDecodeError::InvalidEscape(0x00)));
 
-        assert_eq!(slip_decoder.get_buffer(), &[]);
+        let mut buf = [END_CHAR, 0x00, ESC_CHAR];
+        let result = slip_decoder.decode_in_place(&mut buf);
+        assert_eq!(result, Err(SlipDecodeError::MalformedFrame));
     }
 
     #[test]
-    fn test_decode_and_reset() {
-        let mut slip_decoder = SlipDecoder::<1>::default();
+    fn test_encode_iter() {
+        let mut iter = SlipEncodeIter::new([0x00, END_CHAR, ESC_CHAR].into_iter());
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        assert_eq!(iter.size_hint(), (5, Some(8)));
+        assert_eq!(iter.next(), Some(END_CHAR));
+        assert_eq!(iter.next(), Some(0x00));
+        assert_eq!(iter.next(), Some(ESC_CHAR));
+        assert_eq!(iter.size_hint(), (3, Some(4)));
+        assert_eq!(iter.next(), Some(ESC_END_CHAR));
+        assert_eq!(iter.next(), Some(ESC_CHAR));
+        assert_eq!(iter.next(), Some(ESC_ESC_CHAR));
+        assert_eq!(iter.next(), Some(END_CHAR));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+    #[test]
+    fn test_encode_iter_matches_encode() {
+        let encoder = SlipEncoder::with_config(SlipConfig::new().with_leading_end(false));
+        let payload = [ESC_CHAR, 0x01, END_CHAR, 0x02];
 
-        let result = slip_decoder.insert(0x00);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+        let mut array = Vec::<u8, 12>::from(payload);
+        encoder.encode(&mut array).unwrap();
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::End);
+        assert!(encoder.encode_iter(payload).eq(array));
+    }
 
-        assert!(slip_decoder.is_buffer_completed());
+    #[test]
+    fn test_decode_iter() {
+        let data = [
+            END_CHAR,
+            0x00,
+            END_CHAR,
+            END_CHAR,
+            0x01,
+            0x02,
+            0x03,
+            END_CHAR,
+            ESC_CHAR,
+            ESC_ESC_CHAR,
+            END_CHAR,
+            END_CHAR,
+        ];
+        let mut frames = SlipDecodeIter::<_, 2>::new(data.into_iter());
 
-        assert_eq!(slip_decoder.get_buffer(), &[0x00]);
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x00]))));
+        assert_eq!(frames.next(), Some(Err(SlipDecodeError::BufferFull)));
+        assert_eq!(frames.next(), Some(Ok(Vec::from([ESC_CHAR]))));
+        assert_eq!(frames.next(), None);
+    }
 
-        slip_decoder.reset();
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
-        assert!(!slip_decoder.is_buffer_completed());
+    #[test]
+    fn test_decode_iter_resumes_after_bad_escape() {
+        let data = [
+            END_CHAR, 0x01, ESC_CHAR, 0x05, 0x02, END_CHAR, END_CHAR, 0x07, END_CHAR, END_CHAR,
+            0x08, END_CHAR,
+        ];
+        let mut frames = SlipDecodeIter::<_, 4>::new(data.into_iter());
+
+        assert_eq!(
+            frames.next(),
+            Some(Err(SlipDecodeError::InvalidEscape(0x05)))
+        );
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x07]))));
+        assert_eq!(frames.next(), Some(Ok(Vec::from([0x08]))));
+        assert_eq!(frames.next(), None);
     }
 
     #[test]
-    fn test_decode_with_not_enough_space() {
-        let mut slip_decoder = SlipDecoder::<1>::default();
+    fn test_encode_with() {
+        let mut frame = Vec::<u8, 8>::new();
 
-        assert_eq!(slip_decoder.state, SlipDecoderState::Start);
+        let result = SlipEncoder::default().encode_with(&[0x01, END_CHAR, 0x02], |chunk| {
+            frame.extend(chunk.iter().copied());
 
-        let result = slip_decoder.insert(END_CHAR);
-        assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+            Ok::<_, ()>(())
+        });
 
-        let result = slip_decoder.insert(0x00);
         assert_eq!(result, Ok(()));
-        assert_eq!(slip_decoder.state, SlipDecoderState::Append);
+        assert_eq!(
+            frame.as_slice(),
+            [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, 0x02, END_CHAR]
+        );
 
-        let result = slip_decoder.insert(0x00);
-        assert_eq!(result, Err(0x00));
+        let mut chunks = 0;
+        let result = SlipEncoder::default().encode_with(&[0x01], |_| {
+            chunks += 1;
+
+            Err(chunks)
+        });
+
+        assert_eq!(result, Err(1));
+    }
+
+    #[test]
+    fn test_escaped_segments() {
+        let payload = [0x00, 0x01, END_CHAR, ESC_CHAR, 0x02];
+        let mut segments = SlipEncoder::default().escape_segments(&payload);
+
+        assert_eq!(segments.next(), Some(&[0x00, 0x01][..]));
+        assert_eq!(segments.next(), Some(&[ESC_CHAR, ESC_END_CHAR][..]));
+        assert_eq!(segments.next(), Some(&[ESC_CHAR, ESC_ESC_CHAR][..]));
+        assert_eq!(segments.next(), Some(&[0x02][..]));
+        assert_eq!(segments.next(), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip(
+            payload in collection::vec(any::<u8>(), 1..64),
+            chunks in collection::vec(1..8_usize, 1..16),
+        ) {
+            let mut encoded = [0x00; max_encoded_len(64)];
+            let length = SlipEncoder::default().encode_to_slice(&payload, &mut encoded).unwrap();
+            let mut slip_decoder = SlipDecoder::<64>::new();
+
+            // Feed the frame in chunks of arbitrary sizes, cycling through them
+            let mut data = &encoded[..length];
+            for &chunk in chunks.iter().cycle() {
+                let (head, tail) = data.split_at(chunk.min(data.len()));
+                let result = slip_decoder.feed(head);
+                prop_assert_eq!(result.consumed, head.len());
+                data = tail;
+
+                if data.is_empty() {
+                    break;
+                }
+            }
+
+            let frame = slip_decoder.take_frame().unwrap();
+            prop_assert_eq!(frame.as_slice(), payload.as_slice());
+        }
+
+        #[test]
+        fn prop_decode_recovers(data in collection::vec(any::<u8>(), 0..256)) {
+            let mut slip_decoder = SlipDecoder::<16>::new();
+
+            let mut data = data.as_slice();
+            while !data.is_empty() {
+                let result = slip_decoder.feed(data);
+                prop_assert!(result.consumed > 0);
+                prop_assert!(slip_decoder.get_buffer().len() <= 16);
+                if result.status != FeedStatus::Incomplete {
+                    slip_decoder.reset();
+                }
+                data = &data[result.consumed..];
+            }
+
+            slip_decoder.reset();
+            slip_decoder.feed(&[END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+            let frame = slip_decoder.take_frame().unwrap();
+            prop_assert_eq!(frame.as_slice(), [0x01, END_CHAR]);
+        }
     }
 }
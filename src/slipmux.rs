@@ -0,0 +1,404 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::END_CHAR;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+use crate::wcet::WorkBound;
+
+/// First byte of a diagnostic frame.
+pub const DIAGNOSTIC_CHAR: u8 = 0x0A;
+
+/// First byte of a configuration frame.
+pub const CONFIGURATION_CHAR: u8 = 0xA9;
+
+/// Length of the FCS trailing a configuration frame.
+const FCS_LENGTH: usize = 2;
+
+/// Computes the 16-bit FCS of `bytes`, as defined by RFC 1662.
+const fn fcs16(bytes: &[u8]) -> u16 {
+    let mut fcs: u16 = 0xFFFF;
+
+    let mut index = 0;
+    while index < bytes.len() {
+        fcs ^= bytes[index] as u16;
+
+        let mut bit = 0;
+        while bit < 8 {
+            fcs = if fcs & 0x0001 == 0 {
+                fcs >> 1
+            } else {
+                (fcs >> 1) ^ 0x8408
+            };
+            bit += 1;
+        }
+        index += 1;
+    }
+
+    !fcs
+}
+
+/// The type of a SLIPMUX frame, told apart by the first byte of the frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlipMuxFrameType {
+    /// An IPv4 or IPv6 packet, starting with its own header.
+    Ip,
+    /// Diagnostic text, starting with `DIAGNOSTIC_CHAR`.
+    Diagnostic,
+    /// A CoAP configuration message, starting with `CONFIGURATION_CHAR` and protected by a FCS.
+    Configuration,
+}
+
+impl SlipMuxFrameType {
+    /// Returns the type of the frame starting with `value`, if any.
+    const fn classify(value: u8) -> Option<Self> {
+        match value {
+            0x45..=0x4F | 0x60..=0x6F => Some(Self::Ip),
+            DIAGNOSTIC_CHAR => Some(Self::Diagnostic),
+            CONFIGURATION_CHAR => Some(Self::Configuration),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded SLIPMUX frame, borrowing its content from the decoder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlipMuxFrame<'a> {
+    /// An IP packet, header included.
+    Ip(&'a [u8]),
+    /// Diagnostic text, without its first byte.
+    Diagnostic(&'a [u8]),
+    /// A CoAP message, without its first byte and its FCS.
+    Configuration(&'a [u8]),
+}
+
+/// A SLIPMUX frame encoder, as described by draft-bormann-t2trg-slipmux.
+///
+/// SLIPMUX multiplexes IP packets, diagnostic text and configuration messages over one SLIP link,
+/// telling them apart by the first byte of each frame.
+#[derive(Clone, Copy, Default)]
+pub struct SlipMuxEncoder {
+    encoder: SlipEncoder,
+}
+
+impl SlipMuxEncoder {
+    /// Creates a SLIPMUX encoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            encoder: SlipEncoder::with_config(SlipConfig::new()),
+        }
+    }
+
+    /// Encodes the payload held by `vec` in place as a SLIPMUX frame of type `frame_type`.
+    ///
+    /// An IP packet is sent as is, a diagnostic text is prefixed with `DIAGNOSTIC_CHAR`, and a
+    /// configuration message is prefixed with `CONFIGURATION_CHAR` and followed by its FCS.
+    ///
+    /// Returns `Err(SlipEncodeError::InvalidPayload)` if the payload of an IP frame does not
+    /// start with an IP header, or `Err(SlipEncodeError::BufferTooSmall)` or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the vector is too small to hold the frame.
+    /// The vector is left untouched on error.
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        frame_type: SlipMuxFrameType,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+
+        match frame_type {
+            SlipMuxFrameType::Ip => {
+                let first = vec.as_slice().first().copied();
+                if first.and_then(SlipMuxFrameType::classify) != Some(SlipMuxFrameType::Ip) {
+                    return Err(SlipEncodeError::InvalidPayload);
+                }
+
+                return self.encoder.encode(vec);
+            }
+            SlipMuxFrameType::Diagnostic => {
+                vec.insert(0, DIAGNOSTIC_CHAR)
+                    .map_err(|_| SlipEncodeError::BufferTooSmall)?;
+            }
+            SlipMuxFrameType::Configuration => {
+                vec.insert(0, CONFIGURATION_CHAR)
+                    .map_err(|_| SlipEncodeError::BufferTooSmall)?;
+
+                for value in fcs16(vec.as_slice()).to_le_bytes() {
+                    if vec.push(value).is_err() {
+                        Self::restore(vec, payload_len);
+
+                        return Err(SlipEncodeError::BufferTooSmall);
+                    }
+                }
+            }
+        }
+
+        self.encoder.encode(vec).inspect_err(|_| {
+            Self::restore(vec, payload_len);
+        })
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to shift the payload, one to compute the FCS, then the encoding itself
+        WorkBound::linear(MAX_LENGTH)
+            .then(WorkBound::linear(MAX_LENGTH))
+            .then(SlipEncoder::encode_work_bound::<MAX_LENGTH>())
+    }
+
+    /// Strips the first byte and FCS added to the payload of `payload_len` bytes held by `vec`.
+    fn restore<const MAX_LENGTH: usize>(vec: &mut Vec<u8, MAX_LENGTH>, payload_len: usize) {
+        vec.truncate(payload_len + 1);
+        let _ = vec.remove(0);
+    }
+}
+
+/// A SLIPMUX frame decoder, as described by draft-bormann-t2trg-slipmux.
+///
+/// `MAX_LENGTH` bounds the decoded frame, first byte and FCS included. Empty frames are skipped,
+/// a frame with an unassigned first byte is rejected as `SlipDecodeError::MalformedFrame`, and a
+/// configuration frame whose FCS does not match is rejected as
+/// `SlipDecodeError::ChecksumMismatch`.
+pub struct SlipMuxDecoder<const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    frame_type: Option<SlipMuxFrameType>,
+}
+
+impl<const MAX_LENGTH: usize> SlipMuxDecoder<MAX_LENGTH> {
+    /// Creates a SLIPMUX decoder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(SlipConfig::new()),
+            frame_type: None,
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected, the frame is too long
+    /// to be buffered, or the frame it completes is invalid.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+
+        if self.decoder.is_buffer_completed() {
+            self.classify()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        // Completing a configuration frame checks its FCS
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound().then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, as for
+    /// `SlipDecoder::feed`. An invalid completed frame is rejected.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let mut consumed = 0;
+
+        loop {
+            let result = self.decoder.feed(&data[consumed..]);
+            consumed += result.consumed;
+
+            let status = match result.status {
+                FeedStatus::Completed => match self.classify() {
+                    Ok(()) if self.frame_type.is_none() => continue,
+                    Ok(()) => FeedStatus::Completed,
+                    Err(error) => FeedStatus::Rejected(error),
+                },
+                status => status,
+            };
+
+            return FeedResult { consumed, status };
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len).then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Classifies the completed frame by its first byte.
+    ///
+    /// An empty frame is skipped: its closing terminator opens the next frame.
+    fn classify(&mut self) -> Result<(), SlipDecodeError> {
+        let frame = self.decoder.get_buffer();
+        let Some(&value) = frame.first() else {
+            self.decoder.reset();
+
+            return self.decoder.insert(END_CHAR);
+        };
+
+        let result = match SlipMuxFrameType::classify(value) {
+            Some(SlipMuxFrameType::Configuration)
+                if frame.len() < 1 + FCS_LENGTH
+                    || fcs16(&frame[..frame.len() - FCS_LENGTH]).to_le_bytes()
+                        != frame[frame.len() - FCS_LENGTH..] =>
+            {
+                Err(SlipDecodeError::ChecksumMismatch)
+            }
+            Some(frame_type) => Ok(frame_type),
+            None => Err(SlipDecodeError::MalformedFrame),
+        };
+
+        match result {
+            Ok(frame_type) => {
+                self.frame_type = Some(frame_type);
+
+                Ok(())
+            }
+            Err(error) => {
+                self.decoder.reset();
+
+                Err(error)
+            }
+        }
+    }
+
+    /// Resets the decoder for the next frame.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.frame_type = None;
+    }
+
+    /// Returns the completed frame, or `None` if no frame is complete.
+    #[must_use]
+    pub fn frame(&self) -> Option<SlipMuxFrame<'_>> {
+        let frame = self.decoder.get_buffer();
+
+        match self.frame_type? {
+            SlipMuxFrameType::Ip => Some(SlipMuxFrame::Ip(frame)),
+            SlipMuxFrameType::Diagnostic => Some(SlipMuxFrame::Diagnostic(&frame[1..])),
+            SlipMuxFrameType::Configuration => Some(SlipMuxFrame::Configuration(
+                &frame[1..frame.len() - FCS_LENGTH],
+            )),
+        }
+    }
+}
+
+/// Default implementation for `SlipMuxDecoder`.
+impl<const MAX_LENGTH: usize> Default for SlipMuxDecoder<MAX_LENGTH> {
+    /// Creates a SLIPMUX decoder.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::slipmux::CONFIGURATION_CHAR;
+    use crate::slipmux::DIAGNOSTIC_CHAR;
+    use crate::slipmux::SlipMuxDecoder;
+    use crate::slipmux::SlipMuxEncoder;
+    use crate::slipmux::SlipMuxFrame;
+    use crate::slipmux::SlipMuxFrameType;
+    use crate::slipmux::fcs16;
+
+    #[test]
+    fn test_fcs16() {
+        assert_eq!(fcs16(b"123456789"), 0x906E);
+    }
+
+    #[test]
+    fn test_encode_diagnostic() {
+        let mut vec = Vec::<u8, 8>::from(*b"ok");
+        SlipMuxEncoder::new()
+            .encode(SlipMuxFrameType::Diagnostic, &mut vec)
+            .unwrap();
+
+        assert_eq!(
+            vec.as_slice(),
+            [END_CHAR, DIAGNOSTIC_CHAR, b'o', b'k', END_CHAR]
+        );
+    }
+
+    #[test]
+    fn test_encode_invalid_ip() {
+        let mut vec = Vec::<u8, 8>::from([0x00, 0x01]);
+
+        assert_eq!(
+            SlipMuxEncoder::new().encode(SlipMuxFrameType::Ip, &mut vec),
+            Err(SlipEncodeError::InvalidPayload)
+        );
+        assert_eq!(vec.as_slice(), [0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_encode_with_not_enough_space() {
+        let mut vec = Vec::<u8, 6>::from([0x40, 0x01]);
+
+        assert_eq!(
+            SlipMuxEncoder::new().encode(SlipMuxFrameType::Configuration, &mut vec),
+            Err(SlipEncodeError::InsufficientCapacity {
+                required: 7,
+                available: 6
+            })
+        );
+        assert_eq!(vec.as_slice(), [0x40, 0x01]);
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let mut vec = Vec::<u8, 16>::from([0x40, 0x01, 0x12, 0x34]);
+        SlipMuxEncoder::new()
+            .encode(SlipMuxFrameType::Configuration, &mut vec)
+            .unwrap();
+
+        assert_eq!(vec.as_slice()[1], CONFIGURATION_CHAR);
+
+        let mut decoder = SlipMuxDecoder::<16>::new();
+
+        assert_eq!(decoder.feed(vec.as_slice()).status, FeedStatus::Completed);
+        assert_eq!(
+            decoder.frame(),
+            Some(SlipMuxFrame::Configuration(&[0x40, 0x01, 0x12, 0x34]))
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = [END_CHAR, END_CHAR, 0x60, 0x00, END_CHAR];
+        let mut decoder = SlipMuxDecoder::<8>::new();
+
+        let result = decoder.feed(&data);
+
+        assert_eq!(result.consumed, data.len());
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.frame(), Some(SlipMuxFrame::Ip(&[0x60, 0x00])));
+    }
+
+    #[test]
+    fn test_decode_with_invalid_frames() {
+        let mut decoder = SlipMuxDecoder::<8>::new();
+
+        assert_eq!(
+            decoder
+                .feed(&[END_CHAR, CONFIGURATION_CHAR, 0x40, 0x00, 0x00, END_CHAR])
+                .status,
+            FeedStatus::Rejected(SlipDecodeError::ChecksumMismatch)
+        );
+        assert_eq!(
+            decoder.feed(&[END_CHAR, 0x00, END_CHAR]).status,
+            FeedStatus::Rejected(SlipDecodeError::MalformedFrame)
+        );
+        assert_eq!(decoder.frame(), None);
+    }
+}
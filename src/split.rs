@@ -0,0 +1,243 @@
+use crate::error::SlipDecodeError;
+use crate::queue::FrameConsumer;
+use crate::queue::FrameProducer;
+use crate::queue::FrameQueue;
+use crate::slip::DecoderStatus;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A SLIP decoder split into a byte-feeding half and a frame-consuming half.
+///
+/// The decoder hands its completed frames over to a `FrameQueue` of `SLOTS` frames. Once split,
+/// the `SplitFeeder` owns the decoder state machine and the producer side of the queue, while a
+/// `FrameConsumer` pops the frames. Both halves are `Send`, so that they can live in different
+/// RTIC tasks or threads without a mutex around the whole decoder: the handoff only relies on
+/// the atomic loads and stores of the queue.
+///
+/// Should the queue be full when a frame completes, the frame is held in the decoder, which
+/// rejects further bytes with `SlipDecodeError::FrameCompleted` until a slot is freed.
+pub struct SplitDecoder<const SLOTS: usize, const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    queue: FrameQueue<SLOTS, MAX_LENGTH>,
+}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> SplitDecoder<SLOTS, MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a decoder using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            queue: FrameQueue::new(),
+        }
+    }
+
+    /// Splits the decoder into its byte-feeding and frame-consuming halves.
+    pub const fn split(
+        &mut self,
+    ) -> (
+        SplitFeeder<'_, SLOTS, MAX_LENGTH>,
+        FrameConsumer<'_, SLOTS, MAX_LENGTH>,
+    ) {
+        let (producer, consumer) = self.queue.split();
+
+        (
+            SplitFeeder {
+                decoder: &mut self.decoder,
+                producer,
+            },
+            consumer,
+        )
+    }
+}
+
+/// Default implementation for `SplitDecoder`.
+impl<const SLOTS: usize, const MAX_LENGTH: usize> Default for SplitDecoder<SLOTS, MAX_LENGTH> {
+    /// Creates a decoder using the default SLIP configuration.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The byte-feeding half of a `SplitDecoder`.
+pub struct SplitFeeder<'a, const SLOTS: usize, const MAX_LENGTH: usize> {
+    decoder: &'a mut SlipDecoder<MAX_LENGTH>,
+    producer: FrameProducer<'a, SLOTS, MAX_LENGTH>,
+}
+
+impl<const SLOTS: usize, const MAX_LENGTH: usize> SplitFeeder<'_, SLOTS, MAX_LENGTH> {
+    /// Moves the completed frame, if any, to the queue, unless the queue is full.
+    ///
+    /// Returns `true` if the decoder holds no completed frame anymore.
+    fn hand_over(&mut self) -> bool {
+        if self.producer.is_full() {
+            return !self.decoder.is_buffer_completed();
+        }
+
+        // The frame always fits in a slot, both being `MAX_LENGTH` bytes long
+        let _ = self
+            .decoder
+            .take_frame_with(|frame| self.producer.push(frame));
+
+        true
+    }
+
+    /// Feeds `value` into the decoder state machine, as `SlipDecoder::insert` does.
+    ///
+    /// A completed frame is moved to the queue right away if it has a free slot. Otherwise it is
+    /// held in the decoder, and moved by the next call once the consumer has freed a slot.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.hand_over();
+        self.decoder.insert(value)?;
+        self.hand_over();
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound()
+            .then(WorkBound::linear(MAX_LENGTH.saturating_mul(2)))
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed across frame boundaries while the queue has free slots, so that a
+    /// chunk holding several frames is consumed whole. Consumption stops at the end of a frame
+    /// held for lack of a free slot, with `FeedStatus::Completed`, or at a rejected byte. The
+    /// status is also `FeedStatus::Completed` whenever a frame of `data` is moved to the queue.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let mut queued = false;
+        let mut consumed = 0;
+
+        if !self.hand_over() {
+            return FeedResult {
+                consumed,
+                status: FeedStatus::Completed,
+            };
+        }
+
+        while let Some(rest) = data.get(consumed..) {
+            let result = self.decoder.feed(rest);
+            consumed += result.consumed;
+
+            let status = match result.status {
+                FeedStatus::Completed => {
+                    queued |= self.hand_over();
+                    if !self.decoder.is_buffer_completed() && consumed < data.len() {
+                        continue;
+                    }
+
+                    FeedStatus::Completed
+                }
+                FeedStatus::Incomplete if queued => FeedStatus::Completed,
+                status => status,
+            };
+
+            return FeedResult { consumed, status };
+        }
+
+        FeedResult {
+            consumed,
+            status: FeedStatus::Incomplete,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len).then(WorkBound::linear(
+            MAX_LENGTH.saturating_mul(data_len.saturating_add(1)),
+        ))
+    }
+
+    /// Resets the decoder to its initial state, dropping the frame in progress.
+    ///
+    /// Frames already moved to the queue are left to the consumer.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns where the decoder stands in the byte stream.
+    #[must_use]
+    pub fn status(&self) -> DecoderStatus {
+        self.decoder.status()
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::thread;
+
+    use crate::error::SlipDecodeError;
+    use crate::slip::DecoderStatus;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::split::SplitDecoder;
+
+    #[test]
+    fn test_split_feed() {
+        let mut decoder = SplitDecoder::<2, 4>::new();
+        let (mut feeder, mut consumer) = decoder.split();
+        let data = [
+            END_CHAR, 0x01, END_CHAR, END_CHAR, 0x02, END_CHAR, END_CHAR, 0x03, END_CHAR, END_CHAR,
+        ];
+
+        let result = feeder.feed(&data);
+
+        assert_eq!(result.consumed, 9);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(feeder.status(), DecoderStatus::FrameReady);
+        assert_eq!(feeder.insert(0x04), Err(SlipDecodeError::FrameCompleted));
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x01]);
+
+        let result = feeder.feed(&data[9..]);
+
+        assert_eq!(result.consumed, 1);
+        assert_eq!(result.status, FeedStatus::Incomplete);
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x02]);
+        assert_eq!(consumer.pop().unwrap().as_slice(), [0x03]);
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_split_across_threads() {
+        let mut decoder = SplitDecoder::<2, 4>::new();
+        let (mut feeder, mut consumer) = decoder.split();
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for value in [END_CHAR, 0x01, 0x02, END_CHAR] {
+                    feeder.insert(value).unwrap();
+                }
+            });
+            scope.spawn(move || {
+                loop {
+                    if let Some(frame) = consumer.pop() {
+                        assert_eq!(frame.as_slice(), [0x01, 0x02]);
+                        break;
+                    }
+                    thread::yield_now();
+                }
+            });
+        });
+    }
+}
@@ -0,0 +1,360 @@
+use core::mem;
+
+use noalloc_vec_rs::vec::Vec;
+
+use crate::buffer::FrameBuffer;
+use crate::checksum::FrameChecksum;
+use crate::checksum::MAX_DIGEST_LENGTH;
+use crate::error::SlipDecodeError;
+use crate::slip::SlipBufferDecoder;
+use crate::slip::SlipConfig;
+use crate::slip::SlipStats;
+use crate::wcet::WorkBound;
+
+/// A handler of the payloads delivered in chunks by a `SlipStreamDecoder`.
+pub trait ChunkSink {
+    /// Handles the next chunk of the payload of the frame in progress.
+    ///
+    /// `chunk` is only valid for the duration of the call. Every chunk of a frame but the last
+    /// one is exactly `CHUNK` bytes long.
+    fn on_chunk(&mut self, chunk: &[u8]);
+
+    /// Handles the end of the frame whose payload was delivered, `checksum_ok` telling whether
+    /// the digest of the frame matched its payload.
+    ///
+    /// The chunks of a frame failing its checksum must be discarded.
+    fn on_frame_end(&mut self, checksum_ok: bool);
+
+    /// Handles `error`, which dropped the frame in progress, if any.
+    ///
+    /// The chunks already delivered for the frame must be discarded.
+    fn on_frame_aborted(&mut self, error: SlipDecodeError);
+}
+
+/// A frame buffer delivering the payload of a frame to a sink in chunks, holding back only the
+/// bytes which may turn out to be its digest.
+struct ChunkWindow<S, C, const CHUNK: usize> {
+    sink: S,
+    checksum: C,
+    // Payload bytes not delivered yet
+    chunk: Vec<u8, CHUNK>,
+    // Last bytes of the frame, which may turn out to be its digest
+    tail: [u8; MAX_DIGEST_LENGTH],
+    held: usize,
+    len: usize,
+}
+
+impl<S: ChunkSink, C: FrameChecksum, const CHUNK: usize> ChunkWindow<S, C, CHUNK> {
+    /// Creates the window of an empty frame, delivering chunks to `sink`.
+    fn new(sink: S) -> Self {
+        Self {
+            sink,
+            checksum: C::default(),
+            chunk: Vec::new(),
+            tail: [0; MAX_DIGEST_LENGTH],
+            held: 0,
+            len: 0,
+        }
+    }
+
+    /// Appends `value` to the payload, delivering the chunk it fills.
+    fn append(&mut self, value: u8) {
+        // The chunk has room, as it is delivered as soon as it is full
+        let _ = self.chunk.push(value);
+
+        if self.chunk.len() == CHUNK {
+            self.deliver();
+        }
+    }
+
+    /// Delivers the payload bytes not delivered yet, if any.
+    fn deliver(&mut self) {
+        if self.chunk.is_empty() {
+            return;
+        }
+
+        self.checksum.update(self.chunk.as_slice());
+        self.sink.on_chunk(self.chunk.as_slice());
+        self.chunk.clear();
+    }
+
+    /// Delivers the rest of the completed frame, and returns `true` if its digest matches.
+    fn finish(&mut self) -> bool {
+        self.deliver();
+        let checksum = mem::take(&mut self.checksum);
+
+        self.held == C::LENGTH && checksum.finalize().as_ref() == self.as_slice()
+    }
+}
+
+/// Implementation of `FrameBuffer` for `ChunkWindow`.
+impl<S: ChunkSink, C: FrameChecksum, const CHUNK: usize> FrameBuffer for ChunkWindow<S, C, CHUNK> {
+    /// Holds back `value`, appending to the payload the byte it pushes out of the window.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        if self.held < C::LENGTH {
+            self.tail[self.held] = value;
+            self.held += 1;
+        } else if C::LENGTH == 0 {
+            self.append(value);
+        } else {
+            let value = mem::replace(&mut self.tail[0], value);
+            self.tail[..C::LENGTH].rotate_left(1);
+            self.append(value);
+        }
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Drops the frame, restarting the checksum. Chunks already delivered are left to the sink.
+    fn clear(&mut self) {
+        self.checksum = C::default();
+        self.chunk.clear();
+        self.held = 0;
+        self.len = 0;
+    }
+
+    /// Returns the bytes held back, which are the digest once the frame is complete.
+    fn as_slice(&self) -> &[u8] {
+        &self.tail[..self.held]
+    }
+
+    /// Returns `usize::MAX`, as frames of any length can be delivered.
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns the number of decoded bytes of the frame, the digest included.
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// A SLIP decoder delivering payloads to a sink in chunks of `CHUNK` bytes, as they arrive.
+///
+/// This struct suits frames larger than the available memory, such as firmware images: only one
+/// chunk and the last `C::LENGTH` bytes of a frame are held, as for `ChecksumValidator`. Once a
+/// frame is complete, the sink is told whether its checksum matched, and the decoder resets
+/// itself for the next frame. A frame interrupted by an error is reported to the sink, so that it
+/// discards the chunks already delivered. The digest of `C` must not be longer than
+/// `MAX_DIGEST_LENGTH`.
+pub struct SlipStreamDecoder<S, C, const CHUNK: usize> {
+    decoder: SlipBufferDecoder<ChunkWindow<S, C, CHUNK>>,
+}
+
+impl<S: ChunkSink, C: FrameChecksum, const CHUNK: usize> SlipStreamDecoder<S, C, CHUNK> {
+    /// Creates a decoder delivering payloads to `sink`, using the default SLIP configuration.
+    #[must_use]
+    pub fn new(sink: S) -> Self {
+        Self::with_config(sink, SlipConfig::new())
+    }
+
+    /// Creates a decoder delivering payloads to `sink`, using the framing options of `config`.
+    ///
+    /// The integrity check is turned off, as there is no stored payload to guard.
+    #[must_use]
+    pub fn with_config(sink: S, config: SlipConfig) -> Self {
+        const {
+            assert!(CHUNK > 0, "chunks must hold at least one byte");
+            assert!(
+                C::LENGTH <= MAX_DIGEST_LENGTH,
+                "the digest is longer than MAX_DIGEST_LENGTH"
+            );
+        }
+
+        Self {
+            decoder: SlipBufferDecoder::with_buffer(
+                ChunkWindow::new(sink),
+                config.with_integrity_check(false),
+            ),
+        }
+    }
+
+    /// Feeds `value` into the decoder, delivering the chunk it completes to the sink.
+    ///
+    /// Returns `Ok(())` on success, or the error that interrupted the frame, which is also
+    /// reported to the sink. A completed frame failing its checksum is rejected with
+    /// `SlipDecodeError::ChecksumMismatch`. The decoder is then reset, so that the next frame is
+    /// decoded without intervention.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.stream(value).map(|_| ())
+    }
+
+    /// Returns the worst-case work of `insert`, excluding the time spent in the sink.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        // Every byte shifts the window, and may fill a chunk to run the checksum over
+        WorkBound::linear(C::LENGTH).then(WorkBound::linear(CHUNK))
+    }
+
+    /// Feeds the bytes of `data` into the decoder, delivering every chunk they complete to the
+    /// sink.
+    ///
+    /// Frames interrupted by an error or failing their checksum are only reported to the sink.
+    /// Returns the number of frames completed with a matching checksum.
+    pub fn feed(&mut self, data: &[u8]) -> usize {
+        data.iter()
+            .filter(|&&value| self.stream(value) == Ok(true))
+            .count()
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes, excluding the time spent in
+    /// the sink.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        // The checksum runs once over every chunk
+        WorkBound::linear(data_len.saturating_mul(C::LENGTH)).then(WorkBound::linear(data_len))
+    }
+
+    /// Feeds `value` into the decoder, delivering the chunk it completes to the sink.
+    ///
+    /// Returns `Ok(true)` if a frame was completed with a matching checksum.
+    fn stream(&mut self, value: u8) -> Result<bool, SlipDecodeError> {
+        if let Err(error) = self.decoder.insert(value) {
+            self.decoder.reset();
+            self.decoder.buffer_mut().sink.on_frame_aborted(error);
+
+            return Err(error);
+        }
+
+        if !self.decoder.is_buffer_completed() {
+            return Ok(false);
+        }
+
+        let window = self.decoder.buffer_mut();
+        let checksum_ok = window.finish();
+        window.sink.on_frame_end(checksum_ok);
+        self.decoder.reset();
+
+        if checksum_ok {
+            Ok(true)
+        } else {
+            Err(SlipDecodeError::ChecksumMismatch)
+        }
+    }
+
+    /// Drops the frame in progress, if any, without reporting it to the sink.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns the statistics of the link.
+    #[must_use]
+    pub const fn stats(&self) -> SlipStats {
+        self.decoder.stats()
+    }
+
+    /// Returns a reference to the sink.
+    #[must_use]
+    pub const fn get_ref(&self) -> &S {
+        &self.decoder.buffer().sink
+    }
+
+    /// Returns a mutable reference to the sink.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut S {
+        &mut self.decoder.buffer_mut().sink
+    }
+
+    /// Consumes the decoder, returning the sink.
+    ///
+    /// The chunks of a partially decoded frame, already delivered, are left to the sink.
+    #[must_use]
+    pub fn into_inner(self) -> S {
+        self.decoder.into_inner().sink
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::crc::Crc16;
+    use crate::crc::SlipCrc16Encoder;
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::stream::ChunkSink;
+    use crate::stream::SlipStreamDecoder;
+
+    /// Records the payload, chunk lengths and notifications it is handed.
+    struct Recorder {
+        payload: Vec<u8, 8>,
+        chunks: Vec<usize, 4>,
+        ends: Vec<bool, 2>,
+        aborted: Option<SlipDecodeError>,
+    }
+
+    impl Recorder {
+        /// Creates an empty recorder.
+        const fn new() -> Self {
+            Self {
+                payload: Vec::new(),
+                chunks: Vec::new(),
+                ends: Vec::new(),
+                aborted: None,
+            }
+        }
+    }
+
+    /// Implementation of `ChunkSink` for `Recorder`.
+    impl ChunkSink for Recorder {
+        /// Records `chunk` and its length.
+        fn on_chunk(&mut self, chunk: &[u8]) {
+            let _ = self.payload.write_slice(self.payload.len(), chunk);
+            let _ = self.chunks.push(chunk.len());
+        }
+
+        /// Records the end of the frame.
+        fn on_frame_end(&mut self, checksum_ok: bool) {
+            let _ = self.ends.push(checksum_ok);
+        }
+
+        /// Records `error`.
+        fn on_frame_aborted(&mut self, error: SlipDecodeError) {
+            self.aborted = Some(error);
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut frame = Vec::<u8, 16>::from([0x01, 0x02, 0x03, 0x04, 0x05]);
+        SlipCrc16Encoder::default().encode(&mut frame).unwrap();
+        let mut decoder = SlipStreamDecoder::<_, Crc16, 2>::new(Recorder::new());
+
+        assert_eq!(decoder.feed(&frame), 1);
+
+        let recorder = decoder.into_inner();
+
+        assert_eq!(recorder.payload.as_slice(), [0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(recorder.chunks.as_slice(), [2, 2, 1]);
+        assert_eq!(recorder.ends.as_slice(), [true]);
+        assert_eq!(recorder.aborted, None);
+    }
+
+    #[test]
+    fn test_stream_with_corrupted_frame() {
+        let mut frame = Vec::<u8, 16>::from([0x01, 0x02, 0x03]);
+        SlipCrc16Encoder::default().encode(&mut frame).unwrap();
+        frame.as_mut_slice()[1] = 0x00;
+        let mut decoder = SlipStreamDecoder::<_, Crc16, 2>::new(Recorder::new());
+
+        assert_eq!(decoder.feed(&frame), 0);
+        assert_eq!(decoder.get_ref().ends.as_slice(), [false]);
+
+        for value in [END_CHAR, 0x01, 0x02] {
+            assert_eq!(decoder.insert(value), Ok(()));
+        }
+
+        assert_eq!(decoder.insert(ESC_CHAR), Ok(()));
+        assert_eq!(
+            decoder.insert(0x03),
+            Err(SlipDecodeError::InvalidEscape(0x03))
+        );
+        assert_eq!(
+            decoder.get_ref().aborted,
+            Some(SlipDecodeError::InvalidEscape(0x03))
+        );
+        assert_eq!(decoder.stats().frames_dropped, 1);
+    }
+}
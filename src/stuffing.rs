@@ -0,0 +1,816 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::scan::find_special;
+use crate::slip::END_CHAR;
+use crate::slip::ESC_CHAR;
+use crate::slip::ESC_END_CHAR;
+use crate::slip::ESC_ESC_CHAR;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::max_encoded_len;
+use crate::wcet::WorkBound;
+
+/// Number of control bytes, which can be escaped on top of the delimiter and the escape byte.
+const CONTROL_COUNT: u8 = 0x20;
+
+/// Mask XOR-ed with an escaped control byte to form the byte following the escape byte.
+const CONTROL_SUBSTITUTION_MASK: u8 = 0x20;
+
+/// How the delimiter and the escape byte are substituted after the escape byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Substitution {
+    /// The delimiter and the escape byte are replaced by the given bytes, as in SLIP.
+    Table {
+        /// Byte replacing the delimiter.
+        delimiter: u8,
+        /// Byte replacing the escape byte.
+        escape: u8,
+    },
+    /// The delimiter and the escape byte are XOR-ed with the given mask, as in HDLC.
+    Xor(u8),
+}
+
+/// The rules of a byte-stuffing framing protocol.
+///
+/// Frames are delimited by a delimiter byte, and the delimiter and escape bytes are replaced
+/// inside a frame by the escape byte followed by their substitution. SLIP is the default
+/// instantiation of these rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteStuffingCodec {
+    delimiter: u8,
+    escape: u8,
+    substitution: Substitution,
+    leading_delimiter: bool,
+    controls: u32,
+}
+
+impl ByteStuffingCodec {
+    /// The SLIP rules, as described by RFC 1055.
+    pub const SLIP: Self = Self::new(
+        END_CHAR,
+        ESC_CHAR,
+        Substitution::Table {
+            delimiter: ESC_END_CHAR,
+            escape: ESC_ESC_CHAR,
+        },
+    );
+
+    /// The HDLC-like asynchronous framing rules, as described by RFC 1662.
+    pub const HDLC: Self = Self::new(0x7E, 0x7D, Substitution::Xor(0x20));
+
+    /// Creates the rules delimiting frames with `delimiter`, and escaping bytes with `escape`
+    /// followed by their `substitution`.
+    ///
+    /// Frames are opened by a delimiter as well, which can be changed with
+    /// `with_leading_delimiter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` and `escape` are equal, or if a substituted byte is the delimiter or
+    /// the escape byte.
+    #[must_use]
+    pub const fn new(delimiter: u8, escape: u8, substitution: Substitution) -> Self {
+        assert!(
+            delimiter != escape,
+            "the delimiter cannot be the escape byte"
+        );
+
+        let codec = Self::new_unchecked(delimiter, escape, substitution);
+        let (escaped_delimiter, escaped_escape) = match substitution {
+            Substitution::Table { delimiter, escape } => (delimiter, escape),
+            Substitution::Xor(mask) => (delimiter ^ mask, escape ^ mask),
+        };
+        assert!(
+            !codec.is_special(escaped_delimiter)
+                && !codec.is_special(escaped_escape)
+                && escaped_delimiter != escaped_escape,
+            "the substituted bytes must be distinct and not special"
+        );
+
+        codec
+    }
+
+    /// Creates the rules of `new`, without checking that the bytes are consistent.
+    ///
+    /// This is for rules checked beforehand, so that building them again cannot panic.
+    pub(crate) const fn new_unchecked(
+        delimiter: u8,
+        escape: u8,
+        substitution: Substitution,
+    ) -> Self {
+        Self {
+            delimiter,
+            escape,
+            substitution,
+            leading_delimiter: true,
+            controls: 0,
+        }
+    }
+
+    /// Sets whether frames are opened by a delimiter.
+    #[must_use]
+    pub const fn with_leading_delimiter(mut self, leading_delimiter: bool) -> Self {
+        self.leading_delimiter = leading_delimiter;
+        self
+    }
+
+    /// Sets the control bytes, from `0x00` to `0x1F`, escaped inside a frame on top of the
+    /// delimiter and the escape byte, e.g. XON and XOFF on links using software flow control.
+    ///
+    /// `controls` is a bitmap, where bit `n` stands for byte `n`, as the async control character
+    /// map of RFC 1662. An escaped control byte is replaced by the escape byte followed by the
+    /// control byte XOR-ed with `0x20`, or with the mask of a `Substitution::Xor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a substituted control byte is the delimiter, the escape byte, or a substitution
+    /// of one of them.
+    #[must_use]
+    pub const fn with_escaped_controls(self, controls: u32) -> Self {
+        let codec = self.with_escaped_controls_unchecked(controls);
+
+        let mut value = 0;
+        while value < CONTROL_COUNT {
+            if codec.escapes_control(value) {
+                let substitute = codec.substitute_control(value);
+                let (escaped_delimiter, escaped_escape) = match codec.substitution {
+                    Substitution::Table { delimiter, escape } => (delimiter, escape),
+                    Substitution::Xor(mask) => (codec.delimiter ^ mask, codec.escape ^ mask),
+                };
+                assert!(
+                    substitute != codec.delimiter
+                        && substitute != codec.escape
+                        && substitute != escaped_delimiter
+                        && substitute != escaped_escape,
+                    "the substituted control bytes must be distinct from the other escapes"
+                );
+            }
+            value += 1;
+        }
+
+        codec
+    }
+
+    /// Sets the escaped control bytes of `with_escaped_controls`, without checking that their
+    /// substitutions are consistent.
+    pub(crate) const fn with_escaped_controls_unchecked(mut self, controls: u32) -> Self {
+        self.controls = controls;
+        self
+    }
+
+    /// Returns the bitmap of the control bytes escaped inside a frame, as set by
+    /// `with_escaped_controls`.
+    #[must_use]
+    pub const fn escaped_controls(&self) -> u32 {
+        self.controls
+    }
+
+    /// Returns `true` if `value` is an escaped control byte.
+    const fn escapes_control(&self, value: u8) -> bool {
+        value < CONTROL_COUNT && self.controls & (1 << value) != 0
+    }
+
+    /// Returns the byte following the escape byte in place of the control byte `value`.
+    const fn substitute_control(&self, value: u8) -> u8 {
+        match self.substitution {
+            Substitution::Table { .. } => value ^ CONTROL_SUBSTITUTION_MASK,
+            Substitution::Xor(mask) => value ^ mask,
+        }
+    }
+
+    /// Returns the index of the first byte of `data` to escape, or the length of `data` if there
+    /// is none.
+    fn find_escaped(&self, data: &[u8]) -> usize {
+        if self.controls == 0 {
+            return find_special(data, self.delimiter, self.escape);
+        }
+
+        data.iter()
+            .position(|&value| self.is_special(value))
+            .unwrap_or(data.len())
+    }
+
+    /// Returns the frame delimiter.
+    #[must_use]
+    pub const fn delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Returns the escape byte.
+    #[must_use]
+    pub const fn escape(&self) -> u8 {
+        self.escape
+    }
+
+    /// Returns `true` if frames are opened by a delimiter.
+    #[must_use]
+    pub const fn leading_delimiter(&self) -> bool {
+        self.leading_delimiter
+    }
+
+    /// Returns `true` if `value` must be escaped inside a frame.
+    #[must_use]
+    pub const fn is_special(&self, value: u8) -> bool {
+        value == self.delimiter || value == self.escape || self.escapes_control(value)
+    }
+
+    /// Returns the byte following the escape byte in place of `value`, or `None` if `value` is
+    /// not escaped.
+    #[must_use]
+    pub const fn substitute(&self, value: u8) -> Option<u8> {
+        if !self.is_special(value) {
+            return None;
+        }
+
+        match self.substitution {
+            Substitution::Table { delimiter, .. } if value == self.delimiter => Some(delimiter),
+            Substitution::Table { escape, .. } if value == self.escape => Some(escape),
+            Substitution::Table { .. } => Some(self.substitute_control(value)),
+            Substitution::Xor(mask) => Some(value ^ mask),
+        }
+    }
+
+    /// Returns the byte escaped as `value` after the escape byte, or `None` if `value` is not a
+    /// valid substitution.
+    #[must_use]
+    pub const fn restore(&self, value: u8) -> Option<u8> {
+        match self.substitution {
+            Substitution::Table { delimiter, escape } => {
+                if value == delimiter {
+                    Some(self.delimiter)
+                } else if value == escape {
+                    Some(self.escape)
+                } else if self.escapes_control(value ^ CONTROL_SUBSTITUTION_MASK) {
+                    Some(value ^ CONTROL_SUBSTITUTION_MASK)
+                } else {
+                    None
+                }
+            }
+            Substitution::Xor(mask) => {
+                if self.is_special(value) {
+                    None
+                } else {
+                    Some(value ^ mask)
+                }
+            }
+        }
+    }
+
+    /// Returns the number of bytes `payload` occupies once encoded.
+    #[must_use]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encoded_len(&self, payload: &[u8]) -> usize {
+        let mut escapes = 0;
+        let mut rest = payload;
+        while let Some((_, tail)) = rest
+            .get(self.find_escaped(rest)..)
+            .and_then(<[u8]>::split_first)
+        {
+            escapes += 1;
+            rest = tail;
+        }
+
+        payload.len() + escapes + usize::from(self.leading_delimiter) + 1
+    }
+
+    /// Returns the number of bytes `payload` occupies once encoded, like `encoded_len`, but in a
+    /// const context, to size the array of `encode_array`.
+    #[must_use]
+    pub const fn const_encoded_len(&self, payload: &[u8]) -> usize {
+        let mut len = payload.len() + 1;
+        if self.leading_delimiter {
+            len += 1;
+        }
+
+        let mut index = 0;
+        while index < payload.len() {
+            if self.is_special(payload[index]) {
+                len += 1;
+            }
+            index += 1;
+        }
+
+        len
+    }
+
+    /// Encodes `payload` as a frame into an array of `N` bytes, like `encode_to_slice`, but in a
+    /// const context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not the encoded length of `payload`, as returned by `const_encoded_len`,
+    /// which fails the build when evaluated in a const context.
+    #[must_use]
+    pub const fn encode_array<const N: usize>(&self, payload: &[u8]) -> [u8; N] {
+        assert!(
+            N == self.const_encoded_len(payload),
+            "the array length must be the encoded length of the payload"
+        );
+
+        let mut frame = [0; N];
+        let mut write_index = if self.leading_delimiter {
+            frame[0] = self.delimiter;
+            1
+        } else {
+            0
+        };
+
+        let mut read_index = 0;
+        while read_index < payload.len() {
+            let value = payload[read_index];
+            if let Some(substitute) = self.substitute(value) {
+                frame[write_index] = self.escape;
+                frame[write_index + 1] = substitute;
+                write_index += 2;
+            } else {
+                frame[write_index] = value;
+                write_index += 1;
+            }
+            read_index += 1;
+        }
+
+        frame[write_index] = self.delimiter;
+
+        frame
+    }
+
+    /// Encodes `vec` in place as a frame.
+    ///
+    /// The encoded length is computed first, then the frame is filled back-to-front in a single
+    /// pass, so that each byte is moved at most once.
+    ///
+    /// Returns `Ok(())` on success, or `Err(SlipEncodeError::InsufficientCapacity)` if `vec`
+    /// lacks capacity for the framing overhead, with the capacity the frame requires. `vec` is
+    /// left untouched on error.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+        let frame_len = self.encoded_len(vec);
+        let error = SlipEncodeError::InsufficientCapacity {
+            required: frame_len,
+            available: MAX_LENGTH,
+        };
+        if frame_len > MAX_LENGTH {
+            return Err(error);
+        }
+
+        // Grow the vector to the frame length
+        while vec.len() < frame_len {
+            vec.push(0).map_err(|_| error)?;
+        }
+
+        self.encode_within(vec.as_mut_slice(), payload_len);
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to compute the encoded length, one to grow the vector, one to fill the frame
+        WorkBound::linear(MAX_LENGTH)
+            .then(WorkBound::linear(MAX_LENGTH))
+            .then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Encodes `vec` in place as a frame, like `encode` does for the crate's own `Vec`.
+    ///
+    /// Returns `Ok(())` on success, or `Err(SlipEncodeError::InsufficientCapacity)` if `vec`
+    /// lacks capacity for the framing overhead, with the capacity the frame requires. `vec` is
+    /// left untouched on error.
+    #[cfg(feature = "heapless")]
+    pub fn encode_heapless<const MAX_LENGTH: usize>(
+        &self,
+        vec: &mut heapless::Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+        let frame_len = self.encoded_len(vec);
+
+        vec.resize(frame_len, 0)
+            .map_err(|_| SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available: MAX_LENGTH,
+            })?;
+        self.encode_within(vec, payload_len);
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `encode_heapless` on a `heapless::Vec<u8, MAX_LENGTH>`.
+    #[cfg(feature = "heapless")]
+    #[must_use]
+    pub const fn encode_heapless_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        Self::encode_work_bound::<MAX_LENGTH>()
+    }
+
+    /// Encodes the first `payload_len` bytes of `buffer` in place as a frame.
+    ///
+    /// Returns the length of the frame, or `Err(SlipEncodeError::InsufficientCapacity)` if
+    /// `buffer` lacks room for the framing overhead. `buffer` is left untouched on error.
+    pub(crate) fn encode_slice(
+        self,
+        buffer: &mut [u8],
+        payload_len: usize,
+    ) -> Result<usize, SlipEncodeError> {
+        let frame_len = self.encoded_len(&buffer[..payload_len]);
+        let available = buffer.len();
+        let frame = buffer
+            .get_mut(..frame_len)
+            .ok_or(SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available,
+            })?;
+        self.encode_within(frame, payload_len);
+
+        Ok(frame_len)
+    }
+
+    /// Encodes the first `payload_len` bytes of `buffer` in place, filling it back-to-front.
+    ///
+    /// `buffer` must be exactly as long as the encoded frame.
+    fn encode_within(self, buffer: &mut [u8], payload_len: usize) {
+        // The write index never falls behind the read index, since it only moves ahead of it by
+        // the framing overhead that remains to be written
+        let mut write_index = buffer.len().wrapping_sub(1);
+        set(buffer, write_index, self.delimiter);
+
+        for read_index in (0..payload_len).rev() {
+            let Some(&value) = buffer.get(read_index) else {
+                continue;
+            };
+
+            if let Some(substitute) = self.substitute(value) {
+                write_index = write_index.wrapping_sub(2);
+                set(buffer, write_index, self.escape);
+                set(buffer, write_index.wrapping_add(1), substitute);
+            } else {
+                write_index = write_index.wrapping_sub(1);
+                set(buffer, write_index, value);
+            }
+        }
+
+        // Begin the frame
+        if self.leading_delimiter {
+            set(buffer, 0, self.delimiter);
+        }
+    }
+
+    /// Encodes `input` as a frame into `output`.
+    ///
+    /// Returns the number of bytes written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` is too small to hold the frame,
+    /// with the length of the frame. Nothing is written to `output` on error.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn encode_to_slice(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<usize, SlipEncodeError> {
+        let frame_len = self.encoded_len(input);
+        let error = SlipEncodeError::InsufficientCapacity {
+            required: frame_len,
+            available: output.len(),
+        };
+        // The frame is written within its computed length, so that no later write can fail
+        let frame = output.get_mut(..frame_len).ok_or(error)?;
+        let mut index = 0;
+
+        if self.leading_delimiter {
+            index = put(frame, index, &[self.delimiter]).ok_or(error)?;
+        }
+
+        // Copy the runs of ordinary bytes as a whole, escaping the special bytes between them
+        let mut rest = input;
+        loop {
+            let (run, special) = rest
+                .split_at_checked(self.find_escaped(rest))
+                .unwrap_or((rest, &[]));
+            index = put(frame, index, run).ok_or(error)?;
+
+            let Some((&value, tail)) = special.split_first() else {
+                break;
+            };
+            index = put(
+                frame,
+                index,
+                &[self.escape, self.substitute(value).unwrap_or(value)],
+            )
+            .ok_or(error)?;
+            rest = tail;
+        }
+
+        put(frame, index, &[self.delimiter]).ok_or(error)?;
+
+        Ok(frame_len)
+    }
+
+    /// Returns the worst-case work of `encode_to_slice` for an input of `input_len` bytes.
+    #[must_use]
+    pub const fn encode_to_slice_work_bound(input_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one pass to write the frame
+        WorkBound::linear(input_len).then(WorkBound::linear(max_encoded_len(input_len)))
+    }
+
+    /// Calls `f` with every byte of the frame encoding `payload`.
+    pub(crate) fn for_each_encoded(self, payload: &[u8], mut f: impl FnMut(u8)) {
+        if self.leading_delimiter {
+            f(self.delimiter);
+        }
+
+        for &value in payload {
+            if let Some(substitute) = self.substitute(value) {
+                f(self.escape);
+                f(substitute);
+            } else {
+                f(value);
+            }
+        }
+
+        f(self.delimiter);
+    }
+}
+
+/// Sets the byte of `buffer` at `index` to `value`, unless `index` is out of bounds.
+pub(crate) fn set(buffer: &mut [u8], index: usize, value: u8) {
+    if let Some(slot) = buffer.get_mut(index) {
+        *slot = value;
+    }
+}
+
+/// Copies `bytes` to `output` at `index`, and returns the index following them.
+///
+/// Returns `None` if `output` is too short to hold `bytes` at `index`.
+fn put(output: &mut [u8], index: usize, bytes: &[u8]) -> Option<usize> {
+    let slots = output.get_mut(index..)?.get_mut(..bytes.len())?;
+    for (slot, &value) in slots.iter_mut().zip(bytes) {
+        *slot = value;
+    }
+
+    Some(index + bytes.len())
+}
+
+/// Default implementation for `ByteStuffingCodec`.
+impl Default for ByteStuffingCodec {
+    /// Returns the SLIP rules.
+    fn default() -> Self {
+        Self::SLIP
+    }
+}
+
+/// The state of the byte-stuffing decoder.
+#[derive(Debug, PartialEq)]
+enum ByteStuffingDecoderState {
+    /// Waiting for the opening delimiter of a frame.
+    Start,
+    /// Received the closing delimiter; frame is complete.
+    End,
+    /// Accumulating payload bytes.
+    Append,
+    /// Received the escape byte; next byte must be a substitution.
+    Escape,
+}
+
+impl ByteStuffingDecoderState {
+    /// Returns the state a decoder using `codec` starts from.
+    const fn initial(codec: ByteStuffingCodec) -> Self {
+        if codec.leading_delimiter {
+            Self::Start
+        } else {
+            Self::Append
+        }
+    }
+}
+
+/// A decoder for the frames of a byte-stuffing framing protocol.
+pub struct ByteStuffingDecoder<const MAX_LENGTH: usize> {
+    state: ByteStuffingDecoderState,
+    buffer: Vec<u8, MAX_LENGTH>,
+    codec: ByteStuffingCodec,
+}
+
+impl<const MAX_LENGTH: usize> ByteStuffingDecoder<MAX_LENGTH> {
+    /// Creates a decoder for the frames described by `codec`.
+    #[must_use]
+    pub const fn new(codec: ByteStuffingCodec) -> Self {
+        Self {
+            state: ByteStuffingDecoderState::initial(codec),
+            buffer: Vec::new(),
+            codec,
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected or the buffer is full.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        match self.state {
+            ByteStuffingDecoderState::Start => {
+                if value == self.codec.delimiter {
+                    self.state = ByteStuffingDecoderState::Append;
+                }
+
+                Ok(())
+            }
+            ByteStuffingDecoderState::Append => {
+                if value == self.codec.delimiter {
+                    self.state = ByteStuffingDecoderState::End;
+                } else if value == self.codec.escape {
+                    self.state = ByteStuffingDecoderState::Escape;
+                } else {
+                    self.push(value)?;
+                }
+
+                Ok(())
+            }
+            ByteStuffingDecoderState::Escape => {
+                self.state = ByteStuffingDecoderState::Append;
+
+                let value = self
+                    .codec
+                    .restore(value)
+                    .ok_or(SlipDecodeError::InvalidEscape(value))?;
+
+                self.push(value)
+            }
+            ByteStuffingDecoderState::End => Err(SlipDecodeError::FrameCompleted),
+        }
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Appends `value` to the buffer.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.buffer
+            .push(value)
+            .map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, so that the caller can
+    /// continue from the leftover bytes once the frame has been handled and the decoder reset.
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        for (index, &value) in data.iter().enumerate() {
+            if self.is_frame_completed() {
+                return FeedResult {
+                    consumed: index,
+                    status: FeedStatus::Completed,
+                };
+            }
+
+            if let Err(error) = self.insert(value) {
+                return FeedResult {
+                    consumed: index + 1,
+                    status: FeedStatus::Rejected(error),
+                };
+            }
+        }
+
+        let status = if self.is_frame_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult {
+            consumed: data.len(),
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Feeds the bytes of `first` then `second` into the decoder state machine.
+    ///
+    /// This is meant for the two regions of a wrapped-around ring buffer, as for
+    /// `SlipDecoder::feed_split`. Bytes are consumed up to the end of a frame or to a rejected
+    /// byte; `consumed` counts across both slices.
+    pub fn feed_split(&mut self, first: &[u8], second: &[u8]) -> FeedResult {
+        let result = self.feed(first);
+        if result.status != FeedStatus::Incomplete {
+            return result;
+        }
+
+        let result = self.feed(second);
+
+        FeedResult {
+            consumed: first.len() + result.consumed,
+            status: result.status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed_split` for `data_len` bytes across both slices.
+    #[must_use]
+    pub const fn feed_split_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Resets the decoder to its initial state.
+    pub fn reset(&mut self) {
+        self.state = ByteStuffingDecoderState::initial(self.codec);
+        self.buffer.clear();
+    }
+
+    /// Returns `true` if the decoder has received a complete frame.
+    #[must_use]
+    pub fn is_frame_completed(&self) -> bool {
+        self.state == ByteStuffingDecoderState::End
+    }
+
+    /// Returns a slice of the decoded bytes accumulated so far.
+    #[must_use]
+    pub const fn get_buffer(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+}
+
+/// Default implementation for `ByteStuffingDecoder`.
+impl<const MAX_LENGTH: usize> Default for ByteStuffingDecoder<MAX_LENGTH> {
+    /// Creates a decoder for SLIP frames.
+    fn default() -> Self {
+        Self::new(ByteStuffingCodec::SLIP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipEncoder;
+    use crate::stuffing::ByteStuffingCodec;
+    use crate::stuffing::ByteStuffingDecoder;
+    use crate::stuffing::Substitution;
+
+    #[test]
+    fn test_encode_slip() {
+        let payload = [0x01, 0xC0, 0xDB, 0x02];
+
+        let mut vec = Vec::<u8, 16>::from(payload);
+        ByteStuffingCodec::SLIP.encode(&mut vec).unwrap();
+
+        let mut expected = Vec::<u8, 16>::from(payload);
+        SlipEncoder::default().encode(&mut expected).unwrap();
+
+        assert_eq!(vec.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_encode_hdlc() {
+        let mut output = [0x00; 16];
+        let length = ByteStuffingCodec::HDLC
+            .encode_to_slice(&[0x01, 0x7E, 0x7D, 0x02], &mut output)
+            .unwrap();
+
+        assert_eq!(
+            output[..length],
+            [0x7E, 0x01, 0x7D, 0x5E, 0x7D, 0x5D, 0x02, 0x7E]
+        );
+    }
+
+    #[test]
+    fn test_decode_hdlc() {
+        let mut decoder = ByteStuffingDecoder::<8>::new(ByteStuffingCodec::HDLC);
+
+        let result = decoder.feed(&[0x7E, 0x01, 0x7D, 0x5E, 0x7D, 0x21, 0x7E]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x01, 0x7E, 0x01]);
+
+        decoder.reset();
+
+        assert_eq!(
+            decoder.feed(&[0x7E, 0x7D, 0x7D]).status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(0x7D))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the substituted bytes must be distinct and not special")]
+    fn test_new_with_special_substitution() {
+        let _ = ByteStuffingCodec::new(
+            0x7E,
+            0x7D,
+            Substitution::Table {
+                delimiter: 0x7D,
+                escape: 0x01,
+            },
+        );
+    }
+}
@@ -0,0 +1,430 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::END_CHAR;
+use crate::slip::ESC_CHAR;
+use crate::slip::ESC_END_CHAR;
+use crate::slip::ESC_ESC_CHAR;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::max_encoded_len;
+use crate::wcet::WorkBound;
+
+/// A symbol carried by a link, such as a byte or a 9-bit UART word.
+pub trait Symbol: Copy + Eq {
+    /// Returns the low byte of the symbol, which describes it in errors.
+    fn low_byte(self) -> u8;
+}
+
+/// Implementation of `Symbol` for `u8`.
+impl Symbol for u8 {
+    /// Returns the byte itself.
+    fn low_byte(self) -> u8 {
+        self
+    }
+}
+
+/// Implementation of `Symbol` for `u16`.
+impl Symbol for u16 {
+    /// Returns the least significant byte of the word.
+    fn low_byte(self) -> u8 {
+        self.to_le_bytes()[0]
+    }
+}
+
+/// Implementation of `Symbol` for `u32`.
+impl Symbol for u32 {
+    /// Returns the least significant byte of the word.
+    fn low_byte(self) -> u8 {
+        self.to_le_bytes()[0]
+    }
+}
+
+/// The special symbols of SLIP framing over symbols of type `T`.
+///
+/// This struct follows the SLIP rules on links whose symbols are not bytes, such as 9-bit UARTs
+/// whose words are carried as `u16`: frames are terminated by `end`, and `end` and `escape` are
+/// replaced inside a frame by `escape` followed by `escaped_end` or `escaped_escape`. With `u8`
+/// symbols, the default rules are those of SLIP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SymbolCodec<T = u8> {
+    end: T,
+    escape: T,
+    escaped_end: T,
+    escaped_escape: T,
+    leading_end: bool,
+}
+
+impl<T: Symbol> SymbolCodec<T> {
+    /// Creates the rules terminating frames with `end`, and escaping `end` and `escape` as
+    /// `escape` followed by `escaped_end` and `escaped_escape`.
+    ///
+    /// Frames are opened by `end` as well, which can be changed with `with_leading_end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` and `escape` are equal, or if an escaped symbol is `end` or `escape`, or
+    /// if both escaped symbols are equal.
+    #[must_use]
+    pub fn new(end: T, escape: T, escaped_end: T, escaped_escape: T) -> Self {
+        assert!(end != escape, "the terminator cannot be the escape symbol");
+
+        let codec = Self {
+            end,
+            escape,
+            escaped_end,
+            escaped_escape,
+            leading_end: true,
+        };
+        assert!(
+            !codec.is_special(escaped_end)
+                && !codec.is_special(escaped_escape)
+                && escaped_end != escaped_escape,
+            "the escaped symbols must be distinct and not special"
+        );
+
+        codec
+    }
+
+    /// Sets whether frames are opened by a terminator.
+    #[must_use]
+    pub const fn with_leading_end(mut self, leading_end: bool) -> Self {
+        self.leading_end = leading_end;
+        self
+    }
+
+    /// Returns the frame terminator.
+    #[must_use]
+    pub const fn end(&self) -> T {
+        self.end
+    }
+
+    /// Returns the escape symbol.
+    #[must_use]
+    pub const fn escape(&self) -> T {
+        self.escape
+    }
+
+    /// Returns `true` if `value` must be escaped inside a frame.
+    #[must_use]
+    pub fn is_special(&self, value: T) -> bool {
+        value == self.end || value == self.escape
+    }
+
+    /// Returns the symbol following the escape symbol in place of `value`, or `None` if `value`
+    /// is not escaped.
+    #[must_use]
+    pub fn substitute(&self, value: T) -> Option<T> {
+        if value == self.end {
+            Some(self.escaped_end)
+        } else if value == self.escape {
+            Some(self.escaped_escape)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the symbol escaped as `value` after the escape symbol, or `None` if `value` is not
+    /// a valid escaped symbol.
+    #[must_use]
+    pub fn restore(&self, value: T) -> Option<T> {
+        if value == self.escaped_end {
+            Some(self.end)
+        } else if value == self.escaped_escape {
+            Some(self.escape)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of symbols `payload` occupies once encoded.
+    #[must_use]
+    pub fn encoded_len(&self, payload: &[T]) -> usize {
+        let escapes = payload
+            .iter()
+            .filter(|&&value| self.is_special(value))
+            .count();
+
+        payload.len() + escapes + usize::from(self.leading_end) + 1
+    }
+
+    /// Encodes `input` as a frame into `output`.
+    ///
+    /// Returns the number of symbols written to `output`, or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if `output` is too small to hold the frame,
+    /// with the length of the frame. Nothing is written to `output` on error.
+    pub fn encode_to_slice(&self, input: &[T], output: &mut [T]) -> Result<usize, SlipEncodeError> {
+        let frame_len = self.encoded_len(input);
+        let available = output.len();
+        let frame = output
+            .get_mut(..frame_len)
+            .ok_or(SlipEncodeError::InsufficientCapacity {
+                required: frame_len,
+                available,
+            })?;
+
+        let mut slots = frame.iter_mut();
+        let mut put = |value| {
+            if let Some(slot) = slots.next() {
+                *slot = value;
+            }
+        };
+
+        if self.leading_end {
+            put(self.end);
+        }
+        for &value in input {
+            if let Some(substitute) = self.substitute(value) {
+                put(self.escape);
+                put(substitute);
+            } else {
+                put(value);
+            }
+        }
+        put(self.end);
+
+        Ok(frame_len)
+    }
+
+    /// Returns the worst-case work of `encode_to_slice` for an input of `input_len` symbols.
+    #[must_use]
+    pub const fn encode_to_slice_work_bound(input_len: usize) -> WorkBound {
+        // One pass to compute the encoded length, one pass to write the frame
+        WorkBound::linear(input_len).then(WorkBound::linear(max_encoded_len(input_len)))
+    }
+}
+
+/// Default implementation for `SymbolCodec`.
+impl Default for SymbolCodec<u8> {
+    /// Returns the SLIP rules.
+    fn default() -> Self {
+        Self::new(END_CHAR, ESC_CHAR, ESC_END_CHAR, ESC_ESC_CHAR)
+    }
+}
+
+/// The state of the symbol decoder.
+#[derive(Debug, PartialEq)]
+enum SymbolDecoderState {
+    /// Waiting for the opening terminator of a frame.
+    Start,
+    /// Received the closing terminator; frame is complete.
+    End,
+    /// Accumulating payload symbols.
+    Append,
+    /// Received the escape symbol; next symbol must be an escaped symbol.
+    Escape,
+}
+
+impl SymbolDecoderState {
+    /// Returns the state a decoder using `codec` starts from.
+    const fn initial<T>(codec: &SymbolCodec<T>) -> Self {
+        if codec.leading_end {
+            Self::Start
+        } else {
+            Self::Append
+        }
+    }
+}
+
+/// A SLIP decoder over symbols of type `T`, holding frames of up to `MAX_LENGTH` symbols.
+///
+/// This struct runs the SLIP state machine with the special symbols of a `SymbolCodec`. An
+/// invalid escape sequence is reported as `SlipDecodeError::InvalidEscape`, with the low byte of
+/// the symbol following the escape symbol.
+pub struct SymbolDecoder<const MAX_LENGTH: usize, T = u8> {
+    state: SymbolDecoderState,
+    buffer: Vec<T, MAX_LENGTH>,
+    codec: SymbolCodec<T>,
+}
+
+impl<const MAX_LENGTH: usize, T: Symbol> SymbolDecoder<MAX_LENGTH, T> {
+    /// Creates a decoder for the frames described by `codec`.
+    #[must_use]
+    pub const fn new(codec: SymbolCodec<T>) -> Self {
+        Self {
+            state: SymbolDecoderState::initial(&codec),
+            buffer: Vec::new(),
+            codec,
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the symbol is unexpected or the buffer is
+    /// full.
+    pub fn insert(&mut self, value: T) -> Result<(), SlipDecodeError> {
+        match self.state {
+            SymbolDecoderState::Start => {
+                if value == self.codec.end {
+                    self.state = SymbolDecoderState::Append;
+                }
+
+                Ok(())
+            }
+            SymbolDecoderState::Append => {
+                if value == self.codec.end {
+                    self.state = SymbolDecoderState::End;
+                } else if value == self.codec.escape {
+                    self.state = SymbolDecoderState::Escape;
+                } else {
+                    self.push(value)?;
+                }
+
+                Ok(())
+            }
+            SymbolDecoderState::Escape => {
+                self.state = SymbolDecoderState::Append;
+
+                let value = self
+                    .codec
+                    .restore(value)
+                    .ok_or_else(|| SlipDecodeError::InvalidEscape(value.low_byte()))?;
+
+                self.push(value)
+            }
+            SymbolDecoderState::End => Err(SlipDecodeError::FrameCompleted),
+        }
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Appends `value` to the buffer.
+    fn push(&mut self, value: T) -> Result<(), SlipDecodeError> {
+        self.buffer
+            .push(value)
+            .map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Feeds the symbols of `data` into the decoder state machine.
+    ///
+    /// Symbols are consumed up to the end of a frame or to a rejected symbol, so that the caller
+    /// can continue from the leftover symbols once the frame has been handled and the decoder
+    /// reset.
+    pub fn feed(&mut self, data: &[T]) -> FeedResult {
+        for (index, &value) in data.iter().enumerate() {
+            if self.is_frame_completed() {
+                return FeedResult {
+                    consumed: index,
+                    status: FeedStatus::Completed,
+                };
+            }
+
+            if let Err(error) = self.insert(value) {
+                return FeedResult {
+                    consumed: index + 1,
+                    status: FeedStatus::Rejected(error),
+                };
+            }
+        }
+
+        let status = if self.is_frame_completed() {
+            FeedStatus::Completed
+        } else {
+            FeedStatus::Incomplete
+        };
+
+        FeedResult {
+            consumed: data.len(),
+            status,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` symbols.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        WorkBound::linear(data_len)
+    }
+
+    /// Resets the decoder to its initial state.
+    pub fn reset(&mut self) {
+        self.state = SymbolDecoderState::initial(&self.codec);
+        self.buffer.clear();
+    }
+
+    /// Returns `true` if the decoder has received a complete frame.
+    #[must_use]
+    pub fn is_frame_completed(&self) -> bool {
+        self.state == SymbolDecoderState::End
+    }
+
+    /// Returns a slice of the decoded symbols accumulated so far.
+    #[must_use]
+    pub const fn get_buffer(&self) -> &[T] {
+        self.buffer.as_slice()
+    }
+}
+
+/// Default implementation for `SymbolDecoder`.
+impl<const MAX_LENGTH: usize> Default for SymbolDecoder<MAX_LENGTH> {
+    /// Creates a decoder for SLIP frames.
+    fn default() -> Self {
+        Self::new(SymbolCodec::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipEncoder;
+    use crate::symbol::SymbolCodec;
+    use crate::symbol::SymbolDecoder;
+
+    #[test]
+    fn test_encode_slip() {
+        let payload = [0x01, 0xC0, 0xDB, 0x02];
+        let mut output = [0x00; 16];
+
+        let length = SymbolCodec::default()
+            .encode_to_slice(&payload, &mut output)
+            .unwrap();
+
+        let mut expected = Vec::<u8, 16>::from(payload);
+        SlipEncoder::default().encode(&mut expected).unwrap();
+
+        assert_eq!(output[..length], *expected.as_slice());
+    }
+
+    #[test]
+    fn test_encode_and_decode_words() {
+        // 9-bit words, whose special symbols have the ninth bit set
+        let codec = SymbolCodec::<u16>::new(0x1C0, 0x1DB, 0x1DC, 0x1DD);
+        let payload = [0x0C0, 0x1C0, 0x1DB, 0x0FF];
+        let mut output = [0x0000; 8];
+
+        let length = codec.encode_to_slice(&payload, &mut output).unwrap();
+
+        assert_eq!(
+            output[..length],
+            [0x1C0, 0x0C0, 0x1DB, 0x1DC, 0x1DB, 0x1DD, 0x0FF, 0x1C0]
+        );
+        assert!(codec.encode_to_slice(&payload, &mut output[..7]).is_err());
+
+        let mut decoder = SymbolDecoder::<4, u16>::new(codec);
+        let result = decoder.feed(&output[..length]);
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), payload);
+
+        decoder.reset();
+
+        assert_eq!(
+            decoder.feed(&[0x1C0, 0x1DB, 0x1C0]).status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidEscape(END_CHAR))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the escaped symbols must be distinct and not special")]
+    fn test_new_with_special_escaped_symbol() {
+        let _ = SymbolCodec::<u16>::new(0x1C0, 0x1DB, 0x1DB, 0x1DD);
+    }
+}
@@ -0,0 +1,13 @@
+/// Emits a trace-level message through the enabled logging frameworks.
+///
+/// The message is forwarded to `log` and `defmt` when their features are enabled, and compiled
+/// out otherwise. Its format string must be understood by both, which restricts it to plain
+/// `{}` placeholders.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+        #[cfg(feature = "defmt")]
+        defmt::trace!($($arg)*);
+    };
+}
@@ -0,0 +1,337 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::error::SlipEncodeError;
+use crate::slip::FeedResult;
+use crate::slip::FeedStatus;
+use crate::slip::SlipConfig;
+use crate::slip::SlipDecoder;
+use crate::slip::SlipEncoder;
+use crate::wcet::WorkBound;
+
+/// A reversible transform of the payload of a frame, such as an encryption or an authentication
+/// code.
+///
+/// The transform is applied to the payload before it is escaped, and inverted once the payload is
+/// unescaped, so that it can output any byte. Both directions run in place, within a buffer which
+/// may be longer than the payload: the payload may grow, e.g. by a nonce or a tag, up to the
+/// length of the buffer. Methods take `&mut self`, so that a transform can keep state across
+/// frames, such as a message counter.
+pub trait FrameTransform {
+    /// Transforms the payload held by the first `len` bytes of `buf`, before it is encoded.
+    ///
+    /// Returns the length of the transformed payload, which must not exceed the length of `buf`,
+    /// or an error if the payload cannot be transformed, e.g.
+    /// `SlipEncodeError::BufferTooSmall` if it does not fit in `buf`.
+    fn apply(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipEncodeError>;
+
+    /// Restores the payload held by the first `len` bytes of `buf`, once it is decoded.
+    ///
+    /// Returns the length of the restored payload, which must not exceed the length of `buf`, or
+    /// an error if the frame is rejected, e.g. `SlipDecodeError::TransformFailed` if its
+    /// authentication fails.
+    fn invert(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipDecodeError>;
+}
+
+/// A SLIP encoder applying a `FrameTransform` to every payload.
+pub struct TransformEncoder<T> {
+    encoder: SlipEncoder,
+    transform: T,
+}
+
+impl<T: FrameTransform> TransformEncoder<T> {
+    /// Creates an encoder applying `transform`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(transform: T) -> Self {
+        Self::with_config(transform, SlipConfig::new())
+    }
+
+    /// Creates an encoder applying `transform`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(transform: T, config: SlipConfig) -> Self {
+        Self {
+            encoder: SlipEncoder::with_config(config),
+            transform,
+        }
+    }
+
+    /// Transforms the payload held by `vec`, then encodes it in place as a SLIP frame.
+    ///
+    /// The transform may use the whole capacity of the vector. Returns the error of the
+    /// transform, or `Err(SlipEncodeError::BufferTooSmall)` or
+    /// `Err(SlipEncodeError::InsufficientCapacity)` if the vector is too small to hold the frame.
+    /// The content of the vector is unspecified on error.
+    pub fn encode<const MAX_LENGTH: usize>(
+        &mut self,
+        vec: &mut Vec<u8, MAX_LENGTH>,
+    ) -> Result<(), SlipEncodeError> {
+        let payload_len = vec.len();
+        while vec.push(0).is_ok() {}
+
+        let len = self
+            .transform
+            .apply(vec.as_mut_slice(), payload_len)
+            .inspect_err(|_| {
+                vec.truncate(payload_len);
+            })?;
+        vec.truncate(len);
+
+        self.encoder.encode(vec)
+    }
+
+    /// Returns the worst-case work of `encode` on a `Vec<u8, MAX_LENGTH>`, not counting the work
+    /// of the transform itself.
+    #[must_use]
+    pub const fn encode_work_bound<const MAX_LENGTH: usize>() -> WorkBound {
+        // One pass to extend the vector to its capacity, then the encoding itself
+        WorkBound::linear(MAX_LENGTH).then(SlipEncoder::encode_work_bound::<MAX_LENGTH>())
+    }
+
+    /// Returns a reference to the transform.
+    #[must_use]
+    pub const fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    /// Returns a mutable reference to the transform.
+    #[must_use]
+    pub const fn transform_mut(&mut self) -> &mut T {
+        &mut self.transform
+    }
+}
+
+/// A SLIP decoder inverting a `FrameTransform` on every frame.
+///
+/// `MAX_LENGTH` bounds both the decoded frame and the restored payload. The payload is restored
+/// into a buffer of its own once the frame is complete, and a frame rejected by the transform is
+/// dropped and reported with the error of the transform.
+pub struct TransformDecoder<T, const MAX_LENGTH: usize> {
+    decoder: SlipDecoder<MAX_LENGTH>,
+    transform: T,
+    // Restored payload of the completed frame
+    payload: Vec<u8, MAX_LENGTH>,
+}
+
+impl<T: FrameTransform, const MAX_LENGTH: usize> TransformDecoder<T, MAX_LENGTH> {
+    /// Creates a decoder inverting `transform`, using the default SLIP configuration.
+    #[must_use]
+    pub const fn new(transform: T) -> Self {
+        Self::with_config(transform, SlipConfig::new())
+    }
+
+    /// Creates a decoder inverting `transform`, using the framing options of `config`.
+    #[must_use]
+    pub const fn with_config(transform: T, config: SlipConfig) -> Self {
+        Self {
+            decoder: SlipDecoder::with_config(config),
+            transform,
+            payload: Vec::new(),
+        }
+    }
+
+    /// Feeds `value` into the decoder state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected, the buffer is full, or
+    /// the frame it completes is rejected by the transform.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.decoder.insert(value)?;
+
+        if self.decoder.is_buffer_completed() {
+            self.restore()?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the worst-case work of `insert`, not counting the work of the transform itself.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        // Completing a frame copies it to restore its payload
+        SlipDecoder::<MAX_LENGTH>::insert_work_bound().then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Feeds the bytes of `data` into the decoder state machine.
+    ///
+    /// Bytes are consumed up to the end of a frame or to a rejected byte, as for
+    /// `SlipDecoder::feed`. A completed frame rejected by the transform is rejected.
+    pub fn feed(&mut self, data: &[u8]) -> FeedResult {
+        let result = self.decoder.feed(data);
+
+        match result.status {
+            FeedStatus::Completed => match self.restore() {
+                Ok(()) => result,
+                Err(error) => FeedResult {
+                    consumed: result.consumed,
+                    status: FeedStatus::Rejected(error),
+                },
+            },
+            _ => result,
+        }
+    }
+
+    /// Returns the worst-case work of `feed` for `data_len` bytes, not counting the work of the
+    /// transform itself.
+    #[must_use]
+    pub const fn feed_work_bound(data_len: usize) -> WorkBound {
+        SlipDecoder::<MAX_LENGTH>::feed_work_bound(data_len).then(WorkBound::linear(MAX_LENGTH))
+    }
+
+    /// Restores the payload of the completed frame, dropping the frame if it is rejected.
+    fn restore(&mut self) -> Result<(), SlipDecodeError> {
+        let frame = self.decoder.get_buffer();
+        self.payload.clear();
+        // The frame fits, as both buffers have the same capacity
+        let _ = self.payload.write_slice(0, frame);
+        let len = frame.len();
+        while self.payload.push(0).is_ok() {}
+
+        match self.transform.invert(self.payload.as_mut_slice(), len) {
+            Ok(len) => {
+                self.payload.truncate(len);
+
+                Ok(())
+            }
+            Err(error) => {
+                self.reset();
+
+                Err(error)
+            }
+        }
+    }
+
+    /// Resets the decoder for the next frame.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.payload.clear();
+    }
+
+    /// Returns `true` if a complete frame, accepted by the transform, has been decoded.
+    #[must_use]
+    pub fn is_buffer_completed(&self) -> bool {
+        self.decoder.is_buffer_completed()
+    }
+
+    /// Returns the restored payload.
+    ///
+    /// The payload is only meaningful once the frame is complete.
+    #[must_use]
+    pub const fn get_buffer(&self) -> &[u8] {
+        self.payload.as_slice()
+    }
+
+    /// Returns a reference to the transform.
+    #[must_use]
+    pub const fn transform(&self) -> &T {
+        &self.transform
+    }
+
+    /// Returns a mutable reference to the transform.
+    #[must_use]
+    pub const fn transform_mut(&mut self) -> &mut T {
+        &mut self.transform
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noalloc_vec_rs::vec::Vec;
+
+    use crate::error::SlipDecodeError;
+    use crate::error::SlipEncodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::transform::FrameTransform;
+    use crate::transform::TransformDecoder;
+    use crate::transform::TransformEncoder;
+
+    /// Masks the payload with a key, and appends the sum of its bytes as a tag.
+    struct MaskTransform(u8);
+
+    /// Implementation of `FrameTransform` for `MaskTransform`.
+    impl FrameTransform for MaskTransform {
+        /// Masks the payload and appends its tag.
+        fn apply(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipEncodeError> {
+            if len >= buf.len() {
+                return Err(SlipEncodeError::BufferTooSmall);
+            }
+
+            let tag = buf[..len]
+                .iter()
+                .fold(0, |sum: u8, &value| sum.wrapping_add(value));
+            for value in &mut buf[..len] {
+                *value ^= self.0;
+            }
+            buf[len] = tag;
+
+            Ok(len + 1)
+        }
+
+        /// Unmasks the payload and checks its tag.
+        fn invert(&mut self, buf: &mut [u8], len: usize) -> Result<usize, SlipDecodeError> {
+            let Some(len) = len.checked_sub(1) else {
+                return Err(SlipDecodeError::TransformFailed);
+            };
+
+            for value in &mut buf[..len] {
+                *value ^= self.0;
+            }
+            let tag = buf[..len]
+                .iter()
+                .fold(0, |sum: u8, &value| sum.wrapping_add(value));
+            if tag != buf[len] {
+                return Err(SlipDecodeError::TransformFailed);
+            }
+
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let mut encoder = TransformEncoder::new(MaskTransform(0x0F));
+        let mut vec = Vec::<u8, 8>::from([0x01, 0x02]);
+
+        encoder.encode(&mut vec).unwrap();
+
+        assert_eq!(vec.as_slice(), [END_CHAR, 0x0E, 0x0D, 0x03, END_CHAR]);
+
+        let mut decoder = TransformDecoder::<_, 4>::new(MaskTransform(0x0F));
+        let result = decoder.feed(vec.as_slice());
+
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(decoder.get_buffer(), [0x01, 0x02]);
+    }
+
+    #[test]
+    fn test_encode_with_not_enough_space() {
+        let mut encoder = TransformEncoder::new(MaskTransform(0x0F));
+        let mut vec = Vec::<u8, 2>::from([0x01, 0x02]);
+
+        assert_eq!(
+            encoder.encode(&mut vec),
+            Err(SlipEncodeError::BufferTooSmall)
+        );
+        assert_eq!(vec.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_with_rejected_frame() {
+        let mut decoder = TransformDecoder::<_, 4>::new(MaskTransform(0x0F));
+
+        assert_eq!(
+            decoder.feed(&[END_CHAR, 0x0E, 0x0D, 0x04, END_CHAR]).status,
+            FeedStatus::Rejected(SlipDecodeError::TransformFailed)
+        );
+        assert!(!decoder.is_buffer_completed());
+
+        for value in [END_CHAR, ESC_CHAR, ESC_END_CHAR] {
+            assert_eq!(decoder.insert(value), Ok(()));
+        }
+        assert_eq!(
+            decoder.insert(END_CHAR),
+            Err(SlipDecodeError::TransformFailed)
+        );
+    }
+}
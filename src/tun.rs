@@ -0,0 +1,265 @@
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::os::fd::AsRawFd;
+use std::string::String;
+use std::sync::mpsc;
+use std::thread;
+
+use crate::error::SlipIoError;
+use crate::io_std::SlipFrameReader;
+use crate::io_std::SlipFrameWriter;
+use crate::slip::EmptyFramePolicy;
+use crate::slip::SlipConfig;
+
+/// A Linux TUN device, carrying raw IP packets.
+///
+/// Every read returns one packet, and every write sends one packet, without any packet
+/// information header. Opening a device requires the `CAP_NET_ADMIN` capability; the interface
+/// still has to be configured and brought up, e.g. with `ip`.
+pub struct TunDevice {
+    file: File,
+    name: String,
+}
+
+impl TunDevice {
+    /// Opens the TUN device `name`, creating it if it does not exist.
+    ///
+    /// An empty `name` lets the kernel pick one, such as `tun0`.
+    pub fn open(name: &str) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/net/tun")?;
+
+        // SAFETY: `ifreq` is a plain C struct, for which all zeroes is a valid value
+        let mut request: libc::ifreq = unsafe { core::mem::zeroed() };
+        if name.len() >= request.ifr_name.len() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "interface name too long",
+            ));
+        }
+        for (slot, &value) in request.ifr_name.iter_mut().zip(name.as_bytes()) {
+            *slot = value.cast_signed();
+        }
+        // Interface flags fit in 16 bits
+        #[allow(clippy::cast_possible_truncation)]
+        let flags = (libc::IFF_TUN | libc::IFF_NO_PI) as libc::c_short;
+        request.ifr_ifru.ifru_flags = flags;
+
+        // SAFETY: `request` is a valid `ifreq`, which outlives the call
+        if unsafe { libc::ioctl(file.as_raw_fd(), libc::TUNSETIFF, &raw mut request) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let name = request
+            .ifr_name
+            .iter()
+            .take_while(|&&value| value != 0)
+            .map(|&value| char::from(value.cast_unsigned()))
+            .collect();
+
+        Ok(Self { file, name })
+    }
+
+    /// Returns the name of the interface.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a new handle to the same device.
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self {
+            file: self.file.try_clone()?,
+            name: self.name.clone(),
+        })
+    }
+
+    /// Forwards packets between this device and a serial line, as SLIP frames of up to `MTU`
+    /// bytes, like `bridge` does.
+    pub fn attach<R, W, const MTU: usize>(self, serial_rx: R, serial_tx: W) -> io::Result<()>
+    where
+        R: Read + Send + 'static,
+        W: Write + Send + 'static,
+    {
+        bridge::<_, _, _, _, MTU>(serial_rx, serial_tx, self.try_clone()?, self)
+    }
+}
+
+/// Implementation of `Read` for `TunDevice`.
+impl Read for TunDevice {
+    /// Reads the next packet into `buf`, truncating it if `buf` is too small.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+/// Implementation of `Write` for `TunDevice`.
+impl Write for TunDevice {
+    /// Sends `buf` as a single packet.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    /// Does nothing, as packets are sent as they are written.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Forwards packets between a serial line and a packet device, as a minimal `slattach`.
+///
+/// Frames of up to `MTU` bytes read from `serial_rx` are written to `packets_tx`, one packet per
+/// write, and packets read from `packets_rx` are written to `serial_tx` as frames. Malformed and
+/// oversized frames are dropped. Each direction runs on its own thread; this function returns
+/// once either direction reaches the end of its input or fails, leaving the other thread
+/// detached until its next read returns.
+pub fn bridge<R, W, P, Q, const MTU: usize>(
+    serial_rx: R,
+    serial_tx: W,
+    packets_rx: P,
+    packets_tx: Q,
+) -> io::Result<()>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+    P: Read + Send + 'static,
+    Q: Write + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    let uplink = sender.clone();
+    thread::spawn(move || {
+        let _ = uplink.send(forward_frames::<_, _, MTU>(serial_rx, packets_tx));
+    });
+    thread::spawn(move || {
+        let _ = sender.send(forward_packets::<_, _, MTU>(packets_rx, serial_tx));
+    });
+
+    receiver
+        .recv()
+        .unwrap_or_else(|_| Err(io::Error::other("forwarding thread panicked")))
+}
+
+/// Writes the payload of every frame read from `serial` to `packets`, until the end of `serial`.
+fn forward_frames<R: Read, W: Write, const MTU: usize>(
+    serial: R,
+    mut packets: W,
+) -> io::Result<()> {
+    // Back-to-back terminators delimit empty frames, which carry no packet
+    let config = SlipConfig::new().with_empty_frames(EmptyFramePolicy::Skip);
+    let mut reader = SlipFrameReader::<_, MTU>::with_config(serial, config);
+    let mut packet = [0; MTU];
+
+    loop {
+        match reader.read_frame(&mut packet) {
+            Ok(length) => packets.write_all(&packet[..length])?,
+            Err(SlipIoError::Io(error)) => return Err(error),
+            Err(SlipIoError::UnexpectedEof) => return Ok(()),
+            Err(
+                SlipIoError::Decode(_)
+                | SlipIoError::BufferTooSmall(_)
+                | SlipIoError::Encode(_)
+                | SlipIoError::Timeout,
+            ) => {
+                trace!("SLIP frame not forwarded");
+            }
+        }
+    }
+}
+
+/// Writes every packet read from `packets` to `serial` as a frame, until the end of `packets`.
+fn forward_packets<R: Read, W: Write, const MTU: usize>(
+    mut packets: R,
+    serial: W,
+) -> io::Result<()> {
+    let mut writer = SlipFrameWriter::new(serial);
+    let mut packet = [0; MTU];
+
+    loop {
+        let length = match packets.read(&mut packet) {
+            Ok(0) => return Ok(()),
+            Ok(length) => length,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+
+        writer.write_frame(&packet[..length])?;
+        writer.flush()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::io::Write;
+    use std::vec;
+    use std::vec::Vec;
+
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::tun::forward_frames;
+    use crate::tun::forward_packets;
+
+    /// A packet device recording every packet written to it.
+    #[derive(Default)]
+    struct MockPackets(Vec<Vec<u8>>);
+
+    impl Write for MockPackets {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.push(buf.to_vec());
+
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_forward_frames() {
+        let serial = [
+            END_CHAR,
+            0x45,
+            ESC_CHAR,
+            ESC_END_CHAR,
+            END_CHAR,
+            END_CHAR,
+            ESC_CHAR,
+            0x00,
+            END_CHAR,
+            END_CHAR,
+            0x45,
+            0x01,
+            0x02,
+            0x03,
+            0x04,
+            END_CHAR,
+            END_CHAR,
+            0x46,
+            END_CHAR,
+        ];
+        let mut packets = MockPackets::default();
+
+        forward_frames::<_, _, 4>(&serial[..], &mut packets).unwrap();
+
+        assert_eq!(packets.0, [vec![0x45, END_CHAR], vec![0x46]]);
+    }
+
+    #[test]
+    fn test_forward_packets() {
+        let packets = [0x45, END_CHAR];
+        let mut serial = Vec::new();
+
+        forward_packets::<_, _, 4>(&packets[..], &mut serial).unwrap();
+
+        assert_eq!(serial, [END_CHAR, 0x45, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+    }
+}
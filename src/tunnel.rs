@@ -0,0 +1,219 @@
+use noalloc_vec_rs::vec::Vec;
+
+use crate::error::SlipDecodeError;
+use crate::slip::ESC_CHAR;
+use crate::slip::ESC_END_CHAR;
+use crate::slip::ESC_ESC_CHAR;
+use crate::slip::SlipConfig;
+use crate::wcet::WorkBound;
+
+/// The state of the SLIP tunnel.
+#[derive(Debug, PartialEq)]
+enum SlipTunnelState {
+    /// Waiting for the opening terminator of a frame.
+    Start,
+    /// Received the closing terminator; frame is complete.
+    End,
+    /// Accumulating escaped payload bytes.
+    Append,
+    /// Received `ESC_CHAR`; next byte must be `ESC_END_CHAR` or `ESC_ESC_CHAR`.
+    Escape,
+}
+
+impl SlipTunnelState {
+    /// Returns the state a tunnel using `config` starts from.
+    const fn initial(config: SlipConfig) -> Self {
+        if config.leading_end() {
+            Self::Start
+        } else {
+            Self::Append
+        }
+    }
+}
+
+/// A SLIP frame forwarder for SLIP-in-SLIP tunnels.
+///
+/// An encoded SLIP frame never contains an unescaped terminator, so it is already a valid frame
+/// for any link using the same dialect: this struct collects a frame from one link, escapes
+/// intact, validating its escape sequences on the way so that a corrupted frame is not
+/// forwarded, and hands it over ready to be written to the other link.
+///
+/// No encapsulation takes place: frames are forwarded verbatim, without any outer framing, and
+/// the far side of the tunnel reads them with the same struct. Both links must therefore use the
+/// same dialect; bridging links of different dialects needs a full decode and re-encode.
+pub struct SlipTunnel<const MAX_LENGTH: usize> {
+    state: SlipTunnelState,
+    frame: Vec<u8, MAX_LENGTH>,
+    config: SlipConfig,
+}
+
+impl<const MAX_LENGTH: usize> SlipTunnel<MAX_LENGTH> {
+    /// Creates a tunnel forwarding frames of the default SLIP dialect.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(SlipConfig::new())
+    }
+
+    /// Creates a tunnel forwarding frames of the dialect described by `config`.
+    #[must_use]
+    pub const fn with_config(config: SlipConfig) -> Self {
+        Self {
+            state: SlipTunnelState::initial(config),
+            frame: Vec::new(),
+            config,
+        }
+    }
+
+    /// Feeds `value`, as received on the wire, into the tunnel state machine.
+    ///
+    /// Returns `Ok(())` on success, or an error if the byte is unexpected or the frame is too
+    /// long to be buffered.
+    pub fn insert(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        let end = self.config.end_char();
+
+        match self.state {
+            SlipTunnelState::Start => {
+                if value == end {
+                    self.push(value)?;
+                    self.state = SlipTunnelState::Append;
+                }
+
+                Ok(())
+            }
+            SlipTunnelState::Append => {
+                self.push(value)?;
+
+                if value == end {
+                    self.state = SlipTunnelState::End;
+                } else if value == ESC_CHAR {
+                    self.state = SlipTunnelState::Escape;
+                }
+
+                Ok(())
+            }
+            SlipTunnelState::Escape => {
+                self.state = SlipTunnelState::Append;
+
+                match value {
+                    ESC_END_CHAR | ESC_ESC_CHAR => self.push(value),
+                    _ => Err(SlipDecodeError::InvalidEscape(value)),
+                }
+            }
+            SlipTunnelState::End => Err(SlipDecodeError::FrameCompleted),
+        }
+    }
+
+    /// Appends `value` to the frame.
+    fn push(&mut self, value: u8) -> Result<(), SlipDecodeError> {
+        self.frame
+            .push(value)
+            .map_err(|_| SlipDecodeError::BufferFull)
+    }
+
+    /// Returns the worst-case work of `insert`.
+    #[must_use]
+    pub const fn insert_work_bound() -> WorkBound {
+        WorkBound::CONSTANT
+    }
+
+    /// Resets the tunnel to its initial state.
+    pub fn reset(&mut self) {
+        self.state = SlipTunnelState::initial(self.config);
+        self.frame.clear();
+    }
+
+    /// Returns `true` if the tunnel has received a complete SLIP frame.
+    #[must_use]
+    pub fn is_frame_completed(&self) -> bool {
+        self.state == SlipTunnelState::End
+    }
+
+    /// Returns the frame received so far, delimiters and escape sequences included.
+    ///
+    /// Once the frame is complete, it can be written as is to the other link.
+    #[must_use]
+    pub const fn get_frame(&self) -> &[u8] {
+        self.frame.as_slice()
+    }
+}
+
+/// Default implementation for `SlipTunnel`.
+impl<const MAX_LENGTH: usize> Default for SlipTunnel<MAX_LENGTH> {
+    /// Creates a tunnel forwarding frames of the default SLIP dialect.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::slip::ESC_ESC_CHAR;
+    use crate::slip::SlipDecoder;
+    use crate::slip::SlipEncoder;
+    use crate::tunnel::SlipTunnel;
+    use noalloc_vec_rs::vec::Vec;
+
+    #[test]
+    fn test_tunnel() {
+        let mut frame = Vec::<u8, 12>::from([0x00, END_CHAR, ESC_CHAR]);
+        SlipEncoder::default().encode(&mut frame).unwrap();
+
+        let mut tunnel = SlipTunnel::<12>::default();
+        for &value in &frame {
+            assert_eq!(tunnel.insert(value), Ok(()));
+        }
+
+        assert!(tunnel.is_frame_completed());
+        assert_eq!(tunnel.get_frame(), &*frame);
+
+        let mut slip_decoder = SlipDecoder::<12>::default();
+        for &value in tunnel.get_frame() {
+            assert_eq!(slip_decoder.insert(value), Ok(()));
+        }
+
+        assert_eq!(slip_decoder.get_buffer(), &[0x00, END_CHAR, ESC_CHAR]);
+    }
+
+    #[test]
+    fn test_tunnel_skips_bytes_before_frame() {
+        let mut tunnel = SlipTunnel::<4>::default();
+
+        for value in [0x01, END_CHAR, 0x02, END_CHAR] {
+            assert_eq!(tunnel.insert(value), Ok(()));
+        }
+
+        assert_eq!(tunnel.get_frame(), &[END_CHAR, 0x02, END_CHAR]);
+
+        tunnel.reset();
+        assert!(!tunnel.is_frame_completed());
+        assert!(tunnel.get_frame().is_empty());
+    }
+
+    #[test]
+    fn test_tunnel_with_bad_escape_character() {
+        let mut tunnel = SlipTunnel::<4>::default();
+
+        assert_eq!(tunnel.insert(END_CHAR), Ok(()));
+        assert_eq!(tunnel.insert(ESC_CHAR), Ok(()));
+        assert_eq!(
+            tunnel.insert(0x00),
+            Err(SlipDecodeError::InvalidEscape(0x00))
+        );
+    }
+
+    #[test]
+    fn test_tunnel_with_frame_too_long() {
+        let mut tunnel = SlipTunnel::<4>::default();
+
+        assert_eq!(tunnel.insert(END_CHAR), Ok(()));
+        assert_eq!(tunnel.insert(ESC_CHAR), Ok(()));
+        assert_eq!(tunnel.insert(ESC_ESC_CHAR), Ok(()));
+        assert_eq!(tunnel.insert(ESC_END_CHAR), Ok(()));
+        assert_eq!(tunnel.insert(END_CHAR), Err(SlipDecodeError::BufferFull));
+        assert!(!tunnel.is_frame_completed());
+    }
+}
@@ -0,0 +1,213 @@
+/// A sanity check of the payload of every frame completed by a decoder.
+///
+/// A decoder given a validator with `SlipDecoder::with_validator` runs it once on every frame as
+/// soon as its terminator is received, before the frame is reported as completed. A rejected frame is
+/// dropped with `SlipDecodeError::InvalidFrame`, and counted in `SlipStats::frames_invalid`, so
+/// that cheap checks run once in the codec instead of in every consumer. Validators run in the
+/// context feeding the decoder, e.g. an interrupt handler, and should stay short. A panicking
+/// validator aborts, so that the decoding paths stay free of unwinding.
+///
+/// This trait is implemented by any `Fn(&[u8]) -> bool` closure.
+pub trait FrameValidator {
+    /// Returns `true` if `frame` is accepted.
+    fn validate(&self, frame: &[u8]) -> bool;
+}
+
+/// Implementation of `FrameValidator` for closures.
+impl<F: Fn(&[u8]) -> bool> FrameValidator for F {
+    /// Calls the closure with `frame`.
+    fn validate(&self, frame: &[u8]) -> bool {
+        self(frame)
+    }
+}
+
+/// A validator accepting every frame, used by decoders without any other validator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AcceptAll;
+
+/// Implementation of `FrameValidator` for `AcceptAll`.
+impl FrameValidator for AcceptAll {
+    /// Returns `true`.
+    fn validate(&self, _frame: &[u8]) -> bool {
+        true
+    }
+}
+
+/// A validator accepting frames of at least the given number of bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinLength(pub usize);
+
+/// Implementation of `FrameValidator` for `MinLength`.
+impl FrameValidator for MinLength {
+    /// Returns `true` if `frame` is long enough.
+    fn validate(&self, frame: &[u8]) -> bool {
+        frame.len() >= self.0
+    }
+}
+
+/// A validator accepting frames starting with the given magic bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MagicPrefix(pub &'static [u8]);
+
+/// Implementation of `FrameValidator` for `MagicPrefix`.
+impl FrameValidator for MagicPrefix {
+    /// Returns `true` if `frame` starts with the magic bytes.
+    fn validate(&self, frame: &[u8]) -> bool {
+        frame.starts_with(self.0)
+    }
+}
+
+/// A validator accepting IPv4 and IPv6 packets whose header matches the length of the frame.
+///
+/// The version field must be 4 or 6, and the length field of the header must account for the
+/// whole frame, which catches most frames truncated, merged or corrupted on the line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IpPacket;
+
+/// Implementation of `FrameValidator` for `IpPacket`.
+impl FrameValidator for IpPacket {
+    /// Returns `true` if `frame` is an IP packet of consistent length.
+    fn validate(&self, frame: &[u8]) -> bool {
+        match frame {
+            [version, _, high, low, ..] if version >> 4 == 4 => {
+                let header_len = usize::from(version & 0x0F) * 4;
+
+                header_len >= 20
+                    && header_len <= frame.len()
+                    && usize::from(u16::from_be_bytes([*high, *low])) == frame.len()
+            }
+            [version, _, _, _, high, low, ..] if version >> 4 == 6 => {
+                frame.len() >= 40
+                    && usize::from(u16::from_be_bytes([*high, *low])) + 40 == frame.len()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::error::SlipDecodeError;
+    use crate::slip::END_CHAR;
+    use crate::slip::FeedStatus;
+    use crate::slip::SlipConfig;
+    use crate::slip::SlipDecoder;
+    use crate::validate::FrameValidator;
+    use crate::validate::IpPacket;
+    use crate::validate::MagicPrefix;
+    use crate::validate::MinLength;
+
+    #[test]
+    fn test_builtin_validators() {
+        let mut ipv4 = [0x00; 20];
+        ipv4[0] = 0x45;
+        ipv4[3] = 20;
+        let mut ipv6 = [0x00; 42];
+        ipv6[0] = 0x60;
+        ipv6[5] = 2;
+
+        assert!(IpPacket.validate(&ipv4));
+        assert!(IpPacket.validate(&ipv6));
+        assert!(!IpPacket.validate(&ipv4[..19]));
+        assert!(!IpPacket.validate(&ipv6[..41]));
+        assert!(!IpPacket.validate(&[0x00; 20]));
+        assert!(MinLength(2).validate(&[0x01, 0x02]));
+        assert!(!MinLength(2).validate(&[0x01]));
+        assert!(MagicPrefix(&[0xCA, 0xFE]).validate(&[0xCA, 0xFE, 0x01]));
+        assert!(!MagicPrefix(&[0xCA, 0xFE]).validate(&[0xCA]));
+    }
+
+    #[test]
+    fn test_decoder_rejects_invalid_frames() {
+        let mut slip_decoder = SlipDecoder::<4>::new().with_validator(MinLength(2));
+
+        let result = slip_decoder.feed(&[END_CHAR, 0x01, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidFrame)
+        );
+        assert!(!slip_decoder.is_buffer_completed());
+
+        // The rejected frame is dropped without a reset, even with resynchronization disabled
+        let result = slip_decoder.feed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, 0x02]);
+
+        let stats = slip_decoder.stats();
+        assert_eq!(stats.frames_invalid, 1);
+        assert_eq!(stats.frames_dropped, 1);
+        assert_eq!(stats.frames_completed, 1);
+    }
+
+    #[test]
+    fn test_decoder_does_not_merge_invalid_frames() {
+        let mut slip_decoder =
+            SlipDecoder::<4>::with_config(SlipConfig::new().with_leading_end(false))
+                .with_validator(MinLength(2));
+
+        let result = slip_decoder.feed(&[0x01, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidFrame)
+        );
+
+        let result = slip_decoder.feed(&[0x02, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidFrame)
+        );
+
+        let result = slip_decoder.feed(&[0x03, 0x04, END_CHAR]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x03, 0x04]);
+        assert_eq!(slip_decoder.stats().frames_invalid, 2);
+    }
+
+    #[test]
+    fn test_decoder_resyncs_after_invalid_frame() {
+        let mut slip_decoder = SlipDecoder::<4>::with_config(
+            SlipConfig::new().with_leading_end(false).with_resync(true),
+        )
+        .with_validator(|frame: &[u8]| frame.first() == Some(&0x01));
+
+        let result = slip_decoder.feed(&[0x02, END_CHAR, 0x01, 0x03, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidFrame)
+        );
+
+        // The terminator ended the rejected frame, so that the next frame follows right away
+        let result = slip_decoder.feed(&[0x01, 0x03, END_CHAR]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(slip_decoder.get_buffer(), [0x01, 0x03]);
+    }
+
+    #[test]
+    fn test_borrowed_frames_validated_once() {
+        let calls = Cell::new(0);
+        let mut slip_decoder = SlipDecoder::<4>::new().with_validator(|frame: &[u8]| {
+            calls.set(calls.get() + 1);
+            frame.len() >= 2
+        });
+
+        let (result, frame) = slip_decoder.feed_borrowed(&[END_CHAR, 0x01, END_CHAR]);
+        assert_eq!(
+            result.status,
+            FeedStatus::Rejected(SlipDecodeError::InvalidFrame)
+        );
+        assert!(frame.is_none());
+        drop(frame);
+
+        let (result, frame) = slip_decoder.feed_borrowed(&[END_CHAR, 0x01, 0x02, END_CHAR]);
+        assert_eq!(result.status, FeedStatus::Completed);
+        assert_eq!(*frame.unwrap(), [0x01, 0x02]);
+
+        let stats = slip_decoder.stats();
+        assert_eq!(calls.get(), 2);
+        assert_eq!(stats.frames_invalid, 1);
+        assert_eq!(stats.frames_dropped, 1);
+        assert_eq!(stats.frames_completed, 1);
+    }
+}
@@ -0,0 +1,195 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use js_sys::Array;
+use js_sys::Function;
+use js_sys::Uint8Array;
+use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::crc::Crc16;
+use crate::error::SlipDecodeError;
+use crate::slip::FeedStatus;
+use crate::slip::SlipEncoder;
+use crate::slip::SlipVecDecoder;
+use crate::stream::ChunkSink;
+use crate::stream::SlipStreamDecoder;
+
+/// Number of payload bytes delivered at once by a `WasmStreamDecoder`.
+pub const WASM_CHUNK_LENGTH: usize = 256;
+
+/// Encodes `payload` as a SLIP frame, using the default SLIP configuration.
+#[wasm_bindgen]
+#[must_use]
+pub fn encode(payload: &[u8]) -> Vec<u8> {
+    SlipEncoder::new().encode_to_vec(payload)
+}
+
+/// A SLIP decoder for JavaScript code, such as a WebSerial-based tool.
+///
+/// This struct wraps a `SlipVecDecoder`, using the default SLIP configuration: the bytes read
+/// from the port are fed as they arrive, and every frame they complete is returned as a
+/// `Uint8Array`. Frames rejected on the way, e.g. for an invalid escape sequence, are dropped and
+/// counted by `framesDropped`.
+#[wasm_bindgen]
+pub struct WasmDecoder {
+    decoder: SlipVecDecoder,
+}
+
+// wasm-bindgen does not export const functions
+#[allow(clippy::missing_const_for_fn)]
+#[wasm_bindgen]
+impl WasmDecoder {
+    /// Creates a decoder of frames of up to `max_frame_len` decoded bytes.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(max_frame_len: usize) -> Self {
+        let mut decoder = SlipVecDecoder::new();
+        decoder.set_max_frame_len(max_frame_len);
+
+        Self { decoder }
+    }
+
+    /// Feeds the bytes of `data` into the decoder, and returns the frames they complete.
+    ///
+    /// A frame left incomplete by `data` is continued by the next call.
+    pub fn feed(&mut self, data: &[u8]) -> Array {
+        let frames = Array::new();
+        self.decode(data, |frame| {
+            frames.push(&Uint8Array::from(frame));
+        });
+
+        frames
+    }
+
+    /// Feeds the bytes of `data` into the decoder, handing every frame they complete to
+    /// `on_frame`.
+    fn decode(&mut self, mut data: &[u8], mut on_frame: impl FnMut(&[u8])) {
+        while !data.is_empty() {
+            let result = self.decoder.feed(data);
+            data = data.get(result.consumed..).unwrap_or_default();
+
+            match result.status {
+                FeedStatus::Completed => {
+                    on_frame(self.decoder.get_buffer());
+                    self.decoder.reset();
+                }
+                FeedStatus::Rejected(_) => self.decoder.reset(),
+                FeedStatus::Incomplete => {}
+            }
+        }
+    }
+
+    /// Drops the frame in progress, if any.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+
+    /// Returns the number of frames dropped since the decoder was created.
+    #[wasm_bindgen(getter, js_name = framesDropped)]
+    #[must_use]
+    pub fn frames_dropped(&self) -> u32 {
+        self.decoder.stats().frames_dropped
+    }
+}
+
+/// A chunk sink calling back JavaScript functions.
+struct JsSink {
+    chunk: Function,
+    frame_end: Function,
+    frame_aborted: Function,
+}
+
+/// Implementation of `ChunkSink` for `JsSink`.
+impl ChunkSink for JsSink {
+    /// Calls back `on_chunk` with a copy of `chunk`, as a `Uint8Array`.
+    fn on_chunk(&mut self, chunk: &[u8]) {
+        // An exception thrown by the callback must not stop the decoder
+        let _ = self.chunk.call1(&JsValue::NULL, &Uint8Array::from(chunk));
+    }
+
+    /// Calls back `on_frame_end` with `checksum_ok`.
+    fn on_frame_end(&mut self, checksum_ok: bool) {
+        let _ = self
+            .frame_end
+            .call1(&JsValue::NULL, &JsValue::from_bool(checksum_ok));
+    }
+
+    /// Calls back `on_frame_aborted` with the description of `error`.
+    fn on_frame_aborted(&mut self, error: SlipDecodeError) {
+        let _ = self
+            .frame_aborted
+            .call1(&JsValue::NULL, &JsValue::from_str(&error.to_string()));
+    }
+}
+
+/// A streaming SLIP decoder for JavaScript code, delivering payloads to callbacks in chunks.
+///
+/// This struct wraps a `SlipStreamDecoder`, using the default SLIP configuration, for frames
+/// protected by a CRC-16 as sent by a `SlipCrc16Encoder`, such as firmware images. Payloads are
+/// handed to `on_chunk` in `Uint8Array`s of up to `WASM_CHUNK_LENGTH` bytes, then `on_frame_end` is
+/// called with whether the CRC matched. A frame interrupted by an error is reported to
+/// `on_frame_aborted`, with the description of the error.
+#[wasm_bindgen]
+pub struct WasmStreamDecoder {
+    decoder: SlipStreamDecoder<JsSink, Crc16, WASM_CHUNK_LENGTH>,
+}
+
+#[wasm_bindgen]
+impl WasmStreamDecoder {
+    /// Creates a decoder calling back `on_chunk`, `on_frame_end` and `on_frame_aborted`.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new(on_chunk: Function, on_frame_end: Function, on_frame_aborted: Function) -> Self {
+        Self {
+            decoder: SlipStreamDecoder::new(JsSink {
+                chunk: on_chunk,
+                frame_end: on_frame_end,
+                frame_aborted: on_frame_aborted,
+            }),
+        }
+    }
+
+    /// Feeds the bytes of `data` into the decoder, calling back the chunks and the frame ends
+    /// they complete.
+    ///
+    /// Returns the number of frames completed with a matching CRC.
+    pub fn feed(&mut self, data: &[u8]) -> usize {
+        self.decoder.feed(data)
+    }
+
+    /// Drops the frame in progress, if any, without calling back `on_frame_aborted`.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::slip::END_CHAR;
+    use crate::slip::ESC_CHAR;
+    use crate::slip::ESC_END_CHAR;
+    use crate::wasm::WasmDecoder;
+    use crate::wasm::encode;
+
+    #[test]
+    fn test_encode_and_decode() {
+        let frame = encode(&[0x01, END_CHAR]);
+
+        assert_eq!(frame, [END_CHAR, 0x01, ESC_CHAR, ESC_END_CHAR, END_CHAR]);
+
+        let mut decoder = WasmDecoder::new(2);
+        let mut frames = Vec::new();
+        let data = [&frame[..], &[END_CHAR, ESC_CHAR, 0x00, 0x02], &frame[..2]].concat();
+        decoder.decode(&data, |frame| frames.push(frame.to_vec()));
+
+        assert_eq!(frames, [[0x01, END_CHAR]]);
+        assert_eq!(decoder.frames_dropped(), 1);
+
+        decoder.decode(&frame[2..], |frame| frames.push(frame.to_vec()));
+
+        assert_eq!(frames, [[0x01, END_CHAR], [0x01, END_CHAR]]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Worst-case execution bounds of the encoding and decoding paths.
+//!
+//! The encoders, decoders and codecs of this crate run loops whose iteration count is statically
+//! bounded by the length of their input or by the `MAX_LENGTH` of the buffer they work on: there
+//! is no recursion, no unbounded retry and no allocation. Their byte-level entry points (`encode`,
+//! `insert`, `feed`, `take_frame`, ...) have a companion `*_work_bound` function returning a
+//! `WorkBound`, so that the worst-case execution time of an interrupt handler can be derived from
+//! the configuration it uses instead of from the source code.
+//!
+//! Calls without such a companion are not covered: this is the case of the container types of the
+//! `queue`, `mailbox` and `pool` modules, and of the `FrameConsumer` half returned by
+//! `SplitDecoder::split`, whose `SplitFeeder` half only bounds `insert` and `feed`.
+
+use core::ops::Add;
+
+/// The worst-case amount of work performed by a single call.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkBound {
+    /// Maximum number of bytes read or written.
+    pub bytes: usize,
+    /// Maximum number of loop iterations, element moves included.
+    pub iterations: usize,
+}
+
+impl WorkBound {
+    /// Bound of a call running in constant time.
+    pub const CONSTANT: Self = Self::linear(1);
+
+    /// Creates a bound processing `bytes` bytes, one loop iteration per byte.
+    #[must_use]
+    pub const fn linear(bytes: usize) -> Self {
+        Self {
+            bytes,
+            iterations: bytes,
+        }
+    }
+
+    /// Returns the bound of running `self` then `other`, saturating on overflow.
+    #[must_use]
+    pub const fn then(self, other: Self) -> Self {
+        Self {
+            bytes: self.bytes.saturating_add(other.bytes),
+            iterations: self.iterations.saturating_add(other.iterations),
+        }
+    }
+}
+
+/// Implementation of `Add` for `WorkBound`.
+///
+/// This allows combining the bounds of successive calls.
+impl Add for WorkBound {
+    type Output = Self;
+
+    /// Returns the bound of running `self` then `rhs`, saturating on overflow.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.then(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wcet::WorkBound;
+
+    #[test]
+    fn test_work_bound_then() {
+        let bound = WorkBound::linear(2).then(WorkBound::CONSTANT);
+
+        assert_eq!(
+            bound,
+            WorkBound {
+                bytes: 3,
+                iterations: 3
+            }
+        );
+        assert_eq!(
+            bound + WorkBound::linear(usize::MAX),
+            WorkBound::linear(usize::MAX)
+        );
+    }
+}